@@ -0,0 +1,349 @@
+//! Alert rules — thresholds on drop rate, stalled nodes, disconnect rate and network
+//! partitions that flash the header (and optionally beep) when crossed. Evaluated on the
+//! diagnostics tick.
+
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use crate::theme::colors;
+use crate::core::events::DISCONNECT_EVENTS;
+use crate::time::now_seconds;
+use super::{with_data, JamApp};
+
+/// A rule that keeps tripping fires again only after this many seconds
+const ALERT_COOLDOWN_SECS: f64 = 30.0;
+/// How long the header flashes after an alert fires
+const ALERT_FLASH_SECS: f64 = 3.0;
+/// Window of the disconnect rate, seconds
+const DISCONNECT_RATE_WINDOW: f64 = 10.0;
+
+/// What a rule watches
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum AlertMetric {
+    /// Events dropped per second, by nodes and by the server
+    DroppedPerSec,
+    /// Nodes without an event for the stall threshold
+    StalledNodes,
+    /// Disconnect events per second across all nodes
+    DisconnectsPerSec,
+    /// Groups the validators are split into (1 while they are all connected)
+    Partitions,
+}
+
+impl AlertMetric {
+    pub(crate) const ALL: [AlertMetric; 4] = [
+        AlertMetric::DroppedPerSec,
+        AlertMetric::StalledNodes,
+        AlertMetric::DisconnectsPerSec,
+        AlertMetric::Partitions,
+    ];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            AlertMetric::DroppedPerSec => "drops/sec",
+            AlertMetric::StalledNodes => "stalled nodes",
+            AlertMetric::DisconnectsPerSec => "disconnects/sec",
+            AlertMetric::Partitions => "partitions",
+        }
+    }
+
+    fn slot(self) -> usize {
+        self as usize
+    }
+}
+
+/// `metric > threshold` raises an alert while `enabled`
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct AlertRule {
+    pub metric: AlertMetric,
+    pub enabled: bool,
+    pub threshold: f64,
+}
+
+/// One disabled rule per metric
+pub(crate) fn default_alert_rules() -> Vec<AlertRule> {
+    let threshold = |metric| match metric {
+        AlertMetric::DroppedPerSec => 100.0,
+        AlertMetric::StalledNodes => 5.0,
+        AlertMetric::DisconnectsPerSec => 5.0,
+        AlertMetric::Partitions => 1.0,
+    };
+    AlertMetric::ALL
+        .iter()
+        .map(|&metric| AlertRule { metric, enabled: false, threshold: threshold(metric) })
+        .collect()
+}
+
+/// Stored rules in `AlertMetric::ALL` order; missing metrics get their default rule
+pub(crate) fn fit_alert_rules(stored: &[AlertRule]) -> Vec<AlertRule> {
+    default_alert_rules()
+        .into_iter()
+        .map(|default| {
+            stored
+                .iter()
+                .find(|r| r.metric == default.metric && r.threshold.is_finite())
+                .map_or(default, |r| AlertRule { threshold: r.threshold.max(0.0), ..*r })
+        })
+        .collect()
+}
+
+/// Metric values taken on one diagnostics tick
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct AlertSample {
+    pub dropped_per_sec: f64,
+    pub stalled_nodes: usize,
+    pub disconnects_per_sec: f64,
+    pub partitions: usize,
+}
+
+impl AlertSample {
+    fn value(&self, metric: AlertMetric) -> f64 {
+        match metric {
+            AlertMetric::DroppedPerSec => self.dropped_per_sec,
+            AlertMetric::StalledNodes => self.stalled_nodes as f64,
+            AlertMetric::DisconnectsPerSec => self.disconnects_per_sec,
+            AlertMetric::Partitions => self.partitions as f64,
+        }
+    }
+}
+
+/// Which rules are tripped, when each last fired, and the header flash
+#[derive(Default)]
+pub(crate) struct AlertState {
+    /// Rules over their threshold on the latest tick, with the value seen
+    tripped: Vec<(AlertMetric, f64)>,
+    last_fired: [Option<f64>; AlertMetric::ALL.len()],
+    flash_since: Option<f64>,
+    #[cfg(target_arch = "wasm32")]
+    audio: Option<web_sys::AudioContext>,
+}
+
+impl AlertState {
+    /// Check every enabled rule against `sample`; returns the alerts that fire now.
+    /// A rule that stays tripped fires again only once its cooldown has passed.
+    fn evaluate(
+        &mut self,
+        rules: &[AlertRule],
+        sample: &AlertSample,
+        now: f64,
+    ) -> Vec<(AlertMetric, f64)> {
+        self.tripped = rules
+            .iter()
+            .filter(|r| r.enabled && sample.value(r.metric) > r.threshold)
+            .map(|r| (r.metric, sample.value(r.metric)))
+            .collect();
+
+        let mut fired = Vec::new();
+        for &(metric, value) in &self.tripped {
+            let last = &mut self.last_fired[metric.slot()];
+            if last.is_some_and(|t| now - t < ALERT_COOLDOWN_SECS) {
+                continue;
+            }
+            *last = Some(now);
+            fired.push((metric, value));
+        }
+        if !fired.is_empty() {
+            self.flash_since = Some(now);
+        }
+        fired
+    }
+
+    /// Header tint 0..1: three quick pulses after an alert fires
+    fn flash(&self, now: f64) -> f32 {
+        let Some(since) = self.flash_since else {
+            return 0.0;
+        };
+        let age = now - since;
+        if !(0.0..ALERT_FLASH_SECS).contains(&age) {
+            return 0.0;
+        }
+        let pulse = (age * std::f64::consts::TAU).cos() * -0.5 + 0.5;
+        (pulse * (1.0 - age / ALERT_FLASH_SECS)) as f32
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.tripped.clear();
+        self.flash_since = None;
+    }
+}
+
+impl JamApp {
+    /// Diagnostics tick: sample the metrics and fire any rule that crossed its threshold
+    pub(crate) fn evaluate_alerts(&mut self) {
+        if self.paused || !self.alert_rules.iter().any(|r| r.enabled) {
+            self.alerts.tripped.clear();
+            return;
+        }
+        let view_now = self.view_now();
+        let disconnects = with_data!(self, |data| {
+            data.events.compute_aggregate_rate(
+                view_now,
+                DISCONNECT_RATE_WINDOW,
+                1,
+                Some(DISCONNECT_EVENTS),
+            )
+        });
+        let sample = AlertSample {
+            dropped_per_sec: self.diag_node_dropped_sec + self.diag_server_dropped_sec,
+            stalled_nodes: self.stalled_nodes.len(),
+            disconnects_per_sec: disconnects.first().copied().unwrap_or(0.0),
+            partitions: self.partitions.partition().map_or(1, <[usize]>::len),
+        };
+        let fired = self.alerts.evaluate(&self.alert_rules, &sample, now_seconds());
+        for &(metric, value) in &fired {
+            warn!(metric = metric.label(), value, "Alert threshold crossed");
+        }
+        if !fired.is_empty() && self.alert_sound {
+            self.alerts.beep();
+        }
+    }
+
+    /// Header fill, tinted red while an alert flash is running
+    pub(crate) fn header_fill(&self) -> egui::Color32 {
+        let base = colors::bg_primary();
+        let flash = self.alerts.flash(now_seconds());
+        if flash <= 0.0 {
+            return base;
+        }
+        egui::Color32::from_rgb(160, 40, 40).lerp_to_gamma(base, 1.0 - flash * 0.6)
+    }
+
+    /// Header badge naming the tripped rules
+    pub(crate) fn render_alert_badge(&self, ui: &mut egui::Ui) {
+        if self.alerts.tripped.is_empty() {
+            return;
+        }
+        let text = self
+            .alerts
+            .tripped
+            .iter()
+            .map(|(metric, value)| format!("{} {:.1}", metric.label(), value))
+            .collect::<Vec<_>>()
+            .join(", ");
+        ui.label(
+            egui::RichText::new(format!("⚠ {}", text))
+                .color(egui::Color32::from_rgb(200, 100, 100)),
+        )
+        .on_hover_text("Alert rules over their threshold (see Settings)");
+        ui.add_space(10.0);
+    }
+
+    pub(crate) fn render_alert_settings(&mut self, ui: &mut egui::Ui) {
+        ui.label(egui::RichText::new("Alerts:").color(colors::text_muted()));
+        for rule in &mut self.alert_rules {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut rule.enabled, rule.metric.label());
+                ui.label(egui::RichText::new(">").color(colors::text_muted()));
+                let speed = match rule.metric {
+                    AlertMetric::StalledNodes | AlertMetric::Partitions => 0.1,
+                    _ => 1.0,
+                };
+                ui.add_enabled(
+                    rule.enabled,
+                    egui::DragValue::new(&mut rule.threshold)
+                        .range(0.0..=100_000.0)
+                        .speed(speed)
+                        .max_decimals(1),
+                );
+            });
+        }
+        let sound = ui.checkbox(&mut self.alert_sound, "Beep when an alert fires");
+        if cfg!(target_arch = "wasm32") {
+            sound.on_hover_text("Short tone through the browser's audio output");
+        } else {
+            sound.on_hover_text("Browser build only; native flashes the header");
+        }
+    }
+}
+
+impl AlertState {
+    /// Short 880 Hz tone through Web Audio; the context is created once and reused
+    #[cfg(target_arch = "wasm32")]
+    fn beep(&mut self) {
+        const BEEP_SECS: f64 = 0.15;
+        if self.audio.is_none() {
+            self.audio = web_sys::AudioContext::new().ok();
+        }
+        let Some(audio) = &self.audio else {
+            return;
+        };
+        // Autoplay policy suspends contexts created without a user gesture
+        let _ = audio.resume();
+        let result = (|| -> Result<(), wasm_bindgen::JsValue> {
+            let oscillator = audio.create_oscillator()?;
+            let gain = audio.create_gain()?;
+            oscillator.frequency().set_value(880.0);
+            gain.gain().set_value(0.1);
+            oscillator.connect_with_audio_node(&gain)?;
+            gain.connect_with_audio_node(&audio.destination())?;
+            oscillator.start()?;
+            oscillator.stop_with_when(audio.current_time() + BEEP_SECS)?;
+            Ok(())
+        })();
+        if let Err(e) = result {
+            warn!(error = ?e, "Alert beep failed");
+        }
+    }
+
+    /// No bundled sound on native; the header flash is the cue
+    #[cfg(not(target_arch = "wasm32"))]
+    fn beep(&mut self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules() -> Vec<AlertRule> {
+        let mut rules = default_alert_rules();
+        rules[AlertMetric::StalledNodes.slot()].enabled = true;
+        rules[AlertMetric::StalledNodes.slot()].threshold = 2.0;
+        rules
+    }
+
+    #[test]
+    fn tripped_rule_fires_once_per_cooldown() {
+        let mut state = AlertState::default();
+        let sample = AlertSample { stalled_nodes: 3, dropped_per_sec: 1e6, ..Default::default() };
+
+        // Drops are far over their threshold but that rule is disabled
+        let fired = state.evaluate(&rules(), &sample, 100.0);
+        assert_eq!(fired, vec![(AlertMetric::StalledNodes, 3.0)]);
+        assert!(state.flash(100.5) > 0.0);
+
+        assert!(state.evaluate(&rules(), &sample, 101.0).is_empty());
+        assert_eq!(state.tripped.len(), 1);
+        assert_eq!(state.evaluate(&rules(), &sample, 100.0 + ALERT_COOLDOWN_SECS).len(), 1);
+
+        let calm = AlertSample { stalled_nodes: 2, ..Default::default() };
+        assert!(state.evaluate(&rules(), &calm, 200.0).is_empty());
+        assert!(state.tripped.is_empty());
+        assert_eq!(state.flash(200.0), 0.0);
+    }
+
+    #[test]
+    fn fit_alert_rules_keeps_one_rule_per_metric() {
+        let stored = [
+            AlertRule { metric: AlertMetric::DisconnectsPerSec, enabled: true, threshold: 2.5 },
+            AlertRule { metric: AlertMetric::StalledNodes, enabled: true, threshold: f64::NAN },
+        ];
+        let rules = fit_alert_rules(&stored);
+        assert_eq!(rules.len(), AlertMetric::ALL.len());
+        assert_eq!(rules[AlertMetric::DisconnectsPerSec.slot()], stored[0]);
+        assert_eq!(rules[AlertMetric::StalledNodes.slot()], default_alert_rules()[1]);
+        assert!(!rules[AlertMetric::DroppedPerSec.slot()].enabled);
+        // Settings saved before the partition rule existed get it, disabled
+        assert!(!rules[AlertMetric::Partitions.slot()].enabled);
+    }
+
+    #[test]
+    fn partition_rule_trips_on_a_split() {
+        let mut state = AlertState::default();
+        let mut rules = default_alert_rules();
+        rules[AlertMetric::Partitions.slot()].enabled = true;
+        let whole = AlertSample { partitions: 1, ..Default::default() };
+        assert!(state.evaluate(&rules, &whole, 0.0).is_empty());
+        let split = AlertSample { partitions: 2, ..Default::default() };
+        assert_eq!(state.evaluate(&rules, &split, 1.0), vec![(AlertMetric::Partitions, 2.0)]);
+    }
+}
@@ -0,0 +1,1278 @@
+//! Analytics tab: block and work statistics aggregated over the shared time window
+
+use std::collections::{HashMap, HashSet};
+use eframe::egui;
+use crate::core::events::{
+    event_name, BlockOutline, ServiceId, CONNECT_EVENTS, DISCONNECT_EVENTS, EVENT_CATEGORIES,
+    REASON_EVENT_TYPES,
+};
+use crate::core::{EpochTickets, EventType, GasSample, GasSource, PeerAddress};
+use crate::theme::colors;
+use super::{JamApp, with_data, RATE_BUCKETS};
+use super::graphs::{format_bytes, validator_color};
+use super::settings::format_window;
+
+/// Bars in the block size histogram
+const BLOCK_SIZE_BINS: usize = 24;
+/// Panels never shrink below this height; the tab scrolls instead
+const MIN_PANEL_HEIGHT: f32 = 200.0;
+/// Reasons listed in the misbehavior and reason breakdown panels
+const REASON_ROWS: usize = 8;
+/// Remote addresses listed when hovering the churn panel's address count
+const CHURN_ADDRESS_ROWS: usize = 8;
+/// Services plotted individually in the gas panel; the rest are summed into "other services"
+const GAS_TOP_SERVICES: usize = 6;
+
+/// Block counts per size bin, with the mean ticket and guarantee counts of the blocks in each bin
+struct SizeHistogram {
+    /// Lower edge of the first bin, bytes
+    start: f64,
+    /// Bin width, bytes
+    width: f64,
+    counts: Vec<u32>,
+    tickets: Vec<f64>,
+    guarantees: Vec<f64>,
+}
+
+impl SizeHistogram {
+    fn new(outlines: &[&BlockOutline], bins: usize) -> Option<Self> {
+        let min = outlines.iter().map(|o| o.size_bytes).min()? as f64;
+        let max = outlines.iter().map(|o| o.size_bytes).max()? as f64;
+        let width = ((max - min) / bins as f64).max(1.0);
+        let mut counts = vec![0u32; bins];
+        let mut tickets = vec![0.0; bins];
+        let mut guarantees = vec![0.0; bins];
+        for outline in outlines {
+            let bin = (((outline.size_bytes as f64 - min) / width) as usize).min(bins - 1);
+            counts[bin] += 1;
+            tickets[bin] += outline.num_tickets as f64;
+            guarantees[bin] += outline.num_guarantees as f64;
+        }
+        for ((count, tickets), guarantees) in counts.iter().zip(&mut tickets).zip(&mut guarantees) {
+            if *count > 0 {
+                *tickets /= *count as f64;
+                *guarantees /= *count as f64;
+            }
+        }
+        Some(Self { start: min, width, counts, tickets, guarantees })
+    }
+
+    fn center(&self, bin: usize) -> f64 {
+        self.start + (bin as f64 + 0.5) * self.width
+    }
+
+    /// (bin center, value) for the bins holding at least one block
+    fn overlay(&self, values: &[f64]) -> Vec<[f64; 2]> {
+        self.counts
+            .iter()
+            .zip(values)
+            .enumerate()
+            .filter(|(_, (&count, _))| count > 0)
+            .map(|(bin, (_, &value))| [self.center(bin), value])
+            .collect()
+    }
+}
+
+/// Nearest-rank percentile of an ascending slice
+fn percentile(sorted: &[u32], p: f64) -> Option<u32> {
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted.get(rank.clamp(1, sorted.len().max(1)) - 1).copied()
+}
+
+/// Mean, p50 and p95 of the block sizes
+fn size_stats(sizes: &mut [u32]) -> Option<(f64, u32, u32)> {
+    if sizes.is_empty() {
+        return None;
+    }
+    sizes.sort_unstable();
+    let mean = sizes.iter().map(|&s| s as f64).sum::<f64>() / sizes.len() as f64;
+    Some((mean, percentile(sizes, 50.0)?, percentile(sizes, 95.0)?))
+}
+
+/// Gas amount with a decimal suffix (1_250_000 → "1.25M")
+fn format_gas(gas: f64) -> String {
+    const UNITS: [&str; 5] = ["", "K", "M", "G", "T"];
+    let mut value = gas;
+    let mut unit = 0;
+    while value.abs() >= 1000.0 && unit < UNITS.len() - 1 {
+        value /= 1000.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{:.0}", value)
+    } else {
+        format!("{:.2}{}", value, UNITS[unit])
+    }
+}
+
+/// Nanoseconds in the largest unit that keeps the value at least 1 (1_500 → "1.5 µs")
+fn format_nanos(ns: f64) -> String {
+    const UNITS: [&str; 4] = ["ns", "µs", "ms", "s"];
+    let mut value = ns;
+    let mut unit = 0;
+    while value.abs() >= 1000.0 && unit < UNITS.len() - 1 {
+        value /= 1000.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{:.0} {}", value, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// One plotted gas series: (seconds before now, gas) points and its totals
+struct GasGroup {
+    label: String,
+    color: egui::Color32,
+    points: Vec<[f64; 2]>,
+    gas: u64,
+    elapsed_ns: u64,
+}
+
+/// Split samples into plot series, largest total gas first. Refinement is always one series;
+/// accumulation is one series, or (`by_service`) one per top-`top` service plus the rest.
+fn group_gas_samples(
+    samples: &[GasSample],
+    now: f64,
+    by_service: bool,
+    top: usize,
+) -> Vec<GasGroup> {
+    let mut service_gas: HashMap<ServiceId, u64> = HashMap::new();
+    for sample in samples {
+        if let GasSource::Accumulate(service) = sample.source {
+            *service_gas.entry(service).or_default() += sample.gas_used;
+        }
+    }
+    let mut ranked: Vec<(ServiceId, u64)> = service_gas.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    let shown: HashSet<ServiceId> = ranked.iter().take(top).map(|&(s, _)| s).collect();
+
+    let mut groups: Vec<GasGroup> = Vec::new();
+    for sample in samples {
+        let (label, color) = match sample.source {
+            GasSource::Refine => ("refine".to_string(), egui::Color32::from_rgb(200, 200, 100)),
+            GasSource::Accumulate(service) if by_service && shown.contains(&service) => {
+                (format!("service {}", service), validator_color(service as u16))
+            }
+            GasSource::Accumulate(_) if by_service => {
+                ("other services".to_string(), colors::text_muted())
+            }
+            GasSource::Accumulate(_) => {
+                ("accumulate".to_string(), egui::Color32::from_rgb(100, 200, 100))
+            }
+        };
+        let group = match groups.iter_mut().position(|g| g.label == label) {
+            Some(i) => &mut groups[i],
+            None => {
+                groups.push(GasGroup { label, color, points: Vec::new(), gas: 0, elapsed_ns: 0 });
+                groups.last_mut().expect("just pushed")
+            }
+        };
+        group.points.push([sample.timestamp - now, sample.gas_used as f64]);
+        group.gas += sample.gas_used;
+        group.elapsed_ns += sample.elapsed_ns;
+    }
+    groups.sort_by(|a, b| b.gas.cmp(&a.gas));
+    groups
+}
+
+/// successes / (successes + failures), `None` when neither happened
+fn success_rate(successes: usize, failures: usize) -> Option<f64> {
+    let total = successes + failures;
+    (total > 0).then(|| successes as f64 / total as f64)
+}
+
+/// Green at or above 95%, yellow down to 80%, red below
+fn success_rate_color(rate: f64) -> egui::Color32 {
+    if rate >= 0.95 {
+        egui::Color32::from_rgb(100, 200, 100)
+    } else if rate >= 0.8 {
+        egui::Color32::from_rgb(200, 200, 100)
+    } else {
+        egui::Color32::from_rgb(200, 100, 100)
+    }
+}
+
+/// A node × event-type heatmap column: one event type, or a whole category when collapsed
+struct HeatColumn {
+    name: &'static str,
+    /// Event types summed into the column
+    event_types: &'static [EventType],
+}
+
+impl HeatColumn {
+    fn count(&self, counts: &[u32; 256]) -> u32 {
+        self.event_types.iter().map(|et| counts[et.idx()]).sum()
+    }
+}
+
+/// Every category when `by_category`, otherwise every event type seen on some node
+fn heatmap_columns(rows: &[(u16, [u32; 256])], by_category: bool) -> Vec<HeatColumn> {
+    if by_category {
+        return EVENT_CATEGORIES
+            .iter()
+            .map(|category| HeatColumn { name: category.name, event_types: category.event_types })
+            .collect();
+    }
+    EVENT_CATEGORIES
+        .iter()
+        .flat_map(|category| category.event_types)
+        .filter(|et| rows.iter().any(|(_, counts)| counts[et.idx()] > 0))
+        .map(|et| HeatColumn { name: event_name(*et), event_types: std::slice::from_ref(et) })
+        .collect()
+}
+
+/// Up to `n` addresses with the most connection attempts, as (address, attempts), busiest
+/// first; ties in address order
+fn busiest_addresses(addresses: &[PeerAddress], n: usize) -> Vec<(PeerAddress, usize)> {
+    let mut counts: HashMap<PeerAddress, usize> = HashMap::new();
+    for &address in addresses {
+        *counts.entry(address).or_default() += 1;
+    }
+    let mut busiest: Vec<(PeerAddress, usize)> = counts.into_iter().collect();
+    busiest.sort_by(|a, b| {
+        b.1.cmp(&a.1).then(a.0.ipv6.cmp(&b.0.ipv6)).then(a.0.port.cmp(&b.0.port))
+    });
+    busiest.truncate(n);
+    busiest
+}
+
+/// Cell brightness in 0..=1: log-scaled against the busiest node of the column
+fn heat_level(count: u32, column_max: u32) -> f32 {
+    if count == 0 || column_max == 0 {
+        return 0.0;
+    }
+    ((1.0 + count as f32).ln() / (1.0 + column_max as f32).ln()).min(1.0)
+}
+
+/// Share of the previous stage's events missing at the next one ("−35%"), or the growth
+/// ("+20%") when a stage fans out. `None` when the previous stage saw nothing.
+fn drop_off_label(prev: usize, next: usize) -> Option<String> {
+    if prev == 0 {
+        return None;
+    }
+    let change = (next as f64 / prev as f64 - 1.0) * 100.0;
+    Some(if change < 0.0 {
+        format!("−{:.0}%", -change)
+    } else {
+        format!("+{:.0}%", change)
+    })
+}
+
+/// Share of set bits over all validators' rows, `None` when there are no bits
+fn coverage(rows: &[(String, Vec<bool>)]) -> Option<f64> {
+    let total: usize = rows.iter().map(|(_, bits)| bits.len()).sum();
+    let set = rows.iter().flat_map(|(_, bits)| bits).filter(|&&b| b).count();
+    (total > 0).then(|| set as f64 / total as f64)
+}
+
+/// Ranked horizontal bars of (reason, count, reporters), largest first. Hovering a row
+/// shows its full reason and, when known, the reporting validators.
+fn draw_reason_bars(ui: &mut egui::Ui, reasons: &[(String, u64, Vec<String>)], empty_text: &str) {
+    const COUNT_WIDTH: f32 = 50.0;
+
+    let (rect, response) = ui.allocate_exact_size(ui.available_size(), egui::Sense::hover());
+    if reasons.is_empty() {
+        ui.painter().text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            empty_text,
+            egui::FontId::proportional(12.0),
+            colors::text_muted(),
+        );
+        return;
+    }
+
+    let painter = ui.painter_at(rect);
+    let font = egui::FontId::proportional(12.0);
+    let row_h = (rect.height() / REASON_ROWS as f32).min(24.0);
+    let bar_span = (rect.width() - COUNT_WIDTH).max(1.0);
+    let max_count = reasons.first().map_or(1, |r| r.1).max(1) as f32;
+
+    for (row, (reason, count, _)) in reasons.iter().enumerate() {
+        let top = rect.top() + row as f32 * row_h;
+        let width = bar_span * *count as f32 / max_count;
+        painter.rect_filled(
+            egui::Rect::from_min_size(
+                egui::pos2(rect.left(), top + 1.0),
+                egui::vec2(width, row_h - 2.0),
+            ),
+            0.0,
+            egui::Color32::from_rgb(200, 100, 100).gamma_multiply(0.5),
+        );
+        painter.text(
+            egui::pos2(rect.left() + 4.0, top + row_h / 2.0),
+            egui::Align2::LEFT_CENTER,
+            reason,
+            font.clone(),
+            colors::text_primary(),
+        );
+        painter.text(
+            egui::pos2(rect.right(), top + row_h / 2.0),
+            egui::Align2::RIGHT_CENTER,
+            count.to_string(),
+            font.clone(),
+            colors::text_secondary(),
+        );
+    }
+
+    if let Some(pos) = response.hover_pos() {
+        let row = ((pos.y - rect.top()) / row_h) as usize;
+        if let Some((reason, count, reporters)) = reasons.get(row) {
+            let text = if reporters.is_empty() {
+                format!("{} ({})", reason, count)
+            } else {
+                format!("{} ({})\nreported by {}", reason, count, reporters.join(", "))
+            };
+            response.on_hover_text_at_pointer(text);
+        }
+    }
+}
+
+impl JamApp {
+    pub(crate) fn render_analytics_tab(&mut self, ui: &mut egui::Ui) {
+        let available = ui.available_size();
+        // Three rows fit the screen; further rows scroll into view
+        let panel_height = ((available.y - 20.0) / 3.0).max(MIN_PANEL_HEIGHT);
+        let half_width = (available.x - 20.0) / 2.0;
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            self.render_analytics_panels(ui, half_width, panel_height);
+        });
+    }
+
+    fn render_analytics_panels(&mut self, ui: &mut egui::Ui, half_width: f32, panel_height: f32) {
+        ui.horizontal(|ui| {
+            ui.allocate_ui(egui::vec2(half_width, panel_height), |ui| {
+                self.render_block_sizes(ui);
+            });
+
+            ui.add_space(10.0);
+
+            ui.allocate_ui(egui::vec2(half_width, panel_height), |ui| {
+                self.render_gas_usage(ui);
+            });
+        });
+
+        ui.add_space(10.0);
+
+        ui.horizontal(|ui| {
+            ui.allocate_ui(egui::vec2(half_width, panel_height), |ui| {
+                self.render_pipeline_funnel(ui);
+            });
+
+            ui.add_space(10.0);
+
+            ui.allocate_ui(egui::vec2(half_width, panel_height), |ui| {
+                self.render_assurance_coverage(ui);
+            });
+        });
+
+        ui.add_space(10.0);
+
+        ui.horizontal(|ui| {
+            ui.allocate_ui(egui::vec2(half_width, panel_height), |ui| {
+                self.render_connection_churn(ui);
+            });
+
+            ui.add_space(10.0);
+
+            ui.allocate_ui(egui::vec2(half_width, panel_height), |ui| {
+                self.render_misbehavior(ui);
+            });
+        });
+
+        ui.add_space(10.0);
+
+        ui.horizontal(|ui| {
+            ui.allocate_ui(egui::vec2(half_width, panel_height), |ui| {
+                self.render_reason_breakdown(ui);
+            });
+
+            ui.add_space(10.0);
+
+            ui.allocate_ui(egui::vec2(half_width, panel_height), |ui| {
+                self.render_ticket_timeline(ui);
+            });
+        });
+
+        ui.add_space(10.0);
+
+        ui.horizontal(|ui| {
+            ui.allocate_ui(egui::vec2(half_width, panel_height), |ui| {
+                self.render_recovery_success(ui);
+            });
+
+            ui.add_space(10.0);
+
+            ui.allocate_ui(egui::vec2(half_width, panel_height), |ui| {
+                self.render_type_heatmap(ui);
+            });
+        });
+    }
+
+    /// Histogram of distinct block sizes, with the mean tickets and guarantees per bin overlaid
+    fn render_block_sizes(&self, ui: &mut egui::Ui) {
+        use egui_plot::{Bar, BarChart, Legend, Line, Plot, PlotPoints};
+
+        let now = self.view_now();
+        let (mut sizes, histogram) = with_data!(self, |data| {
+            let outlines = data.events.block_outlines(now, self.time_window as f64);
+            let sizes: Vec<u32> = outlines.iter().map(|o| o.size_bytes).collect();
+            (sizes, SizeHistogram::new(&outlines, BLOCK_SIZE_BINS))
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(
+                egui::RichText::new("Block Sizes")
+                    .color(colors::text_muted())
+                    .size(14.0),
+            );
+            if let Some((mean, p50, p95)) = size_stats(&mut sizes) {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "{} blocks  mean {}  p50 {}  p95 {}",
+                        sizes.len(),
+                        format_bytes(mean),
+                        format_bytes(p50 as f64),
+                        format_bytes(p95 as f64),
+                    ))
+                    .color(colors::text_secondary())
+                    .size(11.0),
+                );
+            }
+        });
+
+        let Some(histogram) = histogram else {
+            ui.label(
+                egui::RichText::new("No blocks authored or imported yet")
+                    .color(colors::text_muted())
+                    .size(11.0),
+            );
+            return;
+        };
+
+        let bars: Vec<Bar> = histogram
+            .counts
+            .iter()
+            .enumerate()
+            .map(|(bin, &count)| {
+                Bar::new(histogram.center(bin), count as f64)
+                    .width(histogram.width * 0.9)
+                    .fill(colors::shade(255, 140))
+            })
+            .collect();
+        let tickets = histogram.overlay(&histogram.tickets);
+        let guarantees = histogram.overlay(&histogram.guarantees);
+
+        Plot::new("block_sizes")
+            .show_axes([true, true])
+            .show_grid(false)
+            .allow_zoom(false)
+            .allow_drag(false)
+            .allow_scroll(false)
+            .show_background(false)
+            .include_y(0.0)
+            .legend(Legend::default())
+            .x_axis_formatter(|mark, _range| format_bytes(mark.value))
+            .label_formatter(|name, value| match name {
+                "" => format_bytes(value.x),
+                "blocks" => format!("{} blocks={:.0}", format_bytes(value.x), value.y),
+                _ => format!("{} {}={:.1}", format_bytes(value.x), name, value.y),
+            })
+            .show(ui, |plot_ui| {
+                plot_ui.bar_chart(BarChart::new(bars).name("blocks"));
+                plot_ui.line(
+                    Line::new(PlotPoints::from(tickets))
+                        .color(egui::Color32::from_rgb(200, 200, 100))
+                        .width(1.5)
+                        .name("tickets/block"),
+                );
+                plot_ui.line(
+                    Line::new(PlotPoints::from(guarantees))
+                        .color(egui::Color32::from_rgb(100, 200, 100))
+                        .width(1.5)
+                        .name("guarantees/block"),
+                );
+            });
+    }
+
+    /// Success rate of each bundle/segment recovery step as a percentage bar; raw counts on hover
+    fn render_recovery_success(&self, ui: &mut egui::Ui) {
+        const LABEL_WIDTH: f32 = 160.0;
+        const VALUE_WIDTH: f32 = 50.0;
+
+        ui.label(
+            egui::RichText::new("Recovery Success Rate")
+                .color(colors::text_muted())
+                .size(14.0),
+        );
+
+        let now = self.view_now();
+        let steps = with_data!(self, |data| {
+            data.events.recovery_outcome_counts(now, self.time_window as f64)
+        });
+
+        let (rect, response) = ui.allocate_exact_size(ui.available_size(), egui::Sense::hover());
+        let painter = ui.painter_at(rect);
+        let font = egui::FontId::proportional(12.0);
+        let row_h = rect.height() / steps.len().max(1) as f32;
+        let bar_left = rect.left() + LABEL_WIDTH;
+        let bar_span = (rect.width() - LABEL_WIDTH - VALUE_WIDTH).max(1.0);
+
+        for (row, &(step, successes, failures)) in steps.iter().enumerate() {
+            let top = rect.top() + row as f32 * row_h;
+            let mid = top + row_h / 2.0;
+            painter.text(
+                egui::pos2(rect.left(), mid),
+                egui::Align2::LEFT_CENTER,
+                step,
+                font.clone(),
+                colors::text_secondary(),
+            );
+            let track = egui::Rect::from_min_size(
+                egui::pos2(bar_left, top + row_h * 0.2),
+                egui::vec2(bar_span, row_h * 0.6),
+            );
+            painter.rect_filled(track, 0.0, colors::shade(255, 20));
+            let (label, color) = match success_rate(successes, failures) {
+                Some(rate) => {
+                    let color = success_rate_color(rate);
+                    let mut filled = track;
+                    filled.set_width(bar_span * rate as f32);
+                    painter.rect_filled(filled, 0.0, color.gamma_multiply(0.7));
+                    (format!("{:.0}%", rate * 100.0), color)
+                }
+                None => ("-".to_string(), colors::text_muted()),
+            };
+            painter.text(
+                egui::pos2(rect.right(), mid),
+                egui::Align2::RIGHT_CENTER,
+                label,
+                font.clone(),
+                color,
+            );
+        }
+
+        if let Some(pos) = response.hover_pos() {
+            let row = (((pos.y - rect.top()) / row_h) as usize).min(steps.len().saturating_sub(1));
+            if let Some(&(step, successes, failures)) = steps.get(row) {
+                response.on_hover_text_at_pointer(format!(
+                    "{}\n{} succeeded, {} failed in the last {}",
+                    step,
+                    successes,
+                    failures,
+                    format_window(self.time_window),
+                ));
+            }
+        }
+    }
+
+    /// Node × event-type grid in the schema colors. Each cell is scaled against the busiest
+    /// node of its column, so a node that never emits what the others do stands out.
+    fn render_type_heatmap(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(
+                egui::RichText::new("Events per Node and Type")
+                    .color(colors::text_muted())
+                    .size(14.0),
+            );
+            ui.checkbox(&mut self.heatmap_by_category, "by category")
+                .on_hover_text("One column per event category instead of per event type");
+        });
+
+        let now = self.view_now();
+        let (node_ids, rows): (Vec<String>, Vec<(u16, [u32; 256])>) = with_data!(self, |data| {
+            data.events
+                .per_node_type_counts(now, self.time_window as f64)
+                .into_iter()
+                .map(|(index, counts)| {
+                    let node_id = data.events.node_id_at(index).unwrap_or_default().to_string();
+                    (node_id, (index, counts))
+                })
+                .unzip()
+        });
+        let columns = heatmap_columns(&rows, self.heatmap_by_category);
+        let total: u32 = rows.iter().map(|(_, counts)| counts.iter().sum::<u32>()).sum();
+        if total == 0 {
+            ui.label(
+                egui::RichText::new(format!("No events in the last {}", format_window(self.time_window)))
+                    .color(colors::text_muted())
+                    .size(11.0),
+            );
+            return;
+        }
+
+        let column_max: Vec<u32> = columns
+            .iter()
+            .map(|column| rows.iter().map(|(_, counts)| column.count(counts)).max().unwrap_or(0))
+            .collect();
+        let (rect, response) = ui.allocate_exact_size(ui.available_size(), egui::Sense::hover());
+        let painter = ui.painter_at(rect);
+        let cell_w = rect.width() / columns.len().max(1) as f32;
+        let cell_h = rect.height() / rows.len().max(1) as f32;
+        // Leave a hairline between cells while they are large enough to spare it
+        let gap = if cell_w > 4.0 && cell_h > 4.0 { 1.0 } else { 0.0 };
+        painter.rect_filled(rect, 0.0, colors::shade(255, 8));
+
+        for (col, column) in columns.iter().enumerate() {
+            let color = self.get_event_color(column.event_types[0]);
+            for (row, (_, counts)) in rows.iter().enumerate() {
+                let level = heat_level(column.count(counts), column_max[col]);
+                if level == 0.0 {
+                    continue;
+                }
+                let cell = egui::Rect::from_min_size(
+                    egui::pos2(rect.left() + col as f32 * cell_w, rect.top() + row as f32 * cell_h),
+                    egui::vec2(cell_w - gap, cell_h - gap),
+                );
+                painter.rect_filled(cell, 0.0, color.gamma_multiply(0.15 + 0.85 * level));
+            }
+        }
+
+        if let Some(pos) = response.hover_pos() {
+            let col = (((pos.x - rect.left()) / cell_w) as usize).min(columns.len() - 1);
+            let row = (((pos.y - rect.top()) / cell_h) as usize).min(rows.len() - 1);
+            let (index, counts) = &rows[row];
+            let column = &columns[col];
+            response.on_hover_text_at_pointer(format!(
+                "{} {}\n{}: {} in the last {}",
+                index,
+                self.node_label(&node_ids[row]),
+                column.name,
+                column.count(counts),
+                format_window(self.time_window),
+            ));
+        }
+    }
+
+    /// Safrole progress: generated vs transferred tickets per epoch, current epoch highlighted
+    fn render_ticket_timeline(&self, ui: &mut egui::Ui) {
+        use egui_plot::{Bar, BarChart, Legend, Plot};
+
+        let epochs: Vec<(u32, EpochTickets)> =
+            with_data!(self, |data| data.tickets.epochs().map(|(e, c)| (e, *c)).collect());
+
+        ui.horizontal(|ui| {
+            ui.label(
+                egui::RichText::new("Safrole Tickets")
+                    .color(colors::text_muted())
+                    .size(14.0),
+            );
+            if let Some((epoch, counts)) = epochs.last() {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "epoch {}  {} generating  {} generated  {} transferred  {} failed",
+                        epoch, counts.generating, counts.generated, counts.transferred, counts.failed,
+                    ))
+                    .color(colors::text_secondary())
+                    .size(11.0),
+                );
+            }
+        });
+
+        if epochs.is_empty() {
+            ui.label(
+                egui::RichText::new("No ticket events yet")
+                    .color(colors::text_muted())
+                    .size(11.0),
+            );
+            return;
+        }
+
+        let current = epochs.last().map(|&(epoch, _)| epoch);
+        let bars = |offset: f64, value: fn(&EpochTickets) -> u32, color: egui::Color32| -> Vec<Bar> {
+            epochs
+                .iter()
+                .map(|(epoch, counts)| {
+                    // Earlier epochs are dimmed so the running one stands out
+                    let fill = if Some(*epoch) == current { color } else { color.gamma_multiply(0.5) };
+                    Bar::new(*epoch as f64 + offset, value(counts) as f64).width(0.35).fill(fill)
+                })
+                .collect()
+        };
+        let generated = bars(-0.2, |c| c.generated, egui::Color32::from_rgb(200, 200, 100));
+        let transferred = bars(0.2, |c| c.transferred, egui::Color32::from_rgb(100, 200, 100));
+
+        Plot::new("ticket_timeline")
+            .show_axes([true, true])
+            .show_grid(false)
+            .allow_zoom(false)
+            .allow_drag(false)
+            .allow_scroll(false)
+            .show_background(false)
+            .include_y(0.0)
+            .legend(Legend::default())
+            .x_axis_formatter(|mark, _range| {
+                // Label whole epochs only
+                if mark.value.fract() == 0.0 {
+                    format!("{:.0}", mark.value)
+                } else {
+                    String::new()
+                }
+            })
+            .label_formatter(|name, value| match name {
+                "" => format!("epoch {:.0}", value.x.round()),
+                _ => format!("epoch {:.0} {}={:.0}", value.x.round(), name, value.y),
+            })
+            .show(ui, |plot_ui| {
+                plot_ui.bar_chart(BarChart::new(generated).name("generated"));
+                plot_ui.bar_chart(BarChart::new(transferred).name("transferred"));
+            });
+    }
+
+    /// Work-package funnel: one horizontal bar per pipeline stage, with the drop-off
+    /// from the stage above
+    fn render_pipeline_funnel(&self, ui: &mut egui::Ui) {
+        const LABEL_WIDTH: f32 = 100.0;
+        const VALUE_WIDTH: f32 = 110.0;
+
+        ui.label(
+            egui::RichText::new("Work-Package Pipeline")
+                .color(colors::text_muted())
+                .size(14.0),
+        );
+
+        let now = self.view_now();
+        let stages = with_data!(self, |data| {
+            data.events.pipeline_stage_counts(now, self.time_window as f64)
+        });
+
+        let (rect, _) = ui.allocate_exact_size(ui.available_size(), egui::Sense::hover());
+        let painter = ui.painter_at(rect);
+        let font = egui::FontId::proportional(12.0);
+        let row_h = rect.height() / stages.len().max(1) as f32;
+        let bar_left = rect.left() + LABEL_WIDTH;
+        let bar_span = (rect.width() - LABEL_WIDTH - VALUE_WIDTH).max(1.0);
+        let max_count = stages.iter().map(|&(_, n)| n).max().unwrap_or(0).max(1) as f32;
+
+        let mut prev = None;
+        for (row, &(stage, count)) in stages.iter().enumerate() {
+            let top = rect.top() + row as f32 * row_h;
+            let mid = top + row_h / 2.0;
+            painter.text(
+                egui::pos2(rect.left(), mid),
+                egui::Align2::LEFT_CENTER,
+                stage,
+                font.clone(),
+                colors::text_secondary(),
+            );
+            let width = bar_span * count as f32 / max_count;
+            painter.rect_filled(
+                egui::Rect::from_min_size(
+                    egui::pos2(bar_left, top + row_h * 0.15),
+                    egui::vec2(width, row_h * 0.7),
+                ),
+                0.0,
+                egui::Color32::from_rgb(100, 255, 200).gamma_multiply(0.6),
+            );
+            painter.text(
+                egui::pos2(bar_left + width + 6.0, mid),
+                egui::Align2::LEFT_CENTER,
+                count.to_string(),
+                font.clone(),
+                colors::text_primary(),
+            );
+            if let Some((label, prev)) = prev.and_then(|p| Some((drop_off_label(p, count)?, p))) {
+                let color = if count < prev {
+                    egui::Color32::from_rgb(200, 100, 100)
+                } else {
+                    colors::text_muted()
+                };
+                painter.text(
+                    egui::pos2(rect.right(), mid),
+                    egui::Align2::RIGHT_CENTER,
+                    label,
+                    font.clone(),
+                    color,
+                );
+            }
+            prev = Some(count);
+        }
+    }
+
+    /// Latest assurance bitfield per validator: validators assuring each core as bars on top,
+    /// validator (rows) × core (columns) availability grid below
+    fn render_assurance_coverage(&self, ui: &mut egui::Ui) {
+        let rows: Vec<(String, Vec<bool>)> = with_data!(self, |data| {
+            // Bitfields are padded to whole bytes; trim to the core count when Status told us
+            let cores = match data.guarantee_queues.num_cores() {
+                0 => data.assurances.max_bits(),
+                cores => cores,
+            };
+            data.assurances
+                .rows(cores)
+                .into_iter()
+                .map(|(id, bits)| (id.to_string(), bits))
+                .collect()
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(
+                egui::RichText::new("Assurance Coverage")
+                    .color(colors::text_muted())
+                    .size(14.0),
+            );
+            if let Some(coverage) = coverage(&rows) {
+                ui.label(
+                    egui::RichText::new(format!("{:.0}% available", coverage * 100.0))
+                        .color(colors::text_secondary())
+                        .size(11.0),
+                );
+            }
+        });
+
+        let (rect, response) = ui.allocate_exact_size(ui.available_size(), egui::Sense::hover());
+        let cores = rows.first().map_or(0, |(_, bits)| bits.len());
+        if cores == 0 {
+            ui.painter().text(
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                "No assurances distributed",
+                egui::FontId::proportional(12.0),
+                colors::text_muted(),
+            );
+            return;
+        }
+
+        let per_core: Vec<usize> = (0..cores)
+            .map(|core| rows.iter().filter(|(_, bits)| bits[core]).count())
+            .collect();
+
+        let painter = ui.painter_at(rect);
+        let cell_w = rect.width() / cores as f32;
+        let bars_bottom = rect.top() + rect.height() * 0.25;
+        let grid_top = bars_bottom + 4.0;
+        let cell_h = (rect.bottom() - grid_top) / rows.len() as f32;
+
+        for (core, &count) in per_core.iter().enumerate() {
+            let x = rect.left() + core as f32 * cell_w;
+            let top = bars_bottom - (bars_bottom - rect.top()) * count as f32 / rows.len() as f32;
+            painter.rect_filled(
+                egui::Rect::from_min_max(
+                    egui::pos2(x + 0.5, top),
+                    egui::pos2(x + cell_w - 0.5, bars_bottom),
+                ),
+                0.0,
+                colors::text_secondary(),
+            );
+        }
+
+        for (row, (_, bits)) in rows.iter().enumerate() {
+            let y = grid_top + row as f32 * cell_h;
+            for (core, &available) in bits.iter().enumerate() {
+                let color = if available {
+                    egui::Color32::from_rgb(100, 200, 100)
+                } else {
+                    egui::Color32::from_gray(40)
+                };
+                let x = rect.left() + core as f32 * cell_w;
+                painter.rect_filled(
+                    egui::Rect::from_min_size(
+                        egui::pos2(x + 0.5, y),
+                        egui::vec2((cell_w - 1.0).max(1.0), (cell_h - 1.0).max(1.0)),
+                    ),
+                    0.0,
+                    color,
+                );
+            }
+        }
+
+        if let Some(pos) = response.hover_pos() {
+            let core = (((pos.x - rect.left()) / cell_w) as usize).min(cores - 1);
+            let core_line = format!("core {}: {}/{} validators", core, per_core[core], rows.len());
+            let text = if pos.y < grid_top {
+                core_line
+            } else {
+                let row = (((pos.y - grid_top) / cell_h) as usize).min(rows.len() - 1);
+                let (node_id, bits) = &rows[row];
+                let set = bits.iter().filter(|&&b| b).count();
+                format!(
+                    "{}\n{}\n{}/{} cores available ({:.0}%)",
+                    self.node_label(node_id),
+                    core_line,
+                    set,
+                    cores,
+                    set as f64 / cores as f64 * 100.0,
+                )
+            };
+            response.on_hover_text_at_pointer(text);
+        }
+    }
+
+    /// Connections coming up vs going down (or failing) per second, across all nodes
+    fn render_connection_churn(&self, ui: &mut egui::Ui) {
+        use egui_plot::{Legend, Line, Plot, PlotPoints};
+
+        let now = self.view_now();
+        let bucket = self.rate_bucket_secs();
+        let (connects, disconnects) = with_data!(self, |data| {
+            (
+                data.events.compute_aggregate_rate(now, bucket, RATE_BUCKETS, Some(CONNECT_EVENTS)),
+                data.events.compute_aggregate_rate(now, bucket, RATE_BUCKETS, Some(DISCONNECT_EVENTS)),
+            )
+        });
+        let net: f64 = (connects.iter().sum::<f64>() - disconnects.iter().sum::<f64>()) * bucket;
+        let addresses = with_data!(self, |data| {
+            data.events.peer_addresses(now, self.time_window as f64)
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(
+                egui::RichText::new("Connection Churn")
+                    .color(colors::text_muted())
+                    .size(14.0),
+            );
+            let color = if net < 0.0 {
+                egui::Color32::from_rgb(200, 100, 100)
+            } else {
+                colors::text_secondary()
+            };
+            ui.label(
+                egui::RichText::new(format!("net {:+.0} in {}", net, format_window(self.time_window)))
+                    .color(color)
+                    .size(11.0),
+            );
+            if !addresses.is_empty() {
+                let busiest = busiest_addresses(&addresses, CHURN_ADDRESS_ROWS);
+                let distinct = addresses.iter().collect::<HashSet<_>>().len();
+                let lines: Vec<String> = busiest
+                    .iter()
+                    .map(|(address, attempts)| format!("{}  ×{}", address, attempts))
+                    .collect();
+                ui.label(
+                    egui::RichText::new(format!("{} remote addresses", distinct))
+                        .color(colors::text_secondary())
+                        .size(11.0),
+                )
+                .on_hover_text(format!("Most connection attempts:\n{}", lines.join("\n")));
+            }
+        });
+
+        let line = |rates: &[f64]| -> Vec<[f64; 2]> {
+            rates.iter().enumerate().map(|(x, &rate)| [x as f64, rate]).collect()
+        };
+
+        Plot::new("connection_churn")
+            .show_axes([false, true])
+            .show_grid(false)
+            .allow_zoom(false)
+            .allow_drag(false)
+            .allow_scroll(false)
+            .show_background(false)
+            .include_x(0.0)
+            .include_x(RATE_BUCKETS as f64)
+            .include_y(0.0)
+            .include_y(1.0)
+            .legend(Legend::default())
+            .label_formatter(|name, value| {
+                let ago = (RATE_BUCKETS as f64 - value.x) * bucket;
+                if name.is_empty() {
+                    format!("t=-{:.0}s {:.0}/s", ago, value.y)
+                } else {
+                    format!("{} t=-{:.0}s {:.0}/s", name, ago, value.y)
+                }
+            })
+            .show(ui, |plot_ui| {
+                plot_ui.line(
+                    Line::new(PlotPoints::from(line(&connects)))
+                        .color(egui::Color32::from_rgb(100, 200, 100))
+                        .width(1.5)
+                        .name("connects"),
+                );
+                plot_ui.line(
+                    Line::new(PlotPoints::from(line(&disconnects)))
+                        .color(egui::Color32::from_rgb(200, 100, 100))
+                        .width(1.5)
+                        .name("disconnects + failures"),
+                );
+            });
+    }
+
+    /// `PeerMisbehaved` reasons ranked by count; hovering a row lists the reporting validators
+    fn render_misbehavior(&self, ui: &mut egui::Ui) {
+        let now = self.view_now();
+        let (total, reasons) = with_data!(self, |data| {
+            let groups = data.events.reason_groups(
+                EventType::PeerMisbehaved as u8,
+                now,
+                self.time_window as f64,
+            );
+            let total: u64 = groups.iter().map(|g| g.count).sum();
+            let reasons: Vec<(String, u64, Vec<String>)> = groups
+                .into_iter()
+                .take(REASON_ROWS)
+                .map(|g| {
+                    let reporters = g.nodes.iter().map(|id| self.node_label(id)).collect();
+                    (g.reason, g.count, reporters)
+                })
+                .collect();
+            (total, reasons)
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(
+                egui::RichText::new("Peer Misbehavior")
+                    .color(colors::text_muted())
+                    .size(14.0),
+            );
+            if total > 0 {
+                ui.label(
+                    egui::RichText::new(format!("{} reports", total))
+                        .color(egui::Color32::from_rgb(200, 100, 100))
+                        .size(11.0),
+                );
+            }
+        });
+
+        draw_reason_bars(ui, &reasons, "No misbehavior reported");
+    }
+
+    /// Reason breakdown of any event type that carries a reason, picked from a dropdown
+    fn render_reason_breakdown(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(
+                egui::RichText::new("Reason Breakdown")
+                    .color(colors::text_muted())
+                    .size(14.0),
+            );
+            egui::ComboBox::from_id_salt("reason_event_type")
+                .selected_text(event_name(self.reason_event_type))
+                .show_ui(ui, |ui| {
+                    for &et in REASON_EVENT_TYPES {
+                        ui.selectable_value(&mut self.reason_event_type, et, event_name(et));
+                    }
+                });
+        });
+
+        let now = self.view_now();
+        let reasons: Vec<(String, u64, Vec<String>)> = with_data!(self, |data| {
+            data.events
+                .reason_distribution(self.reason_event_type as u8, now, self.time_window as f64)
+                .into_iter()
+                .take(REASON_ROWS)
+                .map(|(reason, count)| (reason, count, Vec::new()))
+                .collect()
+        });
+
+        draw_reason_bars(ui, &reasons, "No events with a reason in the window");
+    }
+
+    /// Gas used per accumulation (by service) and per refined work package over time
+    fn render_gas_usage(&mut self, ui: &mut egui::Ui) {
+        use egui_plot::{Plot, PlotPoints, Points};
+
+        ui.horizontal(|ui| {
+            ui.label(
+                egui::RichText::new("Gas Used")
+                    .color(colors::text_muted())
+                    .size(14.0),
+            );
+            ui.checkbox(&mut self.gas_by_service, "per service")
+                .on_hover_text("Plot the busiest services separately instead of one accumulate series");
+        });
+
+        let now = self.view_now();
+        let window = self.time_window as f64;
+        let samples = with_data!(self, |data| data.events.gas_samples(now, window));
+        let groups = group_gas_samples(&samples, now, self.gas_by_service, GAS_TOP_SERVICES);
+
+        if groups.is_empty() {
+            ui.label(
+                egui::RichText::new("No BlockExecuted or Refined events yet")
+                    .color(colors::text_muted())
+                    .size(11.0),
+            );
+            return;
+        }
+
+        ui.horizontal_wrapped(|ui| {
+            for group in &groups {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "{}: {} gas, {}",
+                        group.label,
+                        format_gas(group.gas as f64),
+                        format_nanos(group.elapsed_ns as f64),
+                    ))
+                    .color(group.color)
+                    .size(11.0),
+                );
+            }
+        });
+
+        Plot::new("gas_usage")
+            .show_axes([true, true])
+            .show_grid(false)
+            .allow_zoom(false)
+            .allow_drag(false)
+            .allow_scroll(false)
+            .show_background(false)
+            .include_x(-window)
+            .include_x(0.0)
+            .include_y(0.0)
+            .x_axis_formatter(|mark, _range| format!("{:.0}s", mark.value))
+            .y_axis_formatter(|mark, _range| format_gas(mark.value))
+            .label_formatter(|name, value| {
+                let gas = format_gas(value.y);
+                if name.is_empty() {
+                    format!("t={:.0}s gas={}", value.x, gas)
+                } else {
+                    format!("{} t={:.0}s gas={}", name, value.x, gas)
+                }
+            })
+            .show(ui, |plot_ui| {
+                for group in groups {
+                    plot_ui.points(
+                        Points::new(PlotPoints::from(group.points))
+                            .color(group.color)
+                            .radius(2.0)
+                            .filled(true)
+                            .name(group.label),
+                    );
+                }
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outline(size_bytes: u32, num_tickets: u32, num_guarantees: u32) -> BlockOutline {
+        BlockOutline {
+            size_bytes,
+            hash: [0; 32],
+            num_tickets,
+            num_preimages: 0,
+            total_preimages_size: 0,
+            num_guarantees,
+            num_assurances: 0,
+            num_dispute_verdicts: 0,
+        }
+    }
+
+    #[test]
+    fn size_stats_use_nearest_rank() {
+        let mut sizes: Vec<u32> = (1..=100).rev().collect();
+        assert_eq!(size_stats(&mut sizes), Some((50.5, 50, 95)));
+        assert_eq!(size_stats(&mut [7]), Some((7.0, 7, 7)));
+        assert_eq!(size_stats(&mut []), None);
+    }
+
+    #[test]
+    fn drop_off_between_stages() {
+        assert_eq!(drop_off_label(200, 130).as_deref(), Some("−35%"));
+        assert_eq!(drop_off_label(100, 120).as_deref(), Some("+20%"));
+        assert_eq!(drop_off_label(50, 50).as_deref(), Some("+0%"));
+        assert_eq!(drop_off_label(0, 10), None);
+    }
+
+    #[test]
+    fn heatmap_columns_collapse_to_categories() {
+        let mut counts = [0u32; 256];
+        counts[EventType::Authoring.idx()] = 3;
+        counts[EventType::BestBlockChanged.idx()] = 1;
+        let rows = [(0u16, counts), (1u16, [0u32; 256])];
+
+        let by_type = heatmap_columns(&rows, false);
+        assert_eq!(by_type.len(), 2);
+        assert!(by_type.iter().all(|column| column.event_types.len() == 1));
+        assert_eq!(by_type.iter().map(|column| column.count(&counts)).sum::<u32>(), 4);
+
+        let by_category = heatmap_columns(&rows, true);
+        assert_eq!(by_category.len(), EVENT_CATEGORIES.len());
+        assert_eq!(by_category.iter().map(|column| column.count(&counts)).sum::<u32>(), 4);
+    }
+
+    #[test]
+    fn busiest_addresses_ranked_by_attempts() {
+        let address = |last: u8| PeerAddress { ipv6: [last; 16], port: 1 };
+        let attempts = [address(2), address(1), address(2), address(3), address(2), address(1)];
+        let busiest = busiest_addresses(&attempts, 2);
+        assert_eq!(busiest, vec![(address(2), 3), (address(1), 2)]);
+    }
+
+    #[test]
+    fn heat_level_is_relative_to_the_column() {
+        assert_eq!(heat_level(0, 10), 0.0);
+        assert_eq!(heat_level(10, 10), 1.0);
+        assert_eq!(heat_level(5, 0), 0.0);
+        assert!(heat_level(1, 10) < heat_level(5, 10));
+    }
+
+    #[test]
+    fn success_rate_over_both_outcomes() {
+        assert_eq!(success_rate(9, 1), Some(0.9));
+        assert_eq!(success_rate(0, 4), Some(0.0));
+        assert_eq!(success_rate(0, 0), None);
+        assert_ne!(success_rate_color(0.99), success_rate_color(0.5));
+    }
+
+    #[test]
+    fn coverage_counts_set_bits() {
+        let rows = vec![
+            ("a".to_string(), vec![true, false, true, true]),
+            ("b".to_string(), vec![false, false, false, true]),
+        ];
+        assert_eq!(coverage(&rows), Some(0.5));
+        assert_eq!(coverage(&[]), None);
+    }
+
+    #[test]
+    fn gas_and_time_units() {
+        assert_eq!(format_gas(950.0), "950");
+        assert_eq!(format_gas(1_250_000.0), "1.25M");
+        assert_eq!(format_gas(3.0e12), "3.00T");
+        assert_eq!(format_nanos(850.0), "850 ns");
+        assert_eq!(format_nanos(1_500.0), "1.5 µs");
+        assert_eq!(format_nanos(2.5e9), "2.5 s");
+    }
+
+    #[test]
+    fn gas_groups_by_service() {
+        let sample = |timestamp, source, gas_used| GasSample { timestamp, source, gas_used, elapsed_ns: 10 };
+        let samples = [
+            sample(1.0, GasSource::Refine, 10),
+            sample(2.0, GasSource::Accumulate(7), 500),
+            sample(2.0, GasSource::Accumulate(9), 100),
+            sample(3.0, GasSource::Accumulate(3), 50),
+            sample(4.0, GasSource::Accumulate(7), 300),
+        ];
+
+        let labels = |groups: &[GasGroup]| -> Vec<(String, u64)> {
+            groups.iter().map(|g| (g.label.clone(), g.gas)).collect()
+        };
+        let grouped = group_gas_samples(&samples, 5.0, true, 2);
+        assert_eq!(labels(&grouped), vec![
+            ("service 7".to_string(), 800),
+            ("service 9".to_string(), 100),
+            ("other services".to_string(), 50),
+            ("refine".to_string(), 10),
+        ]);
+        assert_eq!(grouped[0].points, vec![[-3.0, 500.0], [-1.0, 300.0]]);
+        assert_eq!(grouped[0].elapsed_ns, 20);
+
+        let merged = group_gas_samples(&samples, 5.0, false, 2);
+        assert_eq!(labels(&merged), vec![("accumulate".to_string(), 950), ("refine".to_string(), 10)]);
+    }
+
+    #[test]
+    fn histogram_bins_and_overlays() {
+        let blocks = [outline(0, 2, 1), outline(10, 4, 3), outline(95, 0, 5), outline(100, 0, 7)];
+        let refs: Vec<&BlockOutline> = blocks.iter().collect();
+        let histogram = SizeHistogram::new(&refs, 10).unwrap();
+        assert_eq!(histogram.width, 10.0);
+        // The largest block lands in the last bin rather than one past it
+        assert_eq!(histogram.counts, vec![1, 1, 0, 0, 0, 0, 0, 0, 0, 2]);
+        assert_eq!(histogram.overlay(&histogram.tickets), vec![[5.0, 2.0], [15.0, 4.0], [95.0, 0.0]]);
+        assert_eq!(histogram.overlay(&histogram.guarantees)[2], [95.0, 6.0]);
+
+        // All blocks the same size still make one bar
+        let same = [outline(64, 0, 0), outline(64, 0, 0)];
+        let refs: Vec<&BlockOutline> = same.iter().collect();
+        assert_eq!(SizeHistogram::new(&refs, 4).unwrap().counts, vec![2, 0, 0, 0]);
+        assert!(SizeHistogram::new(&[], 4).is_none());
+    }
+}
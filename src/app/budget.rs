@@ -0,0 +1,110 @@
+//! Adaptive message-processing budget — parse as much as the frame has room for
+
+/// Frame time to stay under (ms), 60fps
+const TARGET_FRAME_MS: f64 = 1000.0 / 60.0;
+/// Reserved for tessellation, painting and present, which happen after `update` returns (ms)
+const PAINT_HEADROOM_MS: f64 = 3.0;
+/// Messages always get at least this long, so a slow frame can't stall the stream (ms)
+const MIN_BUDGET_MS: f64 = 2.0;
+/// Upper bound even when rendering is nearly free (ms)
+const MAX_BUDGET_MS: f64 = 12.0;
+/// How fast the render estimate decays after a cheap frame; expensive frames apply at once
+const RENDER_DECAY: f64 = 0.1;
+/// Render cost assumed before the first measurement (ms)
+const INITIAL_RENDER_MS: f64 = 4.0;
+
+/// Splits each frame between message processing and rendering.
+///
+/// Render cost is measured from the end of processing to the end of `update`.
+/// The estimate rises with the first expensive frame and decays slowly, so a burst
+/// of cheap frames doesn't hand all the time back to the parser at once.
+pub(crate) struct MessageBudget {
+    render_ms: f64,
+    render_start: Option<f64>,
+}
+
+impl Default for MessageBudget {
+    fn default() -> Self {
+        Self {
+            render_ms: INITIAL_RENDER_MS,
+            render_start: None,
+        }
+    }
+}
+
+impl MessageBudget {
+    /// Milliseconds this frame may spend processing messages
+    pub(crate) fn budget_ms(&self) -> f64 {
+        (TARGET_FRAME_MS - PAINT_HEADROOM_MS - self.render_ms).clamp(MIN_BUDGET_MS, MAX_BUDGET_MS)
+    }
+
+    /// Processing finished at `now_ms`; rendering starts
+    pub(crate) fn start_render(&mut self, now_ms: f64) {
+        self.render_start = Some(now_ms);
+    }
+
+    /// The frame's UI was built at `now_ms`
+    pub(crate) fn end_render(&mut self, now_ms: f64) {
+        let Some(start) = self.render_start.take() else {
+            return;
+        };
+        let sample = (now_ms - start).max(0.0);
+        if sample > self.render_ms {
+            self.render_ms = sample;
+        } else {
+            self.render_ms += (sample - self.render_ms) * RENDER_DECAY;
+        }
+    }
+
+    /// Current render cost estimate (ms)
+    pub(crate) fn render_ms(&self) -> f64 {
+        self.render_ms
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(budget: &mut MessageBudget, t: &mut f64, render_ms: f64) {
+        budget.start_render(*t);
+        *t += render_ms;
+        budget.end_render(*t);
+        *t += 1.0;
+    }
+
+    #[test]
+    fn cheap_frames_raise_the_budget_to_the_ceiling() {
+        let mut budget = MessageBudget::default();
+        let initial = budget.budget_ms();
+        let mut t = 0.0;
+        for _ in 0..100 {
+            frame(&mut budget, &mut t, 0.5);
+        }
+        assert!(budget.budget_ms() > initial);
+        assert_eq!(budget.budget_ms(), MAX_BUDGET_MS);
+    }
+
+    #[test]
+    fn expensive_frame_backs_off_at_once_and_recovers_slowly() {
+        let mut budget = MessageBudget::default();
+        let mut t = 0.0;
+        frame(&mut budget, &mut t, 30.0);
+        assert_eq!(budget.render_ms(), 30.0);
+        assert_eq!(budget.budget_ms(), MIN_BUDGET_MS);
+
+        frame(&mut budget, &mut t, 1.0);
+        assert_eq!(budget.budget_ms(), MIN_BUDGET_MS);
+        for _ in 0..60 {
+            frame(&mut budget, &mut t, 1.0);
+        }
+        assert!(budget.budget_ms() > MIN_BUDGET_MS);
+    }
+
+    #[test]
+    fn end_without_start_is_ignored() {
+        let mut budget = MessageBudget::default();
+        budget.end_render(100.0);
+        assert_eq!(budget.render_ms(), INITIAL_RENDER_MS);
+    }
+}
@@ -0,0 +1,196 @@
+//! Connection panel — switch the jamtart WebSocket endpoint at runtime
+
+use eframe::egui;
+use crate::theme::colors;
+use super::JamApp;
+
+/// How many successfully-connected URLs to remember for the dropdown
+const MAX_RECENT_URLS: usize = 8;
+
+/// Check that `input` is a `ws://` or `wss://` URL with a plausible host (and port, if given).
+/// Returns the trimmed URL on success.
+pub(crate) fn validate_ws_url(input: &str) -> Result<String, &'static str> {
+    let url = input.trim();
+    let rest = url
+        .strip_prefix("ws://")
+        .or_else(|| url.strip_prefix("wss://"))
+        .ok_or("URL must start with ws:// or wss://")?;
+
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or("");
+    let (host, port) = if let Some(v6) = authority.strip_prefix('[') {
+        // IPv6 literal: [::1]:port
+        let (host, after) = v6.split_once(']').ok_or("Unterminated IPv6 address")?;
+        if !after.is_empty() && !after.starts_with(':') {
+            return Err("Invalid host");
+        }
+        (host, after.strip_prefix(':'))
+    } else {
+        match authority.split_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (authority, None),
+        }
+    };
+
+    if host.is_empty() {
+        return Err("Missing host");
+    }
+    let host_ok = host
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | ':'));
+    if !host_ok {
+        return Err("Invalid host");
+    }
+    if let Some(port) = port {
+        if port.parse::<u16>().is_err() {
+            return Err("Invalid port");
+        }
+    }
+
+    Ok(url.to_string())
+}
+
+/// Move `url` to the front of `recent`, dropping duplicates and the oldest entries
+pub(crate) fn push_recent_url(recent: &mut Vec<String>, url: &str) {
+    recent.retain(|u| u != url);
+    recent.insert(0, url.to_string());
+    recent.truncate(MAX_RECENT_URLS);
+}
+
+impl JamApp {
+    /// Remember the current endpoint once the socket reports Connected
+    pub(crate) fn note_connected_url(&mut self) {
+        if self.get_ws_state().is_connected()
+            && self.recent_urls.first() != Some(&self.ws_url)
+        {
+            push_recent_url(&mut self.recent_urls, &self.ws_url);
+        }
+    }
+
+    /// Validate the URL field and, if valid, switch to it (clears data from the old endpoint)
+    fn connect_to_input(&mut self) {
+        match validate_ws_url(&self.connection_url_input) {
+            Ok(url) => {
+                self.connection_error = None;
+                self.connection_url_input = url.clone();
+                // A URL typed here replaces every endpoint of a multi-endpoint session
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    self.ws_urls = vec![url.clone()];
+                }
+                self.ws_url = url;
+                self.clear_and_reconnect();
+            }
+            Err(e) => self.connection_error = Some(e.to_string()),
+        }
+    }
+
+    pub(crate) fn render_connection_panel(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_connection_panel;
+        egui::Window::new("Connection")
+            .id(egui::Id::new("connection_panel"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new("Current:").color(colors::text_muted()));
+                    ui.label(egui::RichText::new(&self.ws_url).monospace());
+                });
+
+                ui.add_space(4.0);
+
+                let mut submit = false;
+                ui.horizontal(|ui| {
+                    let edit = ui.add(
+                        egui::TextEdit::singleline(&mut self.connection_url_input)
+                            .hint_text("ws://host:port/api/ws")
+                            .desired_width(320.0),
+                    );
+                    if edit.changed() {
+                        self.connection_error = None;
+                    }
+                    if edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                        submit = true;
+                    }
+                    if ui.button("Connect").clicked() {
+                        submit = true;
+                    }
+                });
+
+                if let Some(err) = &self.connection_error {
+                    ui.label(
+                        egui::RichText::new(err.as_str())
+                            .color(egui::Color32::from_rgb(200, 100, 100))
+                            .small(),
+                    );
+                }
+
+                if !self.recent_urls.is_empty() {
+                    ui.add_space(4.0);
+                    let mut picked = None;
+                    egui::ComboBox::from_id_salt("recent_urls")
+                        .selected_text(egui::RichText::new("Recent").color(colors::text_muted()))
+                        .width(320.0)
+                        .show_ui(ui, |ui| {
+                            for url in &self.recent_urls {
+                                if ui.selectable_label(false, url.as_str()).clicked() {
+                                    picked = Some(url.clone());
+                                }
+                            }
+                        });
+                    if let Some(url) = picked {
+                        self.connection_url_input = url;
+                        self.connection_error = None;
+                    }
+                }
+
+                if submit {
+                    self.connect_to_input();
+                }
+            });
+        self.show_connection_panel = open;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_accepts_ws_and_wss() {
+        assert_eq!(
+            validate_ws_url(" ws://127.0.0.1:38080/api/ws ").as_deref(),
+            Ok("ws://127.0.0.1:38080/api/ws")
+        );
+        assert!(validate_ws_url("wss://tart.example.org/api/ws").is_ok());
+        assert!(validate_ws_url("ws://localhost").is_ok());
+        assert!(validate_ws_url("ws://[::1]:38080/api/ws").is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_malformed() {
+        assert!(validate_ws_url("http://localhost:38080").is_err());
+        assert!(validate_ws_url("localhost:38080").is_err());
+        assert!(validate_ws_url("ws://").is_err());
+        assert!(validate_ws_url("ws://:38080/api/ws").is_err());
+        assert!(validate_ws_url("ws://host name/api/ws").is_err());
+        assert!(validate_ws_url("ws://localhost:port/api/ws").is_err());
+        assert!(validate_ws_url("ws://localhost:99999").is_err());
+        assert!(validate_ws_url("ws://[::1/api/ws").is_err());
+    }
+
+    #[test]
+    fn recent_urls_dedup_and_cap() {
+        let mut recent = Vec::new();
+        push_recent_url(&mut recent, "ws://a");
+        push_recent_url(&mut recent, "ws://b");
+        push_recent_url(&mut recent, "ws://a");
+        assert_eq!(recent, vec!["ws://a", "ws://b"]);
+
+        for i in 0..20 {
+            push_recent_url(&mut recent, &format!("ws://n{}", i));
+        }
+        assert_eq!(recent.len(), MAX_RECENT_URLS);
+        assert_eq!(recent[0], "ws://n19");
+    }
+}
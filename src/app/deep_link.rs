@@ -0,0 +1,213 @@
+//! Shareable view state in the URL hash (WASM): `#f=<filter>&c=vivid&tab=ring&w=60`
+//!
+//! `f` is the event filter bitfield as little-endian bytes in unpadded base64url, trailing
+//! zero bytes dropped. Unknown keys and malformed values are ignored, so older links keep working.
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD as BASE64URL, Engine as _};
+use crate::vring::ColorSchema;
+use super::{ActiveTab, JamApp};
+
+/// Seconds the view must stay unchanged before the hash is rewritten
+#[cfg(target_arch = "wasm32")]
+const HASH_DEBOUNCE_SECS: f64 = 0.5;
+
+fn encode_filter(bitfield: &[u64; 4]) -> String {
+    let mut bytes: Vec<u8> = bitfield.iter().flat_map(|w| w.to_le_bytes()).collect();
+    while bytes.last() == Some(&0) {
+        bytes.pop();
+    }
+    BASE64URL.encode(&bytes)
+}
+
+fn decode_filter(text: &str) -> Option<[u64; 4]> {
+    let bytes = BASE64URL.decode(text).ok()?;
+    if bytes.len() > 32 {
+        return None;
+    }
+    let mut bitfield = [0u64; 4];
+    for (i, &b) in bytes.iter().enumerate() {
+        bitfield[i / 8] |= (b as u64) << (8 * (i % 8));
+    }
+    Some(bitfield)
+}
+
+fn schema_key(schema: ColorSchema) -> &'static str {
+    match schema {
+        ColorSchema::Vivid => "vivid",
+        ColorSchema::Accessible => "accessible",
+        ColorSchema::Pipeline => "pipeline",
+        ColorSchema::Monochrome => "mono",
+        ColorSchema::Custom(_) => "custom",
+    }
+}
+
+fn tab_key(tab: ActiveTab) -> &'static str {
+    match tab {
+        ActiveTab::Ring => "ring",
+        ActiveTab::Graphs => "graphs",
+        ActiveTab::Analytics => "analytics",
+    }
+}
+
+/// The parts of a hash that parsed; everything else keeps its current value
+#[derive(Debug, Default, PartialEq)]
+struct ViewState {
+    filter: Option<[u64; 4]>,
+    schema: Option<&'static str>,
+    tab: Option<ActiveTab>,
+    time_window: Option<f32>,
+}
+
+fn parse_hash(hash: &str) -> ViewState {
+    let mut state = ViewState::default();
+    for pair in hash.trim_start_matches('#').split('&') {
+        let Some((key, value)) = pair.split_once('=') else {
+            continue;
+        };
+        match key {
+            "f" => state.filter = decode_filter(value),
+            "c" => {
+                state.schema = ["vivid", "accessible", "pipeline", "mono", "custom"]
+                    .into_iter()
+                    .find(|&k| k == value)
+            }
+            "tab" => {
+                state.tab = [ActiveTab::Ring, ActiveTab::Graphs, ActiveTab::Analytics]
+                    .into_iter()
+                    .find(|&t| tab_key(t) == value)
+            }
+            "w" => {
+                state.time_window = value
+                    .parse::<f32>()
+                    .ok()
+                    .filter(|w| w.is_finite())
+                    .map(|w| w.clamp(1.0, 600.0))
+            }
+            _ => {}
+        }
+    }
+    state
+}
+
+impl JamApp {
+    /// Hash (without `#`) describing the current view
+    pub(crate) fn view_hash(&self) -> String {
+        format!(
+            "f={}&c={}&tab={}&w={}",
+            encode_filter(&self.build_filter_bitfield()),
+            schema_key(self.color_schema),
+            tab_key(self.active_tab),
+            self.time_window,
+        )
+    }
+
+    /// Override the view with whatever `hash` specifies
+    pub(crate) fn apply_view_hash(&mut self, hash: &str) {
+        let state = parse_hash(hash);
+        if let Some(bitfield) = state.filter {
+            for (i, selected) in self.selected_events.iter_mut().enumerate().take(256) {
+                *selected = bitfield[i / 64] >> (i % 64) & 1 == 1;
+            }
+        }
+        if let Some(key) = state.schema {
+            self.color_schema = match key {
+                "custom" => ColorSchema::Custom(self.custom_palette),
+                _ => ColorSchema::ALL
+                    .iter()
+                    .copied()
+                    .find(|&s| schema_key(s) == key)
+                    .unwrap_or_default(),
+            };
+        }
+        if let Some(tab) = state.tab {
+            self.active_tab = tab;
+        }
+        if let Some(window) = state.time_window {
+            self.time_window = window;
+        }
+        self.color_lut = super::build_color_lut(&self.selected_events, self.color_schema);
+    }
+}
+
+/// Hash last written to the address bar, and a change waiting out the debounce
+#[cfg(target_arch = "wasm32")]
+#[derive(Default)]
+pub(crate) struct UrlHashSync {
+    written: String,
+    pending: Option<(String, f64)>,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl JamApp {
+    /// Startup: let a shared link override the restored settings
+    pub(crate) fn apply_url_hash(&mut self) {
+        let hash = web_sys::window().and_then(|w| w.location().hash().ok()).unwrap_or_default();
+        if !hash.is_empty() {
+            self.apply_view_hash(&hash);
+        }
+        self.url_hash.written = self.view_hash();
+    }
+
+    /// Rewrite the hash once the view has settled. `replaceState` keeps the back
+    /// button free of one entry per filter click.
+    pub(crate) fn sync_url_hash(&mut self, now: f64) {
+        let hash = self.view_hash();
+        let sync = &mut self.url_hash;
+        if hash == sync.written {
+            sync.pending = None;
+            return;
+        }
+        match &sync.pending {
+            Some((pending, since)) if *pending == hash => {
+                if now - since < HASH_DEBOUNCE_SECS {
+                    return;
+                }
+            }
+            _ => {
+                sync.pending = Some((hash, now));
+                return;
+            }
+        }
+        sync.pending = None;
+        let url = format!("#{}", hash);
+        if let Some(history) = web_sys::window().and_then(|w| w.history().ok()) {
+            let _ = history.replace_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some(&url));
+        }
+        sync.written = hash;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filter_drops_trailing_zero_bytes() {
+        let bitfield = [0b1011, 1 << 63, 0, 0];
+        let text = encode_filter(&bitfield);
+        assert_eq!(text.len(), BASE64URL.encode([0; 16]).len());
+        assert_eq!(decode_filter(&text), Some(bitfield));
+        assert_eq!(decode_filter(""), Some([0; 4]));
+        assert_eq!(decode_filter(&BASE64URL.encode([1; 33])), None);
+        // URL-safe alphabet, no padding
+        assert_eq!(encode_filter(&[0xfffb, 0, 0, 0]), "-_8");
+        assert_eq!(decode_filter("a+b/"), None);
+    }
+
+    #[test]
+    fn parse_hash_keeps_what_parses() {
+        let state = parse_hash("#f=Cw&c=mono&tab=graphs&w=30&x=1");
+        assert_eq!(
+            state,
+            ViewState {
+                filter: Some([11, 0, 0, 0]),
+                schema: Some("mono"),
+                tab: Some(ActiveTab::Graphs),
+                time_window: Some(30.0),
+            }
+        );
+        let state = parse_hash("c=neon&tab=&w=9999&f=!");
+        assert_eq!(state, ViewState { time_window: Some(600.0), ..Default::default() });
+        assert_eq!(parse_hash(""), ViewState::default());
+    }
+}
@@ -1,11 +1,166 @@
 //! Diagnostics window — connection status, rates, and drop counts
 
+use std::collections::{HashMap, VecDeque};
+
 use eframe::egui;
+use tracing::warn;
+use crate::core::{Codec, ParseError};
 use crate::theme::colors;
-use super::{JamApp, with_data};
+use crate::time::now_seconds;
+use crate::ws_state::WsState;
+use super::inspector::copy_button;
+use super::labels::{hash_hex, short_hex};
+use super::{DiagRateMode, JamApp, with_data};
+
+/// Sampling intervals averaged in `DiagRateMode::Average`
+const DIAG_AVERAGE_SAMPLES: usize = 5;
+/// Failing messages kept for the diagnostics window (also the most recorded per frame)
+pub(crate) const PARSE_ERROR_SAMPLES: usize = 8;
+/// Characters of a failing payload kept in its sample
+const PARSE_ERROR_PREVIEW_CHARS: usize = 240;
+/// Seconds between parse error log lines; failures in between are only counted
+const PARSE_ERROR_LOG_SECS: f64 = 5.0;
+/// Validators listed under "Top churning validators"
+const CHURN_TOP_NODES: usize = 5;
+/// One frame at 60fps (ms), drawn as the reference line in the frame-time graph
+const FRAME_BUDGET_MS: f64 = 1000.0 / 60.0;
+/// Frames longer than this (two missed vsyncs at 60fps) count as spikes
+const FRAME_SPIKE_MS: f64 = 2.0 * FRAME_BUDGET_MS;
+/// Height of the frame-time graph
+const FRAME_GRAPH_HEIGHT: f32 = 36.0;
+
+/// Counters accumulated over one diagnostics sampling interval
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub(crate) struct DiagCounts {
+    pub events: u64,
+    pub node_dropped: u64,
+    pub server_dropped: u64,
+    pub rx_bytes: u64,
+    pub parse_errors: u64,
+    pub buffer_evicted: u64,
+}
+
+/// Per-second rates derived from [`DiagCounts`]
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub(crate) struct DiagRates {
+    pub events: f64,
+    pub node_dropped: f64,
+    pub server_dropped: f64,
+    pub rx_bytes: f64,
+    pub parse_errors: f64,
+    pub buffer_evicted: f64,
+}
+
+/// The last few sampling intervals as (elapsed seconds, counts), newest at the back
+#[derive(Default)]
+pub(crate) struct DiagHistory {
+    samples: VecDeque<(f64, DiagCounts)>,
+}
+
+impl DiagHistory {
+    pub(crate) fn push(&mut self, elapsed: f64, counts: DiagCounts) {
+        if self.samples.len() >= DIAG_AVERAGE_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((elapsed, counts));
+    }
+
+    /// Rates over the newest interval, or over all kept intervals weighted by their length
+    pub(crate) fn rates(&self, mode: DiagRateMode) -> DiagRates {
+        let take = match mode {
+            DiagRateMode::Instant => 1,
+            DiagRateMode::Average => self.samples.len(),
+        };
+        let mut elapsed = 0.0;
+        let mut sum = DiagCounts::default();
+        for (dt, c) in self.samples.iter().rev().take(take) {
+            elapsed += dt;
+            sum.events += c.events;
+            sum.node_dropped += c.node_dropped;
+            sum.server_dropped += c.server_dropped;
+            sum.rx_bytes += c.rx_bytes;
+            sum.parse_errors += c.parse_errors;
+            sum.buffer_evicted += c.buffer_evicted;
+        }
+        if elapsed <= 0.0 {
+            return DiagRates::default();
+        }
+        DiagRates {
+            events: sum.events as f64 / elapsed,
+            node_dropped: sum.node_dropped as f64 / elapsed,
+            server_dropped: sum.server_dropped as f64 / elapsed,
+            rx_bytes: sum.rx_bytes as f64 / elapsed,
+            parse_errors: sum.parse_errors as f64 / elapsed,
+            buffer_evicted: sum.buffer_evicted as f64 / elapsed,
+        }
+    }
+}
+
+/// One message that failed to parse
+pub(crate) struct ParseErrorSample {
+    /// App time of the failure
+    pub at: f64,
+    pub error: ParseError,
+    /// Start of the payload: text as-is, binary frames as hex
+    pub payload: String,
+}
+
+/// Most recent parse failures, plus rate-limited logging of them
+#[derive(Default)]
+pub(crate) struct ParseErrorLog {
+    samples: VecDeque<ParseErrorSample>,
+    last_logged: Option<f64>,
+    /// Failures since the last log line
+    unlogged: u64,
+}
+
+impl ParseErrorLog {
+    pub(crate) fn record(&mut self, error: ParseError, payload: String, now: f64) {
+        let due = match self.last_logged {
+            Some(at) => now - at >= PARSE_ERROR_LOG_SECS,
+            None => true,
+        };
+        if due {
+            warn!(
+                error = %error,
+                payload = %payload,
+                suppressed = self.unlogged,
+                "Failed to parse message"
+            );
+            self.last_logged = Some(now);
+            self.unlogged = 0;
+        } else {
+            self.unlogged += 1;
+        }
+        if self.samples.len() >= PARSE_ERROR_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(ParseErrorSample { at: now, error, payload });
+    }
+
+    /// Kept failures, newest first
+    pub(crate) fn recent(&self) -> impl Iterator<Item = &ParseErrorSample> {
+        self.samples.iter().rev()
+    }
+}
+
+/// Truncated, printable start of a failing payload
+pub(crate) fn payload_preview(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => match text.char_indices().nth(PARSE_ERROR_PREVIEW_CHARS) {
+            Some((end, _)) => format!("{}…", &text[..end]),
+            None => text.to_string(),
+        },
+        Err(_) => {
+            let end = bytes.len().min(PARSE_ERROR_PREVIEW_CHARS / 2);
+            let ellipsis = if end < bytes.len() { "…" } else { "" };
+            format!("0x{}{}", hex::encode(&bytes[..end]), ellipsis)
+        }
+    }
+}
 
 /// Format a count with human-readable suffix (1234 → "1.2k", 5000000 → "5.0M")
-fn format_count(n: u64) -> String {
+pub(crate) fn format_count(n: u64) -> String {
     if n >= 1_000_000 {
         format!("{:.1}M", n as f64 / 1_000_000.0)
     } else if n >= 10_000 {
@@ -30,40 +185,333 @@ fn format_rate(r: f64) -> String {
     }
 }
 
+/// Format a byte rate with SI suffix (1_800_000 → "1.8 MB/s")
+fn format_bytes_rate(r: f64) -> String {
+    if r >= 1_000_000_000.0 {
+        format!("{:.1} GB/s", r / 1_000_000_000.0)
+    } else if r >= 1_000_000.0 {
+        format!("{:.1} MB/s", r / 1_000_000.0)
+    } else if r >= 1_000.0 {
+        format!("{:.1} kB/s", r / 1_000.0)
+    } else {
+        format!("{:.0} B/s", r)
+    }
+}
+
+/// (max, median) of per-validator finalization lags; lower median for even counts
+fn max_and_median(lags: &[(u16, i64)]) -> Option<(i64, i64)> {
+    let mut values: Vec<i64> = lags.iter().map(|&(_, lag)| lag).collect();
+    values.sort_unstable();
+    let max = *values.last()?;
+    Some((max, values[(values.len() - 1) / 2]))
+}
+
+/// Frame-time summary: nearest-rank p50 and p99 in ms, and the number of spikes
+fn frame_time_stats(times: &[f64]) -> Option<(f64, f64, usize)> {
+    if times.is_empty() {
+        return None;
+    }
+    let mut sorted = times.to_vec();
+    sorted.sort_unstable_by(f64::total_cmp);
+    let rank = |p: f64| sorted[((p * sorted.len() as f64).ceil() as usize).max(1) - 1];
+    let spikes = times.iter().filter(|&&t| t > FRAME_SPIKE_MS).count();
+    Some((rank(0.5), rank(0.99), spikes))
+}
+
+/// One bar per frame, scaled so the 60fps budget line sits mid-height when frames are on time
+fn draw_frame_times(ui: &mut egui::Ui, times: &[f64]) {
+    let size = egui::vec2(ui.available_width(), FRAME_GRAPH_HEIGHT);
+    let (rect, response) = ui.allocate_exact_size(size, egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, colors::shade(255, 8));
+    if times.is_empty() {
+        return;
+    }
+
+    let max = times.iter().copied().fold(FRAME_SPIKE_MS, f64::max);
+    let y_of = |ms: f64| rect.bottom() - (ms / max) as f32 * rect.height();
+    let bar_width = rect.width() / times.len() as f32;
+    for (i, &ms) in times.iter().enumerate() {
+        let color = if ms > FRAME_SPIKE_MS {
+            egui::Color32::from_rgb(200, 100, 100)
+        } else {
+            colors::text_muted()
+        };
+        let x = rect.left() + i as f32 * bar_width;
+        painter.rect_filled(
+            egui::Rect::from_min_max(egui::pos2(x, y_of(ms)), egui::pos2(x + bar_width, rect.bottom())),
+            0.0,
+            color,
+        );
+    }
+    let budget_y = y_of(FRAME_BUDGET_MS);
+    painter.hline(
+        rect.x_range(),
+        budget_y,
+        egui::Stroke::new(1.0, egui::Color32::from_rgb(100, 200, 100)),
+    );
+
+    if let Some(pos) = response.hover_pos() {
+        let i = (((pos.x - rect.left()) / bar_width) as usize).min(times.len() - 1);
+        response.on_hover_text_at_pointer(format!(
+            "{:.1} ms ({} frames ago)\ngreen line: 60fps budget",
+            times[i],
+            times.len() - 1 - i,
+        ));
+    }
+}
+
+/// Server-side drop detection from gaps in each endpoint's `data.id` sequence
+#[derive(Default)]
+pub(crate) struct EventIdTracker {
+    /// Last seen event id per endpoint index; absent until the first id after a (re)connect
+    last_id: HashMap<usize, u64>,
+}
+
+impl EventIdTracker {
+    /// Events skipped between the previous id from `endpoint` and `id`
+    pub(crate) fn gap(&mut self, endpoint: usize, id: u64) -> u64 {
+        match self.last_id.insert(endpoint, id) {
+            Some(last_id) => id.saturating_sub(last_id).saturating_sub(1),
+            None => 0,
+        }
+    }
+
+    /// The endpoint resubscribed: its ids may restart or jump, so the next id starts a new sequence
+    pub(crate) fn reset(&mut self, endpoint: usize) {
+        self.last_id.remove(&endpoint);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.last_id.clear();
+    }
+}
+
+/// Seconds validators must stay split before it counts as a partition; a validator that
+/// just connected has no edges yet and splits off for a moment
+const PARTITION_CONFIRM_SECS: f64 = 5.0;
+
+/// Validator partitions of the connection topology, sampled on the diagnostics tick
+#[derive(Default)]
+pub(crate) struct PartitionTracker {
+    /// Validators per component on the latest tick, largest first
+    sizes: Vec<usize>,
+    /// When the validators last went from one component to several
+    split_since: Option<f64>,
+    /// The split has lasted `PARTITION_CONFIRM_SECS`
+    confirmed: bool,
+}
+
+impl PartitionTracker {
+    /// Take this tick's component sizes; true when a split has just been confirmed
+    pub(crate) fn update(&mut self, sizes: Vec<usize>, now: f64) -> bool {
+        let split = sizes.len() > 1;
+        self.sizes = sizes;
+        if !split {
+            self.split_since = None;
+            self.confirmed = false;
+            return false;
+        }
+        let since = *self.split_since.get_or_insert(now);
+        let was_confirmed = self.confirmed;
+        self.confirmed = now - since >= PARTITION_CONFIRM_SECS;
+        self.confirmed && !was_confirmed
+    }
+
+    /// Validators per component (largest first) while a confirmed partition lasts
+    pub(crate) fn partition(&self) -> Option<&[usize]> {
+        self.confirmed.then_some(self.sizes.as_slice())
+    }
+
+    /// When the current split began
+    pub(crate) fn split_since(&self) -> Option<f64> {
+        self.split_since
+    }
+}
+
+/// Server-side gaps kept for the gap timeline
+const GAP_HISTORY_LEN: usize = 512;
+/// Columns of the gap timeline
+const GAP_BUCKETS: usize = 60;
+/// Height of the gap timeline, pixels
+const GAP_GRAPH_HEIGHT: f32 = 28.0;
+
+/// When event id gaps were detected and how many events each one skipped
+#[derive(Default)]
+pub(crate) struct GapHistory {
+    /// (app time, events missing), oldest first
+    gaps: VecDeque<(f64, u64)>,
+}
+
+impl GapHistory {
+    pub(crate) fn record(&mut self, at: f64, gap: u64) {
+        if self.gaps.len() >= GAP_HISTORY_LEN {
+            self.gaps.pop_front();
+        }
+        self.gaps.push_back((at, gap));
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.gaps.clear();
+    }
+
+    /// Events missing per bucket over the `window` seconds before `now`, oldest bucket first
+    fn buckets(&self, now: f64, window: f64, buckets: usize) -> Vec<u64> {
+        let mut out = vec![0u64; buckets];
+        let width = window / buckets as f64;
+        for &(at, gap) in &self.gaps {
+            let age = now - at;
+            if (0.0..window).contains(&age) {
+                let bucket = buckets - 1 - ((age / width) as usize).min(buckets - 1);
+                out[bucket] += gap;
+            }
+        }
+        out
+    }
+}
+
+/// Events missing per time bucket, red bars on a log scale so a single large gap does not
+/// flatten the small ones
+fn draw_gap_timeline(ui: &mut egui::Ui, buckets: &[u64], window: f64) {
+    let size = egui::vec2(ui.available_width(), GAP_GRAPH_HEIGHT);
+    let (rect, response) = ui.allocate_exact_size(size, egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, colors::shade(255, 8));
+    let max = buckets.iter().copied().max().unwrap_or(0);
+    if max == 0 {
+        return;
+    }
+
+    let scale = (1.0 + max as f32).ln();
+    let bar_width = rect.width() / buckets.len() as f32;
+    for (i, &missing) in buckets.iter().enumerate().filter(|&(_, &m)| m > 0) {
+        let height = ((1.0 + missing as f32).ln() / scale).max(0.1) * rect.height();
+        let x = rect.left() + i as f32 * bar_width;
+        painter.rect_filled(
+            egui::Rect::from_min_max(egui::pos2(x, rect.bottom() - height), egui::pos2(x + bar_width, rect.bottom())),
+            0.0,
+            egui::Color32::from_rgb(200, 100, 100),
+        );
+    }
+
+    if let Some(pos) = response.hover_pos() {
+        let i = (((pos.x - rect.left()) / bar_width) as usize).min(buckets.len() - 1);
+        let width = window / buckets.len() as f64;
+        let ago = (buckets.len() - 1 - i) as f64 * width;
+        response.on_hover_text_at_pointer(format!(
+            "{} events missing {:.0}–{:.0}s ago",
+            buckets[i],
+            ago,
+            ago + width,
+        ));
+    }
+}
+
+/// Indicator glyph, status text and color for a connection state
+pub(crate) fn ws_state_indicator(state: &WsState) -> (&'static str, String, egui::Color32) {
+    match state {
+        WsState::Connected => ("●", "Connected".to_string(), egui::Color32::from_rgb(100, 200, 100)),
+        WsState::Connecting => (
+            "●",
+            "Connecting...".to_string(),
+            egui::Color32::from_rgb(200, 200, 100),
+        ),
+        WsState::Disconnected => (
+            "✕",
+            "Disconnected".to_string(),
+            egui::Color32::from_rgb(200, 100, 100),
+        ),
+        WsState::Error(_) => ("✕", "Error".to_string(), egui::Color32::from_rgb(200, 100, 100)),
+        WsState::Reconnecting { attempt } => (
+            "●",
+            format!("Reconnecting ({})...", attempt),
+            egui::Color32::from_rgb(200, 200, 100),
+        ),
+        WsState::Replaying => ("▶", "Replaying".to_string(), egui::Color32::from_rgb(100, 150, 200)),
+    }
+}
+
 impl JamApp {
+    /// Banner below the header offering recovery while parse errors are spiking.
+    pub(crate) fn render_parse_error_banner(&mut self, ctx: &egui::Context) {
+        if !self.parse_error_spike_active() || self.parse_error_banner_dismissed {
+            return;
+        }
+
+        egui::TopBottomPanel::top("parse_error_banner")
+            .frame(
+                egui::Frame::new()
+                    .fill(egui::Color32::from_rgb(60, 20, 20))
+                    .inner_margin(6.0),
+            )
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "Parse errors at {}/s for {}s, visualizations may be stale",
+                            format_rate(self.diag_parse_errors_sec),
+                            self.parse_error_spike_secs,
+                        ))
+                        .color(egui::Color32::from_rgb(230, 140, 140)),
+                    );
+                    if ui.button("Clear data & reconnect").clicked() {
+                        self.clear_and_reconnect();
+                    }
+                    if ui.button("Dismiss").clicked() {
+                        self.parse_error_banner_dismissed = true;
+                    }
+                });
+            });
+    }
+
+    /// Last few failing messages with the decoder's error, newest first
+    fn draw_parse_error_samples(&self, ui: &mut egui::Ui) {
+        let now = now_seconds();
+        egui::ScrollArea::vertical()
+            .id_salt("parse_error_samples")
+            .max_height(240.0)
+            .show(ui, |ui| {
+                for sample in self.parse_error_log.recent() {
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "{:.0}s ago: {}",
+                            (now - sample.at).max(0.0),
+                            sample.error,
+                        ))
+                        .color(egui::Color32::from_rgb(200, 100, 100))
+                        .small(),
+                    );
+                    ui.horizontal(|ui| {
+                        copy_button(ui, &sample.payload);
+                        ui.add(
+                            egui::Label::new(
+                                egui::RichText::new(sample.payload.as_str())
+                                    .monospace()
+                                    .small()
+                                    .color(colors::text_muted()),
+                            )
+                            .wrap(),
+                        );
+                    });
+                    ui.add_space(2.0);
+                }
+            });
+    }
+
     pub(crate) fn draw_diagnostics(&self, ctx: &egui::Context) {
         let ws_state = self.get_ws_state();
 
-        let (node_count, highest_slot) = with_data!(self, |data| {
+        let (node_count, highest_slot, lag, sync) = with_data!(self, |data| {
             (
                 data.events.node_count(),
                 data.blocks.highest_slot(),
+                max_and_median(&data.blocks.finalization_lag()),
+                data.sync_status.synced_count(),
             )
         });
 
         // Status indicator and text
-        let (indicator, status_text, status_color) = match &ws_state {
-            crate::ws_state::WsState::Connected => (
-                "●",
-                "Connected",
-                egui::Color32::from_rgb(100, 200, 100),
-            ),
-            crate::ws_state::WsState::Connecting => (
-                "●",
-                "Connecting...",
-                egui::Color32::from_rgb(200, 200, 100),
-            ),
-            crate::ws_state::WsState::Disconnected => (
-                "✕",
-                "Disconnected",
-                egui::Color32::from_rgb(200, 100, 100),
-            ),
-            crate::ws_state::WsState::Error(_) => (
-                "✕",
-                "Error",
-                egui::Color32::from_rgb(200, 100, 100),
-            ),
-        };
+        let (indicator, status_text, status_color) = ws_state_indicator(&ws_state);
 
         let title = egui::RichText::new(format!("{} {}", indicator, status_text))
             .color(status_color);
@@ -72,7 +520,7 @@ impl JamApp {
             .anchor(egui::Align2::RIGHT_TOP, egui::vec2(-8.0, 36.0))
             .show(ctx, |ui| {
                 egui::Frame::new()
-                    .fill(egui::Color32::from_rgba_unmultiplied(20, 20, 20, 200))
+                    .fill(colors::shade(20, 200))
                     .corner_radius(4.0)
                     .inner_margin(8.0)
                     .show(ui, |ui| {
@@ -81,20 +529,77 @@ impl JamApp {
 
                         header.show(ui, |ui| {
                             ui.set_min_width(ctx.screen_rect().width() * 0.2);
+                            let frame_times = self.fps_counter.frame_times();
+                            ui.horizontal(|ui| {
+                                ui.label(
+                                    egui::RichText::new(format!("{:.0} fps", self.fps_counter.fps()))
+                                        .color(colors::text_secondary()),
+                                );
+                                if let Some((p50, p99, spikes)) = frame_time_stats(&frame_times) {
+                                    let color = if spikes > 0 {
+                                        egui::Color32::from_rgb(200, 200, 100)
+                                    } else {
+                                        colors::text_muted()
+                                    };
+                                    ui.label(
+                                        egui::RichText::new(format!(
+                                            "p50 {:.1} ms / p99 {:.1} ms, {} spikes",
+                                            p50, p99, spikes,
+                                        ))
+                                        .color(color),
+                                    )
+                                    .on_hover_text(format!(
+                                        "Frame times over the last {} frames; spikes take longer than {:.0} ms",
+                                        frame_times.len(),
+                                        FRAME_SPIKE_MS,
+                                    ));
+                                }
+                            });
+                            draw_frame_times(ui, &frame_times);
                             ui.label(
-                                egui::RichText::new(format!("{:.0} fps", self.fps_counter.fps()))
-                                    .color(colors::TEXT_SECONDARY),
+                                egui::RichText::new(format!(
+                                    "message budget {:.1} ms (render {:.1} ms)",
+                                    self.message_budget.budget_ms(),
+                                    self.message_budget.render_ms(),
+                                ))
+                                .color(colors::text_muted()),
+                            )
+                            .on_hover_text(
+                                "Time per frame given to parsing messages: what's left of a 60fps frame \
+                                 after the estimated UI cost",
                             );
 
                             ui.label(
                                 egui::RichText::new(format!("{} nodes", node_count))
-                                    .color(colors::TEXT_MUTED),
+                                    .color(colors::text_muted()),
                             );
 
                             if let Some(slot) = highest_slot {
                                 ui.label(
                                     egui::RichText::new(format!("slot {}", slot))
-                                        .color(colors::TEXT_MUTED),
+                                        .color(colors::text_muted()),
+                                );
+                            }
+
+                            if let Some((max, median)) = lag {
+                                ui.label(
+                                    egui::RichText::new(format!(
+                                        "finality lag max {} / median {}",
+                                        max, median,
+                                    ))
+                                    .color(colors::text_muted()),
+                                );
+                            }
+
+                            if let (synced, total @ 1..) = sync {
+                                ui.label(
+                                    egui::RichText::new(format!(
+                                        "synced {}/{} ({:.0}%)",
+                                        synced,
+                                        total,
+                                        synced as f64 / total as f64 * 100.0,
+                                    ))
+                                    .color(colors::text_muted()),
                                 );
                             }
 
@@ -103,25 +608,233 @@ impl JamApp {
                                     "{}/s WS events",
                                     format_rate(self.diag_events_sec),
                                 ))
-                                .color(colors::TEXT_MUTED),
+                                .color(colors::text_muted()),
                             );
 
-                            // Dropped events — always show rate, highlight in red if > 0
-                            let total_dropped = self.diag_server_dropped_total;
-                            let dropped_text = format!(
-                                "{} dropped ({}/s)",
-                                format_count(total_dropped),
-                                format_rate(self.diag_dropped_sec),
-                            );
-                            let dropped_color = if total_dropped > 0 {
-                                egui::Color32::from_rgb(200, 100, 100)
-                            } else {
-                                colors::TEXT_MUTED
+                            let codec = match self.diag_codec {
+                                Some(Codec::Json) => " (JSON)",
+                                Some(Codec::Scale) => " (SCALE)",
+                                None => "",
                             };
                             ui.label(
-                                egui::RichText::new(dropped_text).color(dropped_color),
+                                egui::RichText::new(format!(
+                                    "rx {}{}",
+                                    format_bytes_rate(self.diag_rx_bytes_sec),
+                                    codec,
+                                ))
+                                .color(colors::text_muted()),
                             );
 
+                            #[cfg(target_arch = "wasm32")]
+                            {
+                                let (depth, cap, dropped) = {
+                                    let buf = self.msg_buffer.borrow();
+                                    (buf.len(), buf.cap(), buf.dropped())
+                                };
+                                let color = if dropped > 0 {
+                                    egui::Color32::from_rgb(200, 100, 100)
+                                } else {
+                                    colors::text_muted()
+                                };
+                                ui.label(
+                                    egui::RichText::new(format!(
+                                        "buffer {}/{}, {} dropped",
+                                        format_count(depth as u64),
+                                        format_count(cap as u64),
+                                        format_count(dropped),
+                                    ))
+                                    .color(color),
+                                )
+                                .on_hover_text(
+                                    "Received messages waiting to be parsed; the oldest are dropped once full",
+                                );
+                            }
+
+                            // Event loss by source — always show rate, highlight if > 0
+                            let red = egui::Color32::from_rgb(200, 100, 100);
+                            let yellow = egui::Color32::from_rgb(200, 200, 100);
+                            let drop_rows = [
+                                (
+                                    "node-dropped",
+                                    self.diag_node_dropped_total,
+                                    self.diag_node_dropped_sec,
+                                    red,
+                                    "Events the nodes reported losing themselves (sum of Dropped.num)",
+                                ),
+                                (
+                                    "server-dropped",
+                                    self.diag_server_dropped_total,
+                                    self.diag_server_dropped_sec,
+                                    red,
+                                    "Events jamtart failed to forward (gaps in the event id sequence)",
+                                ),
+                                (
+                                    "buffer-evicted",
+                                    self.diag_buffer_evicted_total,
+                                    self.diag_buffer_evicted_sec,
+                                    yellow,
+                                    "Received events pushed out of a full per-node buffer in this app \
+                                     (raise the cap under Settings > Advanced)",
+                                ),
+                            ];
+                            for (name, total, rate, highlight, tooltip) in drop_rows {
+                                let color = if total > 0 { highlight } else { colors::text_muted() };
+                                ui.label(
+                                    egui::RichText::new(format!(
+                                        "{} {} ({}/s)",
+                                        format_count(total),
+                                        name,
+                                        format_rate(rate),
+                                    ))
+                                    .color(color),
+                                )
+                                .on_hover_text(tooltip);
+                            }
+                            let window = self.time_window as f64;
+                            let gaps = self.gap_history.buckets(now_seconds(), window, GAP_BUCKETS);
+                            if gaps.iter().any(|&missing| missing > 0) {
+                                ui.label(
+                                    egui::RichText::new(format!(
+                                        "server gaps, last {:.0}s",
+                                        window,
+                                    ))
+                                    .color(colors::text_muted())
+                                    .small(),
+                                )
+                                .on_hover_text("Where event id gaps occurred; compare with the event rate");
+                                draw_gap_timeline(ui, &gaps, window);
+                            }
+
+                            if !self.stalled_nodes.is_empty() {
+                                let names: Vec<String> = with_data!(self, |data| {
+                                    self.stalled_nodes
+                                        .iter()
+                                        .map(|&idx| match data.events.node_id_at(idx) {
+                                            Some(id) => self.node_label(id),
+                                            None => format!("node {}", idx),
+                                        })
+                                        .collect()
+                                });
+                                ui.label(
+                                    egui::RichText::new(format!(
+                                        "{} stalled: {}",
+                                        names.len(),
+                                        names.join(", "),
+                                    ))
+                                    .color(egui::Color32::from_rgb(200, 100, 100)),
+                                );
+                            }
+
+                            let churning: Vec<(String, u64, u64)> = with_data!(self, |data| {
+                                data.events
+                                    .top_churning(CHURN_TOP_NODES)
+                                    .into_iter()
+                                    .map(|(id, up, down)| (self.node_label(id), up, down))
+                                    .collect()
+                            });
+                            if !churning.is_empty() {
+                                ui.collapsing("Top churning validators", |ui| {
+                                    for (name, up, down) in &churning {
+                                        ui.label(
+                                            egui::RichText::new(format!(
+                                                "{}: +{} / −{} (net {:+})",
+                                                name,
+                                                format_count(*up),
+                                                format_count(*down),
+                                                *up as i64 - *down as i64,
+                                            ))
+                                            .color(colors::text_muted()),
+                                        );
+                                    }
+                                })
+                                .header_response
+                                .on_hover_text(
+                                    "Connections up vs down or failed since each validator was first seen",
+                                );
+                            }
+
+                            if let Some(ref fork) = self.fork {
+                                ui.label(
+                                    egui::RichText::new(format!("fork at slot {}", fork.slot))
+                                        .color(egui::Color32::from_rgb(200, 100, 100)),
+                                );
+                                for (hash, nodes) in &fork.branches {
+                                    let names: Vec<String> =
+                                        nodes.iter().map(|id| self.node_label(id)).collect();
+                                    let hex = hash_hex(hash);
+                                    ui.horizontal(|ui| {
+                                        ui.label(
+                                            egui::RichText::new(format!(
+                                                "  {}: {}",
+                                                short_hex(&hex),
+                                                names.join(", "),
+                                            ))
+                                            .color(colors::text_muted()),
+                                        );
+                                        copy_button(ui, &hex);
+                                    });
+                                }
+                            }
+
+                            if let Some(sizes) = self.partitions.partition() {
+                                let groups: Vec<String> = sizes.iter().map(usize::to_string).collect();
+                                let since = self
+                                    .partitions
+                                    .split_since()
+                                    .map_or(0.0, |since| now_seconds() - since);
+                                ui.label(
+                                    egui::RichText::new(format!(
+                                        "partitioned into {} groups for {:.0}s",
+                                        sizes.len(),
+                                        since,
+                                    ))
+                                    .color(egui::Color32::from_rgb(200, 100, 100)),
+                                );
+                                ui.label(
+                                    egui::RichText::new(format!("  validators per group: {}", groups.join(", ")))
+                                        .color(colors::text_muted()),
+                                );
+                            }
+
+                            if self.diag_parse_errors_total > 0 {
+                                ui.label(
+                                    egui::RichText::new(format!(
+                                        "{} parse errors ({}/s)",
+                                        format_count(self.diag_parse_errors_total),
+                                        format_rate(self.diag_parse_errors_sec),
+                                    ))
+                                    .color(egui::Color32::from_rgb(200, 100, 100)),
+                                );
+                                ui.collapsing("Recent parse errors", |ui| {
+                                    self.draw_parse_error_samples(ui);
+                                });
+                            }
+
+                            if self.diag_unknown_events_total > 0 {
+                                ui.label(
+                                    egui::RichText::new(format!(
+                                        "{} events of unknown type",
+                                        format_count(self.diag_unknown_events_total),
+                                    ))
+                                    .color(egui::Color32::from_rgb(200, 200, 100)),
+                                )
+                                .on_hover_text("Newer event types than this build knows; shown as Unknown");
+                            }
+
+                            if self.diag_partial_events_total > 0 {
+                                ui.label(
+                                    egui::RichText::new(format!(
+                                        "{} partially parsed events",
+                                        format_count(self.diag_partial_events_total),
+                                    ))
+                                    .color(egui::Color32::from_rgb(200, 200, 100)),
+                                )
+                                .on_hover_text(
+                                    "Known event types whose fields didn't decode: the server \
+                                     schema has drifted. Counted by type and time only.",
+                                );
+                            }
+
                             if self.particle_max > 0 {
                                 ui.label(
                                     egui::RichText::new(format!(
@@ -129,7 +842,7 @@ impl JamApp {
                                         format_count(self.particle_count as u64),
                                         format_count(self.particle_max as u64),
                                     ))
-                                    .color(colors::TEXT_MUTED),
+                                    .color(colors::text_muted()),
                                 );
                             }
                         });
@@ -137,3 +850,126 @@ impl JamApp {
             });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn payload_preview_truncates() {
+        assert_eq!(payload_preview(b"{\"type\":1}"), "{\"type\":1}");
+        let long = "é".repeat(PARSE_ERROR_PREVIEW_CHARS + 10);
+        let preview = payload_preview(long.as_bytes());
+        assert_eq!(preview.chars().count(), PARSE_ERROR_PREVIEW_CHARS + 1);
+        assert!(preview.ends_with('…'));
+        assert_eq!(payload_preview(&[0xff, 0x01]), "0xff01");
+    }
+
+    #[test]
+    fn parse_error_log_keeps_newest() {
+        let mut log = ParseErrorLog::default();
+        for i in 0..PARSE_ERROR_SAMPLES + 3 {
+            log.record(ParseError::MissingNodeId, i.to_string(), i as f64);
+        }
+        let recent: Vec<&str> = log.recent().map(|s| s.payload.as_str()).collect();
+        assert_eq!(recent.len(), PARSE_ERROR_SAMPLES);
+        assert_eq!(recent[0], (PARSE_ERROR_SAMPLES + 2).to_string());
+        // Logged at t=0, 5 and 10; the failures in between were only counted
+        assert_eq!((log.last_logged, log.unlogged), (Some(10.0), 0));
+    }
+
+    #[test]
+    fn event_id_gap_resets_on_reconnect() {
+        let mut tracker = EventIdTracker::default();
+        tracker.reset(0);
+        assert_eq!(tracker.gap(0, 100), 0);
+        assert_eq!(tracker.gap(0, 104), 3);
+        assert_eq!(tracker.gap(0, 105), 0);
+
+        // Reconnect: the server restarted its ids, no spurious gap
+        tracker.reset(0);
+        assert_eq!(tracker.gap(0, 1_000_000), 0);
+        assert_eq!(tracker.gap(0, 1_000_002), 1);
+
+        // Other endpoints keep their own sequence
+        tracker.reset(1);
+        assert_eq!(tracker.gap(1, 5), 0);
+        tracker.reset(0);
+        assert_eq!(tracker.gap(1, 7), 1);
+        assert_eq!(tracker.gap(0, 1), 0);
+    }
+
+    #[test]
+    fn partition_confirmed_after_persisting() {
+        let mut tracker = PartitionTracker::default();
+        assert!(!tracker.update(vec![4], 0.0));
+        // A node that just joined splits off briefly, then links up
+        assert!(!tracker.update(vec![4, 1], 1.0));
+        assert!(!tracker.update(vec![5], 2.0));
+        assert_eq!(tracker.split_since(), None);
+
+        assert!(!tracker.update(vec![3, 2], 10.0));
+        assert_eq!(tracker.partition(), None);
+        assert!(tracker.update(vec![3, 2], 10.0 + PARTITION_CONFIRM_SECS));
+        assert!(!tracker.update(vec![3, 1, 1], 20.0)); // still split: reported once
+        assert_eq!(tracker.partition(), Some(&[3, 1, 1][..]));
+        assert_eq!(tracker.split_since(), Some(10.0));
+
+        tracker.update(vec![5], 21.0);
+        assert_eq!(tracker.partition(), None);
+    }
+
+    #[test]
+    fn gap_history_buckets_by_age() {
+        let mut history = GapHistory::default();
+        history.record(41.0, 3);
+        history.record(95.0, 2);
+        history.record(99.5, 5);
+        // 60s window in 6 buckets of 10s: t=41 is 59s old, t=95 and 99.5 land in the newest
+        assert_eq!(history.buckets(100.0, 60.0, 6), vec![3, 0, 0, 0, 0, 7]);
+        assert_eq!(history.buckets(200.0, 60.0, 6), vec![0; 6]);
+
+        for i in 0..GAP_HISTORY_LEN + 10 {
+            history.record(i as f64, 1);
+        }
+        assert_eq!(history.gaps.len(), GAP_HISTORY_LEN);
+        assert_eq!(history.gaps.front(), Some(&(10.0, 1)));
+    }
+
+    #[test]
+    fn diag_history_instant_and_average() {
+        let mut history = DiagHistory::default();
+        assert_eq!(history.rates(DiagRateMode::Average), DiagRates::default());
+
+        history.push(1.0, DiagCounts { events: 100, ..Default::default() });
+        history.push(0.5, DiagCounts { events: 200, rx_bytes: 50, ..Default::default() });
+        let instant = history.rates(DiagRateMode::Instant);
+        assert_eq!((instant.events, instant.rx_bytes), (400.0, 100.0));
+        // Weighted by interval length: 300 events over 1.5s
+        assert_eq!(history.rates(DiagRateMode::Average).events, 200.0);
+
+        // Only the newest DIAG_AVERAGE_SAMPLES intervals are kept
+        for _ in 0..DIAG_AVERAGE_SAMPLES {
+            history.push(1.0, DiagCounts { events: 10, ..Default::default() });
+        }
+        assert_eq!(history.rates(DiagRateMode::Average).events, 10.0);
+    }
+
+    #[test]
+    fn frame_time_stats_percentiles_and_spikes() {
+        assert_eq!(frame_time_stats(&[]), None);
+        let mut times = vec![16.0; 98];
+        times.extend([50.0, 17.0]);
+        let (p50, p99, spikes) = frame_time_stats(&times).unwrap();
+        assert_eq!((p50, p99, spikes), (16.0, 17.0, 1));
+        assert_eq!(frame_time_stats(&[40.0]), Some((40.0, 40.0, 1)));
+    }
+
+    #[test]
+    fn max_and_median_of_lags() {
+        assert_eq!(max_and_median(&[]), None);
+        assert_eq!(max_and_median(&[(0, 4)]), Some((4, 4)));
+        assert_eq!(max_and_median(&[(0, 9), (1, 1), (2, 3)]), Some((9, 3)));
+        assert_eq!(max_and_median(&[(0, 2), (1, 8), (2, 1), (3, 5)]), Some((8, 2)));
+    }
+}
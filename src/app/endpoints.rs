@@ -0,0 +1,161 @@
+//! Multi-endpoint sessions (native only): several jamtart instances feeding one dashboard
+//!
+//! Endpoints come from repeated `--url` flags or a comma-separated `JAMTART_WS`. With more
+//! than one, each message is parsed with its endpoint's source tag (`s1`, `s2`, …) so node
+//! ids are namespaced per network and two networks never share a ring slot.
+
+use eframe::egui;
+use crate::recorder::Recorder;
+use crate::theme::colors;
+use crate::websocket_native::NativeWsClient;
+use crate::ws_state::{Backoff, WsState};
+use super::connection::validate_ws_url;
+use super::diagnostics::ws_state_indicator;
+use super::{JamApp, DEFAULT_WS_URL};
+
+/// Source ids that can be hidden individually (bits of `JamApp::hidden_sources`); endpoints
+/// past this share the last id
+pub(crate) const MAX_SOURCES: usize = 64;
+
+/// One live connection and the tag its node ids are namespaced with
+pub(crate) struct Endpoint {
+    pub url: String,
+    /// `None` when this is the only endpoint (node ids stay as jamtart sends them)
+    pub source: Option<String>,
+    /// Provenance id of its events and particles: 1-based position when tagged, else 0
+    pub id: u8,
+    pub client: NativeWsClient,
+}
+
+/// Endpoint list: `--url` flags if any, else the comma-separated `env` (`JAMTART_WS`),
+/// else [`DEFAULT_WS_URL`]. Every URL is validated; duplicates are kept once.
+fn parse_ws_urls(url_flags: Vec<String>, env: Option<String>) -> Result<Vec<String>, String> {
+    let raw = if url_flags.is_empty() {
+        env.map(|list| list.split(',').map(str::to_string).collect()).unwrap_or_default()
+    } else {
+        url_flags
+    };
+    let mut urls: Vec<String> = Vec::new();
+    for entry in raw.iter().map(|s| s.trim()).filter(|s| !s.is_empty()) {
+        let url = validate_ws_url(entry).map_err(|e| format!("{}: {}", entry, e))?;
+        if !urls.contains(&url) {
+            urls.push(url);
+        }
+    }
+    if urls.is_empty() {
+        urls.push(DEFAULT_WS_URL.to_string());
+    }
+    Ok(urls)
+}
+
+/// Native endpoints from the command line (`--url`, repeatable) or `JAMTART_WS`
+pub fn native_ws_urls(args: &[String]) -> Result<Vec<String>, String> {
+    let flags = args
+        .windows(2)
+        .filter(|pair| pair[0] == "--url")
+        .map(|pair| pair[1].clone())
+        .collect();
+    parse_ws_urls(flags, std::env::var("JAMTART_WS").ok())
+}
+
+/// Open a client per URL, each reconnecting per `backoff`; sources are tagged only when
+/// there is more than one, and the recorder writes each endpoint's tag into its lines
+pub(crate) fn connect_endpoints(
+    urls: &[String],
+    recorder: Option<&Recorder>,
+    backoff: &Backoff,
+) -> Vec<Endpoint> {
+    let tagged = urls.len() > 1;
+    urls.iter()
+        .enumerate()
+        .map(|(i, url)| {
+            let source = tagged.then(|| format!("s{}", i + 1));
+            let recorder = recorder.map(|r| r.with_source(source.as_deref()));
+            let client = NativeWsClient::connect(url, recorder);
+            *client.backoff.lock() = backoff.clone();
+            Endpoint {
+                url: url.clone(),
+                id: source.as_deref().map_or(0, source_id),
+                source,
+                client,
+            }
+        })
+        .collect()
+}
+
+/// Provenance id of a source tag: `s3` → 3 (capped at `MAX_SOURCES`), 0 for anything else
+pub(crate) fn source_id(tag: &str) -> u8 {
+    match tag.strip_prefix('s').and_then(|n| n.parse::<usize>().ok()) {
+        Some(n) if n > 0 => n.min(MAX_SOURCES) as u8,
+        _ => 0,
+    }
+}
+
+impl JamApp {
+    /// Header row of per-endpoint status dots, shown only with several endpoints
+    pub(crate) fn render_endpoint_status(&self, ui: &mut egui::Ui) {
+        if self.endpoints.len() < 2 {
+            return;
+        }
+        for endpoint in &self.endpoints {
+            let state: WsState = endpoint.client.state.lock().clone();
+            let (indicator, status, color) = ws_state_indicator(&state);
+            let tag = endpoint.source.as_deref().unwrap_or_default();
+            ui.label(egui::RichText::new(format!("{} {}", tag, indicator)).color(color))
+                .on_hover_text(format!("{}\n{}", endpoint.url, status));
+        }
+        ui.add_space(10.0);
+    }
+
+    /// Filter sidebar group toggling each endpoint's events; hidden with a single endpoint
+    pub(crate) fn render_source_filter(&mut self, ui: &mut egui::Ui, frame: egui::Frame) {
+        if self.endpoints.len() < 2 {
+            return;
+        }
+        let sources: Vec<(u8, String, String)> = self
+            .endpoints
+            .iter()
+            .map(|e| (e.id, e.source.clone().unwrap_or_default(), e.url.clone()))
+            .collect();
+        frame.show(ui, |ui| {
+            ui.set_min_width(ui.available_width());
+            ui.label(egui::RichText::new("Sources:").color(colors::text_muted()));
+            for (id, tag, url) in sources {
+                let mut shown = self.source_visible(id);
+                if ui.checkbox(&mut shown, tag).on_hover_text(url).changed() {
+                    self.set_source_hidden(id, !shown);
+                }
+            }
+        });
+        ui.add_space(4.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_win_over_env_and_lists_are_split() {
+        let env = Some(" ws://a:1/api/ws, ws://b:2/api/ws ,ws://a:1/api/ws".to_string());
+        assert_eq!(
+            parse_ws_urls(Vec::new(), env.clone()).unwrap(),
+            vec!["ws://a:1/api/ws", "ws://b:2/api/ws"]
+        );
+        assert_eq!(
+            parse_ws_urls(vec!["wss://c/api/ws".to_string()], env).unwrap(),
+            vec!["wss://c/api/ws"]
+        );
+        assert_eq!(parse_ws_urls(Vec::new(), None).unwrap(), vec![DEFAULT_WS_URL]);
+        assert!(parse_ws_urls(Vec::new(), Some("ws://a:1,http://b".to_string())).is_err());
+    }
+
+    #[test]
+    fn source_tags_map_to_ids() {
+        assert_eq!(source_id("s1"), 1);
+        assert_eq!(source_id("s12"), 12);
+        assert_eq!(source_id("s999"), MAX_SOURCES as u8);
+        assert_eq!(source_id("s0"), 0);
+        assert_eq!(source_id("x1"), 0);
+    }
+}
@@ -0,0 +1,259 @@
+//! Event log — a live, filtered, one-line-per-event stream of incoming events (tail -f style)
+
+use std::fmt::Write as _;
+use eframe::egui;
+use serde_json::Value;
+use crate::core::{event_name, Event, EventStore, EventType, ParseResult, PeerAddress};
+use crate::theme::colors;
+use crate::time::{now_unix_seconds, utc_parts};
+use super::inspector::copy_button;
+use super::JamApp;
+
+/// Lines kept in the log; older lines scroll out
+pub(crate) const EVENT_LOG_LINES: usize = 500;
+/// Scalar fields shown per line, after the node and event name
+const SUMMARY_FIELDS: usize = 4;
+/// Longest string field value shown before it is cut off
+const MAX_FIELD_CHARS: usize = 48;
+
+/// `a.b.c.d:port` / `[v6]:port` of a peer address field, or of the address in peer details
+fn address_text(value: &Value) -> Option<String> {
+    let value = value.get("peer_address").unwrap_or(value);
+    serde_json::from_value::<PeerAddress>(value.clone()).ok().map(|address| address.to_string())
+}
+
+/// `key=value` pairs of the event's scalar fields (timestamp left out), byte arrays of 16
+/// or more bytes as short hex, peer addresses as socket addresses; other nested structures
+/// are skipped
+fn event_summary(event: &Event) -> String {
+    let value = serde_json::to_value(event).unwrap_or(Value::Null);
+    // Skip the variant wrapper: {"BestBlockChanged": {...}}
+    let Some(Value::Object(fields)) = value.as_object().and_then(|m| m.values().next()) else {
+        return String::new();
+    };
+    let mut summary = String::new();
+    let mut shown = 0;
+    for (key, field) in fields {
+        if key == "timestamp" || shown == SUMMARY_FIELDS {
+            continue;
+        }
+        let text = match field {
+            Value::Bool(b) => b.to_string(),
+            Value::Number(n) => n.to_string(),
+            Value::String(s) if s.chars().count() > MAX_FIELD_CHARS => {
+                format!("\"{}…\"", s.chars().take(MAX_FIELD_CHARS).collect::<String>())
+            }
+            Value::String(s) => format!("\"{}\"", s),
+            Value::Object(_) => match address_text(field) {
+                Some(address) => address,
+                None => continue,
+            },
+            Value::Array(items) => {
+                let bytes: Option<Vec<u8>> = items
+                    .iter()
+                    .map(|v| v.as_u64().and_then(|b| u8::try_from(b).ok()))
+                    .collect();
+                match bytes {
+                    Some(bytes) if bytes.len() >= 16 => format!("{}…", hex::encode(&bytes[..4])),
+                    _ => continue,
+                }
+            }
+            _ => continue,
+        };
+        if !summary.is_empty() {
+            summary.push(' ');
+        }
+        let _ = write!(summary, "{}={}", key, text);
+        shown += 1;
+    }
+    summary
+}
+
+/// `HH:MM:SS.mmm` (UTC) of a unix timestamp
+fn format_log_time(unix_seconds: f64) -> String {
+    let (_, _, _, hour, minute, second) = utc_parts(unix_seconds);
+    let millis = (unix_seconds.fract() * 1000.0) as u32;
+    format!("{:02}:{:02}:{:02}.{:03}", hour, minute, second, millis)
+}
+
+/// An event taken for the log right after it was parsed, made into a line by
+/// [`JamApp::append_event_log`]
+pub(crate) struct LoggedEvent {
+    node_index: u16,
+    node_id: String,
+    event_type: EventType,
+    summary: String,
+}
+
+/// The event `result` was just parsed from, if `selected` (the selected event types while
+/// the log is open, else None) lets its type through. Call it before the next message is
+/// parsed: the event is the newest of its type stored for the node.
+pub(crate) fn capture_log_event(
+    selected: Option<&[bool]>,
+    events: &EventStore,
+    result: &ParseResult,
+) -> Option<LoggedEvent> {
+    if !selected?.get(result.event_type.idx()).copied().unwrap_or(false) {
+        return None;
+    }
+    let node_index = result.node_index?;
+    let stored = events.node_events(&result.node_id, result.event_type as u8)?.back()?;
+    if !events.shown(stored) {
+        return None;
+    }
+    Some(LoggedEvent {
+        node_index,
+        node_id: result.node_id.clone(),
+        event_type: result.event_type,
+        summary: event_summary(&stored.event),
+    })
+}
+
+impl JamApp {
+    /// Append the events captured while parsing this frame's messages to the log
+    pub(crate) fn append_event_log(&mut self, logged: Vec<LoggedEvent>) {
+        if logged.is_empty() {
+            return;
+        }
+        let time = format_log_time(now_unix_seconds());
+        for event in logged {
+            let line = format!(
+                "{} {:>4} {:<12} {} {}",
+                time,
+                event.node_index,
+                self.node_label(&event.node_id),
+                event_name(event.event_type),
+                event.summary,
+            );
+            if self.event_log.len() == EVENT_LOG_LINES {
+                self.event_log.pop_front();
+            }
+            self.event_log.push_back(line);
+        }
+    }
+
+    /// Header toggle for the event log
+    pub(crate) fn render_event_log_button(&mut self, ui: &mut egui::Ui) {
+        let color = if self.show_event_log {
+            colors::text_primary()
+        } else {
+            colors::text_muted()
+        };
+        if ui
+            .selectable_label(self.show_event_log, egui::RichText::new("Log").color(color))
+            .on_hover_text("Live stream of incoming events of the selected types")
+            .clicked()
+        {
+            self.show_event_log = !self.show_event_log;
+        }
+    }
+
+    pub(crate) fn render_event_log(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_event_log;
+        egui::Window::new("Event Log")
+            .id(egui::Id::new("event_log"))
+            .open(&mut open)
+            .default_width(640.0)
+            .default_height(320.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.event_log_scroll_paused, "Pause scroll")
+                        .on_hover_text("Stop following new lines; the log keeps filling");
+                    if ui.small_button("clear").clicked() {
+                        self.event_log.clear();
+                    }
+                    let all: Vec<&str> = self.event_log.iter().map(String::as_str).collect();
+                    copy_button(ui, &all.join("\n"));
+                    ui.label(
+                        egui::RichText::new(format!("{} / {} lines", self.event_log.len(), EVENT_LOG_LINES))
+                            .color(colors::text_muted())
+                            .small(),
+                    );
+                });
+                ui.add_space(4.0);
+
+                if self.event_log.is_empty() {
+                    ui.label(
+                        egui::RichText::new("Waiting for events of the selected types")
+                            .color(colors::text_muted()),
+                    );
+                    return;
+                }
+                let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+                egui::ScrollArea::both()
+                    .auto_shrink([false, false])
+                    .stick_to_bottom(!self.event_log_scroll_paused)
+                    .show_rows(ui, row_height, self.event_log.len(), |ui, range| {
+                        for line in self.event_log.range(range) {
+                            ui.label(
+                                egui::RichText::new(line)
+                                    .monospace()
+                                    .color(colors::text_secondary()),
+                            );
+                        }
+                    });
+            });
+        self.show_event_log = open;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_lists_scalar_fields_without_timestamp() {
+        let event = Event::BestBlockChanged { timestamp: 7, slot: 42, hash: [0xab; 32] };
+        let summary = event_summary(&event);
+        assert!(summary.contains("slot=42"));
+        assert!(summary.contains("hash=abababab…"));
+        assert!(!summary.contains("timestamp"));
+    }
+
+    #[test]
+    fn summary_shows_peer_addresses() {
+        let mut ipv6 = [0u8; 16];
+        ipv6[10..].copy_from_slice(&[0xff, 0xff, 10, 0, 0, 7]);
+        let event = Event::ConnectingIn { timestamp: 0, from: PeerAddress { ipv6, port: 30333 } };
+        assert_eq!(event_summary(&event), "from=10.0.0.7:30333");
+    }
+
+    #[test]
+    fn each_parsed_event_is_captured_once() {
+        use crate::core::Codec;
+
+        let mut store = EventStore::new(100, 60.0);
+        let mut selected = vec![true; 256];
+        let parsed = |store: &mut EventStore, slot: u32| {
+            store.push("node1", Event::BestBlockChanged { timestamp: 0, slot, hash: [0; 32] }, 1.0);
+            ParseResult {
+                event_id: None,
+                codec: Codec::Json,
+                node_dropped: 0,
+                event_type: EventType::BestBlockChanged,
+                node_index: store.node_index("node1"),
+                node_id: "node1".to_string(),
+                partial: false,
+            }
+        };
+        // Two events of one node in a batch: each captured as it is parsed
+        let first = parsed(&mut store, 1);
+        let first = capture_log_event(Some(&selected), &store, &first).unwrap();
+        let second = parsed(&mut store, 2);
+        let second = capture_log_event(Some(&selected), &store, &second).unwrap();
+        assert!(first.summary.contains("slot=1"));
+        assert!(second.summary.contains("slot=2"));
+
+        // Log closed, or the type filtered out
+        let third = parsed(&mut store, 3);
+        assert!(capture_log_event(None, &store, &third).is_none());
+        selected[EventType::BestBlockChanged.idx()] = false;
+        assert!(capture_log_event(Some(&selected), &store, &third).is_none());
+    }
+
+    #[test]
+    fn log_time_is_utc_with_millis() {
+        // 2025-01-01 00:00:00 UTC plus 1h 2m 3.25s
+        assert_eq!(format_log_time(1_735_732_800.0 + 3_723.25), "01:02:03.250");
+    }
+}
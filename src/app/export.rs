@@ -0,0 +1,49 @@
+//! CSV export of the in-memory EventStore (native only)
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use tracing::{error, info};
+use crate::core::EventStore;
+use super::{with_data, JamApp};
+
+/// Write all events in `events` to `path` as CSV. Returns the number of rows written.
+pub(crate) fn write_events_csv(events: &EventStore, path: &Path) -> io::Result<usize> {
+    let mut w = BufWriter::new(File::create(path)?);
+    let rows = events.export_csv(&mut w)?;
+    w.flush()?;
+    Ok(rows)
+}
+
+impl JamApp {
+    /// Write all stored events to `path` as CSV. Returns the number of rows written.
+    pub(crate) fn export_events_csv(&self, path: &Path) -> io::Result<usize> {
+        with_data!(self, |data| write_events_csv(&data.events, path))
+    }
+
+    /// Ask for a destination with a save dialog, then export
+    pub(crate) fn export_csv_dialog(&self) {
+        let Some(path) = rfd::FileDialog::new()
+            .set_file_name("jam-orbit-events.csv")
+            .add_filter("CSV", &["csv"])
+            .save_file()
+        else {
+            return;
+        };
+        self.export_csv_logged(&path);
+    }
+
+    /// `--export-csv-on-exit`: called from `on_exit`
+    pub(crate) fn export_csv_on_exit(&self) {
+        if let Some(path) = self.export_csv_on_exit.as_deref() {
+            self.export_csv_logged(path);
+        }
+    }
+
+    fn export_csv_logged(&self, path: &Path) {
+        match self.export_events_csv(path) {
+            Ok(rows) => info!(path = %path.display(), rows, "Exported events to CSV"),
+            Err(e) => error!(path = %path.display(), error = %e, "CSV export failed"),
+        }
+    }
+}
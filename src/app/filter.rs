@@ -1,10 +1,14 @@
 //! Accordion-style event filter with tri-state + errors checkboxes
 
 use eframe::egui;
+use regex::Regex;
 use crate::core::{event_name, EVENT_CATEGORIES, OUTBOUND_EVENTS, INBOUND_EVENTS, BIDIR_EVENTS};
 use crate::core::events::{ERROR_EVENT_TYPES, EventType};
 use crate::theme::colors;
-use super::JamApp;
+use super::{JamApp, with_data};
+
+/// Errors listed under the reason filter
+const RECENT_ERRORS_LIMIT: usize = 8;
 
 // ── Pure state-transition functions (testable without egui) ──
 
@@ -17,6 +21,14 @@ pub fn toggle_category_all(selected: &mut [bool], event_types: &[EventType]) {
     }
 }
 
+/// Legend entry click: lit (any on)→none, dimmed→all.
+pub fn toggle_legend_entry(selected: &mut [bool], event_types: &[EventType]) {
+    let any_on = event_types.iter().any(|&et| selected[et.idx()]);
+    for &et in event_types {
+        selected[et.idx()] = !any_on;
+    }
+}
+
 /// Errors checkbox click: if already errors-only→all, else→errors-only.
 pub fn toggle_category_errors(selected: &mut [bool], event_types: &[EventType]) {
     let is_errors_only = event_types.iter().all(|&et| {
@@ -55,6 +67,25 @@ pub fn narrow_keep_only(selected: &mut [bool], keep: &[EventType]) {
     }
 }
 
+/// Event types of a category whose name contains `query` (already lowercased).
+/// An empty query matches everything.
+pub fn matching_events(event_types: &[EventType], query: &str) -> Vec<EventType> {
+    event_types
+        .iter()
+        .copied()
+        .filter(|&et| query.is_empty() || event_name(et).to_lowercase().contains(query))
+        .collect()
+}
+
+/// Compile a reason filter. Blank means no filter; an invalid pattern returns the regex error.
+pub fn compile_reason_filter(pattern: &str) -> Result<Option<Regex>, String> {
+    let pattern = pattern.trim();
+    if pattern.is_empty() {
+        return Ok(None);
+    }
+    Regex::new(pattern).map(Some).map_err(|e| e.to_string())
+}
+
 /// Narrow selection: remove events in `remove` set.
 pub fn narrow_remove(selected: &mut [bool], remove: &[EventType]) {
     for &et in remove {
@@ -69,17 +100,34 @@ impl JamApp {
         egui::SidePanel::left("event_filter")
             .default_width(300.0)
             .resizable(true)
-            .frame(egui::Frame::new().fill(colors::BG_PRIMARY).inner_margin(8.0))
+            .frame(egui::Frame::new().fill(colors::bg_primary()).inner_margin(8.0))
             .show(ctx, |ui| {
                 let group_frame = egui::Frame::new()
-                    .stroke(egui::Stroke::new(1.0, colors::TEXT_MUTED.gamma_multiply(0.6)))
+                    .stroke(egui::Stroke::new(1.0, colors::text_muted().gamma_multiply(0.6)))
                     .corner_radius(4.0)
                     .inner_margin(6.0);
 
-                // ── Select group ──
+                // ── Search ──
+                ui.horizontal(|ui| {
+                    let clear_width = if self.event_search.is_empty() { 0.0 } else { 24.0 };
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.event_search)
+                            .hint_text("Search events")
+                            .desired_width(ui.available_width() - clear_width),
+                    );
+                    if !self.event_search.is_empty() && ui.small_button("✕").clicked() {
+                        self.event_search.clear();
+                    }
+                });
+                let query = self.event_search.trim().to_lowercase();
+                let searching = !query.is_empty();
+
+                ui.add_space(4.0);
+
+                // ── Select group (always acts on the full set, not just search hits) ──
                 group_frame.show(ui, |ui| {
                     ui.set_min_width(ui.available_width());
-                    ui.label(egui::RichText::new("Select:").color(colors::TEXT_MUTED));
+                    ui.label(egui::RichText::new("Select:").color(colors::text_muted()));
                     ui.horizontal(|ui| {
                         if ui.button("All").clicked() {
                             self.apply_all_filter();
@@ -98,7 +146,7 @@ impl JamApp {
 
                 // ── Narrow group ──
                 group_frame.show(ui, |ui| {
-                    ui.label(egui::RichText::new("Narrow:").color(colors::TEXT_MUTED));
+                    ui.label(egui::RichText::new("Narrow:").color(colors::text_muted()));
                     ui.horizontal_wrapped(|ui| {
                         if ui.button("Outbound").clicked() {
                             narrow_keep_only(&mut self.selected_events, OUTBOUND_EVENTS);
@@ -120,6 +168,102 @@ impl JamApp {
                     });
                 });
 
+                ui.add_space(4.0);
+
+                // ── Server-side filter ──
+                group_frame.show(ui, |ui| {
+                    ui.set_min_width(ui.available_width());
+                    ui.label(egui::RichText::new("Server:").color(colors::text_muted()));
+                    ui.checkbox(&mut self.server_filter_enabled, "Filter on server")
+                        .on_hover_text(
+                            "Only receive the selected event types. Saves bandwidth, but \
+                             events of deselected types are never stored and can't be \
+                             shown later.",
+                        );
+                    if self.server_filter_enabled && self.server_filter.is_rejected() {
+                        ui.label(
+                            egui::RichText::new("Not supported by server, filtering locally")
+                                .color(egui::Color32::from_rgb(200, 100, 100))
+                                .small(),
+                        );
+                    }
+                });
+
+                ui.add_space(4.0);
+
+                // ── Endpoints (multi-endpoint sessions only) ──
+                #[cfg(not(target_arch = "wasm32"))]
+                self.render_source_filter(ui, group_frame);
+
+                // ── Recent errors, optionally filtered by reason ──
+                group_frame.show(ui, |ui| {
+                    ui.set_min_width(ui.available_width());
+                    ui.label(egui::RichText::new("Recent errors:").color(colors::text_muted()));
+                    let response = ui.add(
+                        egui::TextEdit::singleline(&mut self.reason_filter)
+                            .hint_text("Reason regex")
+                            .desired_width(ui.available_width()),
+                    );
+                    if response.changed() {
+                        match compile_reason_filter(&self.reason_filter) {
+                            Ok(regex) => {
+                                self.reason_regex = regex;
+                                self.reason_filter_error = None;
+                            }
+                            Err(e) => {
+                                self.reason_regex = None;
+                                self.reason_filter_error = Some(e);
+                            }
+                        }
+                    }
+                    if let Some(ref error) = self.reason_filter_error {
+                        // regex errors are multi-line (pattern, caret, message); show the message,
+                        // full text on hover
+                        let summary = error.lines().last().unwrap_or(error.as_str());
+                        ui.label(
+                            egui::RichText::new(summary)
+                                .color(egui::Color32::from_rgb(200, 100, 100))
+                                .small(),
+                        )
+                        .on_hover_text(egui::RichText::new(error).monospace());
+                        return;
+                    }
+
+                    let now = self.view_now();
+                    let errors: Vec<(String, &'static str, String)> = with_data!(self, |data| {
+                        data.events
+                            .recent_errors(
+                                ERROR_EVENT_TYPES,
+                                self.reason_regex.as_ref(),
+                                RECENT_ERRORS_LIMIT,
+                                now,
+                                self.time_window as f64,
+                            )
+                            .into_iter()
+                            .map(|(node_id, stored)| {
+                                (
+                                    self.node_label(node_id),
+                                    event_name(stored.event.event_type()),
+                                    stored.event.reason().unwrap_or_default().into_owned(),
+                                )
+                            })
+                            .collect()
+                    });
+                    if errors.is_empty() {
+                        ui.label(egui::RichText::new("none").color(colors::text_muted()).small());
+                    }
+                    for (node, name, reason) in errors {
+                        ui.label(
+                            egui::RichText::new(format!("{} {}", node, name))
+                                .color(colors::text_secondary())
+                                .small(),
+                        );
+                        if !reason.is_empty() {
+                            ui.label(egui::RichText::new(format!("  {}", reason)).color(colors::text_muted()).small());
+                        }
+                    }
+                });
+
                 ui.add_space(4.0);
                 ui.separator();
                 ui.add_space(4.0);
@@ -138,6 +282,10 @@ impl JamApp {
                         let is_single_category = active_cat_count == 1;
 
                         for (cat_idx, category) in EVENT_CATEGORIES.iter().enumerate() {
+                            let visible_events = matching_events(category.event_types, &query);
+                            if visible_events.is_empty() {
+                                continue;
+                            }
                             let selected_count = category
                                 .event_types
                                 .iter()
@@ -146,7 +294,8 @@ impl JamApp {
                             let total = category.event_types.len();
                             let all_selected = selected_count == total;
                             let none_selected = selected_count == 0;
-                            let is_expanded = self.expanded_category == Some(cat_idx);
+                            // Categories with search hits open automatically
+                            let is_expanded = searching || self.expanded_category == Some(cat_idx);
                             let has_errors = category
                                 .event_types
                                 .iter()
@@ -170,7 +319,7 @@ impl JamApp {
                                     let h = rect.width() * 0.2;
                                     ui.painter().line_segment(
                                         [egui::pos2(c.x - h, c.y), egui::pos2(c.x + h, c.y)],
-                                        egui::Stroke::new(2.0, colors::TEXT_PRIMARY),
+                                        egui::Stroke::new(2.0, colors::text_primary()),
                                     );
                                 }
                                 let left_clicked = cb.clicked();
@@ -270,9 +419,9 @@ impl JamApp {
 
                                 // Category name + count + arrow (single clickable element)
                                 let text_color = if none_selected {
-                                    colors::TEXT_MUTED
+                                    colors::text_muted()
                                 } else {
-                                    colors::TEXT_SECONDARY
+                                    colors::text_secondary()
                                 };
                                 let arrow = if is_expanded { "▾" } else { "▸" };
                                 let label_text = format!(
@@ -283,7 +432,7 @@ impl JamApp {
                                     is_expanded,
                                     egui::RichText::new(label_text).color(text_color),
                                 );
-                                if label.clicked() {
+                                if label.clicked() && !searching {
                                     new_expanded = if is_expanded { None } else { Some(cat_idx) };
                                 }
                             });
@@ -291,13 +440,13 @@ impl JamApp {
                             // ── Expanded events ──
                             if is_expanded {
                                 ui.indent(cat_idx, |ui| {
-                                    for &et in category.event_types {
+                                    for &et in &visible_events {
                                         let mut enabled = self.selected_events[et.idx()];
                                         let name = event_name(et);
                                         let text_color = if enabled {
-                                            colors::TEXT_PRIMARY
+                                            colors::text_primary()
                                         } else {
-                                            colors::TEXT_MUTED
+                                            colors::text_muted()
                                         };
                                         ui.horizontal(|ui| {
                                             ui.spacing_mut().item_spacing.x = 4.0;
@@ -398,6 +547,21 @@ mod tests {
         assert!(CONNECTION_EVENTS.iter().all(|&et| sel[et.idx()]));
     }
 
+    #[test]
+    fn legend_click_turns_lit_entry_off() {
+        // A partially enabled category is lit in the legend, so a click hides it
+        let mut sel = none_selected();
+        sel[EventType::ConnectingIn.idx()] = true;
+        toggle_legend_entry(&mut sel, CONNECTION_EVENTS);
+        assert!(CONNECTION_EVENTS.iter().all(|&et| !sel[et.idx()]));
+        toggle_legend_entry(&mut sel, CONNECTION_EVENTS);
+        assert!(CONNECTION_EVENTS.iter().all(|&et| sel[et.idx()]));
+
+        toggle_legend_entry(&mut sel, &[EventType::Disconnected]);
+        assert!(!sel[EventType::Disconnected.idx()]);
+        assert!(sel[EventType::ConnectedIn.idx()]);
+    }
+
     #[test]
     fn click_left_when_errors_only_turns_all() {
         let mut sel = none_selected();
@@ -509,6 +673,25 @@ mod tests {
         assert!(sel[EventType::GuaranteeBuilt as usize]);
     }
 
+    #[test]
+    fn matching_events_filters_by_name() {
+        assert_eq!(matching_events(CONNECTION_EVENTS, "").len(), CONNECTION_EVENTS.len());
+        assert_eq!(
+            matching_events(CONNECTION_EVENTS, "failed"),
+            vec![EventType::ConnectInFailed, EventType::ConnectOutFailed],
+        );
+        assert!(matching_events(CONNECTION_EVENTS, "guarantee").is_empty());
+    }
+
+    #[test]
+    fn compile_reason_filter_cases() {
+        assert!(compile_reason_filter("  ").unwrap().is_none());
+        let re = compile_reason_filter("time(d )?out").unwrap().unwrap();
+        assert!(re.is_match("handshake timed out"));
+        assert!(!re.is_match("connection reset"));
+        assert!(compile_reason_filter("(unclosed").is_err());
+    }
+
     #[test]
     fn is_errors_only_false_for_category_without_errors() {
         let sel = all_selected();
@@ -0,0 +1,190 @@
+//! Peer map — remote addresses of connection attempts placed on a world map by GeoIP
+//! (native, `--geoip <mmdb>`); the browser build only counts them
+
+use std::collections::{HashMap, HashSet};
+use eframe::egui;
+use crate::core::PeerAddress;
+use crate::theme::colors;
+use super::{with_data, JamApp};
+
+/// Size of the grid cells peers are grouped into, degrees
+const REGION_DEG: f32 = 5.0;
+/// Graticule spacing, degrees
+const GRID_DEG: f32 = 30.0;
+
+/// Offline GeoIP lookups from a MaxMind City database, cached per address
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) struct GeoIp {
+    reader: maxminddb::Reader<Vec<u8>>,
+    /// Address → (latitude, longitude); `None` when the database has no location for it
+    cache: HashMap<[u8; 16], Option<(f32, f32)>>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl GeoIp {
+    pub(crate) fn open(path: &std::path::Path) -> Result<Self, String> {
+        let reader = maxminddb::Reader::open_readfile(path).map_err(|e| e.to_string())?;
+        Ok(Self { reader, cache: HashMap::new() })
+    }
+
+    fn locate(&mut self, address: &PeerAddress) -> Option<(f32, f32)> {
+        let reader = &self.reader;
+        *self.cache.entry(address.ipv6).or_insert_with(|| {
+            let city: maxminddb::geoip2::City = reader.lookup(address.ip()).ok()?;
+            let location = city.location?;
+            Some((location.latitude? as f32, location.longitude? as f32))
+        })
+    }
+}
+
+/// Peers per `cell`-degree grid cell as (cell center latitude, longitude, count), busiest first
+fn region_counts(points: &[(f32, f32)], cell: f32) -> Vec<(f32, f32, usize)> {
+    let mut cells: HashMap<(i32, i32), usize> = HashMap::new();
+    for &(lat, lon) in points {
+        let key = ((lat / cell).floor() as i32, (lon / cell).floor() as i32);
+        *cells.entry(key).or_default() += 1;
+    }
+    let mut regions: Vec<(f32, f32, usize)> = cells
+        .into_iter()
+        .map(|((lat, lon), count)| ((lat as f32 + 0.5) * cell, (lon as f32 + 0.5) * cell, count))
+        .collect();
+    regions.sort_by(|a, b| b.2.cmp(&a.2).then(a.0.total_cmp(&b.0)).then(a.1.total_cmp(&b.1)));
+    regions
+}
+
+impl JamApp {
+    /// Header toggle for the peer map
+    pub(crate) fn render_geo_map_button(&mut self, ui: &mut egui::Ui) {
+        let color = if self.show_geo_map {
+            colors::text_primary()
+        } else {
+            colors::text_muted()
+        };
+        if ui
+            .selectable_label(self.show_geo_map, egui::RichText::new("Map").color(color))
+            .on_hover_text("Where connecting peers are, by GeoIP of their addresses")
+            .clicked()
+        {
+            self.show_geo_map = !self.show_geo_map;
+        }
+    }
+
+    pub(crate) fn render_geo_map(&mut self, ctx: &egui::Context) {
+        let now = self.view_now();
+        let addresses = with_data!(self, |data| {
+            data.events.peer_addresses(now, self.time_window as f64)
+        });
+        let distinct = addresses.iter().map(PeerAddress::ip).collect::<HashSet<_>>().len();
+        #[cfg(not(target_arch = "wasm32"))]
+        let located: Option<Vec<(f32, f32)>> = self
+            .geoip
+            .as_mut()
+            .map(|geoip| addresses.iter().filter_map(|a| geoip.locate(a)).collect());
+        #[cfg(target_arch = "wasm32")]
+        let located: Option<Vec<(f32, f32)>> = None;
+
+        let mut open = self.show_geo_map;
+        egui::Window::new("Peer Map")
+            .id(egui::Id::new("geo_map"))
+            .open(&mut open)
+            .default_size([640.0, 360.0])
+            .show(ctx, |ui| {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "{} connection attempts from {} addresses in the last {:.0}s",
+                        addresses.len(),
+                        distinct,
+                        self.time_window,
+                    ))
+                    .color(colors::text_muted()),
+                );
+                let Some(located) = located else {
+                    ui.label(
+                        egui::RichText::new(
+                            "No GeoIP database: start the native app with --geoip <GeoLite2-City.mmdb>",
+                        )
+                        .color(colors::text_muted())
+                        .small(),
+                    );
+                    return;
+                };
+                if located.len() < addresses.len() {
+                    ui.label(
+                        egui::RichText::new(format!("{} without a location", addresses.len() - located.len()))
+                            .color(colors::text_muted())
+                            .small(),
+                    );
+                }
+                draw_world_map(ui, &region_counts(&located, REGION_DEG));
+            });
+        self.show_geo_map = open;
+    }
+}
+
+/// Equirectangular map: graticule, equator and one dot per region, area by peer count
+fn draw_world_map(ui: &mut egui::Ui, regions: &[(f32, f32, usize)]) {
+    let available = ui.available_size();
+    let width = available.x.min(available.y * 2.0).max(100.0);
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(width, width / 2.0), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, colors::shade(255, 8));
+    let to_screen = |lat: f32, lon: f32| {
+        egui::pos2(
+            rect.left() + (lon + 180.0) / 360.0 * rect.width(),
+            rect.top() + (90.0 - lat) / 180.0 * rect.height(),
+        )
+    };
+
+    let grid = egui::Stroke::new(1.0, colors::shade(255, 20));
+    let mut lon = -180.0 + GRID_DEG;
+    while lon < 180.0 {
+        painter.vline(to_screen(0.0, lon).x, rect.y_range(), grid);
+        lon += GRID_DEG;
+    }
+    let mut lat = -90.0 + GRID_DEG;
+    while lat < 90.0 {
+        let stroke = if lat == 0.0 { egui::Stroke::new(1.0, colors::shade(255, 45)) } else { grid };
+        painter.hline(rect.x_range(), to_screen(lat, 0.0).y, stroke);
+        lat += GRID_DEG;
+    }
+
+    let radius = |count: usize| (2.0 + 2.0 * (count as f32).sqrt()).min(24.0);
+    let dot = egui::Color32::from_rgb(100, 150, 200);
+    for &(lat, lon, count) in regions.iter().rev() {
+        painter.circle_filled(to_screen(lat, lon), radius(count), dot.gamma_multiply(0.6));
+    }
+
+    if let Some(pos) = response.hover_pos() {
+        let hovered = regions
+            .iter()
+            .map(|&(lat, lon, count)| (lat, lon, count, to_screen(lat, lon).distance(pos)))
+            .filter(|&(_, _, count, distance)| distance <= radius(count).max(6.0))
+            .min_by(|a, b| a.3.total_cmp(&b.3));
+        if let Some((lat, lon, count, _)) = hovered {
+            response.on_hover_text_at_pointer(format!(
+                "{} connection attempts\naround {:.0}°{}, {:.0}°{}",
+                count,
+                lat.abs(),
+                if lat >= 0.0 { "N" } else { "S" },
+                lon.abs(),
+                if lon >= 0.0 { "E" } else { "W" },
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn regions_group_nearby_peers() {
+        let points = [(52.5, 13.4), (51.0, 12.0), (40.7, -74.0), (-33.9, 151.2)];
+        let regions = region_counts(&points, 5.0);
+        assert_eq!(regions.len(), 3);
+        // Berlin and Leipzig share the 50–55°N, 10–15°E cell
+        assert_eq!(regions[0], (52.5, 12.5, 2));
+        assert!(regions.contains(&(42.5, -72.5, 1)));
+        assert!(regions.contains(&(-32.5, 152.5, 1)));
+    }
+}
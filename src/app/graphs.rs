@@ -1,23 +1,226 @@
 //! Graphs tab: peer count, particle trails, event rates, block scatter plots
 
 use eframe::egui;
-use crate::core::EVENT_CATEGORIES;
+use crate::core::{TimeSeriesData, EVENT_CATEGORIES};
 use crate::theme::colors;
-use crate::time::now_seconds;
-use super::{JamApp, with_data};
+use super::{JamApp, RateMode, RateSmoothing, SeriesBand, SharedData, RATE_BUCKETS, with_data};
+use super::inspector::INSPECT_HIT_PX;
 
 use std::sync::Arc;
 use crate::vring::FilterBitfield;
 use crate::scatter::{ScatterCallback, ScatterParticle, ScatterUniforms};
 
+/// Stable pseudo-random value in [0, 1) derived from a particle's identity (splitmix64)
+fn particle_unit_hash(p: &ScatterParticle) -> f64 {
+    let mut x = (p.node_index.to_bits() as u64) << 32 | p.birth_time.to_bits() as u64;
+    x ^= (p.event_type.to_bits() as u64).rotate_left(17);
+    x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^= x >> 31;
+    (x >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Share of particles to keep so that about `cap` of `total` visible ones are drawn
+fn keep_fraction(total: usize, cap: usize) -> f64 {
+    if total <= cap {
+        1.0
+    } else {
+        cap as f64 / total as f64
+    }
+}
+
+/// Keep a `keep` share of the particles, chosen by a hash of each particle so the same event
+/// is always kept (or dropped) and density stays proportional everywhere.
+fn downsample_particles(particles: &mut Vec<ScatterParticle>, keep: f64) {
+    if keep < 1.0 {
+        particles.retain(|p| particle_unit_hash(p) < keep);
+    }
+}
+
+/// Human-readable byte size with binary units (1536 → "1.5 KiB")
+pub(crate) fn format_bytes(bytes: f64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes;
+    let mut unit = 0;
+    while value.abs() >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{:.0} {}", value, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// Validators listed in the aggregate-series hover breakdown
+const AGGREGATE_BREAKDOWN_ROWS: usize = 5;
+
+fn totals_at(totals: &[f32], x: usize) -> f32 {
+    totals.get(x).copied().unwrap_or(0.0)
+}
+
+/// Largest per-validator values at point `x` of an end-aligned aggregate of length `len`
+/// (see [`TimeSeriesData::aggregate`]): up to `limit` (label, value) pairs, largest first,
+/// and how many other validators contributed.
+fn breakdown_at(
+    per_node: &[(String, Vec<f32>)],
+    len: usize,
+    x: usize,
+    limit: usize,
+) -> (Vec<(&str, f32)>, usize) {
+    let mut values: Vec<(&str, f32)> = per_node
+        .iter()
+        .filter_map(|(label, series)| {
+            let offset = len.checked_sub(series.len())?;
+            let value = *series.get(x.checked_sub(offset)?)?;
+            Some((label.as_str(), value))
+        })
+        .filter(|(_, v)| *v > 0.0)
+        .collect();
+    values.sort_by(|a, b| b.1.total_cmp(&a.1));
+    let rest = values.len().saturating_sub(limit);
+    values.truncate(limit);
+    (values, rest)
+}
+
+/// Stable per-validator line color: hues spaced by the golden ratio so neighbouring
+/// indices stay distinguishable
+pub(crate) fn validator_color(index: u16) -> egui::Color32 {
+    let hue = (index as f32 * 0.618_034).fract();
+    egui::ecolor::Hsva::new(hue, 0.65, 0.95, 1.0).into()
+}
+
+/// One per-node series drawn by [`JamApp::render_node_series`]; several channels share a plot
+/// and are told apart by line style (and by color when lines aren't colored per validator)
+struct SeriesChannel {
+    select: fn(&SharedData) -> &TimeSeriesData,
+    /// Appended to the line name; empty for single-channel plots
+    name: &'static str,
+    style: egui_plot::LineStyle,
+    color: fn() -> egui::Color32,
+}
+
+impl SeriesChannel {
+    /// The only series of its plot
+    fn single(select: fn(&SharedData) -> &TimeSeriesData) -> Self {
+        Self { select, name: "", style: egui_plot::LineStyle::Solid, color: colors::text_primary }
+    }
+}
+
+/// Peer count channels in `JamApp::peer_channels` order: total, validator, sync
+const PEER_CHANNELS: [SeriesChannel; 3] = [
+    SeriesChannel {
+        select: |data| &data.time_series,
+        name: "total",
+        style: egui_plot::LineStyle::Solid,
+        color: colors::text_primary,
+    },
+    SeriesChannel {
+        select: |data| &data.peers.val_peers,
+        name: "validator",
+        style: egui_plot::LineStyle::Dashed { length: 6.0 },
+        color: || egui::Color32::from_rgb(100, 200, 100),
+    },
+    SeriesChannel {
+        select: |data| &data.peers.sync_peers,
+        name: "sync",
+        style: egui_plot::LineStyle::Dotted { spacing: 4.0 },
+        color: || egui::Color32::from_rgb(200, 200, 100),
+    },
+];
+
+/// Labels of the peer count channels, for the settings checkboxes
+pub(crate) const PEER_CHANNEL_NAMES: [&str; 3] = ["Total", "Validator peers", "Sync peers"];
+
+/// Heatmap color for a guarantee queue depth: green (shallow) → yellow → red (deepest seen)
+fn queue_depth_color(depth: u8, max_depth: u8) -> egui::Color32 {
+    let t = depth as f32 / max_depth.max(1) as f32;
+    let (from, to, t) = if t < 0.5 {
+        ([100.0, 200.0, 100.0], [200.0, 200.0, 100.0], t * 2.0)
+    } else {
+        ([200.0, 200.0, 100.0], [200.0, 100.0, 100.0], (t - 0.5) * 2.0)
+    };
+    let mix = |i: usize| (from[i] + (to[i] - from[i]) * t) as u8;
+    egui::Color32::from_rgb(mix(0), mix(1), mix(2))
+}
+
 impl JamApp {
-    pub(crate) fn render_graphs_tab(&self, ui: &mut egui::Ui) {
+    pub(crate) fn render_graphs_tab(&mut self, ui: &mut egui::Ui) {
         let available = ui.available_size();
-        let graph_height = (available.y - 40.0) / 5.0;
+        let graph_height = (available.y - 44.0) / 6.0;
 
-        // Time series (num_peers)
+        // Peer counts: total, validator and sync channels as enabled in settings
         ui.allocate_ui(egui::vec2(available.x, graph_height), |ui| {
-            self.render_time_series(ui);
+            let channels: Vec<&SeriesChannel> = PEER_CHANNELS
+                .iter()
+                .zip(self.peer_channels)
+                .filter_map(|(channel, enabled)| enabled.then_some(channel))
+                .collect();
+            self.render_node_series(
+                ui,
+                "Peer Count",
+                &channels,
+                self.peer_band,
+                "peers",
+                |v| format!("{:.0}", v),
+            );
+        });
+
+        ui.add_space(4.0);
+
+        // Shard metrics per validator, then preimage pool totals
+        ui.horizontal(|ui| {
+            let quarter_width = (available.x - 30.0) / 4.0;
+
+            ui.allocate_ui(egui::vec2(quarter_width, graph_height), |ui| {
+                self.render_node_series(
+                    ui,
+                    "Shards",
+                    &[&SeriesChannel::single(|data| &data.shards.shard_counts)],
+                    SeriesBand::Lines,
+                    "shards",
+                    |v| format!("{:.0}", v),
+                );
+            });
+
+            ui.add_space(10.0);
+
+            ui.allocate_ui(egui::vec2(quarter_width, graph_height), |ui| {
+                self.render_node_series(
+                    ui,
+                    "Shard Size",
+                    &[&SeriesChannel::single(|data| &data.shards.shard_sizes)],
+                    SeriesBand::Lines,
+                    "size",
+                    format_bytes,
+                );
+            });
+
+            ui.add_space(10.0);
+
+            ui.allocate_ui(egui::vec2(quarter_width, graph_height), |ui| {
+                self.render_aggregate_series(
+                    ui,
+                    "Preimages",
+                    |data| &data.preimages.preimage_counts,
+                    "preimages",
+                    |v| format!("{:.0}", v),
+                );
+            });
+
+            ui.add_space(10.0);
+
+            ui.allocate_ui(egui::vec2(quarter_width, graph_height), |ui| {
+                self.render_aggregate_series(
+                    ui,
+                    "Preimage Pool Size",
+                    |data| &data.preimages.preimage_sizes,
+                    "size",
+                    format_bytes,
+                );
+            });
         });
 
         ui.add_space(4.0);
@@ -36,49 +239,94 @@ impl JamApp {
 
         ui.add_space(4.0);
 
-        // Bottom row: Block scatter plots side by side
+        // Bottom row: Block scatter plots and guarantee queues side by side
         ui.horizontal(|ui| {
-            let half_width = (available.x - 10.0) / 2.0;
+            let quarter_width = (available.x - 30.0) / 4.0;
 
-            ui.allocate_ui(egui::vec2(half_width, graph_height * 2.0 - 10.0), |ui| {
+            ui.allocate_ui(egui::vec2(quarter_width, graph_height * 2.0 - 10.0), |ui| {
                 self.render_best_blocks(ui);
             });
 
             ui.add_space(10.0);
 
-            ui.allocate_ui(egui::vec2(half_width, graph_height * 2.0 - 10.0), |ui| {
+            ui.allocate_ui(egui::vec2(quarter_width, graph_height * 2.0 - 10.0), |ui| {
                 self.render_finalized_blocks(ui);
             });
+
+            ui.add_space(10.0);
+
+            ui.allocate_ui(egui::vec2(quarter_width, graph_height * 2.0 - 10.0), |ui| {
+                self.render_finalization_lag(ui);
+            });
+
+            ui.add_space(10.0);
+
+            ui.allocate_ui(egui::vec2(quarter_width, graph_height * 2.0 - 10.0), |ui| {
+                self.render_guarantee_queues(ui);
+            });
         });
 
     }
 
-    fn render_time_series(&self, ui: &mut egui::Ui) {
-        use egui_plot::{Line, Plot, PlotPoints};
+    /// One line per validator and channel of per-node Status series. Lines are colored by
+    /// validator when enabled and the focused node is drawn on top at full opacity; hover names
+    /// the line. With a band, the first channel's network min/max is shaded behind a median
+    /// line; band-only mode still draws the focused node's lines.
+    fn render_node_series(
+        &self,
+        ui: &mut egui::Ui,
+        title: &str,
+        channels: &[&SeriesChannel],
+        band: SeriesBand,
+        value_name: &str,
+        format_value: fn(f64) -> String,
+    ) {
+        use egui_plot::{Line, Plot, PlotPoints, Polygon};
 
         ui.label(
-            egui::RichText::new("Peer Count")
-                .color(colors::TEXT_MUTED)
+            egui::RichText::new(title)
+                .color(colors::text_muted())
                 .size(14.0),
         );
 
-        let (point_count, y_min, y_max, series_data) = with_data!(self, |data| {
-            let point_count = data.time_series.point_count();
-            let (y_min, y_max) = data
-                .time_series
-                .series
-                .iter()
-                .flat_map(|s| s.iter())
-                .fold((f32::MAX, f32::MIN), |(min, max), &v| {
-                    (min.min(v), max.max(v))
-                });
-
-            let series_data: Vec<Vec<f32>> =
-                data.time_series.series.to_vec();
+        let (point_count, y_min, y_max, mut series_data, spread) = with_data!(self, |data| {
+            let spread = match (band, channels.first()) {
+                (SeriesBand::Band | SeriesBand::Both, Some(first)) => (first.select)(data).spread(),
+                _ => Vec::new(),
+            };
+            let mut point_count = 0;
+            let (mut y_min, mut y_max) = (f32::MAX, f32::MIN);
+            // (node_id, ring index, channel, values) — the ring index keys color and focus
+            let mut series_data: Vec<(String, Option<u16>, usize, Vec<f32>)> = Vec::new();
+            for (channel, selected) in channels.iter().enumerate() {
+                let time_series = (selected.select)(data);
+                point_count = point_count.max(time_series.max_series_len());
+                for &v in time_series.series.iter().flat_map(|s| s.iter()) {
+                    y_min = y_min.min(v);
+                    y_max = y_max.max(v);
+                }
+                series_data.extend(
+                    time_series
+                        .labeled_series()
+                        .filter(|(_, _, s)| s.len() >= 2)
+                        .map(|(id, _, s)| (id.to_string(), data.events.node_index(id), channel, s.clone())),
+                );
+            }
+            series_data.sort_by_key(|(_, idx, channel, _)| (idx.unwrap_or(u16::MAX), *channel));
 
-            (point_count, y_min, y_max, series_data)
+            (point_count, y_min, y_max, series_data, spread)
         });
 
+        // Everything is aligned on the newest sample, as `spread` is: x runs up to
+        // `point_count` and shorter series start further right
+        let spread_offset = point_count.saturating_sub(spread.len());
+
+        // Focused validator last, so it draws on top
+        let focus = self.focused_node;
+        if focus.is_some() {
+            series_data.sort_by_key(|(_, idx, _, _)| *idx == focus);
+        }
+
         let (y_min, y_max) = if y_min > y_max {
             (0.0, 100.0)
         } else {
@@ -86,7 +334,7 @@ impl JamApp {
             (y_min - pad, y_max + pad)
         };
 
-        Plot::new("time_series")
+        Plot::new(title)
             .show_axes([false, true])
             .show_grid(false)
             .allow_zoom(false)
@@ -97,24 +345,160 @@ impl JamApp {
             .include_x(point_count.max(1) as f64)
             .include_y(y_min as f64)
             .include_y(y_max as f64)
-            .label_formatter(|_name, value| {
-                format!("t={} peers={:.0}", value.x as u32, value.y)
+            .y_axis_formatter(move |mark, _range| format_value(mark.value))
+            .label_formatter(move |name, value| {
+                let reading = format!("t={} {}={}", value.x as u32, value_name, format_value(value.y));
+                if name.is_empty() {
+                    reading
+                } else {
+                    format!("{}\n{}", name, reading)
+                }
             })
             .show(ui, |plot_ui| {
-                for series in &series_data {
-                    if series.len() < 2 {
-                        continue;
+                if spread.len() >= 2 {
+                    // Same per-step quads as the stacked event rates: egui only fills convex shapes
+                    let lower: Vec<f64> = spread.iter().map(|&(min, _, _)| min as f64).collect();
+                    let upper: Vec<f64> = spread.iter().map(|&(_, _, max)| max as f64).collect();
+                    let fill = colors::text_secondary().gamma_multiply(0.25);
+                    for x in 0..spread.len() - 1 {
+                        if let Some(mut quad) = band_segment(x, &lower, &upper) {
+                            for point in &mut quad {
+                                point[0] += spread_offset as f64;
+                            }
+                            plot_ui.polygon(
+                                Polygon::new(PlotPoints::from(quad))
+                                    .name("min–max")
+                                    .fill_color(fill)
+                                    .stroke(egui::Stroke::NONE),
+                            );
+                        }
                     }
+                    let median: PlotPoints = spread
+                        .iter()
+                        .enumerate()
+                        .map(|(x, &(_, median, _))| [(spread_offset + x) as f64, median as f64])
+                        .collect();
+                    plot_ui.line(Line::new(median).color(colors::text_secondary()).width(2.0).name("median"));
+                }
 
+                for (node_id, index, channel, series) in &series_data {
+                    let focused = focus.is_some() && *index == focus;
+                    if band == SeriesBand::Band && !focused {
+                        continue;
+                    }
+                    let channel = channels[*channel];
+                    let offset = point_count - series.len();
                     let points: PlotPoints = series
                         .iter()
                         .enumerate()
-                        .map(|(x, &y)| [x as f64, y as f64])
+                        .map(|(x, &y)| [(offset + x) as f64, y as f64])
                         .collect();
 
-                    let color =
-                        egui::Color32::from_rgba_unmultiplied(255, 255, 255, colors::LINE_ALPHA);
-                    plot_ui.line(Line::new(points).color(color).width(1.0));
+                    let base = match (self.peer_lines_colored, index) {
+                        (true, Some(index)) => validator_color(*index),
+                        _ => (channel.color)(),
+                    };
+                    let (alpha, width) = if focused {
+                        (255, 2.0)
+                    } else {
+                        (colors::LINE_ALPHA, 1.0)
+                    };
+                    let color = egui::Color32::from_rgba_unmultiplied(
+                        base.r(),
+                        base.g(),
+                        base.b(),
+                        alpha,
+                    );
+                    let name = if channel.name.is_empty() {
+                        self.node_label(node_id)
+                    } else {
+                        format!("{} ({})", self.node_label(node_id), channel.name)
+                    };
+                    plot_ui.line(
+                        Line::new(points)
+                            .color(color)
+                            .width(width)
+                            .style(channel.style)
+                            .name(name),
+                    );
+                }
+            });
+    }
+
+    /// Sum of a per-node Status series across validators as a single line.
+    /// Hovering lists the validators contributing most at that point.
+    fn render_aggregate_series(
+        &self,
+        ui: &mut egui::Ui,
+        title: &str,
+        select: fn(&SharedData) -> &TimeSeriesData,
+        value_name: &str,
+        format_value: fn(f64) -> String,
+    ) {
+        use egui_plot::{Line, Plot, PlotPoints};
+
+        ui.label(
+            egui::RichText::new(title)
+                .color(colors::text_muted())
+                .size(14.0),
+        );
+
+        let (totals, per_node) = with_data!(self, |data| {
+            let time_series = select(data);
+            let per_node: Vec<(String, Vec<f32>)> = time_series
+                .labeled_series()
+                .filter(|(_, _, s)| !s.is_empty())
+                .map(|(id, _, s)| (id.to_string(), s.clone()))
+                .collect();
+            (time_series.aggregate(), per_node)
+        });
+        let per_node: Vec<(String, Vec<f32>)> = per_node
+            .into_iter()
+            .map(|(id, s)| (self.node_label(&id), s))
+            .collect();
+
+        let y_max = totals.iter().copied().fold(0.0f32, f32::max).max(1.0) * 1.1;
+        let len = totals.len();
+        let line: Vec<[f64; 2]> = totals
+            .iter()
+            .enumerate()
+            .map(|(x, &y)| [x as f64, y as f64])
+            .collect();
+
+        Plot::new(title)
+            .show_axes([false, true])
+            .show_grid(false)
+            .allow_zoom(false)
+            .allow_drag(false)
+            .allow_scroll(false)
+            .show_background(false)
+            .include_x(0.0)
+            .include_x(len.max(1) as f64)
+            .include_y(0.0)
+            .include_y(y_max as f64)
+            .y_axis_formatter(move |mark, _range| format_value(mark.value))
+            .label_formatter(move |_name, value| {
+                let x = value.x.round().max(0.0) as usize;
+                let mut text = format!(
+                    "t={} total {}={}",
+                    x,
+                    value_name,
+                    format_value(totals_at(&totals, x) as f64),
+                );
+                let (top, rest) = breakdown_at(&per_node, len, x, AGGREGATE_BREAKDOWN_ROWS);
+                for (label, v) in top {
+                    text.push_str(&format!("\n  {}: {}", label, format_value(v as f64)));
+                }
+                if rest > 0 {
+                    text.push_str(&format!("\n  +{} more", rest));
+                }
+                text
+            })
+            .show(ui, |plot_ui| {
+                if line.len() >= 2 {
+                    plot_ui.line(
+                        Line::new(PlotPoints::from(line)).color(colors::text_secondary()).width(1.5),
+                    );
                 }
             });
     }
@@ -124,7 +508,7 @@ impl JamApp {
 
         ui.label(
             egui::RichText::new("Best Block")
-                .color(colors::TEXT_MUTED)
+                .color(colors::text_muted())
                 .size(14.0),
         );
 
@@ -156,7 +540,7 @@ impl JamApp {
             .show(ui, |plot_ui| {
                 plot_ui.points(
                     Points::new(PlotPoints::from(points_data))
-                        .color(egui::Color32::from_rgba_unmultiplied(255, 255, 255, 180))
+                        .color(colors::shade(255, 180))
                         .radius(2.0)
                         .filled(true),
                 );
@@ -168,7 +552,7 @@ impl JamApp {
 
         ui.label(
             egui::RichText::new("Finalized Block")
-                .color(colors::TEXT_MUTED)
+                .color(colors::text_muted())
                 .size(14.0),
         );
 
@@ -207,8 +591,135 @@ impl JamApp {
             });
     }
 
+    fn render_finalization_lag(&self, ui: &mut egui::Ui) {
+        use egui_plot::{Plot, PlotPoints, Points};
+
+        ui.label(
+            egui::RichText::new("Finalization Lag")
+                .color(colors::text_muted())
+                .size(14.0),
+        );
+
+        let points_data: Vec<[f64; 2]> = with_data!(self, |data| {
+            data.blocks
+                .finalization_lag()
+                .into_iter()
+                .map(|(id, lag)| [id as f64, lag as f64])
+                .collect()
+        });
+
+        Plot::new("finalization_lag")
+            .show_axes([false, true])
+            .show_grid(false)
+            .allow_zoom(false)
+            .allow_drag(false)
+            .allow_scroll(false)
+            .show_background(false)
+            .include_y(0.0)
+            .include_y(5.0)
+            .label_formatter(|_name, value| {
+                format!("validator={} lag={:.0}", value.x as u32, value.y)
+            })
+            .show(ui, |plot_ui| {
+                plot_ui.points(
+                    Points::new(PlotPoints::from(points_data))
+                        .color(egui::Color32::from_rgba_unmultiplied(200, 200, 100, 180))
+                        .radius(2.0)
+                        .filled(true),
+                );
+            });
+    }
+
+    /// Guarantee queue depth: per-core totals as bars on top,
+    /// validator (rows) × core (columns) heatmap below.
+    fn render_guarantee_queues(&self, ui: &mut egui::Ui) {
+        ui.label(
+            egui::RichText::new("Guarantee Queues")
+                .color(colors::text_muted())
+                .size(14.0),
+        );
+
+        let (totals, rows) = with_data!(self, |data| {
+            let rows: Vec<(String, Vec<u8>)> = data
+                .guarantee_queues
+                .rows()
+                .into_iter()
+                .map(|(id, depths)| (id.to_string(), depths))
+                .collect();
+            (data.guarantee_queues.aggregate_per_core(), rows)
+        });
+
+        let (rect, response) = ui.allocate_exact_size(ui.available_size(), egui::Sense::hover());
+        if totals.is_empty() {
+            ui.painter().text(
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                "No guarantee reports",
+                egui::FontId::proportional(12.0),
+                colors::text_muted(),
+            );
+            return;
+        }
+
+        let painter = ui.painter_at(rect);
+        let cell_w = rect.width() / totals.len() as f32;
+        let bars_bottom = rect.top() + rect.height() * 0.25;
+        let heat_top = bars_bottom + 4.0;
+        let cell_h = (rect.bottom() - heat_top) / rows.len().max(1) as f32;
+
+        let max_total = totals.iter().copied().max().unwrap_or(0).max(1) as f32;
+        for (core, &total) in totals.iter().enumerate() {
+            let x = rect.left() + core as f32 * cell_w;
+            let top = bars_bottom - (bars_bottom - rect.top()) * total as f32 / max_total;
+            painter.rect_filled(
+                egui::Rect::from_min_max(
+                    egui::pos2(x + 0.5, top),
+                    egui::pos2(x + cell_w - 0.5, bars_bottom),
+                ),
+                0.0,
+                colors::text_secondary(),
+            );
+        }
+
+        let max_depth = rows
+            .iter()
+            .flat_map(|(_, depths)| depths.iter().copied())
+            .max()
+            .unwrap_or(0);
+        for (row, (_, depths)) in rows.iter().enumerate() {
+            let y = heat_top + row as f32 * cell_h;
+            for (core, &depth) in depths.iter().enumerate() {
+                if depth == 0 {
+                    continue;
+                }
+                let x = rect.left() + core as f32 * cell_w;
+                painter.rect_filled(
+                    egui::Rect::from_min_size(egui::pos2(x, y), egui::vec2(cell_w, cell_h.max(1.0))),
+                    0.0,
+                    queue_depth_color(depth, max_depth),
+                );
+            }
+        }
+
+        if let Some(pos) = response.hover_pos() {
+            let core = (((pos.x - rect.left()) / cell_w) as usize).min(totals.len() - 1);
+            let text = if pos.y < heat_top {
+                format!("core {}: {} queued", core, totals[core])
+            } else {
+                let row = (((pos.y - heat_top) / cell_h) as usize).min(rows.len().saturating_sub(1));
+                match rows.get(row) {
+                    Some((node_id, depths)) => {
+                        format!("{}\ncore {}: {} queued", self.node_label(node_id), core, depths[core])
+                    }
+                    None => format!("core {}: {} queued", core, totals[core]),
+                }
+            };
+            response.on_hover_text_at_pointer(text);
+        }
+    }
+
     /// Render Event Particles — routes to GPU or CPU path.
-    fn render_particle_trails(&self, ui: &mut egui::Ui) {
+    fn render_particle_trails(&mut self, ui: &mut egui::Ui) {
         if self.scatter_texture_id.is_some() && !self.use_cpu {
             self.render_particle_trails_gpu(ui);
         } else {
@@ -217,45 +728,67 @@ impl JamApp {
     }
 
     /// GPU scatter rendering path.
-    fn render_particle_trails_gpu(&self, ui: &mut egui::Ui) {
-        ui.label(
-            egui::RichText::new("Event Particles")
-                .color(colors::TEXT_MUTED)
-                .size(14.0),
-        );
+    ///
+    /// Only events stored since `scatter_upload_cursor` are uploaded; the shader hides
+    /// particles older than `max_age` and those of filtered-out types.
+    fn render_particle_trails_gpu(&mut self, ui: &mut egui::Ui) {
+        let now = self.view_now();
+        let max_age = self.scatter_max_age();
+        // The shader discards on age * speed_factor, so slowed-down points stay longer
+        let cutoff = now - max_age / self.speed_factor as f64;
+        let focus = self.focused_node;
+        let cursor = self.scatter_upload_cursor;
 
-        let now = now_seconds();
-        let max_age = 10.0;
-        let cutoff = now - max_age;
-
-        // Collect scatter particles from EventStore
-        let (new_particles, node_count) = with_data!(self, |data| {
-            let mut particles = Vec::new();
-            for (_, node) in data.events.nodes() {
-                for (&event_type, events) in &node.by_type {
-                    if (event_type as usize) >= self.selected_events.len()
-                        || !self.selected_events[event_type as usize]
-                    {
-                        continue;
-                    }
-                    for stored in events {
-                        if stored.timestamp < cutoff {
-                            continue;
-                        }
-                        particles.push(ScatterParticle {
-                            node_index: node.index as f32,
-                            birth_time: stored.timestamp as f32,
-                            event_type: event_type as f32,
-                        });
-                    }
-                }
-            }
-            (particles, data.events.node_count().max(1) as f32)
+        let (mut new_particles, new_cursor, total, node_count) = with_data!(self, |data| {
+            let (events, new_cursor) = data.events.get_new_since(cursor, focus);
+            let particles: Vec<ScatterParticle> = events
+                .filter(|(_, _, stored)| stored.timestamp >= cutoff)
+                .map(|(node_index, event_type, stored)| ScatterParticle {
+                    node_index: node_index as f32,
+                    birth_time: stored.timestamp as f32,
+                    event_type: event_type as f32,
+                })
+                .collect();
+            let total = data.events.count_in_window(cutoff, &self.selected_events, focus);
+            (particles, new_cursor, total, data.events.index_span().max(1) as f32)
         });
+        self.scatter_upload_cursor = new_cursor;
+
+        // Bound the visible density; the title shows how much is hidden
+        let keep = keep_fraction(total, self.scatter_particle_cap);
+        downsample_particles(&mut new_particles, keep);
+        let title = if keep < 1.0 {
+            format!("Event Particles (~{} / {} shown)", self.scatter_particle_cap, total)
+        } else {
+            "Event Particles".to_string()
+        };
+        ui.label(egui::RichText::new(title).color(colors::text_muted()).size(14.0));
 
         // Allocate canvas area
         let available = ui.available_size();
-        let (rect, _response) = ui.allocate_exact_size(available, egui::Sense::hover());
+        let (rect, response) = ui.allocate_exact_size(available, egui::Sense::click());
+
+        // Click a column to open that node's detail popup (x_range = [-0.5, n - 0.5])
+        if response.clicked() {
+            if let Some(pos) = response.interact_pointer_pos() {
+                let frac = (pos.x - rect.left()) / rect.width();
+                let node = (frac * node_count - 0.5).round().clamp(0.0, node_count - 1.0);
+                self.selected_node = Some(node as u16);
+            }
+        }
+
+        // Double-click a point to inspect it. The shader plots age * speed_factor,
+        // with age 0 at the bottom edge.
+        if response.double_clicked() {
+            if let Some(pos) = response.interact_pointer_pos() {
+                let frac = (pos.x - rect.left()) / rect.width();
+                let node = (frac * node_count - 0.5).round().clamp(0.0, node_count - 1.0);
+                let seconds_per_px = max_age / rect.height() as f64 / self.speed_factor as f64;
+                let age = (rect.bottom() - pos.y) as f64 * seconds_per_px;
+                let tolerance = INSPECT_HIT_PX as f64 * seconds_per_px;
+                self.inspect_event_near(node as u16, now - age, tolerance);
+            }
+        }
 
         // Display the off-screen texture
         let texture_id = self.scatter_texture_id.unwrap();
@@ -287,26 +820,29 @@ impl JamApp {
                 new_particles: Arc::new(new_particles),
                 uniforms,
                 filter,
-                color_lut: self.color_lut,
+                color_lut: self.display_lut(),
                 rect,
-                reset: true,
+                reset: std::mem::take(&mut self.scatter_reset_pending),
             },
         ));
     }
 
     /// CPU scatter rendering path (WASM + native --use-cpu fallback)
-    fn render_particle_trails_cpu(&self, ui: &mut egui::Ui) {
+    fn render_particle_trails_cpu(&mut self, ui: &mut egui::Ui) {
         use egui_plot::{Plot, PlotPoints, Points};
 
         ui.label(
             egui::RichText::new("Event Particles")
-                .color(colors::TEXT_MUTED)
+                .color(colors::text_muted())
                 .size(14.0),
         );
 
-        let now = now_seconds();
-        let max_age = 10.0;
-        let cutoff = now - max_age;
+        let now = self.view_now();
+        let max_age = self.scatter_max_age();
+        // Plot age * speed_factor like the GPU shader, so both paths slow down alike and
+        // keep the same points
+        let speed = self.speed_factor as f64;
+        let cutoff = now - max_age / speed;
 
         let category_points: Vec<(egui::Color32, Vec<[f64; 2]>)> = with_data!(self, |data| {
             let mut result = Vec::new();
@@ -324,10 +860,13 @@ impl JamApp {
 
                     let et_u8 = event_type as u8;
                     for (_, node) in data.events.nodes() {
+                        if self.focused_node.is_some_and(|f| f != node.index) {
+                            continue;
+                        }
                         if let Some(events) = node.by_type.get(&et_u8) {
-                            for stored in events {
+                            for stored in events.iter().filter(|e| data.events.shown(e)) {
                                 if stored.timestamp >= cutoff {
-                                    let age = now - stored.timestamp;
+                                    let age = (now - stored.timestamp) * speed;
                                     points.push([node.index as f64, age]);
                                 }
                             }
@@ -343,7 +882,7 @@ impl JamApp {
             result
         });
 
-        Plot::new("particle_trails")
+        let (clicked_node, inspect_at) = Plot::new("particle_trails")
             .show_axes([false, true])
             .show_grid(false)
             .allow_zoom(false)
@@ -352,8 +891,8 @@ impl JamApp {
             .show_background(false)
             .include_y(0.0)
             .include_y(max_age)
-            .label_formatter(|_name, value| {
-                format!("node={} age={:.1}s", value.x as u32, value.y)
+            .label_formatter(move |_name, value| {
+                format!("node={} age={:.1}s", value.x as u32, value.y / speed)
             })
             .show(ui, |plot_ui| {
                 for (color, points) in &category_points {
@@ -364,57 +903,425 @@ impl JamApp {
                             .filled(true),
                     );
                 }
-            });
+
+                // Click a column to open that node's detail popup
+                let clicked = if plot_ui.response().clicked() {
+                    plot_ui.pointer_coordinate().map(|p| p.x.round())
+                } else {
+                    None
+                };
+                // Double-click a point to inspect it: (node, age, tolerance in seconds)
+                let inspect = if plot_ui.response().double_clicked() {
+                    let seconds_per_px = plot_ui.transform().dvalue_dpos()[1].abs() / speed;
+                    plot_ui
+                        .pointer_coordinate()
+                        .map(|p| (p.x.round(), p.y / speed, INSPECT_HIT_PX as f64 * seconds_per_px))
+                } else {
+                    None
+                };
+                (clicked, inspect)
+            })
+            .inner;
+
+        if let Some(x) = clicked_node.filter(|&x| x >= 0.0) {
+            self.selected_node = Some(x as u16);
+        }
+        if let Some((x, age, tolerance)) = inspect_at.filter(|&(x, _, _)| x >= 0.0) {
+            self.inspect_event_near(x as u16, now - age, tolerance);
+        }
+    }
+
+    /// Event-rate Y ceiling in plotted units for the current scale settings
+    fn rate_ceiling(&self, max_rate: f64) -> f64 {
+        let ceiling = rate_ceiling(max_rate, self.rate_y_autoscale, self.rate_y_max as f64);
+        rate_y(ceiling, self.rate_y_log)
     }
 
     fn render_event_rates(&self, ui: &mut egui::Ui) {
-        use egui_plot::{Line, Plot, PlotPoints};
+        use egui_plot::{Line, PlotPoints};
 
-        ui.label(
-            egui::RichText::new("Event Rate (per node)")
-                .color(colors::TEXT_MUTED)
-                .size(14.0),
-        );
+        let title = match self.rate_mode {
+            RateMode::PerNode => "Event Rate (per node)",
+            RateMode::PerCategory => "Event Rate (per category)",
+            RateMode::Stacked => "Event Rate (stacked by category)",
+        };
+        ui.label(egui::RichText::new(title).color(colors::text_muted()).size(14.0));
 
-        let now = now_seconds();
+        let now = self.view_now();
+        let bucket = self.rate_bucket_secs();
 
-        let rates: Vec<(u16, Vec<u32>)> = with_data!(self, |data| {
-            data.events
-                .compute_rates_per_node(now, 1.0, 60, &self.selected_events)
-        });
+        match self.rate_mode {
+            RateMode::PerNode => {
+                let rates: Vec<(u16, Vec<u32>)> = with_data!(self, |data| {
+                    data.events
+                        .compute_rates_per_node(now, bucket, RATE_BUCKETS, &self.selected_events)
+                })
+                .into_iter()
+                .filter(|(idx, _)| !self.focused_node.is_some_and(|f| f != *idx))
+                .collect();
 
-        Plot::new("event_rates")
-            .show_axes([false, true])
-            .show_grid(false)
-            .allow_zoom(false)
-            .allow_drag(false)
-            .allow_scroll(false)
-            .show_background(false)
-            .include_x(0.0)
-            .include_x(60.0)
-            .include_y(0.0)
-            .include_y(50.0)
-            .label_formatter(|_name, value| {
-                format!("t=-{:.0}s rate={:.0}/s", 60.0 - value.x, value.y)
-            })
-            .show(ui, |plot_ui| {
-                let num_nodes = rates.len().max(1);
-                let alpha = (255.0_f32 / num_nodes as f32).clamp(10.0, 200.0) as u8;
+                let smoothed: Vec<Vec<f32>> = rates
+                    .iter()
+                    .filter(|(_, node_rates)| node_rates.len() >= 2)
+                    .map(|(_, node_rates)| {
+                        // Bucket counts to events/sec
+                        smooth_rates(
+                            node_rates,
+                            self.rate_smoothing,
+                            self.rate_smoothing_window,
+                            self.rate_smoothing_alpha,
+                        )
+                        .into_iter()
+                        .map(|count| count / bucket as f32)
+                        .collect::<Vec<f32>>()
+                    })
+                    .collect();
+                let max_rate = smoothed.iter().flatten().fold(0.0f32, |m, &r| m.max(r));
+                let ceiling = self.rate_ceiling(max_rate as f64);
 
-                for (_node_idx, node_rates) in rates.iter() {
-                    if node_rates.len() < 2 {
-                        continue;
+                event_rate_plot(self.rate_y_log, bucket).show(ui, |plot_ui| {
+                    plot_ui.set_plot_bounds(rate_bounds(ceiling));
+                    let num_nodes = rates.len().max(1);
+                    let alpha = (255.0_f32 / num_nodes as f32).clamp(10.0, 200.0) as u8;
+
+                    for node_rates in &smoothed {
+                        let line_points: Vec<[f64; 2]> = node_rates
+                            .iter()
+                            .enumerate()
+                            .map(|(x, &rate)| [x as f64, rate_y(rate as f64, self.rate_y_log)])
+                            .collect();
+
+                        let color = colors::shade(255, alpha);
+                        plot_ui.line(Line::new(PlotPoints::from(line_points)).color(color).width(1.0));
                     }
+                });
+            }
+            RateMode::PerCategory => {
+                let focus = self.focused_node;
+                let rates = with_data!(self, |data| {
+                    data.events
+                        .compute_category_rates(now, bucket, RATE_BUCKETS, &self.selected_events, focus)
+                });
 
-                    let line_points: Vec<[f64; 2]> = node_rates
-                        .iter()
-                        .enumerate()
-                        .map(|(x, &count)| [x as f64, count as f64])
-                        .collect();
+                let max_rate = rates.iter().flat_map(|(_, r)| r).fold(0.0f64, |m, &r| m.max(r));
+                let ceiling = self.rate_ceiling(max_rate);
 
-                    let color = egui::Color32::from_rgba_unmultiplied(255, 255, 255, alpha);
-                    plot_ui.line(Line::new(PlotPoints::from(line_points)).color(color).width(1.0));
-                }
-            });
+                event_rate_plot(self.rate_y_log, bucket).show(ui, |plot_ui| {
+                    plot_ui.set_plot_bounds(rate_bounds(ceiling));
+                    for (cat_idx, cat_rates) in rates.iter() {
+                        let line_points: Vec<[f64; 2]> = cat_rates
+                            .iter()
+                            .enumerate()
+                            .map(|(x, &rate)| [x as f64, rate_y(rate, self.rate_y_log)])
+                            .collect();
+
+                        plot_ui.line(
+                            Line::new(PlotPoints::from(line_points))
+                                .name(EVENT_CATEGORIES[*cat_idx].name)
+                                .color(self.category_color(*cat_idx))
+                                .width(1.5),
+                        );
+                    }
+                });
+            }
+            RateMode::Stacked => {
+                let focus = self.focused_node;
+                let rates = with_data!(self, |data| {
+                    data.events
+                        .compute_category_rates(now, bucket, RATE_BUCKETS, &self.selected_events, focus)
+                });
+                let bands = stack_rates(&rates);
+                let max_rate = bands
+                    .last()
+                    .map_or(0.0, |(_, _, total)| total.iter().fold(0.0f64, |m, &r| m.max(r)));
+                let ceiling = self.rate_ceiling(max_rate);
+                let log = self.rate_y_log;
+
+                event_rate_plot(log, bucket).show(ui, |plot_ui| {
+                    use egui_plot::Polygon;
+
+                    plot_ui.set_plot_bounds(rate_bounds(ceiling));
+
+                    for (cat_idx, lower, upper) in &bands {
+                        let color = self.category_color(*cat_idx);
+                        let fill = egui::Color32::from_rgba_unmultiplied(
+                            color.r(), color.g(), color.b(), 170,
+                        );
+                        // egui only fills convex shapes, so each band is drawn as one
+                        // quad per bucket step rather than a single outline
+                        for x in 0..upper.len().saturating_sub(1) {
+                            let Some(mut quad) = band_segment(x, lower, upper) else {
+                                continue;
+                            };
+                            for point in &mut quad {
+                                point[1] = rate_y(point[1], log);
+                            }
+                            plot_ui.polygon(
+                                Polygon::new(PlotPoints::from(quad))
+                                    .name(EVENT_CATEGORIES[*cat_idx].name)
+                                    .fill_color(fill)
+                                    .stroke(egui::Stroke::NONE),
+                            );
+                        }
+                    }
+
+                    if let Some((_, _, total)) = bands.last() {
+                        let line_points: Vec<[f64; 2]> = total
+                            .iter()
+                            .enumerate()
+                            .map(|(x, &rate)| [x as f64, rate_y(rate, log)])
+                            .collect();
+                        plot_ui.line(
+                            Line::new(PlotPoints::from(line_points))
+                                .name("Total")
+                                .color(colors::text_secondary())
+                                .width(1.0),
+                        );
+                    }
+                });
+            }
+        }
+    }
+}
+
+/// Per-bucket rates after smoothing, index-aligned with `buckets` (oldest first).
+/// The moving average is centered, shrinking at the edges, so peaks stay on their bucket;
+/// exponential smoothing is causal and trails the raw line by roughly `1 / alpha` buckets.
+fn smooth_rates(buckets: &[u32], smoothing: RateSmoothing, window: usize, alpha: f32) -> Vec<f32> {
+    match smoothing {
+        RateSmoothing::Raw => buckets.iter().map(|&c| c as f32).collect(),
+        RateSmoothing::MovingAverage => {
+            let half = window / 2;
+            (0..buckets.len())
+                .map(|i| {
+                    let span = &buckets[i.saturating_sub(half)..(i + half + 1).min(buckets.len())];
+                    span.iter().sum::<u32>() as f32 / span.len() as f32
+                })
+                .collect()
+        }
+        RateSmoothing::Exponential => {
+            let mut level = buckets.first().map_or(0.0, |&c| c as f32);
+            buckets
+                .iter()
+                .map(|&c| {
+                    level += alpha * (c as f32 - level);
+                    level
+                })
+                .collect()
+        }
+    }
+}
+
+/// Cumulative baselines for a stacked chart: (category_idx, lower, upper) per band,
+/// in the input (EVENT_CATEGORIES) order so bands never swap places between frames.
+fn stack_rates(rates: &[(usize, Vec<f64>)]) -> Vec<(usize, Vec<f64>, Vec<f64>)> {
+    let len = rates.first().map_or(0, |(_, r)| r.len());
+    let mut baseline = vec![0.0; len];
+    rates
+        .iter()
+        .map(|(cat_idx, cat_rates)| {
+            let lower = baseline.clone();
+            for (b, r) in baseline.iter_mut().zip(cat_rates) {
+                *b += r;
+            }
+            (*cat_idx, lower, baseline.clone())
+        })
+        .collect()
+}
+
+/// Quad between buckets `x` and `x + 1` of a band, or None where the band has zero height
+/// at both ends. A band that is empty at only one end becomes a triangle.
+fn band_segment(x: usize, lower: &[f64], upper: &[f64]) -> Option<Vec<[f64; 2]>> {
+    let (x0, x1) = (x as f64, (x + 1) as f64);
+    let mut points = vec![[x0, lower[x]], [x1, lower[x + 1]]];
+    if upper[x + 1] > lower[x + 1] {
+        points.push([x1, upper[x + 1]]);
+    }
+    if upper[x] > lower[x] {
+        points.push([x0, upper[x]]);
+    }
+    (points.len() > 2).then_some(points)
+}
+
+/// Shared frame for the Event Rate plot: last 60s, fixed axes
+fn event_rate_plot(log_scale: bool, bucket_secs: f64) -> egui_plot::Plot<'static> {
+    // Axis and tooltip always read in events/sec, whatever the plotted scale
+    egui_plot::Plot::new("event_rates")
+        .show_axes([false, true])
+        .show_grid(false)
+        .allow_zoom(false)
+        .allow_drag(false)
+        .allow_scroll(false)
+        .show_background(false)
+        .y_axis_formatter(move |mark, _range| {
+            format!("{:.0}", rate_from_y(mark.value, log_scale))
+        })
+        .label_formatter(move |_name, value| {
+            format!(
+                "t=-{:.0}s rate={:.0}/s",
+                (RATE_BUCKETS as f64 - value.x) * bucket_secs,
+                rate_from_y(value.y, log_scale)
+            )
+        })
+}
+
+/// Event-rate plot bounds: all buckets of the window, 0 to `ceiling` (already in plotted units)
+fn rate_bounds(ceiling: f64) -> egui_plot::PlotBounds {
+    egui_plot::PlotBounds::from_min_max([0.0, 0.0], [RATE_BUCKETS as f64, ceiling])
+}
+
+/// Plotted Y for a rate: `ln(1 + rate)` in log scale
+fn rate_y(rate: f64, log_scale: bool) -> f64 {
+    if log_scale {
+        rate.ln_1p()
+    } else {
+        rate
+    }
+}
+
+/// Inverse of [`rate_y`], for axis labels and tooltips
+fn rate_from_y(y: f64, log_scale: bool) -> f64 {
+    if log_scale {
+        y.exp_m1()
+    } else {
+        y
+    }
+}
+
+/// Y ceiling in rate units: the visible maximum plus headroom, or the manual ceiling
+fn rate_ceiling(max_rate: f64, autoscale: bool, manual_ceiling: f64) -> f64 {
+    const HEADROOM: f64 = 1.15;
+    const MIN_CEILING: f64 = 5.0;
+    if autoscale {
+        (max_rate * HEADROOM).max(MIN_CEILING)
+    } else {
+        manual_ceiling
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_scale_round_trips() {
+        assert!((rate_ceiling(100.0, true, 50.0) - 115.0).abs() < 1e-9);
+        assert_eq!(rate_ceiling(0.0, true, 50.0), 5.0);
+        assert_eq!(rate_ceiling(100.0, false, 50.0), 50.0);
+        for rate in [0.0, 1.0, 42.0, 10_000.0] {
+            assert!((rate_from_y(rate_y(rate, true), true) - rate).abs() < 1e-9);
+            assert_eq!(rate_y(rate, false), rate);
+        }
+        assert!(rate_y(10_000.0, true) < 10.0);
+    }
+
+    #[test]
+    fn smoothing_keeps_alignment() {
+        let spike = [0, 0, 9, 0, 0];
+        assert_eq!(smooth_rates(&spike, RateSmoothing::Raw, 3, 0.5), vec![0.0, 0.0, 9.0, 0.0, 0.0]);
+        // Centered: the peak stays on bucket 2, edges average over fewer buckets
+        let sma = smooth_rates(&spike, RateSmoothing::MovingAverage, 3, 0.5);
+        assert_eq!(sma, vec![0.0, 3.0, 3.0, 3.0, 0.0]);
+        assert_eq!(smooth_rates(&[4, 8], RateSmoothing::MovingAverage, 5, 0.5), vec![6.0, 6.0]);
+        // Exponential: causal, nothing before the spike moves
+        let ema = smooth_rates(&spike, RateSmoothing::Exponential, 3, 0.5);
+        assert_eq!(ema, vec![0.0, 0.0, 4.5, 2.25, 1.125]);
+    }
+
+    fn particles(n: usize) -> Vec<ScatterParticle> {
+        (0..n)
+            .map(|i| ScatterParticle {
+                node_index: (i % 64) as f32,
+                birth_time: 1000.0 + i as f32 * 0.001,
+                event_type: (i % 7) as f32,
+            })
+            .collect()
+    }
+
+    fn birth_times(ps: &[ScatterParticle]) -> Vec<u32> {
+        ps.iter().map(|p| p.birth_time.to_bits()).collect()
+    }
+
+    #[test]
+    fn downsample_is_bounded_and_stable() {
+        assert_eq!(keep_fraction(100, 100), 1.0);
+        let mut under = particles(100);
+        downsample_particles(&mut under, keep_fraction(100, 100));
+        assert_eq!(under.len(), 100);
+
+        let mut a = particles(20_000);
+        downsample_particles(&mut a, keep_fraction(20_000, 5_000));
+        assert!((4_500..=5_500).contains(&a.len()), "kept {}", a.len());
+
+        // Same input → same subset
+        let mut b = particles(20_000);
+        downsample_particles(&mut b, 0.25);
+        assert_eq!(birth_times(&a), birth_times(&b));
+
+        // A looser cap keeps a superset, so points don't flicker as the total drifts
+        let mut looser = particles(20_000);
+        downsample_particles(&mut looser, keep_fraction(20_000, 6_000));
+        let looser = birth_times(&looser);
+        assert!(birth_times(&a).iter().all(|t| looser.contains(t)));
+    }
+
+    #[test]
+    fn validator_colors_are_stable_and_distinct() {
+        assert_eq!(validator_color(7), validator_color(7));
+        for i in 0..32u16 {
+            assert_ne!(validator_color(i), validator_color(i + 1));
+        }
+    }
+
+    #[test]
+    fn queue_depth_color_spans_green_to_red() {
+        assert_eq!(queue_depth_color(1, 10).g(), 200);
+        assert_eq!(queue_depth_color(5, 10), egui::Color32::from_rgb(200, 200, 100));
+        assert_eq!(queue_depth_color(10, 10), egui::Color32::from_rgb(200, 100, 100));
+    }
+
+    #[test]
+    fn format_bytes_uses_binary_units() {
+        assert_eq!(format_bytes(512.0), "512 B");
+        assert_eq!(format_bytes(1536.0), "1.5 KiB");
+        assert_eq!(format_bytes(3.0 * 1024.0 * 1024.0), "3.0 MiB");
+        assert_eq!(format_bytes(5.0 * 1024.0 * 1024.0 * 1024.0 * 1024.0), "5120.0 GiB");
+    }
+
+    #[test]
+    fn breakdown_at_aligns_series_on_newest_point() {
+        let per_node = vec![
+            ("a".to_string(), vec![1.0, 2.0, 3.0]),
+            ("b".to_string(), vec![5.0, 0.0]),
+            ("c".to_string(), vec![4.0]),
+        ];
+        // x=1: a=2, b=5 (its first point); c has no point yet
+        assert_eq!(breakdown_at(&per_node, 3, 1, 5), (vec![("b", 5.0), ("a", 2.0)], 0));
+        // x=2: b reported 0 and is skipped; limit truncates the rest
+        assert_eq!(breakdown_at(&per_node, 3, 2, 1), (vec![("c", 4.0)], 1));
+        assert_eq!(breakdown_at(&per_node, 3, 7, 5), (vec![], 0));
+    }
+
+    #[test]
+    fn stack_rates_accumulates_in_order() {
+        let rates = vec![(0, vec![1.0, 2.0]), (3, vec![0.0, 0.5]), (5, vec![4.0, 0.0])];
+        let bands = stack_rates(&rates);
+        let order: Vec<usize> = bands.iter().map(|(idx, _, _)| *idx).collect();
+        assert_eq!(order, vec![0, 3, 5]);
+        assert_eq!(bands[1].1, vec![1.0, 2.0]);
+        assert_eq!(bands[1].2, vec![1.0, 2.5]);
+        assert_eq!(bands[2].2, vec![5.0, 2.5]);
+        assert!(stack_rates(&[]).is_empty());
+    }
+
+    #[test]
+    fn band_segment_handles_empty_buckets() {
+        let lower = [1.0, 1.0, 1.0];
+        // Zero height at both ends: nothing to draw
+        assert!(band_segment(0, &lower, &[1.0, 1.0, 3.0]).is_none());
+        // Zero height at one end: triangle
+        assert_eq!(band_segment(1, &lower, &[1.0, 1.0, 3.0]).unwrap().len(), 3);
+        // Non-zero at both ends: quad
+        assert_eq!(band_segment(0, &lower, &[2.0, 3.0, 1.0]).unwrap().len(), 4);
     }
 }
@@ -1,9 +1,38 @@
 //! Header bar with controls and tabs
 
 use eframe::egui;
+use std::collections::VecDeque;
 use crate::theme::colors;
 use crate::time::now_seconds;
-use super::{JamApp, ActiveTab};
+use super::{JamApp, ActiveTab, with_data};
+
+/// Seconds of history in the header throughput sparkline (one bucket per second)
+const SPARKLINE_SECS: usize = 60;
+/// Total events/sec at which the sparkline turns yellow
+const SPARKLINE_WARN_RATE: f64 = 1_000.0;
+/// Total events/sec at which the sparkline turns red
+const SPARKLINE_HIGH_RATE: f64 = 5_000.0;
+
+/// Seconds of best-slot history kept for the header slot staircase (one sample per second)
+const SLOT_HISTORY_SECS: usize = 120;
+/// Seconds without a new best slot after which the slot staircase turns red
+const SLOT_STALL_SECS: usize = 18;
+
+/// Frame timestamps kept for the diagnostics frame-time graph
+const FRAME_HISTORY: usize = 240;
+/// Most recent frames averaged into the fps readout
+const FPS_FRAMES: usize = 60;
+
+/// Sparkline color for the current total rate: green → yellow → red
+fn sparkline_color(rate: f64) -> egui::Color32 {
+    if rate >= SPARKLINE_HIGH_RATE {
+        egui::Color32::from_rgb(200, 100, 100)
+    } else if rate >= SPARKLINE_WARN_RATE {
+        egui::Color32::from_rgb(200, 200, 100)
+    } else {
+        egui::Color32::from_rgb(100, 200, 100)
+    }
+}
 
 impl JamApp {
     pub(crate) fn render_header(&mut self, ui: &mut egui::Ui) {
@@ -18,9 +47,9 @@ impl JamApp {
 
             // Settings toggle
             let settings_color = if self.show_settings {
-                colors::TEXT_PRIMARY
+                colors::text_primary()
             } else {
-                colors::TEXT_MUTED
+                colors::text_muted()
             };
             if ui
                 .selectable_label(
@@ -32,19 +61,61 @@ impl JamApp {
                 self.show_settings = !self.show_settings;
             }
 
+            // Connection panel toggle
+            let connection_color = if self.show_connection_panel {
+                colors::text_primary()
+            } else {
+                colors::text_muted()
+            };
+            if ui
+                .selectable_label(
+                    self.show_connection_panel,
+                    egui::RichText::new("Connection").color(connection_color),
+                )
+                .clicked()
+            {
+                self.show_connection_panel = !self.show_connection_panel;
+                if self.show_connection_panel {
+                    self.connection_url_input = self.ws_url.clone();
+                    self.connection_error = None;
+                }
+            }
+
+            // Node list panel toggle
+            let nodes_color = if self.show_node_list {
+                colors::text_primary()
+            } else {
+                colors::text_muted()
+            };
+            if ui
+                .selectable_label(
+                    self.show_node_list,
+                    egui::RichText::new("Nodes").color(nodes_color),
+                )
+                .clicked()
+            {
+                self.show_node_list = !self.show_node_list;
+            }
+            self.render_snapshot_button(ui);
+            self.render_status_table_button(ui);
+            self.render_event_log_button(ui);
+            self.render_geo_map_button(ui);
+            self.render_topology_button(ui);
+
             ui.add_space(10.0);
 
             // Tab buttons
             const TABS: &[(ActiveTab, &str)] = &[
                 (ActiveTab::Ring, "Ring"),
                 (ActiveTab::Graphs, "Graphs"),
+                (ActiveTab::Analytics, "Analytics"),
             ];
 
             for &(tab, label) in TABS {
                 let color = if self.active_tab == tab {
-                    colors::TEXT_PRIMARY
+                    colors::text_primary()
                 } else {
-                    colors::TEXT_MUTED
+                    colors::text_muted()
                 };
 
                 if ui
@@ -58,39 +129,312 @@ impl JamApp {
                 }
             }
 
+            ui.add_space(10.0);
+            self.render_throughput_sparkline(ui);
+            ui.add_space(6.0);
+            self.render_slot_staircase(ui);
+
+            let block_time = with_data!(self, |data| {
+                let bt = &data.blocks.block_times;
+                bt.mean().zip(bt.variance()).map(|(mean, var)| (mean, var.sqrt(), bt.sample_count()))
+            });
+            if let Some((mean, std_dev, samples)) = block_time {
+                ui.add_space(6.0);
+                ui.label(
+                    egui::RichText::new(format!("block time {:.1}s", mean))
+                        .color(colors::text_secondary())
+                        .small(),
+                )
+                .on_hover_text(format!(
+                    "Mean time between new best blocks: {:.2}s ± {:.2}s over the last {} blocks",
+                    mean, std_dev, samples,
+                ));
+            }
+
+            // Solo-node focus (set by clicking a ring dot)
+            if let Some(focus) = self.focused_node {
+                ui.add_space(10.0);
+                let node_id = with_data!(self, |data| {
+                    data.events.node_id_at(focus).map(str::to_string)
+                });
+                let label = match node_id {
+                    Some(id) => format!("Focus: node {} ({})", focus, self.node_label(&id)),
+                    None => format!("Focus: node {}", focus),
+                };
+                ui.label(egui::RichText::new(label).color(colors::text_primary()));
+                if ui.small_button("x").on_hover_text("Clear node focus").clicked() {
+                    self.set_focused_node(None);
+                }
+            }
+
+            ui.add_space(10.0);
+            #[cfg(not(target_arch = "wasm32"))]
+            self.render_endpoint_status(ui);
+            self.render_sync_indicator(ui);
+
+            // Fork warning badge (details in diagnostics)
+            if let Some(ref fork) = self.fork {
+                ui.label(
+                    egui::RichText::new(format!("⚠ Fork @ slot {}", fork.slot))
+                        .color(egui::Color32::from_rgb(200, 100, 100)),
+                )
+                .on_hover_text(format!(
+                    "{} different best blocks reported at the tip",
+                    fork.branches.len()
+                ));
+                ui.add_space(10.0);
+            }
+
+            // Partition warning badge (components in the topology window)
+            if let Some(sizes) = self.partitions.partition() {
+                let groups: Vec<String> = sizes.iter().map(usize::to_string).collect();
+                let badge = ui
+                    .add(
+                        egui::Label::new(
+                            egui::RichText::new(format!("⚠ Partitioned: {} groups", sizes.len()))
+                                .color(egui::Color32::from_rgb(200, 100, 100))
+                                .strong(),
+                        )
+                        .sense(egui::Sense::click()),
+                    )
+                    .on_hover_text(format!(
+                        "Validators split into groups of {} with no connection path between \
+                         them. Click to open the topology.",
+                        groups.join(", "),
+                    ));
+                if badge.clicked() {
+                    self.show_topology = true;
+                }
+                ui.add_space(10.0);
+            }
+
+            self.render_alert_badge(ui);
+
+            #[cfg(target_arch = "wasm32")]
+            if self.msg_backlog_secs >= super::MSG_BACKLOG_WARN_SECS {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "⚠ {} messages behind",
+                        self.buffered_message_count()
+                    ))
+                    .color(egui::Color32::from_rgb(200, 200, 100)),
+                )
+                .on_hover_text(format!(
+                    "Events arrive faster than this tab can process them; {} dropped so far. \
+                     Narrow the event filter or enable server-side filtering.",
+                    self.buffered_dropped_count()
+                ));
+                ui.add_space(10.0);
+            }
+
+            // Pause/resume ingestion (socket stays open, messages are buffered)
+            let pause_text = if self.paused { "Resume" } else { "Pause" };
+            if ui.button(egui::RichText::new(pause_text)).clicked() {
+                self.set_paused(!self.paused);
+            }
+            if self.paused {
+                let mut status = format!("paused, {} buffered", self.buffered_message_count());
+                let dropped = self.buffered_dropped_count();
+                if dropped > 0 {
+                    status.push_str(&format!(", {} dropped", dropped));
+                }
+                ui.label(
+                    egui::RichText::new(status)
+                        .color(egui::Color32::from_rgb(200, 200, 100))
+                        .small(),
+                );
+            }
+
+            ui.add_space(10.0);
+            if ui
+                .button("Screenshot")
+                .on_hover_text("Save the window as PNG, with time and endpoint in a footer")
+                .clicked()
+            {
+                self.start_screenshot();
+            }
+
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                ui.add_space(10.0);
+                if ui.button("Export CSV").on_hover_text("Save all stored events as CSV").clicked() {
+                    self.export_csv_dialog();
+                }
+            }
+
         });
     }
 }
 
+impl JamApp {
+    /// Tiny always-visible line of total events/sec; buckets are recomputed at most once a second
+    fn render_throughput_sparkline(&mut self, ui: &mut egui::Ui) {
+        let now = now_seconds();
+        if now - self.throughput_refreshed_at >= 1.0 {
+            let view_now = self.view_now();
+            self.throughput_history = with_data!(self, |data| {
+                data.events.compute_aggregate_rate(view_now, 1.0, SPARKLINE_SECS, None)
+            });
+            self.throughput_refreshed_at = now;
+        }
+
+        let (rect, response) =
+            ui.allocate_exact_size(egui::vec2(80.0, 16.0), egui::Sense::hover());
+        let current = self.throughput_history.last().copied().unwrap_or(0.0);
+        let peak = self.throughput_history.iter().copied().fold(0.0, f64::max);
+        if peak > 0.0 && self.throughput_history.len() > 1 {
+            let step = rect.width() / (self.throughput_history.len() - 1) as f32;
+            let points: Vec<egui::Pos2> = self
+                .throughput_history
+                .iter()
+                .enumerate()
+                .map(|(i, &rate)| {
+                    let y = rect.bottom() - (rate / peak) as f32 * rect.height();
+                    egui::pos2(rect.left() + i as f32 * step, y)
+                })
+                .collect();
+            ui.painter().add(egui::Shape::line(
+                points,
+                egui::Stroke::new(1.0, sparkline_color(current)),
+            ));
+        } else {
+            ui.painter().hline(
+                rect.x_range(),
+                rect.bottom(),
+                egui::Stroke::new(1.0, colors::text_muted()),
+            );
+        }
+        response.on_hover_text(format!(
+            "{:.0} events/s now, peak {:.0} over the last {}s",
+            current, peak, SPARKLINE_SECS
+        ));
+    }
+}
+
+impl JamApp {
+    /// Network tip over the last two minutes as a staircase; flat steps mean no new blocks
+    fn render_slot_staircase(&self, ui: &mut egui::Ui) {
+        let (rect, response) =
+            ui.allocate_exact_size(egui::vec2(80.0, 16.0), egui::Sense::hover());
+        let samples = &self.slot_history.samples;
+        let (Some(&first), Some(&last)) = (samples.front(), samples.back()) else {
+            ui.painter().hline(
+                rect.x_range(),
+                rect.bottom(),
+                egui::Stroke::new(1.0, colors::text_muted()),
+            );
+            response.on_hover_text("No best block reported yet");
+            return;
+        };
+
+        let stalled_secs = self.slot_history.stalled_secs();
+        let color = if stalled_secs > SLOT_STALL_SECS {
+            egui::Color32::from_rgb(200, 100, 100)
+        } else {
+            colors::text_secondary()
+        };
+        let span = (last - first).max(1) as f32;
+        let step = rect.width() / (SLOT_HISTORY_SECS - 1) as f32;
+        // Right-aligned: the newest sample sits at the right edge
+        let x0 = rect.right() - (samples.len() - 1) as f32 * step;
+        let y_of = |slot: u64| rect.bottom() - (slot - first) as f32 / span * rect.height();
+        let mut points = Vec::with_capacity(samples.len() * 2);
+        let mut prev_y = y_of(first);
+        for (i, &slot) in samples.iter().enumerate() {
+            let x = x0 + i as f32 * step;
+            let y = y_of(slot);
+            points.push(egui::pos2(x, prev_y));
+            points.push(egui::pos2(x, y));
+            prev_y = y;
+        }
+        ui.painter().add(egui::Shape::line(points, egui::Stroke::new(1.0, color)));
+        response.on_hover_text(format!(
+            "best slot {} ({} new over the last {}s), last advanced {}s ago",
+            last,
+            last - first,
+            samples.len(),
+            stalled_secs,
+        ));
+    }
+}
+
+/// Highest best slot across the network, sampled once per second
+#[derive(Default)]
+pub struct SlotHistory {
+    /// Oldest first; never decreases, so late reports of older slots don't dip the line
+    samples: VecDeque<u64>,
+}
+
+impl SlotHistory {
+    /// Record one sample. `None` (no best block yet) is skipped.
+    pub fn push(&mut self, highest_slot: Option<u64>) {
+        let Some(slot) = highest_slot else {
+            return;
+        };
+        let slot = self.samples.back().map_or(slot, |&prev| prev.max(slot));
+        if self.samples.len() >= SLOT_HISTORY_SECS {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(slot);
+    }
+
+    /// Seconds since the slot last advanced (samples equal to the newest, minus one)
+    pub fn stalled_secs(&self) -> usize {
+        let Some(&last) = self.samples.back() else {
+            return 0;
+        };
+        self.samples.iter().rev().take_while(|&&s| s == last).count() - 1
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+}
+
 /// FPS counter using platform-agnostic time
 pub struct FpsCounter {
-    frames: Vec<f64>,
+    /// Frame start times in milliseconds, oldest first
+    frames: VecDeque<f64>,
 }
 
 impl FpsCounter {
     pub fn new() -> Self {
         Self {
-            frames: Vec::with_capacity(60),
+            frames: VecDeque::with_capacity(FRAME_HISTORY + 1),
         }
     }
 
     pub fn tick(&mut self) {
-        let now = now_seconds() * 1000.0;
-        self.frames.push(now);
-        if self.frames.len() > 60 {
-            self.frames.remove(0);
+        self.push(now_seconds() * 1000.0);
+    }
+
+    fn push(&mut self, now_ms: f64) {
+        self.frames.push_back(now_ms);
+        if self.frames.len() > FRAME_HISTORY {
+            self.frames.pop_front();
         }
     }
 
+    /// Frames per second over the last `FPS_FRAMES` frames
     pub fn fps(&self) -> f64 {
-        if self.frames.len() < 2 {
+        let recent = self.frames.len().min(FPS_FRAMES);
+        if recent < 2 {
             return 0.0;
         }
-        let elapsed = self.frames.last().unwrap() - self.frames.first().unwrap();
+        let elapsed = self.frames[self.frames.len() - 1] - self.frames[self.frames.len() - recent];
         if elapsed == 0.0 {
             return 0.0;
         }
-        (self.frames.len() as f64 - 1.0) / (elapsed / 1000.0)
+        (recent as f64 - 1.0) / (elapsed / 1000.0)
+    }
+
+    /// Milliseconds between consecutive frames, oldest first
+    pub fn frame_times(&self) -> Vec<f64> {
+        self.frames
+            .iter()
+            .zip(self.frames.iter().skip(1))
+            .map(|(prev, next)| next - prev)
+            .collect()
     }
 }
 
@@ -99,3 +443,51 @@ impl Default for FpsCounter {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fps_counter_frame_times() {
+        let mut counter = FpsCounter::new();
+        assert_eq!(counter.fps(), 0.0);
+        assert!(counter.frame_times().is_empty());
+
+        for i in 0..FRAME_HISTORY + 10 {
+            counter.push(i as f64 * 20.0);
+        }
+        let times = counter.frame_times();
+        assert_eq!(times.len(), FRAME_HISTORY - 1);
+        assert!(times.iter().all(|&t| t == 20.0));
+        assert_eq!(counter.fps(), 50.0);
+
+        // A stutter shows up in the deltas and only the recent frames count toward fps
+        counter.push((FRAME_HISTORY + 9) as f64 * 20.0 + 100.0);
+        assert_eq!(counter.frame_times().last(), Some(&100.0));
+        assert!(counter.fps() < 50.0);
+    }
+
+    #[test]
+    fn slot_history_is_monotonic_and_tracks_stalls() {
+        let mut history = SlotHistory::default();
+        history.push(None);
+        assert!(history.samples.is_empty());
+        assert_eq!(history.stalled_secs(), 0);
+
+        for slot in [10, 11, 11, 9, 11] {
+            history.push(Some(slot));
+        }
+        // A late report of slot 9 doesn't dip the line
+        assert_eq!(history.samples, [10, 11, 11, 11, 11]);
+        assert_eq!(history.stalled_secs(), 3);
+
+        history.push(Some(12));
+        assert_eq!(history.stalled_secs(), 0);
+
+        for slot in 0..SLOT_HISTORY_SECS as u64 * 2 {
+            history.push(Some(100 + slot));
+        }
+        assert_eq!(history.samples.len(), SLOT_HISTORY_SECS);
+    }
+}
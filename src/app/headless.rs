@@ -0,0 +1,230 @@
+//! Headless `--json-stats` run (native only): parse the live endpoints (or a replay) into
+//! the data stores and write the periodic stats, without opening a window
+
+use std::time::Duration;
+use tracing::{debug, error, info};
+use crate::core::events::{default_travel_durations, TravelDurations};
+use crate::core::{parse_message, EventType, ParserContext};
+use crate::metrics_server::MetricsSnapshot;
+use crate::time::now_seconds;
+use crate::ws_state::{subscribe_message, WsMessage};
+use super::diagnostics::{DiagCounts, DiagHistory, EventIdTracker};
+use super::endpoints::{connect_endpoints, source_id};
+use super::export::write_events_csv;
+use super::stats_log::{emit_stats, StatsSample};
+use super::{DiagRateMode, LaunchOptions, SharedData, DEFAULT_PULSE_TYPES};
+
+/// Pause between two drains of the message channels
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Parsed data and the counters behind the stats rates
+struct Collector {
+    data: SharedData,
+    travel_durations: TravelDurations,
+    event_ids: EventIdTracker,
+    counts: DiagCounts,
+    /// Types kept by `--events`, applied to live and replayed messages alike; `None` keeps all
+    event_types: Option<Vec<EventType>>,
+}
+
+impl Collector {
+    fn new(event_types: Option<&[EventType]>) -> Self {
+        Self {
+            data: SharedData::new(),
+            travel_durations: default_travel_durations(),
+            event_ids: EventIdTracker::default(),
+            counts: DiagCounts::default(),
+            event_types: event_types.map(<[EventType]>::to_vec),
+        }
+    }
+
+    /// Parse one message from `endpoint` into the stores, stamped `now`
+    fn process(&mut self, msg: &WsMessage, endpoint: usize, source: Option<&str>, source_id: u8, now: f64) {
+        if let WsMessage::Connected = msg {
+            // A fresh subscription restarts the id sequence used for gap detection
+            self.event_ids.reset(endpoint);
+            return;
+        }
+        self.counts.rx_bytes += msg.as_bytes().len() as u64;
+        let d = &mut self.data;
+        let mut ctx = ParserContext {
+            time_series: &mut d.time_series,
+            blocks: &mut d.blocks,
+            guarantee_queues: &mut d.guarantee_queues,
+            sync_status: &mut d.sync_status,
+            peers: &mut d.peers,
+            shards: &mut d.shards,
+            preimages: &mut d.preimages,
+            events: &mut d.events,
+            directed_buffer: &mut d.directed_buffer,
+            pulse_events: &mut d.pulse_events,
+            assurances: &mut d.assurances,
+            latest_status: &mut d.latest_status,
+            tickets: &mut d.tickets,
+            topology: &mut d.topology,
+            travel_durations: &self.travel_durations,
+            pulse_types: DEFAULT_PULSE_TYPES,
+            source,
+            source_id,
+            stored_types: self.event_types.as_deref(),
+        };
+        match parse_message(msg.as_bytes(), msg.codec(), &mut ctx, now) {
+            Ok(Some(result)) => {
+                // Ids advance over unselected events too, so they don't show up as gaps
+                if let Some(id) = result.event_id {
+                    self.counts.server_dropped += self.event_ids.gap(endpoint, id);
+                }
+                let selected = self
+                    .event_types
+                    .as_ref()
+                    .is_none_or(|types| types.contains(&result.event_type));
+                if selected {
+                    self.counts.events += 1;
+                    self.counts.node_dropped += result.node_dropped;
+                }
+            }
+            Ok(None) => {}
+            Err(e) => {
+                self.counts.parse_errors += 1;
+                debug!(error = %e, "Failed to parse message");
+            }
+        }
+        // Nothing draws the pulses here
+        d.pulse_events.clear();
+    }
+}
+
+/// Run until the replay has played through (a live session runs until killed), writing
+/// stats every `--stats-interval`. `events` narrows the subscription and what is counted
+/// and stored, as `--events` does.
+pub(crate) fn run_headless(options: LaunchOptions, events: Option<&[EventType]>) {
+    let LaunchOptions {
+        ws_urls, recorder, mut replay, export_csv_on_exit, live_metrics, stats, backoff, ..
+    } = options;
+    let endpoints = if replay.is_some() {
+        Vec::new()
+    } else {
+        connect_endpoints(&ws_urls, recorder.as_ref(), &backoff)
+    };
+    if let Some(types) = events {
+        let types: Vec<u8> = types.iter().map(|&et| et as u8).collect();
+        for endpoint in &endpoints {
+            endpoint.client.subscribe(subscribe_message(Some(&types)));
+        }
+    }
+    info!(endpoints = endpoints.len(), replay = replay.is_some(), "Running headless");
+
+    let mut collector = Collector::new(events);
+    let mut history = DiagHistory::default();
+    let mut events_total = 0u64;
+    let mut evicted_total = 0u64;
+    let mut last_emit = now_seconds();
+    loop {
+        let now = now_seconds();
+        for (index, endpoint) in endpoints.iter().enumerate() {
+            for msg in endpoint.client.rx.try_iter() {
+                collector.process(&msg, index, endpoint.source.as_deref(), endpoint.id, now);
+            }
+        }
+        let mut finished = false;
+        if let Some(replay) = replay.as_mut() {
+            replay.advance(now, 1.0);
+            while let Some((msg, source, ago)) = replay.next_due() {
+                // Each endpoint of a multi-endpoint recording numbers its own events
+                let id = source.map_or(0, source_id);
+                collector.process(msg, id as usize, source, id, now - ago);
+            }
+            finished = replay.is_finished();
+        }
+
+        if finished || now - last_emit >= stats.interval {
+            let data = &mut collector.data;
+            let evicted = data.events.capacity_evictions();
+            let mut counts = std::mem::take(&mut collector.counts);
+            counts.buffer_evicted = evicted.saturating_sub(evicted_total);
+            evicted_total = evicted;
+            events_total += counts.events;
+            history.push(now - last_emit, counts);
+            last_emit = now;
+            data.events.prune(now);
+
+            let rates = history.rates(DiagRateMode::Instant);
+            emit_stats(
+                &stats,
+                &StatsSample {
+                    rates,
+                    active_particles: data.directed_buffer.active_count(now as f32, 5.0),
+                    events: &data.events,
+                    view_now: now,
+                },
+            );
+            if let Some(metrics) = &live_metrics {
+                let highest_slot = data.blocks.highest_slot();
+                metrics.publish(&MetricsSnapshot {
+                    events_total,
+                    events_per_sec: rates.events,
+                    dropped_per_sec: rates.node_dropped + rates.server_dropped,
+                    nodes: data.events.node_count(),
+                    validators: data.blocks.validator_count(),
+                    highest_slot,
+                    finalization_lag: highest_slot
+                        .zip(data.blocks.highest_finalized())
+                        .map(|(best, finalized)| best.saturating_sub(finalized)),
+                });
+            }
+        }
+        if finished {
+            break;
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+
+    info!("Replay finished");
+    if let Some(path) = export_csv_on_exit.as_deref() {
+        match write_events_csv(&collector.data.events, path) {
+            Ok(rows) => info!(path = %path.display(), rows, "Exported events to CSV"),
+            Err(e) => error!(path = %path.display(), error = %e, "CSV export failed"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(id: u64) -> WsMessage {
+        WsMessage::Text(format!(
+            r#"{{"type":"event","data":{{"id":{},"node_id":"aa","event":{{"BestBlockChanged":{{"timestamp":10000000,"slot":1,"hash":{:?}}}}}}}}}"#,
+            id, [0u8; 32]
+        ))
+    }
+
+    #[test]
+    fn collector_counts_events_gaps_and_errors() {
+        let mut collector = Collector::new(None);
+        collector.process(&WsMessage::Connected, 0, None, 0, 1.0);
+        collector.process(&event(1), 0, None, 0, 1.0);
+        collector.process(&event(4), 0, None, 0, 1.0);
+        collector.process(&WsMessage::Text("not json".to_string()), 0, None, 0, 1.0);
+        // A reconnect restarts the ids without counting a gap
+        collector.process(&WsMessage::Connected, 0, None, 0, 1.0);
+        collector.process(&event(1), 0, None, 0, 1.0);
+
+        let counts = collector.counts;
+        assert_eq!((counts.events, counts.server_dropped, counts.parse_errors), (3, 2, 1));
+        assert!(counts.rx_bytes > 0);
+        assert_eq!(collector.data.events.node_count(), 1);
+    }
+
+    #[test]
+    fn collector_skips_unselected_events() {
+        let mut collector = Collector::new(Some(&[EventType::Status]));
+        collector.process(&event(1), 0, None, 0, 1.0);
+        collector.process(&event(2), 0, None, 0, 1.0);
+
+        let counts = collector.counts;
+        assert_eq!((counts.events, counts.server_dropped), (0, 0));
+        assert_eq!(collector.data.events.node_count(), 0);
+        assert_eq!(collector.data.blocks.highest_slot(), None);
+    }
+}
@@ -0,0 +1,218 @@
+//! Event inspector — the full decoded event behind a double-clicked scatter point or ring node
+
+use eframe::egui;
+use serde_json::Value;
+use crate::core::{event_name, Event};
+use crate::theme::colors;
+use super::labels::{hash_hex, short_hex};
+use super::{JamApp, with_data};
+
+/// Pointer distance in pixels within which a double-click picks an event
+pub(crate) const INSPECT_HIT_PX: f32 = 8.0;
+
+/// Copy of the inspected event, so it outlives retention pruning of the store
+pub(crate) struct InspectedEvent {
+    pub node_index: u16,
+    pub node_id: String,
+    pub timestamp: f64,
+    pub event: Event,
+}
+
+/// Pretty-printed JSON of `event`, with hashes and ids shown as hex
+fn event_json(event: &Event) -> String {
+    let mut value = serde_json::to_value(event).unwrap_or(Value::Null);
+    hexify_byte_arrays(&mut value);
+    serde_json::to_string_pretty(&value).unwrap_or_default()
+}
+
+/// Replace arrays of 16 or more byte values (hashes, peer ids, addresses) with "0x…" strings
+fn hexify_byte_arrays(value: &mut Value) {
+    let hex = match value {
+        Value::Array(items) => {
+            let bytes: Option<Vec<u8>> = items
+                .iter()
+                .map(|v| v.as_u64().and_then(|b| u8::try_from(b).ok()))
+                .collect();
+            match bytes {
+                Some(bytes) if bytes.len() >= 16 => Some(format!("0x{}", hex::encode(bytes))),
+                _ => {
+                    items.iter_mut().for_each(hexify_byte_arrays);
+                    None
+                }
+            }
+        }
+        Value::Object(map) => {
+            map.values_mut().for_each(hexify_byte_arrays);
+            None
+        }
+        _ => None,
+    };
+    if let Some(hex) = hex {
+        *value = Value::String(hex);
+    }
+}
+
+/// Every 32-byte field of the event JSON (hashes, peer ids, roots) as (field path, bytes)
+fn hash_fields(value: &Value, path: &str, out: &mut Vec<(String, [u8; 32])>) {
+    match value {
+        Value::Array(items) => {
+            let bytes: Option<Vec<u8>> = items
+                .iter()
+                .map(|v| v.as_u64().and_then(|b| u8::try_from(b).ok()))
+                .collect();
+            match bytes.and_then(|b| <[u8; 32]>::try_from(b).ok()) {
+                Some(hash) => out.push((path.to_string(), hash)),
+                None => {
+                    for (i, item) in items.iter().enumerate() {
+                        hash_fields(item, &format!("{}[{}]", path, i), out);
+                    }
+                }
+            }
+        }
+        Value::Object(map) => {
+            for (key, item) in map {
+                let path = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                hash_fields(item, &path, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Small button that copies `text` to the clipboard. The copy happens in the frame that
+/// handles the click, so on WASM the browser still sees it as part of the user gesture.
+pub(crate) fn copy_button(ui: &mut egui::Ui, text: &str) {
+    if ui.small_button("copy").on_hover_text("Copy to clipboard").clicked() {
+        ui.ctx().copy_text(text.to_string());
+    }
+}
+
+impl JamApp {
+    /// Open the inspector on the event of node `node_index` nearest to `timestamp`.
+    /// Leaves the inspector unchanged if nothing lies within `tolerance` seconds.
+    pub(crate) fn inspect_event_near(&mut self, node_index: u16, timestamp: f64, tolerance: f64) {
+        let found = with_data!(self, |data| {
+            data.events
+                .find_event_near(node_index, timestamp, tolerance)
+                .map(|(node_id, stored)| InspectedEvent {
+                    node_index,
+                    node_id: node_id.to_string(),
+                    timestamp: stored.timestamp,
+                    event: stored.event.clone(),
+                })
+        });
+        if found.is_some() {
+            self.inspected_event = found;
+        }
+    }
+
+    /// Right side panel with every field of the inspected event
+    pub(crate) fn render_event_inspector(&mut self, ctx: &egui::Context) {
+        let Some(inspected) = &self.inspected_event else {
+            return;
+        };
+        let event_type = inspected.event.event_type();
+        let label = self.node_label(&inspected.node_id);
+        let age = self.view_now() - inspected.timestamp;
+        let reason = inspected.event.reason().map(|r| r.into_owned());
+        let json = event_json(&inspected.event);
+        let mut hashes = Vec::new();
+        if let Ok(value) = serde_json::to_value(&inspected.event) {
+            // Skip the variant wrapper so paths read "hash", not "BestBlockChanged.hash"
+            match value.as_object().and_then(|m| m.values().next()) {
+                Some(fields) => hash_fields(fields, "", &mut hashes),
+                None => hash_fields(&value, "", &mut hashes),
+            }
+        }
+
+        let mut close = false;
+        egui::SidePanel::right("event_inspector")
+            .default_width(ctx.screen_rect().width() * 0.25)
+            .frame(egui::Frame::new().fill(colors::bg_primary()).inner_margin(8.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(
+                        egui::RichText::new(event_name(event_type))
+                            .color(self.get_event_color(event_type))
+                            .strong(),
+                    );
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.small_button("×").on_hover_text("Close inspector").clicked() {
+                            close = true;
+                        }
+                    });
+                });
+                ui.horizontal(|ui| {
+                    ui.label(
+                        egui::RichText::new(format!("node {} · {}", inspected.node_index, label))
+                            .color(colors::text_secondary()),
+                    );
+                    copy_button(ui, &inspected.node_id);
+                });
+                ui.label(
+                    egui::RichText::new(format!("{:.0}s ago", age.max(0.0)))
+                        .color(colors::text_muted()),
+                );
+                if let Some(reason) = reason {
+                    ui.add_space(4.0);
+                    ui.label(egui::RichText::new("Reason:").color(colors::text_muted()));
+                    ui.label(egui::RichText::new(reason).monospace().color(colors::text_primary()));
+                }
+                if !hashes.is_empty() {
+                    ui.add_space(4.0);
+                    egui::Grid::new("event_inspector_hashes").num_columns(3).show(ui, |ui| {
+                        for (path, hash) in &hashes {
+                            let hex = hash_hex(hash);
+                            ui.label(egui::RichText::new(path).color(colors::text_muted()));
+                            ui.label(egui::RichText::new(short_hex(&hex)).monospace())
+                                .on_hover_text(hex.as_str());
+                            copy_button(ui, &hex);
+                            ui.end_row();
+                        }
+                    });
+                }
+                ui.add_space(4.0);
+                ui.separator();
+                egui::ScrollArea::both().show(ui, |ui| {
+                    ui.label(egui::RichText::new(json).monospace().color(colors::text_primary()));
+                });
+            });
+        if close {
+            self.inspected_event = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn byte_arrays_become_hex() {
+        let event = Event::BestBlockChanged { timestamp: 7, slot: 42, hash: [0xab; 32] };
+        let json = event_json(&event);
+        assert!(json.contains(&format!("\"0x{}\"", "ab".repeat(32))));
+        assert!(json.contains("\"slot\": 42"));
+        // Short number arrays are left alone
+        let mut value = serde_json::json!({ "cores": [1, 2, 3] });
+        hexify_byte_arrays(&mut value);
+        assert_eq!(value["cores"], serde_json::json!([1, 2, 3]));
+    }
+
+    #[test]
+    fn hash_fields_finds_nested_hashes() {
+        let value = serde_json::json!({
+            "slot": 1,
+            "hash": vec![7u8; 32],
+            "reports": [{ "root": vec![9u8; 32] }],
+            "addr": vec![1u8; 16],
+        });
+        let mut out = Vec::new();
+        hash_fields(&value, "", &mut out);
+        out.sort();
+        assert_eq!(out, vec![
+            ("hash".to_string(), [7u8; 32]),
+            ("reports[0].root".to_string(), [9u8; 32]),
+        ]);
+    }
+}
@@ -0,0 +1,123 @@
+//! Peer ID → human-readable label mapping (`peer_labels.json`)
+//!
+//! File format: `{ "<hex_peer_id>": "val-07-fra", ... }`.
+//! Native loads it from `--labels <path>`; WASM fetches `window.__jam_labels_url`
+//! (default `peer_labels.json` next to the page).
+
+use std::collections::HashMap;
+use crate::core::parser::split_source;
+use super::JamApp;
+
+/// Parse a label file. Keys are normalized to lowercase hex without a `0x` prefix
+/// so they match jamtart node ids.
+pub(crate) fn parse_peer_labels(json: &str) -> Result<HashMap<String, String>, serde_json::Error> {
+    let raw: HashMap<String, String> = serde_json::from_str(json)?;
+    Ok(raw
+        .into_iter()
+        .map(|(id, label)| {
+            let id = id.trim();
+            let id = id.strip_prefix("0x").unwrap_or(id).to_ascii_lowercase();
+            (id, label)
+        })
+        .collect())
+}
+
+/// Short form of a hex id for display: `a3f1…9c`
+pub(crate) fn short_hex(node_id: &str) -> String {
+    if node_id.len() <= 8 || !node_id.is_ascii() {
+        return node_id.to_string();
+    }
+    format!("{}…{}", &node_id[..4], &node_id[node_id.len() - 2..])
+}
+
+/// Full lowercase hex of a hash or peer id (same form as node ids and label-file keys)
+pub(crate) fn hash_hex(bytes: &[u8; 32]) -> String {
+    hex::encode(bytes)
+}
+
+/// Read and parse a label file from disk (native `--labels`)
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn load_peer_labels(path: &std::path::Path) -> Result<HashMap<String, String>, String> {
+    let contents = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    parse_peer_labels(&contents).map_err(|e| e.to_string())
+}
+
+/// Fetch the label file in the background; the result lands in `slot` for `update()` to pick up
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn fetch_peer_labels(
+    url: String,
+    slot: std::rc::Rc<std::cell::RefCell<Option<HashMap<String, String>>>>,
+) {
+    use tracing::{debug, info, warn};
+
+    wasm_bindgen_futures::spawn_local(async move {
+        match fetch_text(&url).await {
+            Ok(text) => match parse_peer_labels(&text) {
+                Ok(labels) => {
+                    info!(url, count = labels.len(), "Loaded peer labels");
+                    *slot.borrow_mut() = Some(labels);
+                }
+                Err(e) => warn!(url, error = %e, "Invalid peer labels file"),
+            },
+            // A missing file is the normal case, not worth a warning
+            Err(e) => debug!(url, error = ?e, "No peer labels loaded"),
+        }
+    });
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn fetch_text(url: &str) -> Result<String, wasm_bindgen::JsValue> {
+    use wasm_bindgen::{JsCast, JsValue};
+    use wasm_bindgen_futures::JsFuture;
+
+    let window = web_sys::window().ok_or_else(|| JsValue::from_str("no window"))?;
+    let resp: web_sys::Response = JsFuture::from(window.fetch_with_str(url)).await?.dyn_into()?;
+    if !resp.ok() {
+        return Err(JsValue::from_str(&format!("HTTP {}", resp.status())));
+    }
+    let text = JsFuture::from(resp.text()?).await?;
+    text.as_string().ok_or_else(|| JsValue::from_str("non-text body"))
+}
+
+impl JamApp {
+    /// Display name for a node: its label if mapped, otherwise a short hex prefix.
+    /// Nodes of a multi-endpoint session keep their source tag (`val-07-fra@s2`).
+    pub(crate) fn node_label(&self, node_id: &str) -> String {
+        let (id, source) = split_source(node_id);
+        let name = self.peer_labels.get(id).cloned().unwrap_or_else(|| short_hex(id));
+        match source {
+            Some(source) => format!("{}@{}", name, source),
+            None => name,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_normalizes_keys() {
+        let labels =
+            parse_peer_labels(r#"{"0xA3F1BEEF": "val-07-fra", " cafe01 ": "val-01-ams"}"#).unwrap();
+        assert_eq!(labels.get("a3f1beef").map(String::as_str), Some("val-07-fra"));
+        assert_eq!(labels.get("cafe01").map(String::as_str), Some("val-01-ams"));
+        assert!(parse_peer_labels("[1, 2]").is_err());
+    }
+
+    #[test]
+    fn short_hex_keeps_prefix_and_suffix() {
+        assert_eq!(short_hex("a3f1000000000000000000009c"), "a3f1…9c");
+        assert_eq!(short_hex("a3f19c"), "a3f19c");
+    }
+
+    #[test]
+    fn hash_hex_is_lowercase_without_prefix() {
+        let mut hash = [0xABu8; 32];
+        hash[0] = 0x01;
+        let hex = hash_hex(&hash);
+        assert_eq!(hex.len(), 64);
+        assert!(hex.starts_with("01abab"));
+        assert_eq!(hex, hex.to_ascii_lowercase());
+    }
+}
@@ -8,25 +8,63 @@ mod ring;
 mod graphs;
 mod settings;
 mod diagnostics;
+mod node_popup;
+mod node_list;
+mod connection;
+mod persist;
+mod labels;
+mod sync_status;
+mod ring_layout;
+mod inspector;
+mod toasts;
+mod subscription;
+mod analytics;
+mod budget;
+mod screenshot;
+#[cfg(any(target_arch = "wasm32", test))]
+mod deep_link;
+mod snapshot;
+mod status_table;
+mod event_log;
+mod geo_map;
+mod topology;
+mod alerts;
+#[cfg(not(target_arch = "wasm32"))]
+mod replay_bar;
+#[cfg(not(target_arch = "wasm32"))]
+mod export;
+#[cfg(not(target_arch = "wasm32"))]
+mod stats_log;
+#[cfg(not(target_arch = "wasm32"))]
+mod headless;
+#[cfg(not(target_arch = "wasm32"))]
+mod endpoints;
+#[cfg(not(target_arch = "wasm32"))]
+mod window_state;
 
 use eframe::egui;
-use tracing::info;
+use std::collections::HashMap;
+use tracing::{info, warn};
 
 #[cfg(target_arch = "wasm32")]
 use std::cell::RefCell;
-#[cfg(target_arch = "wasm32")]
 use std::collections::VecDeque;
 #[cfg(target_arch = "wasm32")]
 use std::rc::Rc;
 
+use crate::core::events::{default_travel_durations, TravelDurations};
 use crate::core::{
-    parse_event, ParseResult, ParserContext, BestBlockData, EventStore, TimeSeriesData,
-    EventType, EVENT_CATEGORIES,
+    parse_message, Codec, ParseError, ParseResult, ParserContext,
+    AssuranceData, BestBlockData, EventStore, Fork, GuaranteeQueueData, LatestStatusData, NodeIndexing,
+    PeerMetrics, PreimageMetrics, ShardMetrics, SyncStatusData, TicketData, TimeSeriesData, TopologyData, EventType, EVENT_CATEGORIES,
 };
-use crate::theme::{colors, minimal_visuals};
+use crate::theme::{self, colors, Theme};
 use crate::time::now_seconds;
-use crate::vring::{DirectedEventBuffer, PulseEvent, ColorLut, ColorSchema};
-use crate::ws_state::WsState;
+use crate::vring::{DirectedEventBuffer, PulseEvent, ColorLut, ColorSchema, CvdMode};
+use crate::ws_state::{is_error_reply, Backoff, WsMessage, WsState};
+#[cfg(target_arch = "wasm32")]
+use crate::ws_state::MessageQueue;
+use diagnostics::{format_count, payload_preview, PARSE_ERROR_SAMPLES};
 
 #[cfg(target_arch = "wasm32")]
 use crate::websocket_wasm::WsClient;
@@ -38,19 +76,152 @@ use crate::scatter::ScatterRenderer;
 use crate::vring::RingRenderer;
 
 #[cfg(not(target_arch = "wasm32"))]
-use crate::websocket_native::NativeWsClient;
+use crate::metrics_server::{LiveMetrics, MetricsSnapshot};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::recorder::Recorder;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::replay::ReplaySource;
 #[cfg(not(target_arch = "wasm32"))]
 use parking_lot::Mutex;
 
-/// Default WebSocket URL for jamtart (override with JAMTART_WS env var)
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use labels::load_peer_labels;
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use stats_log::{StatsOptions, DEFAULT_STATS_INTERVAL};
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use headless::run_headless;
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use endpoints::native_ws_urls;
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use geo_map::GeoIp;
+
+/// Default WebSocket URL for jamtart (override with JAMTART_WS env var or `--url`)
 pub const DEFAULT_WS_URL: &str = "ws://127.0.0.1:38080/api/ws";
 
+/// Parse errors/sec above which the error rate counts as a spike
+const PARSE_ERROR_SPIKE_RATE: f64 = 5.0;
+/// Consecutive housekeeping ticks (~1s each) above the spike rate before recovery is offered
+const PARSE_ERROR_SPIKE_SECS: u32 = 5;
+/// Max messages held back while paused (and the WASM receive buffer at any time);
+/// the oldest are dropped beyond this
+const PAUSE_BUFFER_CAP: usize = 200_000;
+/// WASM receive buffer depth counted as backed up
+#[cfg(target_arch = "wasm32")]
+const MSG_BACKLOG_WARN: usize = 10_000;
+/// Consecutive housekeeping ticks (~1s each) backed up before the header warns
+#[cfg(target_arch = "wasm32")]
+pub(crate) const MSG_BACKLOG_WARN_SECS: u32 = 3;
+
+/// Default seconds of silence before a node is flagged as stalled
+pub(crate) const DEFAULT_STALL_THRESHOLD: f32 = 10.0;
+/// Default seconds of events kept in the EventStore
+pub(crate) const DEFAULT_RETENTION_SECS: f32 = 60.0;
+/// Default per-type ring buffer capacity of each node in the EventStore
+pub(crate) const DEFAULT_MAX_EVENTS_PER_NODE: usize = 50_000;
+/// Default max particles visible in the GPU scatter before downsampling kicks in
+pub(crate) const DEFAULT_SCATTER_PARTICLE_CAP: usize = 500_000;
+/// Default ring radius (fraction of half the ring view height)
+pub(crate) const DEFAULT_RING_RADIUS: f32 = 0.75;
+/// Default node dot radius on the ring, in pixels
+pub(crate) const DEFAULT_NODE_DOT_RADIUS: f32 = 4.0;
+/// Default buckets in the event-rate moving average
+pub(crate) const DEFAULT_RATE_SMOOTHING_WINDOW: usize = 5;
+/// Default newest-bucket weight of the event-rate exponential smoothing
+pub(crate) const DEFAULT_RATE_SMOOTHING_ALPHA: f32 = 0.3;
+/// Default manual event-rate Y ceiling, events/sec
+pub(crate) const DEFAULT_RATE_Y_MAX: f32 = 50.0;
+/// Default particle trail length, in units of particle progress
+pub(crate) const DEFAULT_TRAIL_LENGTH: f32 = 0.3;
+/// Default time window shared by the rate, error and analytics panels, seconds
+pub(crate) const DEFAULT_TIME_WINDOW: f32 = 60.0;
+/// Default own window of the Event Particles panel, seconds
+pub(crate) const DEFAULT_SCATTER_WINDOW: f32 = 10.0;
+/// Time window presets offered in settings, seconds
+pub(crate) const TIME_WINDOW_PRESETS: [f32; 4] = [5.0, 30.0, 60.0, 300.0];
+/// Buckets in the event-rate and churn plots; their width follows the time window
+pub(crate) const RATE_BUCKETS: usize = 60;
+/// Range of per-type travel duration overrides, seconds; the CPU ring keeps particles 5s,
+/// which covers the longest directed trail at normal speed
+pub(crate) const MIN_TRAVEL_DURATION: f32 = 0.5;
+pub(crate) const MAX_TRAVEL_DURATION: f32 = 15.0;
+/// Default lifetime of a collapsing pulse, seconds
+pub(crate) const DEFAULT_PULSE_DURATION: f32 = 0.4;
+/// Default starting radius of a collapsing pulse, pixels
+pub(crate) const DEFAULT_PULSE_RADIUS: f32 = 40.0;
+/// Event types that pulse by default: block authoring and work-package submission
+pub(crate) const DEFAULT_PULSE_TYPES: &[EventType] =
+    &[EventType::Authoring, EventType::WorkPackageSubmission];
+/// Default speed reduction of ring particles under the pointer
+pub(crate) const DEFAULT_HOVER_SLOWDOWN: f32 = 0.8;
+/// Default seconds between diagnostics rate samples
+pub(crate) const DEFAULT_DIAG_INTERVAL: f32 = 1.0;
+/// Validators tracked by the per-node Status series (peer count, shards, preimages)
+const SERIES_NODES: usize = 1024;
+/// Points kept per validator in the Status series (one per Status report)
+const SERIES_POINTS: usize = 200;
+/// Seconds between recounts of the legend's per-entry event counts
+const LEGEND_REFRESH_SECS: f64 = 0.5;
+
 /// Active tab in the visualization
-#[derive(Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
 pub enum ActiveTab {
     #[default]
     Ring,
     Graphs,
+    Analytics,
+}
+
+/// What the Event Rate panel plots
+#[derive(Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum RateMode {
+    /// One white line per node
+    #[default]
+    PerNode,
+    /// One colored line per event category, summed across nodes
+    PerCategory,
+    /// Event categories stacked as filled bands; the top edge is total throughput
+    Stacked,
+}
+
+/// Smoothing applied to the per-node event-rate lines
+#[derive(Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum RateSmoothing {
+    /// Raw per-second bucket counts
+    #[default]
+    Raw,
+    /// Centered moving average over `rate_smoothing_window` buckets (no time shift)
+    MovingAverage,
+    /// Causal exponential smoothing with `rate_smoothing_alpha` (trails the raw line)
+    Exponential,
+}
+
+/// What the peer-count chart draws: per-node lines, the network min/median/max band, or both
+#[derive(Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum SeriesBand {
+    #[default]
+    Lines,
+    Band,
+    Both,
+}
+
+/// How ring nodes are clustered into arcs
+#[derive(Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum RingGrouping {
+    /// One continuous ring
+    #[default]
+    None,
+    /// Synced, syncing, then nodes without a recent sync report
+    SyncStatus,
+}
+
+/// How the diagnostics rates are computed from the sampling intervals
+#[derive(Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum DiagRateMode {
+    /// Rate over the latest interval
+    #[default]
+    Instant,
+    /// Moving average over the last few intervals
+    Average,
 }
 
 /// An active collapsing-pulse animation on the ring.
@@ -64,9 +235,75 @@ pub(crate) struct CollapsingPulse {
 pub struct SharedData {
     pub time_series: TimeSeriesData,
     pub blocks: BestBlockData,
+    pub guarantee_queues: GuaranteeQueueData,
+    pub sync_status: SyncStatusData,
+    pub peers: PeerMetrics,
+    pub shards: ShardMetrics,
+    pub preimages: PreimageMetrics,
     pub events: EventStore,
     pub directed_buffer: DirectedEventBuffer,
     pub pulse_events: Vec<PulseEvent>,
+    pub assurances: AssuranceData,
+    pub latest_status: LatestStatusData,
+    pub tickets: TicketData,
+    pub topology: TopologyData,
+}
+
+impl SharedData {
+    fn new() -> Self {
+        Self {
+            time_series: TimeSeriesData::new(SERIES_NODES, SERIES_POINTS),
+            blocks: BestBlockData::new(1024),
+            guarantee_queues: GuaranteeQueueData::new(1024),
+            sync_status: SyncStatusData::new(),
+            peers: PeerMetrics::new(SERIES_NODES, SERIES_POINTS),
+            shards: ShardMetrics::new(SERIES_NODES, SERIES_POINTS),
+            preimages: PreimageMetrics::new(SERIES_NODES, SERIES_POINTS),
+            events: EventStore::new(DEFAULT_MAX_EVENTS_PER_NODE, DEFAULT_RETENTION_SECS as f64),
+            directed_buffer: DirectedEventBuffer::default(),
+            pulse_events: Vec::new(),
+            assurances: AssuranceData::new(),
+            latest_status: LatestStatusData::new(),
+            tickets: TicketData::new(),
+            topology: TopologyData::new(),
+        }
+    }
+
+    /// Index assignment for nodes seen from now on; set it while the data is still empty
+    fn set_node_indexing(&mut self, mode: NodeIndexing) {
+        self.time_series.set_indexing(mode);
+        self.blocks.set_indexing(mode);
+        self.peers.val_peers.set_indexing(mode);
+        self.peers.sync_peers.set_indexing(mode);
+        self.shards.shard_counts.set_indexing(mode);
+        self.shards.shard_sizes.set_indexing(mode);
+        self.preimages.preimage_counts.set_indexing(mode);
+        self.preimages.preimage_sizes.set_indexing(mode);
+        self.events.set_indexing(mode);
+    }
+}
+
+/// Command-line inputs of the native app, parsed and opened by main.rs
+#[cfg(not(target_arch = "wasm32"))]
+pub struct LaunchOptions {
+    /// CPU rendering instead of wgpu (--use-cpu)
+    pub use_cpu: bool,
+    /// Endpoints to connect to, first one shown as the main URL (--url / JAMTART_WS)
+    pub ws_urls: Vec<String>,
+    /// Session recorder (--record)
+    pub recorder: Option<Recorder>,
+    /// Recording played back instead of connecting (--replay)
+    pub replay: Option<ReplaySource>,
+    /// Node names by hex peer id (--labels)
+    pub peer_labels: HashMap<String, String>,
+    /// CSV written with all stored events on exit (--export-csv-on-exit)
+    pub export_csv_on_exit: Option<std::path::PathBuf>,
+    /// Aggregates served on /metrics (--metrics-addr)
+    pub live_metrics: Option<Arc<LiveMetrics>>,
+    /// Stats output format and interval (--json-stats, --stats-interval)
+    pub stats: StatsOptions,
+    /// Reconnect policy of every client (--reconnect-delay-ms, --reconnect-attempts)
+    pub backoff: Backoff,
 }
 
 /// JAM Orbit App - runs on both native and WASM
@@ -85,19 +322,96 @@ pub struct JamApp {
 
     /// WebSocket client (kept alive)
     #[cfg(target_arch = "wasm32")]
-    #[allow(dead_code)]
     ws_client: Option<WsClient>,
+    /// One client per endpoint, all feeding `data` (native only)
+    #[cfg(not(target_arch = "wasm32"))]
+    endpoints: Vec<endpoints::Endpoint>,
+    /// WebSocket endpoint (kept for reconnects); the first one when several are connected
+    pub(crate) ws_url: String,
+    /// Every endpoint of the session, `ws_url` first (native only)
+    #[cfg(not(target_arch = "wasm32"))]
+    ws_urls: Vec<String>,
+    /// Reconnect policy handed to every new client
+    backoff: Backoff,
+    /// Session recorder (--record), handed to every new client (native only)
+    #[cfg(not(target_arch = "wasm32"))]
+    recorder: Option<Recorder>,
+    /// Recorded session being played back instead of a live socket (--replay, native only)
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) replay: Option<ReplaySource>,
+    /// CSV file written with all stored events when the window closes (--export-csv-on-exit)
+    #[cfg(not(target_arch = "wasm32"))]
+    export_csv_on_exit: Option<std::path::PathBuf>,
+    /// Aggregates served on /metrics (--metrics-addr), refreshed on the housekeeping tick
+    #[cfg(not(target_arch = "wasm32"))]
+    live_metrics: Option<Arc<LiveMetrics>>,
+    /// Stats output format and interval (--json-stats, --stats-interval)
     #[cfg(not(target_arch = "wasm32"))]
-    ws_client: Option<NativeWsClient>,
+    stats: StatsOptions,
+    #[cfg(not(target_arch = "wasm32"))]
+    stats_last_emit: f64,
+    /// Offline GeoIP database for the peer map (--geoip)
+    #[cfg(not(target_arch = "wasm32"))]
+    geoip: Option<geo_map::GeoIp>,
+    /// Show the connection panel
+    pub(crate) show_connection_panel: bool,
+    /// Show the node list side panel
+    pub(crate) show_node_list: bool,
+    /// Show the per-validator sync status panel (opened from the header)
+    pub(crate) show_sync_panel: bool,
+    /// Node list sort column and direction
+    pub(crate) node_list_sort: node_list::NodeListSort,
+    /// Show the snapshot diff window (toggled from the header)
+    pub(crate) show_snapshots: bool,
+    /// Snapshots A and B compared in that window
+    pub(crate) snapshots: snapshot::SnapshotPair,
+    /// Show the latest-Status table window (toggled from the header)
+    pub(crate) show_status_table: bool,
+    /// Show the live event log window (toggled from the header)
+    pub(crate) show_event_log: bool,
+    /// Show the peer map window (toggled from the header)
+    pub(crate) show_geo_map: bool,
+    /// Show the connection topology window (toggled from the header)
+    pub(crate) show_topology: bool,
+    /// Recent events of the selected types, one line each, oldest first
+    pub(crate) event_log: VecDeque<String>,
+    /// Event log stops following new lines
+    pub(crate) event_log_scroll_paused: bool,
+    /// Threshold rules checked on the diagnostics tick, one per metric
+    pub(crate) alert_rules: Vec<alerts::AlertRule>,
+    /// Beep (browser only) when an alert fires
+    pub(crate) alert_sound: bool,
+    /// Tripped rules, cooldowns and the header flash
+    pub(crate) alerts: alerts::AlertState,
+    /// URL being edited in the connection panel
+    pub(crate) connection_url_input: String,
+    /// Validation error for `connection_url_input`
+    pub(crate) connection_error: Option<String>,
+    /// Recently connected URLs, newest first
+    pub(crate) recent_urls: Vec<String>,
 
     /// FPS counter
     pub(crate) fps_counter: header::FpsCounter,
+    /// Per-frame time allowance for message processing
+    pub(crate) message_budget: budget::MessageBudget,
+    /// PNG capture in progress (header "Screenshot" button)
+    pub(crate) screenshot: screenshot::ScreenshotState,
+    /// Network best slot sampled on the diagnostics tick (header staircase)
+    pub(crate) slot_history: header::SlotHistory,
     /// Event filter: [event_type] = enabled
     pub(crate) selected_events: Vec<bool>,
     /// Toggle event selector panel visibility
     pub(crate) show_event_selector: bool,
     /// Currently selected category index in the filter panel
     pub(crate) expanded_category: Option<usize>,
+    /// Event filter search text; non-empty limits the accordion to matching event names
+    pub(crate) event_search: String,
+    /// Reason filter pattern for error lists, as typed
+    pub(crate) reason_filter: String,
+    /// Compiled `reason_filter` (None when empty or invalid), rebuilt only when the text changes
+    pub(crate) reason_regex: Option<regex::Regex>,
+    /// Compile error for `reason_filter`, shown inline
+    pub(crate) reason_filter_error: Option<String>,
     /// Currently active tab
     pub(crate) active_tab: ActiveTab,
     /// Use CPU rendering (--use-cpu on native, fallback if no wgpu on WASM)
@@ -115,41 +429,233 @@ pub struct JamApp {
     /// Diagnostics: events/sec (computed each tick)
     pub(crate) diag_events_sec: f64,
     /// Diagnostics: node-reported drops (sum of Event::Dropped.num)
+    pub(crate) diag_node_dropped_total: u64,
+    /// Diagnostics: node-reported drops/sec (computed each tick)
+    pub(crate) diag_node_dropped_sec: f64,
+    /// Internal: node-reported drops since last rate sample
+    diag_node_dropped_counter: u64,
     /// Diagnostics: jamtart-side drops (detected via id gaps)
     pub(crate) diag_server_dropped_total: u64,
-    /// Diagnostics: total dropped/sec (both sources)
-    pub(crate) diag_dropped_sec: f64,
-    /// Internal: events since last 1-second tick
+    /// Diagnostics: jamtart-side drops/sec (computed each tick)
+    pub(crate) diag_server_dropped_sec: f64,
+    /// Internal: events since last rate sample
     diag_events_counter: u64,
-    /// Internal: drops since last 1-second tick
-    diag_dropped_counter: u64,
-    /// Internal: timestamp of last 1-second tick
+    /// Diagnostics: received WebSocket bytes/sec (computed each tick)
+    pub(crate) diag_rx_bytes_sec: f64,
+    /// Internal: jamtart-side drops since last rate sample
+    diag_server_dropped_counter: u64,
+    /// Diagnostics: events evicted from full EventStore ring buffers, total
+    pub(crate) diag_buffer_evicted_total: u64,
+    /// Diagnostics: buffer evictions/sec (computed each tick)
+    pub(crate) diag_buffer_evicted_sec: f64,
+    /// Internal: received bytes since last rate sample
+    diag_rx_bytes_counter: u64,
+    /// Internal: timestamp of last rate sample
     diag_last_tick: f64,
-    /// Internal: last seen data.id for gap detection
-    diag_last_event_id: Option<u64>,
+    /// Internal: timestamp of last 1-second housekeeping tick (stalls, forks, slot history)
+    housekeeping_last_tick: f64,
+    /// Seconds between diagnostics rate samples
+    pub(crate) diag_interval: f32,
+    /// Per-interval or moving-average diagnostics rates
+    pub(crate) diag_rate_mode: DiagRateMode,
+    /// Internal: recent sampling intervals for the moving average
+    diag_history: diagnostics::DiagHistory,
+    /// Internal: last seen data.id per endpoint index for gap detection, reset on reconnect
+    diag_last_event_id: diagnostics::EventIdTracker,
+    /// Diagnostics: codec of the most recently parsed event
+    pub(crate) diag_codec: Option<Codec>,
+    /// Diagnostics: total messages that failed to parse
+    pub(crate) diag_parse_errors_total: u64,
+    /// Diagnostics: parse errors/sec (computed each tick)
+    pub(crate) diag_parse_errors_sec: f64,
+    /// Internal: parse errors since last rate sample
+    diag_parse_errors_counter: u64,
+    /// Diagnostics: the last few failing messages and their errors
+    pub(crate) parse_error_log: diagnostics::ParseErrorLog,
+    /// When server-side event id gaps were detected, for the diagnostics gap timeline
+    pub(crate) gap_history: diagnostics::GapHistory,
+    /// Diagnostics: events of a type this build doesn't know (newer server schema)
+    pub(crate) diag_unknown_events_total: u64,
+    /// Diagnostics: events of a known type whose payload didn't decode (schema drift)
+    pub(crate) diag_partial_events_total: u64,
+    /// Consecutive ticks with parse errors above PARSE_ERROR_SPIKE_RATE
+    pub(crate) parse_error_spike_secs: u32,
+    /// Parse-error banner dismissed by the user (until the spike ends)
+    pub(crate) parse_error_banner_dismissed: bool,
+    /// Automatically clear data and reconnect on a sustained parse-error spike
+    pub(crate) auto_recover_enabled: bool,
+    /// Reset GPU ring buffers on the next frame (after clearing data)
+    pub(crate) ring_reset_pending: bool,
+    /// EventStore sequence cursor for incremental scatter uploads
+    pub(crate) scatter_upload_cursor: u64,
+    /// Clear the GPU scatter buffers before the next upload
+    pub(crate) scatter_reset_pending: bool,
+    /// Node index whose detail popup is open (set by clicking a node)
+    pub(crate) selected_node: Option<u16>,
+    /// Transient notifications for notable error events
+    pub(crate) toasts: toasts::ToastQueue,
+    /// Error types that raise a toast (user-configurable, subset of ERROR_EVENT_TYPES)
+    pub(crate) toast_event_types: Vec<EventType>,
+    /// Event shown in the inspector panel (set by double-clicking the scatter or a ring node)
+    pub(crate) inspected_event: Option<inspector::InspectedEvent>,
+    /// Solo filter: only show events to/from this node index (set by clicking a ring dot)
+    pub(crate) focused_node: Option<u16>,
+    /// Endpoints whose events are hidden from the ring, bit `id - 1` per source id
+    pub(crate) hidden_sources: u64,
+    /// Operator-provided node names: hex node_id → label
+    pub(crate) peer_labels: HashMap<String, String>,
+    /// Labels fetched in the background, moved into `peer_labels` by update() (WASM only)
+    #[cfg(target_arch = "wasm32")]
+    peer_labels_pending: Rc<RefCell<Option<HashMap<String, String>>>>,
     /// Active collapsing-pulse animations on the ring
     pub(crate) active_pulses: Vec<CollapsingPulse>,
+    /// Event types that emit a collapsing pulse instead of a radial particle
+    pub(crate) pulse_event_types: Vec<EventType>,
+    /// Lifetime of a collapsing pulse, seconds
+    pub(crate) pulse_duration: f32,
+    /// Starting radius of a collapsing pulse, pixels
+    pub(crate) pulse_radius: f32,
     /// Errors-only filter preset active
     pub(crate) errors_only: bool,
+    /// Ask the server for the selected event types only, instead of every event
+    pub(crate) server_filter_enabled: bool,
+    /// Subscription state for the server-side filter
+    pub(crate) server_filter: subscription::ServerFilter,
     /// Last known particle count (for header display)
     pub(crate) particle_count: usize,
     /// Last known particle capacity (for header display)
     pub(crate) particle_max: usize,
+    /// Dark or light UI; applied to the visuals and [`colors`] at the start of a frame
+    pub(crate) theme: Theme,
     /// Active color schema (selectable via header dropdown)
     pub(crate) color_schema: ColorSchema,
+    /// Palette edited in Settings; kept while a built-in schema is selected
+    pub(crate) custom_palette: [[f32; 4]; 13],
+    /// Color-blindness preview (display only, `color_lut` is left untouched)
+    pub(crate) cvd_mode: CvdMode,
+    /// Also preview `cvd_mode` on the GPU/CPU particles, not just legend and swatches
+    pub(crate) cvd_whole_view: bool,
     /// Dynamic color lookup table (recomputed on filter/schema change)
     pub(crate) color_lut: ColorLut,
     /// Show settings sidebar
     pub(crate) show_settings: bool,
     /// Slot pulse animation enabled
     pub(crate) slot_pulse_enabled: bool,
+    /// Bloom glow over the GPU ring (no-op on the CPU path)
+    pub(crate) ring_bloom: bool,
     /// Node brightness by peer count enabled
     pub(crate) node_brightness_enabled: bool,
     /// Particle speed factor (0.1 = 10x slow, 1.0 = normal, 2.0 = 2x fast)
     pub(crate) speed_factor: f32,
+    /// Directed-particle travel duration per event type, editable in Settings
+    pub(crate) travel_durations: TravelDurations,
+    /// Ring radius as a fraction of half the ring view height (NDC, shared with the shader)
+    pub(crate) ring_radius: f32,
+    /// Radius of the node dots on the ring, in pixels
+    pub(crate) node_dot_radius: f32,
+    /// Draw ring particles as short streaks with an afterglow
+    pub(crate) particle_trails: bool,
+    /// Trail length as a fraction of a particle's progress (0.05–1)
+    pub(crate) trail_length: f32,
+    /// Place ring nodes by a stable sort of label / peer id instead of arrival order
+    pub(crate) ring_order_stable: bool,
+    /// Derive node indices from a hash of the node id instead of arrival order,
+    /// so ring slots and scatter columns survive reconnects (applies after a clear)
+    pub(crate) hashed_node_indices: bool,
+    /// Cluster ring nodes into arcs by this key
+    pub(crate) ring_grouping: RingGrouping,
+    /// Draw node index labels just outside the ring
+    pub(crate) show_node_indices: bool,
+    /// Node → ring slot mapping drawn this frame by both ring paths; equal to `ring_target`
+    /// except while a transition runs (see `refresh_ring_layout`)
+    pub(crate) ring_layout: ring_layout::RingLayout,
+    /// Layout for the current node set and options; GPU particles are placed with it
+    pub(crate) ring_target: ring_layout::RingLayout,
+    /// Move from the previous layout in progress, if any
+    pub(crate) ring_transition: Option<ring_layout::RingTransition>,
+    /// Animate nodes to their new slots when the node set or order changes
+    pub(crate) ring_transitions: bool,
+    /// Ring particle clock, slowed while the pointer is over the ring
+    pub(crate) ring_clock: ring::HoverClock,
+    /// Speed reduction while hovering the ring: 0 = none, 1 = freeze
+    pub(crate) hover_slowdown: f32,
+    /// Event Rate panel mode
+    pub(crate) rate_mode: RateMode,
+    /// Smoothing of the per-node event-rate lines
+    pub(crate) rate_smoothing: RateSmoothing,
+    /// Buckets in the centered moving average (odd)
+    pub(crate) rate_smoothing_window: usize,
+    /// Weight of the newest bucket in exponential smoothing
+    pub(crate) rate_smoothing_alpha: f32,
+    /// Fit the event-rate Y axis to the visible data (otherwise use `rate_y_max`)
+    pub(crate) rate_y_autoscale: bool,
+    /// Manual event-rate Y ceiling, events/sec
+    pub(crate) rate_y_max: f32,
+    /// Plot event rates as ln(1 + rate)
+    pub(crate) rate_y_log: bool,
+    /// Gas panel plots each top service separately (otherwise all accumulation as one series)
+    pub(crate) gas_by_service: bool,
+    /// Node × event-type heatmap columns are whole categories
+    pub(crate) heatmap_by_category: bool,
+    /// Event type shown in the Reason Breakdown panel (one of REASON_EVENT_TYPES)
+    pub(crate) reason_event_type: EventType,
+    /// Seconds of history shown by the rate, error and analytics panels
+    pub(crate) time_window: f32,
+    /// Event Particles window when it overrides `time_window`, seconds
+    pub(crate) scatter_window: Option<f32>,
+    /// Seconds without any event before a node counts as stalled
+    pub(crate) stall_threshold: f32,
+    /// Seconds of events kept in the EventStore (applied before each prune)
+    pub(crate) retention_secs: f32,
+    /// Per-type ring buffer capacity of each node in the EventStore
+    pub(crate) max_events_per_node: usize,
+    /// Event Particles (GPU) are downsampled beyond this many points
+    pub(crate) scatter_particle_cap: usize,
+    /// Ring indices of stalled nodes (refreshed on the diagnostics tick)
+    pub(crate) stalled_nodes: Vec<u16>,
+    /// Validators disagreeing on the tip block (refreshed on the diagnostics tick)
+    pub(crate) fork: Option<Fork>,
+    /// Validators split into disconnected groups (refreshed on the diagnostics tick)
+    pub(crate) partitions: diagnostics::PartitionTracker,
+    /// Header sparkline: total events/sec per second over the last minute (newest last)
+    pub(crate) throughput_history: Vec<f64>,
+    /// `now_seconds()` of the last `throughput_history` refresh
+    pub(crate) throughput_refreshed_at: f64,
+    /// Show the category legend (bottom-left)
+    pub(crate) show_legend: bool,
+    /// Events in the time window per legend entry, and the single category they were
+    /// counted per event type for (None: per category)
+    pub(crate) legend_counts: (Option<usize>, Vec<usize>),
+    /// `now_seconds()` of the last `legend_counts` refresh
+    pub(crate) legend_counts_refreshed_at: f64,
+    /// Per-node series graphs (peers, shards): one hue per validator instead of white lines
+    pub(crate) peer_lines_colored: bool,
+    /// Peer count channels drawn: total, validator peers, sync peers
+    pub(crate) peer_channels: [bool; 3],
+    /// Peer count lines, min/median/max band, or both
+    pub(crate) peer_band: SeriesBand,
+    /// Ingestion paused: messages are buffered, visualizations frozen
+    pub(crate) paused: bool,
+    /// `now_seconds()` snapshot taken when pausing (frozen animation clock)
+    paused_at: f64,
+    /// `now_unix_seconds()` snapshot taken when pausing (frozen slot clock)
+    paused_at_unix: f64,
+    /// Messages dropped because the pause buffer hit PAUSE_BUFFER_CAP (native only;
+    /// the WASM buffer counts its own drops)
+    #[cfg(not(target_arch = "wasm32"))]
+    pause_overflow_total: u64,
+    /// Messages pulled off the channel while paused, drained first on resume (native only)
+    #[cfg(not(target_arch = "wasm32"))]
+    pause_backlog: VecDeque<(usize, WsMessage)>,
     /// Buffered WebSocket messages for time-budgeted processing (WASM only)
     #[cfg(target_arch = "wasm32")]
-    msg_buffer: Rc<RefCell<VecDeque<String>>>,
+    msg_buffer: Rc<RefCell<MessageQueue>>,
+    /// Consecutive housekeeping ticks with `msg_buffer` above `MSG_BACKLOG_WARN` (WASM only)
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) msg_backlog_secs: u32,
+    /// View state mirrored into the URL hash (WASM only)
+    #[cfg(target_arch = "wasm32")]
+    url_hash: deep_link::UrlHashSync,
 }
 
 // Helper macro to access data on both platforms
@@ -157,7 +663,8 @@ macro_rules! with_data {
     ($self:expr, |$data:ident| $body:expr) => {{
         #[cfg(target_arch = "wasm32")]
         {
-            let $data = $self.data.borrow();
+            let guard = $self.data.borrow();
+            let $data = &*guard;
             $body
         }
         #[cfg(not(target_arch = "wasm32"))]
@@ -189,7 +696,7 @@ impl JamApp {
     /// Create new app for WASM platform
     #[cfg(target_arch = "wasm32")]
     pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
-        cc.egui_ctx.set_visuals(minimal_visuals());
+        cc.egui_ctx.set_visuals(theme::visuals(Theme::Dark));
         load_custom_fonts(&cc.egui_ctx);
 
         // Register GPU renderers (wgpu backend on WASM via WebGPU)
@@ -225,38 +732,76 @@ impl JamApp {
                 (None, true) // fallback to CPU if wgpu unavailable
             };
 
-        let data = Rc::new(RefCell::new(SharedData {
-            time_series: TimeSeriesData::new(1024, 200),
-            blocks: BestBlockData::new(1024),
-            events: EventStore::new(50000, 60.0),
-            directed_buffer: DirectedEventBuffer::default(),
-            pulse_events: Vec::new(),
-        }));
+        let data = Rc::new(RefCell::new(SharedData::new()));
 
         let ws_state = Rc::new(RefCell::new(WsState::Connecting));
-        let msg_buffer: Rc<RefCell<VecDeque<String>>> =
-            Rc::new(RefCell::new(VecDeque::new()));
+        let msg_buffer = Rc::new(RefCell::new(MessageQueue::new(PAUSE_BUFFER_CAP)));
+
+        // Peer labels are optional; fetched in the background
+        let peer_labels_pending = Rc::new(RefCell::new(None));
+        let labels_url = js_sys::eval("window.__jam_labels_url")
+            .ok()
+            .and_then(|v| v.as_string())
+            .unwrap_or_else(|| "peer_labels.json".to_string());
+        labels::fetch_peer_labels(labels_url, peer_labels_pending.clone());
 
         // Connect WebSocket — messages buffered, drained in update()
         let ws_url = js_sys::eval("window.__jam_ws_url")
             .ok()
             .and_then(|v| v.as_string())
             .unwrap_or_else(|| DEFAULT_WS_URL.to_string());
-        let ws_client = WsClient::connect(
-            &ws_url,
-            msg_buffer.clone(),
-            ws_state.clone(),
-        )
-        .ok();
+        // Optional reconnect overrides, set by the page like the URL
+        let js_number = |name: &str| js_sys::eval(name).ok().and_then(|v| v.as_f64());
+        let defaults = Backoff::default();
+        let backoff = Backoff {
+            base_delay: js_number("window.__jam_reconnect_delay_ms")
+                .filter(|ms| ms.is_finite() && *ms > 0.0)
+                .map_or(defaults.base_delay, |ms| std::time::Duration::from_millis(ms as u64)),
+            max_attempts: js_number("window.__jam_reconnect_attempts")
+                .filter(|n| n.is_finite() && *n >= 1.0)
+                .map(|n| n as u32),
+            ..defaults
+        };
+        let ws_client = WsClient::connect(&ws_url, msg_buffer.clone(), ws_state.clone()).ok();
+        if let Some(client) = &ws_client {
+            *client.backoff.borrow_mut() = backoff.clone();
+        }
 
-        Self {
+        let mut app = Self {
             data,
             ws_state,
             ws_client,
+            connection_url_input: ws_url.clone(),
+            ws_url,
+            backoff,
+            show_connection_panel: false,
+            show_node_list: false,
+            show_sync_panel: false,
+            node_list_sort: node_list::NodeListSort::default(),
+            show_snapshots: false,
+            show_status_table: false,
+            show_event_log: false,
+            show_geo_map: false,
+            show_topology: false,
+            event_log: VecDeque::with_capacity(event_log::EVENT_LOG_LINES),
+            event_log_scroll_paused: false,
+            snapshots: snapshot::SnapshotPair::default(),
+            alert_rules: alerts::default_alert_rules(),
+            alert_sound: false,
+            alerts: alerts::AlertState::default(),
+            connection_error: None,
+            recent_urls: Vec::new(),
             fps_counter: header::FpsCounter::new(),
+            message_budget: budget::MessageBudget::default(),
+            screenshot: screenshot::ScreenshotState::default(),
+            slot_history: header::SlotHistory::default(),
             selected_events: Self::default_selected_events(),
             show_event_selector: false,
             expanded_category: None,
+            event_search: String::new(),
+            reason_filter: String::new(),
+            reason_regex: None,
+            reason_filter_error: None,
             active_tab: ActiveTab::default(),
             use_cpu,
             gpu_upload_cursor: 0,
@@ -265,30 +810,134 @@ impl JamApp {
             prev_color_schema: ColorSchema::default(),
             diag_events_total: 0,
             diag_events_sec: 0.0,
+            diag_node_dropped_total: 0,
+            diag_node_dropped_sec: 0.0,
+            diag_node_dropped_counter: 0,
             diag_server_dropped_total: 0,
-            diag_dropped_sec: 0.0,
+            diag_server_dropped_sec: 0.0,
+            diag_rx_bytes_sec: 0.0,
             diag_events_counter: 0,
-            diag_dropped_counter: 0,
+            diag_server_dropped_counter: 0,
+            diag_buffer_evicted_total: 0,
+            diag_buffer_evicted_sec: 0.0,
+            diag_rx_bytes_counter: 0,
             diag_last_tick: 0.0,
-            diag_last_event_id: None,
+            housekeeping_last_tick: 0.0,
+            diag_interval: DEFAULT_DIAG_INTERVAL,
+            diag_rate_mode: DiagRateMode::default(),
+            diag_history: diagnostics::DiagHistory::default(),
+            diag_last_event_id: diagnostics::EventIdTracker::default(),
+            diag_codec: None,
+            diag_parse_errors_total: 0,
+            diag_parse_errors_sec: 0.0,
+            diag_parse_errors_counter: 0,
+            parse_error_log: diagnostics::ParseErrorLog::default(),
+            gap_history: diagnostics::GapHistory::default(),
+            diag_unknown_events_total: 0,
+            diag_partial_events_total: 0,
+            parse_error_spike_secs: 0,
+            parse_error_banner_dismissed: false,
+            auto_recover_enabled: false,
+            ring_reset_pending: false,
+            scatter_upload_cursor: 0,
+            scatter_reset_pending: false,
+            selected_node: None,
+            toasts: toasts::ToastQueue::default(),
+            toast_event_types: toasts::DEFAULT_TOAST_TYPES.to_vec(),
+            inspected_event: None,
+            focused_node: None,
+            hidden_sources: 0,
+            peer_labels: HashMap::new(),
+            peer_labels_pending,
             active_pulses: Vec::new(),
+            pulse_event_types: DEFAULT_PULSE_TYPES.to_vec(),
+            pulse_duration: DEFAULT_PULSE_DURATION,
+            pulse_radius: DEFAULT_PULSE_RADIUS,
             errors_only: false,
+            server_filter_enabled: false,
+            server_filter: subscription::ServerFilter::default(),
             particle_count: 0,
             particle_max: 0,
+            theme: Theme::default(),
             color_schema: ColorSchema::default(),
+            custom_palette: *ColorSchema::default().colors(),
+            cvd_mode: CvdMode::Off,
+            cvd_whole_view: false,
             color_lut: build_color_lut(&Self::default_selected_events(), ColorSchema::default()),
             show_settings: false,
             slot_pulse_enabled: true,
+            ring_bloom: false,
             node_brightness_enabled: true,
             speed_factor: 1.0,
+            travel_durations: default_travel_durations(),
+            ring_radius: DEFAULT_RING_RADIUS,
+            particle_trails: false,
+            trail_length: DEFAULT_TRAIL_LENGTH,
+            node_dot_radius: DEFAULT_NODE_DOT_RADIUS,
+            ring_order_stable: false,
+            hashed_node_indices: false,
+            ring_grouping: RingGrouping::default(),
+            show_node_indices: false,
+            ring_layout: ring_layout::RingLayout::default(),
+            ring_target: ring_layout::RingLayout::default(),
+            ring_transition: None,
+            ring_transitions: true,
+            ring_clock: ring::HoverClock::default(),
+            hover_slowdown: DEFAULT_HOVER_SLOWDOWN,
+            rate_mode: RateMode::default(),
+            rate_smoothing: RateSmoothing::default(),
+            rate_smoothing_window: DEFAULT_RATE_SMOOTHING_WINDOW,
+            rate_smoothing_alpha: DEFAULT_RATE_SMOOTHING_ALPHA,
+            rate_y_autoscale: true,
+            rate_y_max: DEFAULT_RATE_Y_MAX,
+            rate_y_log: false,
+            gas_by_service: true,
+            heatmap_by_category: false,
+            reason_event_type: EventType::ConnectInFailed,
+            time_window: DEFAULT_TIME_WINDOW,
+            scatter_window: Some(DEFAULT_SCATTER_WINDOW),
+            stall_threshold: DEFAULT_STALL_THRESHOLD,
+            retention_secs: DEFAULT_RETENTION_SECS,
+            max_events_per_node: DEFAULT_MAX_EVENTS_PER_NODE,
+            scatter_particle_cap: DEFAULT_SCATTER_PARTICLE_CAP,
+            stalled_nodes: Vec::new(),
+            throughput_history: Vec::new(),
+            throughput_refreshed_at: 0.0,
+            legend_counts: (None, Vec::new()),
+            legend_counts_refreshed_at: 0.0,
+            fork: None,
+            partitions: diagnostics::PartitionTracker::default(),
+            show_legend: true,
+            peer_lines_colored: true,
+            peer_channels: [true, false, false],
+            peer_band: SeriesBand::Lines,
+            paused: false,
+            paused_at: 0.0,
+            paused_at_unix: 0.0,
             msg_buffer,
-        }
+            msg_backlog_secs: 0,
+            url_hash: deep_link::UrlHashSync::default(),
+        };
+        app.restore_settings(cc.storage);
+        app.apply_url_hash();
+        app
     }
 
     /// Create new app for native platform
     #[cfg(not(target_arch = "wasm32"))]
-    pub fn new(cc: &eframe::CreationContext<'_>, use_cpu: bool) -> Self {
-        cc.egui_ctx.set_visuals(minimal_visuals());
+    pub fn new(cc: &eframe::CreationContext<'_>, options: LaunchOptions) -> Self {
+        let LaunchOptions {
+            use_cpu,
+            ws_urls,
+            recorder,
+            replay,
+            peer_labels,
+            export_csv_on_exit,
+            live_metrics,
+            stats,
+            backoff,
+        } = options;
+        cc.egui_ctx.set_visuals(theme::visuals(Theme::Dark));
         load_custom_fonts(&cc.egui_ctx);
         let mut style = (*cc.egui_ctx.style()).clone();
         for (_text_style, font_id) in style.text_styles.iter_mut() {
@@ -334,27 +983,65 @@ impl JamApp {
             None
         };
 
-        let data = SharedData {
-            time_series: TimeSeriesData::new(1024, 200),
-            blocks: BestBlockData::new(1024),
-            events: EventStore::new(50000, 60.0),
-            directed_buffer: DirectedEventBuffer::default(),
-            pulse_events: Vec::new(),
-        };
+        let data = SharedData::new();
 
-        let ws_url = std::env::var("JAMTART_WS").unwrap_or_else(|_| DEFAULT_WS_URL.to_string());
-        info!(url = %ws_url, env_set = std::env::var("JAMTART_WS").is_ok(), "WebSocket URL resolved");
-        let ws_client = NativeWsClient::connect(&ws_url);
-        let ws_state = ws_client.state.clone();
+        let ws_url = ws_urls.first().cloned().unwrap_or_else(|| DEFAULT_WS_URL.to_string());
+        // Replay replaces the live sockets entirely
+        let (endpoints, ws_state) = if replay.is_some() {
+            (Vec::new(), Arc::new(Mutex::new(WsState::Replaying)))
+        } else {
+            info!(urls = ?ws_urls, env_set = std::env::var("JAMTART_WS").is_ok(), "WebSocket URLs resolved");
+            let endpoints = endpoints::connect_endpoints(&ws_urls, recorder.as_ref(), &backoff);
+            let state = match endpoints.first() {
+                Some(first) => first.client.state.clone(),
+                None => Arc::new(Mutex::new(WsState::Disconnected)),
+            };
+            (endpoints, state)
+        };
 
-        Self {
+        let mut app = Self {
             data,
             ws_state,
-            ws_client: Some(ws_client),
+            endpoints,
+            connection_url_input: ws_url.clone(),
+            ws_url,
+            ws_urls,
+            backoff,
+            recorder,
+            replay,
+            export_csv_on_exit,
+            live_metrics,
+            stats,
+            stats_last_emit: 0.0,
+            geoip: None,
+            show_connection_panel: false,
+            show_node_list: false,
+            show_sync_panel: false,
+            node_list_sort: node_list::NodeListSort::default(),
+            show_snapshots: false,
+            show_status_table: false,
+            show_event_log: false,
+            show_geo_map: false,
+            show_topology: false,
+            event_log: VecDeque::with_capacity(event_log::EVENT_LOG_LINES),
+            event_log_scroll_paused: false,
+            snapshots: snapshot::SnapshotPair::default(),
+            alert_rules: alerts::default_alert_rules(),
+            alert_sound: false,
+            alerts: alerts::AlertState::default(),
+            connection_error: None,
+            recent_urls: Vec::new(),
             fps_counter: header::FpsCounter::new(),
+            message_budget: budget::MessageBudget::default(),
+            screenshot: screenshot::ScreenshotState::default(),
+            slot_history: header::SlotHistory::default(),
             selected_events: Self::default_selected_events(),
             show_event_selector: false,
             expanded_category: None,
+            event_search: String::new(),
+            reason_filter: String::new(),
+            reason_regex: None,
+            reason_filter_error: None,
             active_tab: ActiveTab::default(),
             use_cpu,
             gpu_upload_cursor: 0,
@@ -363,28 +1050,120 @@ impl JamApp {
             prev_color_schema: ColorSchema::default(),
             diag_events_total: 0,
             diag_events_sec: 0.0,
+            diag_node_dropped_total: 0,
+            diag_node_dropped_sec: 0.0,
+            diag_node_dropped_counter: 0,
             diag_server_dropped_total: 0,
-            diag_dropped_sec: 0.0,
+            diag_server_dropped_sec: 0.0,
+            diag_rx_bytes_sec: 0.0,
             diag_events_counter: 0,
-            diag_dropped_counter: 0,
+            diag_server_dropped_counter: 0,
+            diag_buffer_evicted_total: 0,
+            diag_buffer_evicted_sec: 0.0,
+            diag_rx_bytes_counter: 0,
             diag_last_tick: 0.0,
-            diag_last_event_id: None,
+            housekeeping_last_tick: 0.0,
+            diag_interval: DEFAULT_DIAG_INTERVAL,
+            diag_rate_mode: DiagRateMode::default(),
+            diag_history: diagnostics::DiagHistory::default(),
+            diag_last_event_id: diagnostics::EventIdTracker::default(),
+            diag_codec: None,
+            diag_parse_errors_total: 0,
+            diag_parse_errors_sec: 0.0,
+            diag_parse_errors_counter: 0,
+            parse_error_log: diagnostics::ParseErrorLog::default(),
+            gap_history: diagnostics::GapHistory::default(),
+            diag_unknown_events_total: 0,
+            diag_partial_events_total: 0,
+            parse_error_spike_secs: 0,
+            parse_error_banner_dismissed: false,
+            auto_recover_enabled: false,
+            ring_reset_pending: false,
+            scatter_upload_cursor: 0,
+            scatter_reset_pending: false,
+            selected_node: None,
+            toasts: toasts::ToastQueue::default(),
+            toast_event_types: toasts::DEFAULT_TOAST_TYPES.to_vec(),
+            inspected_event: None,
+            focused_node: None,
+            hidden_sources: 0,
+            peer_labels,
             active_pulses: Vec::new(),
+            pulse_event_types: DEFAULT_PULSE_TYPES.to_vec(),
+            pulse_duration: DEFAULT_PULSE_DURATION,
+            pulse_radius: DEFAULT_PULSE_RADIUS,
             errors_only: false,
+            server_filter_enabled: false,
+            server_filter: subscription::ServerFilter::default(),
             particle_count: 0,
             particle_max: 0,
+            theme: Theme::default(),
             color_schema: ColorSchema::default(),
+            custom_palette: *ColorSchema::default().colors(),
+            cvd_mode: CvdMode::Off,
+            cvd_whole_view: false,
             color_lut: build_color_lut(&Self::default_selected_events(), ColorSchema::default()),
             show_settings: false,
             slot_pulse_enabled: true,
+            ring_bloom: false,
             node_brightness_enabled: true,
             speed_factor: 1.0,
-        }
+            travel_durations: default_travel_durations(),
+            ring_radius: DEFAULT_RING_RADIUS,
+            particle_trails: false,
+            trail_length: DEFAULT_TRAIL_LENGTH,
+            node_dot_radius: DEFAULT_NODE_DOT_RADIUS,
+            ring_order_stable: false,
+            hashed_node_indices: false,
+            ring_grouping: RingGrouping::default(),
+            show_node_indices: false,
+            ring_layout: ring_layout::RingLayout::default(),
+            ring_target: ring_layout::RingLayout::default(),
+            ring_transition: None,
+            ring_transitions: true,
+            ring_clock: ring::HoverClock::default(),
+            hover_slowdown: DEFAULT_HOVER_SLOWDOWN,
+            rate_mode: RateMode::default(),
+            rate_smoothing: RateSmoothing::default(),
+            rate_smoothing_window: DEFAULT_RATE_SMOOTHING_WINDOW,
+            rate_smoothing_alpha: DEFAULT_RATE_SMOOTHING_ALPHA,
+            rate_y_autoscale: true,
+            rate_y_max: DEFAULT_RATE_Y_MAX,
+            rate_y_log: false,
+            gas_by_service: true,
+            heatmap_by_category: false,
+            reason_event_type: EventType::ConnectInFailed,
+            time_window: DEFAULT_TIME_WINDOW,
+            scatter_window: Some(DEFAULT_SCATTER_WINDOW),
+            stall_threshold: DEFAULT_STALL_THRESHOLD,
+            retention_secs: DEFAULT_RETENTION_SECS,
+            max_events_per_node: DEFAULT_MAX_EVENTS_PER_NODE,
+            scatter_particle_cap: DEFAULT_SCATTER_PARTICLE_CAP,
+            stalled_nodes: Vec::new(),
+            throughput_history: Vec::new(),
+            throughput_refreshed_at: 0.0,
+            legend_counts: (None, Vec::new()),
+            legend_counts_refreshed_at: 0.0,
+            fork: None,
+            partitions: diagnostics::PartitionTracker::default(),
+            show_legend: true,
+            peer_lines_colored: true,
+            peer_channels: [true, false, false],
+            peer_band: SeriesBand::Lines,
+            paused: false,
+            paused_at: 0.0,
+            paused_at_unix: 0.0,
+            pause_overflow_total: 0,
+            pause_backlog: VecDeque::new(),
+        };
+        app.restore_settings(cc.storage);
+        app.restore_window_state(cc.storage);
+        app
     }
 
     pub(crate) fn default_selected_events() -> Vec<bool> {
-        // Enable all events by default
-        vec![true; 200]
+        // Enable all events by default (one slot per discriminant, including Unknown = 255)
+        vec![true; 256]
     }
 
     /// Build a [u64; 4] bitfield from selected_events for DirectedEventBuffer filtering
@@ -410,90 +1189,580 @@ impl JamApp {
         self.errors_only = true;
     }
 
+    /// Place peers on the peer map with this GeoIP database (`--geoip`); opens the map
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn set_geoip(&mut self, geoip: GeoIp) {
+        self.geoip = Some(geoip);
+        self.show_geo_map = true;
+    }
+
+    /// Show and subscribe to only `types` (`--events`); overrides the restored filter
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn apply_event_selection(&mut self, types: &[EventType]) {
+        self.selected_events.fill(false);
+        for &et in types {
+            self.selected_events[et.idx()] = true;
+        }
+        self.errors_only = false;
+        self.server_filter_enabled = true;
+        self.color_lut = build_color_lut(&self.selected_events, self.color_schema);
+    }
+
     /// Restore all events and clear errors-only mode.
     pub(crate) fn apply_all_filter(&mut self) {
         self.selected_events.fill(true);
         self.errors_only = false;
     }
 
-    /// Track parse result for diagnostics (jamtart-side gap detection only)
-    fn track_parse_result(&mut self, result: &ParseResult) {
+    /// Track parse result for diagnostics (node-reported drops and jamtart-side gap detection).
+    /// `gap` is the server-side drop count from `data.id`, taken in message order by the caller.
+    fn track_parse_result(&mut self, result: &ParseResult, gap: u64) {
         self.diag_events_counter += 1;
-        // Server-side gap detection via data.id
-        if let Some(id) = result.event_id {
-            if let Some(last_id) = self.diag_last_event_id {
-                let gap = id.saturating_sub(last_id).saturating_sub(1);
-                if gap > 0 {
-                    self.diag_dropped_counter += gap;
-                    self.diag_server_dropped_total += gap;
-                }
-            }
-            self.diag_last_event_id = Some(id);
+        self.diag_node_dropped_counter += result.node_dropped;
+        if gap > 0 {
+            self.diag_server_dropped_counter += gap;
+            self.diag_server_dropped_total += gap;
+            self.gap_history.record(now_seconds(), gap);
+        }
+        self.diag_codec = Some(result.codec);
+        if result.event_type == EventType::Unknown {
+            self.diag_unknown_events_total += 1;
+        }
+        if result.partial {
+            self.diag_partial_events_total += 1;
+        }
+        self.notify_error_event(result.event_type, result.node_index);
+    }
+
+    /// Keep samples of this frame's parse failures (at most `PARSE_ERROR_SAMPLES`)
+    fn record_parse_failures(&mut self, failures: Vec<(ParseError, String)>) {
+        let now = now_seconds();
+        for (error, payload) in failures {
+            self.parse_error_log.record(error, payload, now);
         }
     }
 
     /// Process incoming WebSocket messages (native)
     #[cfg(not(target_arch = "wasm32"))]
     fn process_messages(&mut self) {
-        // Time-budget message processing: yield after ~12ms to maintain 60fps.
+        // Time-budget message processing: yield once the frame's allowance is used up.
         // Remaining messages stay in the channel for the next frame.
         use std::time::{Duration, Instant};
-        const BUDGET: Duration = Duration::from_millis(12);
-        let deadline = Instant::now() + BUDGET;
+        let budget = Duration::from_secs_f64(self.message_budget.budget_ms() / 1000.0);
+        if self.replay.is_some() {
+            self.process_replay(budget);
+            return;
+        }
+        if self.paused {
+            // Keep the channels drained into a bounded backlog so nothing piles up unbounded
+            for (index, endpoint) in self.endpoints.iter().enumerate() {
+                self.pause_backlog.extend(endpoint.client.rx.try_iter().map(|msg| (index, msg)));
+            }
+            let overflow = self.pause_backlog.len().saturating_sub(PAUSE_BUFFER_CAP);
+            self.pause_backlog.drain(..overflow);
+            self.pause_overflow_total += overflow as u64;
+            return;
+        }
+
+        let deadline = Instant::now() + budget;
         let mut results = Vec::new();
-        if let Some(ref client) = self.ws_client {
-            while let Ok(msg) = client.rx.try_recv() {
-                let now = now_seconds();
-                let d = &mut self.data;
-                let mut ctx = ParserContext {
-                    time_series: &mut d.time_series,
-                    blocks: &mut d.blocks,
-                    events: &mut d.events,
-                    directed_buffer: &mut d.directed_buffer,
-                    pulse_events: &mut d.pulse_events,
-                };
-                if let Some(result) = parse_event(&msg, &mut ctx, now) {
-                    results.push(result);
+        let mut rx_bytes = 0u64;
+        let mut parse_errors = 0u64;
+        let mut failures = Vec::new();
+        let typed_filter = self.server_filter.is_typed();
+        let mut server_error = false;
+        let log_filter = self.show_event_log.then_some(self.selected_events.as_slice());
+        let mut logged = Vec::new();
+        // Backlog from a previous pause goes first to keep ordering, then the endpoints
+        // take turns so a busy one can't starve the others within the frame budget
+        let endpoint_count = self.endpoints.len();
+        let mut turn = 0;
+        let mut idle = 0;
+        while idle < endpoint_count || !self.pause_backlog.is_empty() {
+            let (index, msg) = match self.pause_backlog.pop_front() {
+                Some(queued) => queued,
+                None => {
+                    let index = turn % endpoint_count;
+                    turn += 1;
+                    match self.endpoints[index].client.rx.try_recv() {
+                        Ok(msg) => {
+                            idle = 0;
+                            (index, msg)
+                        }
+                        Err(_) => {
+                            idle += 1;
+                            continue;
+                        }
+                    }
                 }
-                if Instant::now() >= deadline {
-                    break;
+            };
+            if let WsMessage::Connected = msg {
+                // A fresh subscription restarts the id sequence used for gap detection
+                self.diag_last_event_id.reset(index);
+                continue;
+            }
+            rx_bytes += msg.as_bytes().len() as u64;
+            let now = now_seconds();
+            let d = &mut self.data;
+            let mut ctx = ParserContext {
+                time_series: &mut d.time_series,
+                blocks: &mut d.blocks,
+                guarantee_queues: &mut d.guarantee_queues,
+                sync_status: &mut d.sync_status,
+                peers: &mut d.peers,
+                shards: &mut d.shards,
+                preimages: &mut d.preimages,
+                events: &mut d.events,
+                directed_buffer: &mut d.directed_buffer,
+                pulse_events: &mut d.pulse_events,
+                assurances: &mut d.assurances,
+                latest_status: &mut d.latest_status,
+                tickets: &mut d.tickets,
+                topology: &mut d.topology,
+                travel_durations: &self.travel_durations,
+                pulse_types: &self.pulse_event_types,
+                source: self.endpoints[index].source.as_deref(),
+                source_id: self.endpoints[index].id,
+                stored_types: None,
+            };
+            match parse_ws_message(&msg, &mut ctx, now) {
+                Ok(Some(result)) => {
+                    logged.extend(event_log::capture_log_event(log_filter, &d.events, &result));
+                    // Each endpoint numbers its own events
+                    let gap = result.event_id.map_or(0, |id| self.diag_last_event_id.gap(index, id));
+                    results.push((result, gap));
+                }
+                Ok(None) if typed_filter => {
+                    let text = std::str::from_utf8(msg.as_bytes()).unwrap_or_default();
+                    server_error |= is_error_reply(text);
                 }
+                Ok(None) => {}
+                Err(e) => {
+                    parse_errors += 1;
+                    if failures.len() < PARSE_ERROR_SAMPLES {
+                        failures.push((e, payload_preview(msg.as_bytes())));
+                    }
+                }
+            }
+            if Instant::now() >= deadline {
+                break;
             }
         }
-        for result in &results {
-            self.track_parse_result(result);
+        self.diag_rx_bytes_counter += rx_bytes;
+        self.diag_parse_errors_counter += parse_errors;
+        self.record_parse_failures(failures);
+        self.append_event_log(logged);
+        for (result, gap) in &results {
+            self.track_parse_result(result, *gap);
+        }
+        if server_error {
+            self.note_server_error();
         }
     }
 
     /// Process buffered WebSocket messages (WASM)
     #[cfg(target_arch = "wasm32")]
     fn process_messages(&mut self) {
-        const BUDGET_MS: f64 = 12.0;
-        let deadline = js_sys::Date::now() + BUDGET_MS;
+        if self.paused {
+            // Messages keep accumulating in msg_buffer, which drops the oldest once full
+            return;
+        }
+
+        let deadline = js_sys::Date::now() + self.message_budget.budget_ms();
         let mut results = Vec::new();
+        let mut rx_bytes = 0u64;
+        let mut parse_errors = 0u64;
+        let mut failures = Vec::new();
+        let typed_filter = self.server_filter.is_typed();
+        let mut server_error = false;
+        let log_filter = self.show_event_log.then_some(self.selected_events.as_slice());
+        let mut logged = Vec::new();
         {
             let mut buf = self.msg_buffer.borrow_mut();
             let mut data = self.data.borrow_mut();
             let d = &mut *data;
             while let Some(msg) = buf.pop_front() {
+                if let WsMessage::Connected = msg {
+                    // A fresh subscription restarts the id sequence used for gap detection
+                    self.diag_last_event_id.reset(0);
+                    continue;
+                }
+                rx_bytes += msg.as_bytes().len() as u64;
                 let now = now_seconds();
                 let mut ctx = ParserContext {
                     time_series: &mut d.time_series,
                     blocks: &mut d.blocks,
+                    guarantee_queues: &mut d.guarantee_queues,
+                    sync_status: &mut d.sync_status,
+                    peers: &mut d.peers,
+                    shards: &mut d.shards,
+                    preimages: &mut d.preimages,
                     events: &mut d.events,
                     directed_buffer: &mut d.directed_buffer,
                     pulse_events: &mut d.pulse_events,
+                    assurances: &mut d.assurances,
+                    latest_status: &mut d.latest_status,
+                    tickets: &mut d.tickets,
+                    topology: &mut d.topology,
+                    travel_durations: &self.travel_durations,
+                    pulse_types: &self.pulse_event_types,
+                    source: None,
+                    source_id: 0,
+                    stored_types: None,
                 };
-                if let Some(result) = parse_event(&msg, &mut ctx, now) {
-                    results.push(result);
+                match parse_ws_message(&msg, &mut ctx, now) {
+                    Ok(Some(result)) => {
+                        logged.extend(event_log::capture_log_event(log_filter, &d.events, &result));
+                        let gap = result.event_id.map_or(0, |id| self.diag_last_event_id.gap(0, id));
+                        results.push((result, gap));
+                    }
+                    Ok(None) if typed_filter => {
+                        let text = std::str::from_utf8(msg.as_bytes()).unwrap_or_default();
+                        server_error |= is_error_reply(text);
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        parse_errors += 1;
+                        if failures.len() < PARSE_ERROR_SAMPLES {
+                            failures.push((e, payload_preview(msg.as_bytes())));
+                        }
+                    }
                 }
                 if js_sys::Date::now() >= deadline {
                     break;
                 }
             }
         }
-        for result in &results {
-            self.track_parse_result(result);
+        self.diag_rx_bytes_counter += rx_bytes;
+        self.diag_parse_errors_counter += parse_errors;
+        self.record_parse_failures(failures);
+        self.append_event_log(logged);
+        for (result, gap) in &results {
+            self.track_parse_result(result, *gap);
+        }
+        if server_error {
+            self.note_server_error();
+        }
+    }
+
+    /// Feed due messages from the replay source through the parser (native)
+    #[cfg(not(target_arch = "wasm32"))]
+    fn process_replay(&mut self, budget: std::time::Duration) {
+        let deadline = std::time::Instant::now() + budget;
+        let now = now_seconds();
+        // Playback speed follows the particle speed factor; pausing stops the playback clock
+        let speed = if self.paused { 0.0 } else { self.speed_factor as f64 };
+        let wrapped = self.replay.as_mut().is_some_and(|r| r.advance(now, speed));
+        if wrapped {
+            self.clear_data();
+        }
+        if self.paused {
+            return;
+        }
+
+        let mut results = Vec::new();
+        let mut rx_bytes = 0u64;
+        let mut parse_errors = 0u64;
+        let mut failures = Vec::new();
+        let log_filter = self.show_event_log.then_some(self.selected_events.as_slice());
+        let mut logged = Vec::new();
+        if let Some(replay) = self.replay.as_mut() {
+            while let Some((msg, source, ago)) = replay.next_due() {
+                rx_bytes += msg.as_bytes().len() as u64;
+                let source_id = source.map_or(0, endpoints::source_id);
+                let d = &mut self.data;
+                let mut ctx = ParserContext {
+                    time_series: &mut d.time_series,
+                    blocks: &mut d.blocks,
+                    guarantee_queues: &mut d.guarantee_queues,
+                    sync_status: &mut d.sync_status,
+                    peers: &mut d.peers,
+                    shards: &mut d.shards,
+                    preimages: &mut d.preimages,
+                    events: &mut d.events,
+                    directed_buffer: &mut d.directed_buffer,
+                    pulse_events: &mut d.pulse_events,
+                    assurances: &mut d.assurances,
+                    latest_status: &mut d.latest_status,
+                    tickets: &mut d.tickets,
+                    topology: &mut d.topology,
+                    travel_durations: &self.travel_durations,
+                    pulse_types: &self.pulse_event_types,
+                    // Keep the networks of a multi-endpoint recording apart, as live
+                    source,
+                    source_id,
+                    stored_types: None,
+                };
+                // Stamp with when the event would have arrived, so ages stay correct after a seek
+                match parse_ws_message(msg, &mut ctx, now - ago / speed.max(0.1)) {
+                    Ok(Some(result)) => {
+                        logged.extend(event_log::capture_log_event(log_filter, &d.events, &result));
+                        // Each endpoint numbers its own events
+                        let endpoint = source_id as usize;
+                        let gap = result.event_id.map_or(0, |id| self.diag_last_event_id.gap(endpoint, id));
+                        results.push((result, gap));
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        parse_errors += 1;
+                        if failures.len() < PARSE_ERROR_SAMPLES {
+                            failures.push((e, payload_preview(msg.as_bytes())));
+                        }
+                    }
+                }
+                if std::time::Instant::now() >= deadline {
+                    break;
+                }
+            }
+        }
+        self.diag_rx_bytes_counter += rx_bytes;
+        self.diag_parse_errors_counter += parse_errors;
+        self.record_parse_failures(failures);
+        self.append_event_log(logged);
+        for (result, gap) in &results {
+            self.track_parse_result(result, *gap);
+        }
+    }
+
+    /// Jump the replay to `t` seconds, rebuilding one retention window of history (native)
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn seek_replay(&mut self, t: f64) {
+        self.clear_data();
+        if let Some(replay) = self.replay.as_mut() {
+            replay.seek(t, self.retention_secs as f64);
+        }
+    }
+
+    /// Hand the index setting to the (empty) data stores
+    pub(crate) fn apply_node_indexing(&mut self) {
+        let mode = if self.hashed_node_indices {
+            NodeIndexing::Hashed
+        } else {
+            NodeIndexing::Arrival
+        };
+        #[cfg(target_arch = "wasm32")]
+        self.data.borrow_mut().set_node_indexing(mode);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.data.set_node_indexing(mode);
+    }
+
+    /// Drop all collected telemetry so visualizations restart from scratch.
+    pub(crate) fn clear_data(&mut self) {
+        #[cfg(target_arch = "wasm32")]
+        {
+            *self.data.borrow_mut() = SharedData::new();
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.data = SharedData::new();
+        }
+        self.apply_node_indexing();
+        self.apply_hidden_sources();
+        self.gpu_upload_cursor = 0;
+        self.ring_reset_pending = true;
+        self.scatter_upload_cursor = 0;
+        self.scatter_reset_pending = true;
+        self.active_pulses.clear();
+        self.selected_node = None;
+        self.inspected_event = None;
+        self.toasts.clear();
+        self.event_log.clear();
+        self.focused_node = None;
+        self.stalled_nodes.clear();
+        self.alerts.clear();
+        self.fork = None;
+        self.partitions = diagnostics::PartitionTracker::default();
+        self.slot_history.clear();
+        self.ring_layout = ring_layout::RingLayout::default();
+        self.ring_target = ring_layout::RingLayout::default();
+        self.ring_transition = None;
+        self.diag_buffer_evicted_total = 0;
+        self.gap_history.clear();
+        self.diag_buffer_evicted_sec = 0.0;
+        self.diag_last_event_id.clear();
+        self.parse_error_spike_secs = 0;
+        self.parse_error_banner_dismissed = false;
+    }
+
+    /// Re-open the WebSocket connections to the current endpoints (native)
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn reconnect(&mut self) {
+        info!(urls = ?self.ws_urls, "Reconnecting WebSocket");
+        // Dropping the old clients signals their background threads to stop
+        self.endpoints.clear();
+        self.pause_backlog.clear();
+        // Connecting to a live endpoint ends any replay
+        self.replay = None;
+        if let Some(ref recorder) = self.recorder {
+            recorder.mark_source(&self.ws_urls.join(","));
+        }
+        self.endpoints =
+            endpoints::connect_endpoints(&self.ws_urls, self.recorder.as_ref(), &self.backoff);
+        if let Some(first) = self.endpoints.first() {
+            self.ws_state = first.client.state.clone();
+        }
+        self.server_filter.reset();
+    }
+
+    /// Re-open the WebSocket connection to the current endpoint (WASM)
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn reconnect(&mut self) {
+        info!(url = %self.ws_url, "Reconnecting WebSocket");
+        // Dropping the old client detaches its callbacks and closes the socket
+        self.ws_client = None;
+        self.msg_buffer.borrow_mut().clear();
+        // Fresh state cell so late callbacks from the old socket can't overwrite it
+        self.ws_state = Rc::new(RefCell::new(WsState::Connecting));
+        match WsClient::connect(&self.ws_url, self.msg_buffer.clone(), self.ws_state.clone()) {
+            Ok(client) => {
+                *client.backoff.borrow_mut() = self.backoff.clone();
+                self.ws_client = Some(client);
+            }
+            Err(e) => *self.ws_state.borrow_mut() = WsState::Error(format!("{:?}", e)),
+        }
+        self.server_filter.reset();
+    }
+
+    /// Set or clear the solo-node filter. The GPU ring and scatter are re-uploaded from scratch
+    /// since their incremental buffers only hold particles matching the old focus.
+    pub(crate) fn set_focused_node(&mut self, focus: Option<u16>) {
+        if focus == self.focused_node {
+            return;
+        }
+        info!(?focus, "Node focus changed");
+        self.focused_node = focus;
+        self.gpu_upload_cursor = 0;
+        self.ring_reset_pending = true;
+        self.scatter_upload_cursor = 0;
+        self.scatter_reset_pending = true;
+    }
+
+    /// Whether events from source `id` are drawn (untagged events always are)
+    pub(crate) fn source_visible(&self, id: u8) -> bool {
+        !crate::core::data::source_hidden(self.hidden_sources, id)
+    }
+
+    /// Hand the hidden sources to the event store, which leaves them out of its queries
+    fn apply_hidden_sources(&mut self) {
+        #[cfg(target_arch = "wasm32")]
+        self.data.borrow_mut().events.set_hidden_sources(self.hidden_sources);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.data.events.set_hidden_sources(self.hidden_sources);
+    }
+
+    pub(crate) fn set_source_hidden(&mut self, id: u8, hidden: bool) {
+        if id == 0 {
+            return;
+        }
+        let bit = 1 << (id - 1);
+        if hidden {
+            self.hidden_sources |= bit;
+        } else {
+            self.hidden_sources &= !bit;
+        }
+        info!(source_id = id, hidden, "Source visibility changed");
+        self.apply_hidden_sources();
+        // Re-upload the ring and scatter without (or with) the source's events
+        self.gpu_upload_cursor = 0;
+        self.ring_reset_pending = true;
+        self.scatter_upload_cursor = 0;
+        self.scatter_reset_pending = true;
+    }
+
+    /// Recovery action: wipe collected data and re-open the WebSocket.
+    pub(crate) fn clear_and_reconnect(&mut self) {
+        self.clear_data();
+        self.reconnect();
+    }
+
+    /// Parse errors have stayed above the spike rate long enough to offer recovery
+    pub(crate) fn parse_error_spike_active(&self) -> bool {
+        self.parse_error_spike_secs >= PARSE_ERROR_SPIKE_SECS
+    }
+
+    /// Pause or resume ingestion. While paused the socket stays open and messages are buffered.
+    pub(crate) fn set_paused(&mut self, paused: bool) {
+        if paused == self.paused {
+            return;
+        }
+        if paused {
+            self.paused_at = now_seconds();
+            self.paused_at_unix = crate::time::now_unix_seconds();
+        }
+        self.paused = paused;
+        info!(paused, "Ingestion pause toggled");
+    }
+
+    /// Width of one event-rate bucket: the time window spread over `RATE_BUCKETS`
+    pub(crate) fn rate_bucket_secs(&self) -> f64 {
+        self.time_window as f64 / RATE_BUCKETS as f64
+    }
+
+    /// Age range of the Event Particles panel: its own window if set, else the shared one
+    pub(crate) fn scatter_max_age(&self) -> f64 {
+        self.scatter_window.unwrap_or(self.time_window) as f64
+    }
+
+    /// Animation clock: `now_seconds()`, frozen while paused
+    pub(crate) fn view_now(&self) -> f64 {
+        if self.paused {
+            self.paused_at
+        } else {
+            now_seconds()
+        }
+    }
+
+    /// Slot clock: `now_unix_seconds()`, frozen while paused
+    pub(crate) fn view_now_unix(&self) -> f64 {
+        if self.paused {
+            self.paused_at_unix
+        } else {
+            crate::time::now_unix_seconds()
+        }
+    }
+
+    /// Copy the current aggregates into the `--metrics-addr` endpoint
+    #[cfg(not(target_arch = "wasm32"))]
+    fn publish_metrics(&self) {
+        let Some(metrics) = &self.live_metrics else {
+            return;
+        };
+        let blocks = &self.data.blocks;
+        let highest_slot = blocks.highest_slot();
+        metrics.publish(&MetricsSnapshot {
+            events_total: self.diag_events_total,
+            events_per_sec: self.diag_events_sec,
+            dropped_per_sec: self.diag_node_dropped_sec + self.diag_server_dropped_sec,
+            nodes: self.data.events.node_count(),
+            validators: blocks.validator_count(),
+            highest_slot,
+            finalization_lag: highest_slot
+                .zip(blocks.highest_finalized())
+                .map(|(best, finalized)| best.saturating_sub(finalized)),
+        });
+    }
+
+    /// Messages waiting to be processed (shown while paused)
+    pub(crate) fn buffered_message_count(&self) -> usize {
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.msg_buffer.borrow().len()
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.pause_backlog.len()
+        }
+    }
+
+    /// Messages dropped because the buffer was full
+    pub(crate) fn buffered_dropped_count(&self) -> u64 {
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.msg_buffer.borrow().dropped()
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.pause_overflow_total
         }
     }
 
@@ -503,15 +1772,40 @@ impl JamApp {
         {
             self.ws_state.borrow().clone()
         }
+        // With several endpoints, any one streaming counts as connected
         #[cfg(not(target_arch = "wasm32"))]
         {
-            self.ws_state.lock().clone()
+            self.endpoints
+                .iter()
+                .map(|endpoint| endpoint.client.state.lock().clone())
+                .find(WsState::is_connected)
+                .unwrap_or_else(|| self.ws_state.lock().clone())
         }
     }
 
-    /// Get color for event type from the dynamic ColorLut
+    /// Get color for event type from the dynamic ColorLut (through the CVD preview, if on)
     pub(crate) fn get_event_color(&self, event_type: EventType) -> egui::Color32 {
-        let [r, g, b, a] = self.color_lut.colors[event_type.idx()];
+        let [r, g, b, a] = self.cvd_mode.simulate(self.color_lut.colors[event_type.idx()]);
+        egui::Color32::from_rgba_unmultiplied(
+            (r * 255.0) as u8,
+            (g * 255.0) as u8,
+            (b * 255.0) as u8,
+            (a * 255.0) as u8,
+        )
+    }
+
+    /// LUT to hand to the particle renderers: `color_lut`, or its CVD preview
+    pub(crate) fn display_lut(&self) -> ColorLut {
+        if self.cvd_whole_view && self.cvd_mode != CvdMode::Off {
+            self.cvd_mode.simulate_lut(&self.color_lut)
+        } else {
+            self.color_lut
+        }
+    }
+
+    /// Schema color for an EVENT_CATEGORIES entry (ignores single-category per-event colors)
+    pub(crate) fn category_color(&self, cat_idx: usize) -> egui::Color32 {
+        let [r, g, b, a] = self.cvd_mode.simulate(self.color_schema.colors()[cat_idx]);
         egui::Color32::from_rgba_unmultiplied(
             (r * 255.0) as u8,
             (g * 255.0) as u8,
@@ -523,7 +1817,9 @@ impl JamApp {
     /// Draw event category color legend as an auto-sized egui Window.
     /// Single-category mode: shows individual event names with distinct colors.
     /// Multi-category mode: shows category names with shared category colors.
-    pub(crate) fn draw_legend(&self, ctx: &egui::Context) {
+    /// Each entry shows its events in the time window, recounted every `LEGEND_REFRESH_SECS`.
+    /// Clicking an entry shows or hides its event type / category, like the filter sidebar.
+    pub(crate) fn draw_legend(&mut self, ctx: &egui::Context) {
         use crate::core::event_name;
 
         // Determine if single-category mode
@@ -540,60 +1836,118 @@ impl JamApp {
             None
         };
 
-        // Build legend entries: (name, color, enabled)
-        let entries: Vec<(&str, egui::Color32, bool)> = if let Some(cat_idx) = single_category {
+        let now = now_seconds();
+        if self.legend_counts.0 != single_category
+            || now - self.legend_counts_refreshed_at >= LEGEND_REFRESH_SECS
+        {
+            let cutoff = self.view_now() - self.time_window as f64;
+            let counts: Vec<usize> = with_data!(self, |data| match single_category {
+                Some(cat_idx) => EVENT_CATEGORIES[cat_idx].event_types.iter()
+                    .map(|&et| data.events.count_events(&[et], cutoff))
+                    .collect(),
+                None => EVENT_CATEGORIES.iter()
+                    .map(|cat| data.events.count_events(cat.event_types, cutoff))
+                    .collect(),
+            });
+            self.legend_counts = (single_category, counts);
+            self.legend_counts_refreshed_at = now;
+        }
+        let count_at = |i: usize| self.legend_counts.1.get(i).copied().unwrap_or(0);
+
+        // Build legend entries: (name, color, enabled, events in the window)
+        let entries: Vec<(&str, egui::Color32, bool, usize)> = if let Some(cat_idx) = single_category {
             let category = &EVENT_CATEGORIES[cat_idx];
-            category.event_types.iter().map(|&et| {
+            category.event_types.iter().enumerate().map(|(i, &et)| {
                 let enabled = et.idx() < self.selected_events.len()
                     && self.selected_events[et.idx()];
-                (event_name(et), self.get_event_color(et), enabled)
+                (event_name(et), self.get_event_color(et), enabled, count_at(i))
             }).collect()
         } else {
-            EVENT_CATEGORIES.iter().map(|cat| {
+            EVENT_CATEGORIES.iter().enumerate().map(|(i, cat)| {
                 let enabled = cat.event_types.iter().any(|&et|
                     et.idx() < self.selected_events.len() && self.selected_events[et.idx()]
                 );
-                (cat.name, self.get_event_color(cat.event_types[0]), enabled)
+                (cat.name, self.get_event_color(cat.event_types[0]), enabled, count_at(i))
             }).collect()
         };
+        let count_hint = format!("Events in the last {}", settings::format_window(self.time_window));
+        let mut toggled = None;
 
         egui::Area::new(egui::Id::new("legend_area"))
             .anchor(egui::Align2::LEFT_BOTTOM, egui::vec2(8.0, -8.0))
             .show(ctx, |ui| {
                 egui::Frame::new()
-                    .fill(egui::Color32::from_rgba_unmultiplied(20, 20, 20, 200))
+                    .fill(colors::shade(20, 200))
                     .corner_radius(4.0)
                     .inner_margin(8.0)
                     .show(ui, |ui| {
                         let header = egui::CollapsingHeader::new(
-                            egui::RichText::new("Legend").color(colors::TEXT_MUTED),
+                            egui::RichText::new("Legend").color(colors::text_muted()),
                         )
                         .default_open(true);
 
                         header.show(ui, |ui| {
-                            for (name, color, enabled) in &entries {
+                            for (i, (name, color, enabled, count)) in entries.iter().enumerate() {
                                 let alpha = if *enabled { 200u8 } else { 40 };
                                 let swatch_color = egui::Color32::from_rgba_unmultiplied(
                                     color.r(), color.g(), color.b(), alpha,
                                 );
-                                let text_color = egui::Color32::from_rgba_unmultiplied(160, 160, 160, alpha);
+                                let text = colors::text_secondary();
+                                let text_color = egui::Color32::from_rgba_unmultiplied(
+                                    text.r(), text.g(), text.b(), alpha,
+                                );
 
                                 ui.horizontal(|ui| {
-                                    let (dot_rect, _) = ui.allocate_exact_size(
+                                    let (dot_rect, dot) = ui.allocate_exact_size(
                                         egui::vec2(10.0, 10.0),
-                                        egui::Sense::hover(),
+                                        egui::Sense::click(),
                                     );
                                     ui.painter().circle_filled(dot_rect.center(), 5.0, swatch_color);
-                                    ui.label(egui::RichText::new(*name).color(text_color));
+                                    let label = ui.add(
+                                        egui::Label::new(egui::RichText::new(*name).color(text_color))
+                                            .sense(egui::Sense::click()),
+                                    );
+                                    let hint = if *enabled { "Click to hide" } else { "Click to show" };
+                                    if (dot | label)
+                                        .on_hover_cursor(egui::CursorIcon::PointingHand)
+                                        .on_hover_text(hint)
+                                        .clicked()
+                                    {
+                                        toggled = Some(i);
+                                    }
+                                    ui.label(
+                                        egui::RichText::new(format_count(*count as u64))
+                                            .color(colors::text_muted())
+                                            .small(),
+                                    )
+                                    .on_hover_text(count_hint.as_str());
                                 });
                             }
                         });
                     });
             });
+
+        // The filter change detection in `update` rebuilds the color LUT
+        if let Some(i) = toggled {
+            let event_types = match single_category {
+                Some(cat_idx) => std::slice::from_ref(&EVENT_CATEGORIES[cat_idx].event_types[i]),
+                None => EVENT_CATEGORIES[i].event_types,
+            };
+            filter::toggle_legend_entry(&mut self.selected_events, event_types);
+        }
     }
 
 }
 
+/// Text frames are JSON, binary frames SCALE
+fn parse_ws_message(
+    msg: &WsMessage,
+    ctx: &mut ParserContext,
+    now: f64,
+) -> Result<Option<ParseResult>, ParseError> {
+    parse_message(msg.as_bytes(), msg.codec(), ctx, now)
+}
+
 /// Build a ColorLut based on current filter state and color schema.
 /// Single-category mode (only one category has any enabled events): distinct colors per event type.
 /// Multi-category mode: shared category color for all events in a category.
@@ -639,45 +1993,152 @@ fn build_color_lut(selected_events: &[bool], schema: ColorSchema) -> ColorLut {
 }
 
 impl eframe::App for JamApp {
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        self.persist_settings(storage);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.persist_window_state(storage);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.export_csv_on_exit();
+    }
+
     #[allow(unused_variables)]
     fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
         // Request continuous repaint for real-time updates
         ctx.request_repaint();
 
+        if self.theme != theme::current_theme() {
+            theme::set_theme(self.theme);
+            ctx.set_visuals(theme::visuals(self.theme));
+        }
+
+        // Pick up peer labels once the background fetch completes
+        #[cfg(target_arch = "wasm32")]
+        if let Some(labels) = self.peer_labels_pending.borrow_mut().take() {
+            self.peer_labels = labels;
+        }
+
         // Process WebSocket messages (time-budgeted on both platforms)
         self.process_messages();
+        self.message_budget.start_render(now_seconds() * 1000.0);
 
-        // Periodic diagnostics tick (~1s) — cross-platform
+        // Diagnostics rate sample (every `diag_interval` seconds) — cross-platform
         let now = now_seconds();
-        if now - self.diag_last_tick >= 1.0 {
+        if now - self.diag_last_tick >= self.diag_interval as f64 {
             let elapsed = now - self.diag_last_tick;
-            self.diag_events_sec = self.diag_events_counter as f64 / elapsed;
-            self.diag_dropped_sec = self.diag_dropped_counter as f64 / elapsed;
-            self.diag_events_total += self.diag_events_counter;
+            // The store counts evictions since it was created; a cleared store starts from zero
+            let evicted = with_data!(self, |data| data.events.capacity_evictions());
+            let counts = diagnostics::DiagCounts {
+                events: self.diag_events_counter,
+                node_dropped: self.diag_node_dropped_counter,
+                server_dropped: self.diag_server_dropped_counter,
+                rx_bytes: self.diag_rx_bytes_counter,
+                parse_errors: self.diag_parse_errors_counter,
+                buffer_evicted: evicted.saturating_sub(self.diag_buffer_evicted_total),
+            };
+            self.diag_history.push(elapsed, counts);
+            let rates = self.diag_history.rates(self.diag_rate_mode);
+            self.diag_events_sec = rates.events;
+            self.diag_node_dropped_sec = rates.node_dropped;
+            self.diag_server_dropped_sec = rates.server_dropped;
+            self.diag_rx_bytes_sec = rates.rx_bytes;
+            self.diag_parse_errors_sec = rates.parse_errors;
+            self.diag_buffer_evicted_sec = rates.buffer_evicted;
+            self.diag_events_total += counts.events;
+            self.diag_parse_errors_total += counts.parse_errors;
+            self.diag_node_dropped_total += counts.node_dropped;
+            self.diag_buffer_evicted_total = evicted;
+
+            self.diag_events_counter = 0;
+            self.diag_node_dropped_counter = 0;
+            self.diag_server_dropped_counter = 0;
+            self.diag_rx_bytes_counter = 0;
+            self.diag_parse_errors_counter = 0;
+            self.diag_last_tick = now;
+
+            let components = with_data!(self, |data| {
+                let reported = data.latest_status.entries().into_iter().map(|(id, s)| (id, s.num_peers));
+                data.topology.validator_components(reported)
+            });
+            if self.partitions.update(components, now) {
+                warn!(
+                    groups = ?self.partitions.partition().unwrap_or_default(),
+                    "Network partition: validators split into disconnected groups"
+                );
+            }
+
+            self.evaluate_alerts();
+        }
+
+        // Housekeeping tick (~1s): stalls, forks, slot history, parse-error spikes
+        if now - self.housekeeping_last_tick >= 1.0 {
+            let view_now = self.view_now();
+            let threshold = self.stall_threshold as f64;
+            self.stalled_nodes =
+                with_data!(self, |data| data.events.stalled_nodes(view_now, threshold));
+            let fork = with_data!(self, |data| data.blocks.forks_at_slot());
+            if let Some(f) = &fork {
+                if self.fork.as_ref().map(|prev| prev.slot) != Some(f.slot) {
+                    warn!(slot = f.slot, branches = f.branches.len(), "Fork detected at tip");
+                }
+            }
+            self.fork = fork;
+            if !self.paused {
+                let highest_slot = with_data!(self, |data| data.blocks.highest_slot());
+                self.slot_history.push(highest_slot);
+            }
+
+            if self.diag_parse_errors_sec > PARSE_ERROR_SPIKE_RATE {
+                self.parse_error_spike_secs += 1;
+            } else {
+                self.parse_error_spike_secs = 0;
+                self.parse_error_banner_dismissed = false;
+            }
+
+            #[cfg(target_arch = "wasm32")]
+            if !self.paused && self.msg_buffer.borrow().len() > MSG_BACKLOG_WARN {
+                self.msg_backlog_secs += 1;
+            } else {
+                self.msg_backlog_secs = 0;
+            }
 
             #[cfg(not(target_arch = "wasm32"))]
             {
-                let active = self.data.directed_buffer.active_count(now as f32, 5.0);
-                let nodes = self.data.events.node_count();
-                info!(
-                    events_per_sec = self.diag_events_counter,
-                    dropped_per_sec = self.diag_dropped_counter,
-                    active_particles = active,
-                    nodes,
-                    "stats"
-                );
+                self.log_stats(now);
+                self.publish_metrics();
             }
 
-            self.diag_events_counter = 0;
-            self.diag_dropped_counter = 0;
-            self.diag_last_tick = now;
+            self.housekeeping_last_tick = now;
+
+            self.note_connected_url();
+
+            if self.auto_recover_enabled && self.parse_error_spike_active() {
+                warn!(
+                    parse_errors_per_sec = self.diag_parse_errors_sec,
+                    "Sustained parse-error spike, clearing data and reconnecting"
+                );
+                self.clear_and_reconnect();
+            }
         }
 
-        // Prune old events periodically
-        #[cfg(target_arch = "wasm32")]
-        self.data.borrow_mut().events.prune(now);
-        #[cfg(not(target_arch = "wasm32"))]
-        self.data.events.prune(now);
+        // Prune old events periodically (suspended while paused so the frozen view stays intact).
+        // Limits are re-applied first so settings changes (and a fresh store after a clear) take
+        // effect on this frame; a shorter retention prunes right away.
+        if !self.paused {
+            let retention = self.retention_secs as f64;
+            let max_events = self.max_events_per_node;
+            #[cfg(target_arch = "wasm32")]
+            let mut data = self.data.borrow_mut();
+            #[cfg(target_arch = "wasm32")]
+            let events = &mut data.events;
+            #[cfg(not(target_arch = "wasm32"))]
+            let events = &mut self.data.events;
+            events.retention = retention;
+            events.set_max_events_per_node(max_events);
+            events.prune(now);
+        }
 
         // Sync event filter to directed buffer for ring visualization
         let filter = self.build_filter_bitfield();
@@ -699,6 +2160,9 @@ impl eframe::App for JamApp {
         self.data.borrow_mut().directed_buffer.set_enabled_types(filter);
         #[cfg(not(target_arch = "wasm32"))]
         self.data.directed_buffer.set_enabled_types(filter);
+        self.sync_server_filter(now_seconds());
+        #[cfg(target_arch = "wasm32")]
+        self.sync_url_hash(now_seconds());
 
         // Drain pending pulse events into active pulses
         {
@@ -714,18 +2178,22 @@ impl eframe::App for JamApp {
                 });
             }
         }
-        // Expire old pulses
-        const PULSE_DURATION: f32 = 0.4;
-        let now_f32 = now as f32;
-        self.active_pulses.retain(|p| now_f32 - p.birth_time < PULSE_DURATION);
+        // Advance the ring clock, then expire old pulses on it
+        self.ring_clock.step(self.view_now(), self.hover_slowdown);
+        let now_f32 = self.ring_now() as f32;
+        let pulse_duration = self.pulse_duration;
+        self.active_pulses.retain(|p| now_f32 - p.birth_time < pulse_duration);
 
         // Header bar (TopBottomPanel spans full width, stays in place regardless of sidebar)
         egui::TopBottomPanel::top("header")
-            .frame(egui::Frame::new().fill(colors::BG_PRIMARY).inner_margin(4.0))
+            .frame(egui::Frame::new().fill(self.header_fill()).inner_margin(4.0))
             .show(ctx, |ui| {
                 self.render_header(ui);
             });
 
+        // Parse-error recovery banner (below header, only while errors are spiking)
+        self.render_parse_error_banner(ctx);
+
         // Filter sidebar (must be shown before CentralPanel)
         if self.show_event_selector {
             self.render_event_selector(ctx);
@@ -737,22 +2205,82 @@ impl eframe::App for JamApp {
         }
 
         // Legend window (collapsible, anchored bottom-left, hidden when filter sidebar open)
-        if !self.show_event_selector {
+        if self.show_legend && !self.show_event_selector {
             self.draw_legend(ctx);
         }
 
         // Diagnostics window (collapsible, anchored top-right)
         self.draw_diagnostics(ctx);
 
+        // Node detail popup (opened by clicking a node)
+        self.render_node_popup(ctx);
+
+        // Replay controls (bottom bar, only when playing back a recording)
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.replay.is_some() {
+            self.render_replay_bar(ctx);
+        }
+
+        // Connection panel (opened from the header)
+        if self.show_connection_panel {
+            self.render_connection_panel(ctx);
+        }
+
+        // Sync status panel (opened from the header)
+        if self.show_sync_panel {
+            self.render_sync_panel(ctx);
+        }
+
+        // Snapshot diff window (opened from the header)
+        if self.show_snapshots {
+            self.render_snapshot_panel(ctx);
+        }
+
+        // Latest Status table (opened from the header)
+        if self.show_status_table {
+            self.render_status_table(ctx);
+        }
+
+        // Live event log (opened from the header)
+        if self.show_event_log {
+            self.render_event_log(ctx);
+        }
+
+        // Peer map (opened from the header)
+        if self.show_geo_map {
+            self.render_geo_map(ctx);
+        }
+
+        // Connection topology (opened from the header)
+        if self.show_topology {
+            self.render_topology(ctx);
+        }
+
+        // Node list (right side panel, must be shown before CentralPanel)
+        if self.show_node_list {
+            self.render_node_list(ctx);
+        }
+
+        // Event inspector (right side panel, opened by double-clicking an event)
+        if self.inspected_event.is_some() {
+            self.render_event_inspector(ctx);
+        }
+
+        // Error toasts (bottom-right corner)
+        self.render_toasts(ctx);
+
         egui::CentralPanel::default()
-            .frame(egui::Frame::new().fill(colors::BG_PRIMARY))
+            .frame(egui::Frame::new().fill(colors::bg_primary()))
             .show(ctx, |ui| {
 
                 match self.active_tab {
                     ActiveTab::Ring => self.render_ring_tab(ui),
                     ActiveTab::Graphs => self.render_graphs_tab(ui),
+                    ActiveTab::Analytics => self.render_analytics_tab(ui),
                 }
             });
+        self.message_budget.end_render(now_seconds() * 1000.0);
+        self.drive_screenshot(ctx);
 
         // Update scatter texture reference after callback has rendered
         if let Some(texture_id) = self.scatter_texture_id {
@@ -779,11 +2307,11 @@ mod tests {
     use super::*;
 
     fn all_selected() -> Vec<bool> {
-        vec![true; 200]
+        vec![true; 256]
     }
 
     fn none_selected() -> Vec<bool> {
-        vec![false; 200]
+        vec![false; 256]
     }
 
     #[test]
@@ -835,7 +2363,7 @@ mod tests {
         let sel = none_selected();
         let lut = build_color_lut(&sel, ColorSchema::Vivid);
         // Multi-category mode (0 active categories) — all should use category colors
-        let vivid = ColorSchema::Vivid.colors();
+        let vivid = *ColorSchema::Vivid.colors();
         for (cat_idx, category) in EVENT_CATEGORIES.iter().enumerate() {
             let expected = vivid[cat_idx];
             for &et in category.event_types {
@@ -875,6 +2403,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn custom_schema_uses_picked_colors() {
+        let mut palette = *ColorSchema::Vivid.colors();
+        palette[3] = [0.1, 0.2, 0.3, 1.0];
+        let custom = ColorSchema::Custom(palette);
+        assert!(custom != ColorSchema::Vivid);
+        assert!(custom == ColorSchema::Custom(palette));
+
+        let mut sel = vec![false; 256];
+        sel[EVENT_CATEGORIES[3].event_types[0].idx()] = true;
+        sel[EVENT_CATEGORIES[0].event_types[0].idx()] = true;
+        let lut = build_color_lut(&sel, custom);
+        assert_eq!(lut.colors[EVENT_CATEGORIES[3].event_types[0].idx()], palette[3]);
+
+        // Interpolated palette starts on the first category color and has the requested size
+        let distinct = custom.generate_distinct_palette(26);
+        assert_eq!(distinct.len(), 26);
+        assert_eq!(distinct[0][..3], palette[0][..3]);
+        assert_eq!(distinct[6][..3], palette[3][..3]);
+    }
+
     #[test]
     fn generate_distinct_palette_all_distinct() {
         for schema in ColorSchema::ALL {
@@ -0,0 +1,274 @@
+//! Node list side panel — one sortable row per known node
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use eframe::egui;
+use crate::theme::colors;
+use super::{JamApp, with_data};
+
+/// Window for the events/sec column
+const RATE_WINDOW: f64 = 5.0;
+
+/// One node's cross-referenced state (EventStore + BestBlockData + TimeSeriesData),
+/// rebuilt every frame while the panel is open
+pub(crate) struct NodeSnapshot {
+    pub index: u16,
+    pub node_id: String,
+    pub label: String,
+    pub peers: Option<f32>,
+    pub best: Option<u64>,
+    pub finalized: Option<u64>,
+    pub synced: Option<bool>,
+    pub events_per_sec: f64,
+    /// Seconds since the newest stored event
+    pub last_seen_age: Option<f64>,
+}
+
+/// Sortable column of the node list
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum NodeColumn {
+    Label,
+    Index,
+    Peers,
+    Best,
+    Finalized,
+    Synced,
+    Rate,
+    LastSeen,
+}
+
+impl NodeColumn {
+    const ALL: &[NodeColumn] = &[
+        NodeColumn::Label,
+        NodeColumn::Index,
+        NodeColumn::Peers,
+        NodeColumn::Best,
+        NodeColumn::Finalized,
+        NodeColumn::Synced,
+        NodeColumn::Rate,
+        NodeColumn::LastSeen,
+    ];
+
+    fn title(self) -> &'static str {
+        match self {
+            NodeColumn::Label => "node",
+            NodeColumn::Index => "#",
+            NodeColumn::Peers => "peers",
+            NodeColumn::Best => "best",
+            NodeColumn::Finalized => "final",
+            NodeColumn::Synced => "sync",
+            NodeColumn::Rate => "ev/s",
+            NodeColumn::LastSeen => "seen",
+        }
+    }
+
+    fn compare(self, a: &NodeSnapshot, b: &NodeSnapshot) -> Ordering {
+        let f64_cmp = |x: Option<f64>, y: Option<f64>| match (x, y) {
+            (Some(x), Some(y)) => x.total_cmp(&y),
+            (x, y) => x.is_some().cmp(&y.is_some()),
+        };
+        match self {
+            NodeColumn::Label => a.label.cmp(&b.label),
+            NodeColumn::Index => a.index.cmp(&b.index),
+            NodeColumn::Peers => f64_cmp(a.peers.map(f64::from), b.peers.map(f64::from)),
+            NodeColumn::Best => a.best.cmp(&b.best),
+            NodeColumn::Finalized => a.finalized.cmp(&b.finalized),
+            NodeColumn::Synced => a.synced.cmp(&b.synced),
+            NodeColumn::Rate => a.events_per_sec.total_cmp(&b.events_per_sec),
+            NodeColumn::LastSeen => f64_cmp(a.last_seen_age, b.last_seen_age),
+        }
+    }
+}
+
+/// Current sort of the node list
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) struct NodeListSort {
+    pub column: NodeColumn,
+    pub ascending: bool,
+}
+
+impl Default for NodeListSort {
+    fn default() -> Self {
+        Self { column: NodeColumn::Index, ascending: true }
+    }
+}
+
+impl NodeListSort {
+    /// Clicking the active column flips direction; another column sorts ascending
+    fn toggle(&mut self, column: NodeColumn) {
+        if self.column == column {
+            self.ascending = !self.ascending;
+        } else {
+            *self = Self { column, ascending: true };
+        }
+    }
+}
+
+/// Sort rows by `sort`, ties broken by ring index
+fn sort_snapshots(rows: &mut [NodeSnapshot], sort: NodeListSort) {
+    rows.sort_by(|a, b| {
+        let ord = sort.column.compare(a, b);
+        let ord = if sort.ascending { ord } else { ord.reverse() };
+        ord.then(a.index.cmp(&b.index))
+    });
+}
+
+impl JamApp {
+    /// Cross-reference all stores into one row per node
+    pub(crate) fn node_snapshots(&self, now: f64) -> Vec<NodeSnapshot> {
+        with_data!(self, |data| {
+            let blocks: HashMap<&str, (Option<u64>, Option<u64>)> = data
+                .blocks
+                .iter()
+                .map(|(node_id, best, finalized)| (node_id, (best, finalized)))
+                .collect();
+            data.events
+                .nodes()
+                .map(|(node_id, node)| {
+                    let (best, finalized) = blocks.get(node_id).copied().unwrap_or_default();
+                    NodeSnapshot {
+                        index: node.index,
+                        node_id: node_id.to_string(),
+                        label: self.node_label(node_id),
+                        peers: data.time_series.latest_value(node_id),
+                        best,
+                        finalized,
+                        synced: node.latest_sync_status(),
+                        events_per_sec: node.count_since(now - RATE_WINDOW) as f64 / RATE_WINDOW,
+                        last_seen_age: Some((now - node.last_seen()).max(0.0)),
+                    }
+                })
+                .collect()
+        })
+    }
+
+    pub(crate) fn render_node_list(&mut self, ctx: &egui::Context) {
+        let mut rows = self.node_snapshots(self.view_now());
+        sort_snapshots(&mut rows, self.node_list_sort);
+
+        let mut sort_by = None;
+        let mut clicked = None;
+        egui::SidePanel::right("node_list")
+            .default_width(ctx.screen_rect().width() * 0.3)
+            .frame(egui::Frame::new().fill(colors::bg_primary()).inner_margin(8.0))
+            .show(ctx, |ui| {
+                ui.label(
+                    egui::RichText::new(format!("Nodes ({})", rows.len()))
+                        .color(colors::text_muted()),
+                );
+                ui.add_space(4.0);
+
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    egui::Grid::new("node_list_grid")
+                        .num_columns(NodeColumn::ALL.len())
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for &column in NodeColumn::ALL {
+                                let mut title = column.title().to_string();
+                                if self.node_list_sort.column == column {
+                                    title.push_str(if self.node_list_sort.ascending { " ▲" } else { " ▼" });
+                                }
+                                let text = egui::RichText::new(title).color(colors::text_secondary());
+                                if ui.add(egui::Label::new(text).sense(egui::Sense::click())).clicked() {
+                                    sort_by = Some(column);
+                                }
+                            }
+                            ui.end_row();
+
+                            let or_dash = |v: Option<u64>| v.map_or("-".to_string(), |s| s.to_string());
+                            for row in &rows {
+                                let color = if self.focused_node == Some(row.index) {
+                                    colors::text_primary()
+                                } else {
+                                    colors::text_muted()
+                                };
+                                let cells = [
+                                    row.label.clone(),
+                                    row.index.to_string(),
+                                    row.peers.map_or("-".to_string(), |p| format!("{:.0}", p)),
+                                    or_dash(row.best),
+                                    or_dash(row.finalized),
+                                    match row.synced {
+                                        Some(true) => "yes".to_string(),
+                                        Some(false) => "no".to_string(),
+                                        None => "-".to_string(),
+                                    },
+                                    format!("{:.1}", row.events_per_sec),
+                                    row.last_seen_age.map_or("-".to_string(), |a| format!("{:.0}s", a)),
+                                ];
+                                for cell in cells {
+                                    let label = egui::Label::new(egui::RichText::new(cell).color(color))
+                                        .sense(egui::Sense::click());
+                                    if ui.add(label).on_hover_text(row.node_id.as_str()).clicked() {
+                                        clicked = Some(row.index);
+                                    }
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+
+        if let Some(column) = sort_by {
+            self.node_list_sort.toggle(column);
+        }
+        if let Some(index) = clicked {
+            let focus = if self.focused_node == Some(index) { None } else { Some(index) };
+            self.set_focused_node(focus);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(index: u16, label: &str, best: Option<u64>, age: Option<f64>) -> NodeSnapshot {
+        NodeSnapshot {
+            index,
+            node_id: label.to_string(),
+            label: label.to_string(),
+            peers: None,
+            best,
+            finalized: None,
+            synced: None,
+            events_per_sec: 0.0,
+            last_seen_age: age,
+        }
+    }
+
+    fn order(rows: &[NodeSnapshot]) -> Vec<u16> {
+        rows.iter().map(|r| r.index).collect()
+    }
+
+    #[test]
+    fn sort_by_column_and_direction() {
+        let mut rows = vec![
+            row(0, "val-b", Some(10), Some(3.0)),
+            row(1, "val-a", None, None),
+            row(2, "val-c", Some(12), Some(1.0)),
+        ];
+
+        let mut sort = NodeListSort::default();
+        sort_snapshots(&mut rows, sort);
+        assert_eq!(order(&rows), vec![0, 1, 2]);
+
+        sort.toggle(NodeColumn::Label);
+        sort_snapshots(&mut rows, sort);
+        assert_eq!(order(&rows), vec![1, 0, 2]);
+
+        // Missing values sort first ascending, last descending
+        sort.toggle(NodeColumn::Best);
+        sort_snapshots(&mut rows, sort);
+        assert_eq!(order(&rows), vec![1, 0, 2]);
+        sort.toggle(NodeColumn::Best);
+        assert!(!sort.ascending);
+        sort_snapshots(&mut rows, sort);
+        assert_eq!(order(&rows), vec![2, 0, 1]);
+
+        sort.toggle(NodeColumn::LastSeen);
+        sort_snapshots(&mut rows, sort);
+        assert_eq!(order(&rows), vec![1, 2, 0]);
+    }
+}
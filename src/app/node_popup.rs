@@ -0,0 +1,165 @@
+//! Node detail popup — per-node event breakdown, peer count, slots, sync status
+
+use eframe::egui;
+use crate::core::{event_name, EventType};
+use crate::theme::colors;
+use super::inspector::copy_button;
+use super::{JamApp, with_data};
+
+/// Remote addresses kept for the popup's "last peer" row
+const RECENT_PEER_ADDRESSES: usize = 8;
+
+/// Snapshot of a single node's state, gathered once per frame for the popup
+struct NodeSummary {
+    node_id: String,
+    peer_count: Option<f32>,
+    /// Live connections in the topology (from the connection events)
+    connections: usize,
+    best_slot: Option<u64>,
+    finalized_slot: Option<u64>,
+    synced: Option<bool>,
+    /// Remote addresses of the latest connection attempts, newest first
+    peer_addresses: Vec<String>,
+    /// (event_type, count) sorted by count descending
+    type_counts: Vec<(u8, usize)>,
+}
+
+impl JamApp {
+    /// Draw the popup for `selected_node` (opened by clicking a node on the ring or scatter).
+    pub(crate) fn render_node_popup(&mut self, ctx: &egui::Context) {
+        let Some(index) = self.selected_node else {
+            return;
+        };
+
+        let summary = with_data!(self, |data| {
+            data.events.node_id_at(index).map(|node_id| {
+                let node = data.events.node(node_id);
+                NodeSummary {
+                    node_id: node_id.to_string(),
+                    peer_count: data.time_series.latest_value(node_id),
+                    connections: data.topology.degree(node_id),
+                    best_slot: data.blocks.best_for(node_id),
+                    finalized_slot: data.blocks.finalized_for(node_id),
+                    synced: node.and_then(|n| n.latest_sync_status()),
+                    peer_addresses: node
+                        .map(|n| n.recent_peer_addresses(RECENT_PEER_ADDRESSES))
+                        .unwrap_or_default()
+                        .iter()
+                        .map(|address| address.to_string())
+                        .collect(),
+                    type_counts: node.map(|n| n.type_counts()).unwrap_or_default(),
+                }
+            })
+        });
+
+        // Node vanished (e.g. data cleared) — close the popup
+        let Some(summary) = summary else {
+            self.selected_node = None;
+            return;
+        };
+
+        let mut open = true;
+        egui::Window::new(format!("Node {} · {}", index, self.node_label(&summary.node_id)))
+            .id(egui::Id::new("node_popup"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let short_id = &summary.node_id[..summary.node_id.len().min(16)];
+                let or_dash = |v: Option<u64>| v.map_or("-".to_string(), |s| s.to_string());
+
+                egui::Grid::new("node_popup_info")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label(egui::RichText::new("id").color(colors::text_muted()));
+                        ui.horizontal(|ui| {
+                            ui.label(egui::RichText::new(short_id).monospace());
+                            copy_button(ui, &summary.node_id);
+                        });
+                        ui.end_row();
+
+                        ui.label(egui::RichText::new("peers").color(colors::text_muted()));
+                        ui.label(summary.peer_count.map_or("-".to_string(), |c| format!("{:.0}", c)));
+                        ui.end_row();
+
+                        ui.label(egui::RichText::new("connections").color(colors::text_muted()));
+                        ui.label(summary.connections.to_string())
+                            .on_hover_text("Connections seen through ConnectedIn / ConnectedOut, minus Disconnected");
+                        ui.end_row();
+
+                        ui.label(egui::RichText::new("best").color(colors::text_muted()));
+                        ui.label(or_dash(summary.best_slot));
+                        ui.end_row();
+
+                        ui.label(egui::RichText::new("finalized").color(colors::text_muted()));
+                        ui.label(or_dash(summary.finalized_slot));
+                        ui.end_row();
+
+                        ui.label(egui::RichText::new("sync").color(colors::text_muted()));
+                        ui.label(match summary.synced {
+                            Some(true) => "synced",
+                            Some(false) => "syncing",
+                            None => "-",
+                        });
+                        ui.end_row();
+
+                        if let Some(latest) = summary.peer_addresses.first() {
+                            ui.label(egui::RichText::new("last peer").color(colors::text_muted()));
+                            ui.horizontal(|ui| {
+                                let more = summary.peer_addresses.len() - 1;
+                                let text = if more > 0 {
+                                    format!("{} (+{})", latest, more)
+                                } else {
+                                    latest.clone()
+                                };
+                                ui.label(egui::RichText::new(text).monospace()).on_hover_text(format!(
+                                    "Latest connection attempts, newest first:\n{}",
+                                    summary.peer_addresses.join("\n"),
+                                ));
+                                copy_button(ui, latest);
+                            });
+                            ui.end_row();
+                        }
+                    });
+
+                ui.separator();
+
+                if summary.type_counts.is_empty() {
+                    ui.label(egui::RichText::new("No events").color(colors::text_muted()));
+                    return;
+                }
+
+                egui::ScrollArea::vertical()
+                    .id_salt("node_popup_counts")
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("node_popup_counts_grid")
+                            .num_columns(2)
+                            .show(ui, |ui| {
+                                for &(et, count) in &summary.type_counts {
+                                    let event_type = EventType::from_u8(et);
+                                    ui.horizontal(|ui| {
+                                        let color = event_type
+                                            .map_or(colors::text_muted(), |et| self.get_event_color(et));
+                                        let (dot_rect, _) = ui.allocate_exact_size(
+                                            egui::vec2(10.0, 10.0),
+                                            egui::Sense::hover(),
+                                        );
+                                        ui.painter().circle_filled(dot_rect.center(), 4.0, color);
+                                        ui.label(event_type.map_or("Unknown", event_name));
+                                    });
+                                    ui.label(
+                                        egui::RichText::new(count.to_string())
+                                            .color(colors::text_secondary()),
+                                    );
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            });
+
+        if !open {
+            self.selected_node = None;
+        }
+    }
+}
@@ -0,0 +1,428 @@
+//! Persisted UI settings — saved through eframe storage (localStorage on WASM, config dir on native)
+
+use serde::{Deserialize, Serialize};
+use crate::core::events::{
+    default_travel_durations, TravelDurations, ERROR_EVENT_TYPES, REASON_EVENT_TYPES,
+};
+use crate::core::EventType;
+use crate::theme::Theme;
+use crate::vring::{deserialize_palette, ColorSchema};
+use super::alerts::{default_alert_rules, fit_alert_rules, AlertRule};
+use super::toasts::DEFAULT_TOAST_TYPES;
+use super::{
+    build_color_lut, ActiveTab, DiagRateMode, JamApp, RateMode, RateSmoothing, RingGrouping, SeriesBand,
+    DEFAULT_DIAG_INTERVAL, DEFAULT_HOVER_SLOWDOWN, DEFAULT_MAX_EVENTS_PER_NODE, DEFAULT_NODE_DOT_RADIUS,
+    DEFAULT_PULSE_DURATION, DEFAULT_PULSE_RADIUS, DEFAULT_PULSE_TYPES,
+    DEFAULT_RATE_SMOOTHING_ALPHA, DEFAULT_RATE_SMOOTHING_WINDOW, DEFAULT_RATE_Y_MAX,
+    DEFAULT_RETENTION_SECS,
+    DEFAULT_RING_RADIUS, DEFAULT_SCATTER_PARTICLE_CAP, DEFAULT_SCATTER_WINDOW,
+    DEFAULT_STALL_THRESHOLD, DEFAULT_TIME_WINDOW, DEFAULT_TRAIL_LENGTH, MAX_TRAVEL_DURATION, MIN_TRAVEL_DURATION,
+};
+
+/// eframe storage key for [`PersistedSettings`]
+const STORAGE_KEY: &str = "jam_orbit_settings";
+
+/// User-facing settings that survive a reload
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct PersistedSettings {
+    selected_events: Vec<bool>,
+    server_filter_enabled: bool,
+    theme: Theme,
+    color_schema: ColorSchema,
+    #[serde(deserialize_with = "deserialize_palette")]
+    custom_palette: [[f32; 4]; 13],
+    active_tab: ActiveTab,
+    speed_factor: f32,
+    hover_slowdown: f32,
+    travel_durations: Vec<f32>,
+    ring_radius: f32,
+    node_dot_radius: f32,
+    particle_trails: bool,
+    trail_length: f32,
+    ring_order_stable: bool,
+    ring_transitions: bool,
+    hashed_node_indices: bool,
+    ring_grouping: RingGrouping,
+    show_node_indices: bool,
+    toast_event_types: Vec<EventType>,
+    diag_interval: f32,
+    diag_rate_mode: DiagRateMode,
+    slot_pulse_enabled: bool,
+    pulse_event_types: Vec<EventType>,
+    pulse_duration: f32,
+    pulse_radius: f32,
+    ring_bloom: bool,
+    node_brightness_enabled: bool,
+    show_legend: bool,
+    peer_lines_colored: bool,
+    peer_channels: [bool; 3],
+    peer_band: SeriesBand,
+    rate_mode: RateMode,
+    rate_smoothing: RateSmoothing,
+    rate_smoothing_window: usize,
+    rate_smoothing_alpha: f32,
+    rate_y_autoscale: bool,
+    rate_y_max: f32,
+    rate_y_log: bool,
+    gas_by_service: bool,
+    heatmap_by_category: bool,
+    reason_event_type: EventType,
+    time_window: f32,
+    scatter_window: Option<f32>,
+    stall_threshold: f32,
+    retention_secs: f32,
+    max_events_per_node: usize,
+    scatter_particle_cap: usize,
+    alert_rules: Vec<AlertRule>,
+    alert_sound: bool,
+}
+
+impl Default for PersistedSettings {
+    fn default() -> Self {
+        Self {
+            selected_events: JamApp::default_selected_events(),
+            server_filter_enabled: false,
+            theme: Theme::default(),
+            color_schema: ColorSchema::default(),
+            custom_palette: *ColorSchema::default().colors(),
+            active_tab: ActiveTab::default(),
+            speed_factor: 1.0,
+            hover_slowdown: DEFAULT_HOVER_SLOWDOWN,
+            travel_durations: default_travel_durations().to_vec(),
+            ring_radius: DEFAULT_RING_RADIUS,
+            node_dot_radius: DEFAULT_NODE_DOT_RADIUS,
+            particle_trails: false,
+            trail_length: DEFAULT_TRAIL_LENGTH,
+            ring_order_stable: false,
+            ring_transitions: true,
+            hashed_node_indices: false,
+            ring_grouping: RingGrouping::default(),
+            show_node_indices: false,
+            toast_event_types: DEFAULT_TOAST_TYPES.to_vec(),
+            diag_interval: DEFAULT_DIAG_INTERVAL,
+            diag_rate_mode: DiagRateMode::default(),
+            slot_pulse_enabled: true,
+            pulse_event_types: DEFAULT_PULSE_TYPES.to_vec(),
+            pulse_duration: DEFAULT_PULSE_DURATION,
+            pulse_radius: DEFAULT_PULSE_RADIUS,
+            ring_bloom: false,
+            node_brightness_enabled: true,
+            show_legend: true,
+            peer_lines_colored: true,
+            peer_channels: [true, false, false],
+            peer_band: SeriesBand::default(),
+            rate_mode: RateMode::default(),
+            rate_smoothing: RateSmoothing::default(),
+            rate_smoothing_window: DEFAULT_RATE_SMOOTHING_WINDOW,
+            rate_smoothing_alpha: DEFAULT_RATE_SMOOTHING_ALPHA,
+            rate_y_autoscale: true,
+            rate_y_max: DEFAULT_RATE_Y_MAX,
+            rate_y_log: false,
+            gas_by_service: true,
+            heatmap_by_category: false,
+            reason_event_type: EventType::ConnectInFailed,
+            time_window: DEFAULT_TIME_WINDOW,
+            scatter_window: Some(DEFAULT_SCATTER_WINDOW),
+            stall_threshold: DEFAULT_STALL_THRESHOLD,
+            retention_secs: DEFAULT_RETENTION_SECS,
+            max_events_per_node: DEFAULT_MAX_EVENTS_PER_NODE,
+            scatter_particle_cap: DEFAULT_SCATTER_PARTICLE_CAP,
+            alert_rules: default_alert_rules(),
+            alert_sound: false,
+        }
+    }
+}
+
+/// Fit a stored filter to the current number of event slots.
+/// Slots added since it was saved start enabled (matching the default); extra slots are dropped.
+fn fit_selected_events(mut stored: Vec<bool>, len: usize) -> Vec<bool> {
+    stored.resize(len, true);
+    stored
+}
+
+/// Stored durations over the built-in table; missing or non-finite entries keep the default
+fn fit_travel_durations(stored: &[f32]) -> TravelDurations {
+    let mut durations = default_travel_durations();
+    for (duration, &value) in durations.iter_mut().zip(stored) {
+        if value.is_finite() {
+            *duration = value.clamp(MIN_TRAVEL_DURATION, MAX_TRAVEL_DURATION);
+        }
+    }
+    durations
+}
+
+impl JamApp {
+    /// Load settings saved by a previous session, if any
+    pub(crate) fn restore_settings(&mut self, storage: Option<&dyn eframe::Storage>) {
+        let Some(settings) =
+            storage.and_then(|s| eframe::get_value::<PersistedSettings>(s, STORAGE_KEY))
+        else {
+            return;
+        };
+
+        self.selected_events =
+            fit_selected_events(settings.selected_events, self.selected_events.len());
+        self.server_filter_enabled = settings.server_filter_enabled;
+        self.theme = settings.theme;
+        self.color_schema = settings.color_schema;
+        self.custom_palette = settings.custom_palette;
+        self.active_tab = settings.active_tab;
+        self.speed_factor = if settings.speed_factor.is_finite() {
+            settings.speed_factor.clamp(0.1, 2.0)
+        } else {
+            1.0
+        };
+        self.hover_slowdown = if settings.hover_slowdown.is_finite() {
+            settings.hover_slowdown.clamp(0.0, 1.0)
+        } else {
+            DEFAULT_HOVER_SLOWDOWN
+        };
+        self.travel_durations = fit_travel_durations(&settings.travel_durations);
+        self.ring_radius = if settings.ring_radius.is_finite() {
+            settings.ring_radius.clamp(0.3, 0.95)
+        } else {
+            DEFAULT_RING_RADIUS
+        };
+        self.node_dot_radius = if settings.node_dot_radius.is_finite() {
+            settings.node_dot_radius.clamp(1.0, 12.0)
+        } else {
+            DEFAULT_NODE_DOT_RADIUS
+        };
+        self.particle_trails = settings.particle_trails;
+        self.trail_length = if settings.trail_length.is_finite() {
+            settings.trail_length.clamp(0.05, 1.0)
+        } else {
+            DEFAULT_TRAIL_LENGTH
+        };
+        self.ring_order_stable = settings.ring_order_stable;
+        self.ring_transitions = settings.ring_transitions;
+        self.hashed_node_indices = settings.hashed_node_indices;
+        self.apply_node_indexing();
+        self.ring_grouping = settings.ring_grouping;
+        self.show_node_indices = settings.show_node_indices;
+        self.toast_event_types = settings
+            .toast_event_types
+            .into_iter()
+            .filter(|et| ERROR_EVENT_TYPES.contains(et))
+            .collect();
+        self.diag_interval = if settings.diag_interval.is_finite() {
+            settings.diag_interval.clamp(0.25, 5.0)
+        } else {
+            DEFAULT_DIAG_INTERVAL
+        };
+        self.diag_rate_mode = settings.diag_rate_mode;
+        self.slot_pulse_enabled = settings.slot_pulse_enabled;
+        self.pulse_event_types = settings
+            .pulse_event_types
+            .into_iter()
+            .filter(|&et| et != EventType::Unknown)
+            .collect();
+        self.pulse_duration = if settings.pulse_duration.is_finite() {
+            settings.pulse_duration.clamp(0.1, 2.0)
+        } else {
+            DEFAULT_PULSE_DURATION
+        };
+        self.pulse_radius = if settings.pulse_radius.is_finite() {
+            settings.pulse_radius.clamp(5.0, 120.0)
+        } else {
+            DEFAULT_PULSE_RADIUS
+        };
+        self.ring_bloom = settings.ring_bloom;
+        self.node_brightness_enabled = settings.node_brightness_enabled;
+        self.show_legend = settings.show_legend;
+        self.peer_lines_colored = settings.peer_lines_colored;
+        self.peer_channels = settings.peer_channels;
+        self.peer_band = settings.peer_band;
+        self.rate_mode = settings.rate_mode;
+        self.rate_smoothing = settings.rate_smoothing;
+        // Centered window: odd so the average sits on its own bucket
+        self.rate_smoothing_window = settings.rate_smoothing_window.clamp(3, 15) | 1;
+        self.rate_smoothing_alpha = if settings.rate_smoothing_alpha.is_finite() {
+            settings.rate_smoothing_alpha.clamp(0.05, 0.9)
+        } else {
+            DEFAULT_RATE_SMOOTHING_ALPHA
+        };
+        self.rate_y_autoscale = settings.rate_y_autoscale;
+        self.rate_y_max = if settings.rate_y_max.is_finite() {
+            settings.rate_y_max.clamp(5.0, 100_000.0)
+        } else {
+            DEFAULT_RATE_Y_MAX
+        };
+        self.rate_y_log = settings.rate_y_log;
+        self.gas_by_service = settings.gas_by_service;
+        self.heatmap_by_category = settings.heatmap_by_category;
+        if REASON_EVENT_TYPES.contains(&settings.reason_event_type) {
+            self.reason_event_type = settings.reason_event_type;
+        }
+        self.time_window = if settings.time_window.is_finite() {
+            settings.time_window.clamp(1.0, 600.0)
+        } else {
+            DEFAULT_TIME_WINDOW
+        };
+        self.scatter_window = settings.scatter_window.map(|w| {
+            if w.is_finite() {
+                w.clamp(1.0, 600.0)
+            } else {
+                DEFAULT_SCATTER_WINDOW
+            }
+        });
+        self.stall_threshold = if settings.stall_threshold.is_finite() {
+            settings.stall_threshold.clamp(2.0, 120.0)
+        } else {
+            DEFAULT_STALL_THRESHOLD
+        };
+        self.retention_secs = if settings.retention_secs.is_finite() {
+            settings.retention_secs.clamp(10.0, 600.0)
+        } else {
+            DEFAULT_RETENTION_SECS
+        };
+        self.max_events_per_node = settings.max_events_per_node.clamp(1_000, 1_000_000);
+        self.scatter_particle_cap = settings.scatter_particle_cap.clamp(10_000, 5_000_000);
+        self.alert_rules = fit_alert_rules(&settings.alert_rules);
+        self.alert_sound = settings.alert_sound;
+        self.color_lut = build_color_lut(&self.selected_events, self.color_schema);
+    }
+
+    /// Write current settings to eframe storage
+    pub(crate) fn persist_settings(&self, storage: &mut dyn eframe::Storage) {
+        let settings = PersistedSettings {
+            selected_events: self.selected_events.clone(),
+            server_filter_enabled: self.server_filter_enabled,
+            theme: self.theme,
+            color_schema: self.color_schema,
+            custom_palette: self.custom_palette,
+            active_tab: self.active_tab,
+            speed_factor: self.speed_factor,
+            hover_slowdown: self.hover_slowdown,
+            travel_durations: self.travel_durations.to_vec(),
+            ring_radius: self.ring_radius,
+            node_dot_radius: self.node_dot_radius,
+            particle_trails: self.particle_trails,
+            trail_length: self.trail_length,
+            ring_order_stable: self.ring_order_stable,
+            ring_transitions: self.ring_transitions,
+            hashed_node_indices: self.hashed_node_indices,
+            ring_grouping: self.ring_grouping,
+            show_node_indices: self.show_node_indices,
+            toast_event_types: self.toast_event_types.clone(),
+            diag_interval: self.diag_interval,
+            diag_rate_mode: self.diag_rate_mode,
+            slot_pulse_enabled: self.slot_pulse_enabled,
+            pulse_event_types: self.pulse_event_types.clone(),
+            pulse_duration: self.pulse_duration,
+            pulse_radius: self.pulse_radius,
+            ring_bloom: self.ring_bloom,
+            node_brightness_enabled: self.node_brightness_enabled,
+            show_legend: self.show_legend,
+            peer_lines_colored: self.peer_lines_colored,
+            peer_channels: self.peer_channels,
+            peer_band: self.peer_band,
+            rate_mode: self.rate_mode,
+            rate_smoothing: self.rate_smoothing,
+            rate_smoothing_window: self.rate_smoothing_window,
+            rate_smoothing_alpha: self.rate_smoothing_alpha,
+            rate_y_autoscale: self.rate_y_autoscale,
+            rate_y_max: self.rate_y_max,
+            rate_y_log: self.rate_y_log,
+            gas_by_service: self.gas_by_service,
+            heatmap_by_category: self.heatmap_by_category,
+            reason_event_type: self.reason_event_type,
+            time_window: self.time_window,
+            scatter_window: self.scatter_window,
+            stall_threshold: self.stall_threshold,
+            retention_secs: self.retention_secs,
+            max_events_per_node: self.max_events_per_node,
+            scatter_particle_cap: self.scatter_particle_cap,
+            alert_rules: self.alert_rules.clone(),
+            alert_sound: self.alert_sound,
+        };
+        eframe::set_value(storage, STORAGE_KEY, &settings);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_selected_events_pads_and_truncates() {
+        let grown = fit_selected_events(vec![false; 3], 5);
+        assert_eq!(grown, vec![false, false, false, true, true]);
+
+        let shrunk = fit_selected_events(vec![false, true, false, true], 2);
+        assert_eq!(shrunk, vec![false, true]);
+    }
+
+    #[test]
+    fn fit_travel_durations_clamps_and_fills() {
+        let durations = fit_travel_durations(&[0.0, f32::NAN, 100.0, 4.0]);
+        let defaults = default_travel_durations();
+        assert_eq!(durations[0], MIN_TRAVEL_DURATION);
+        assert_eq!(durations[1], defaults[1]);
+        assert_eq!(durations[2], MAX_TRAVEL_DURATION);
+        assert_eq!(durations[3], 4.0);
+        assert_eq!(durations[4..], defaults[4..]);
+    }
+
+    #[test]
+    fn missing_fields_fall_back_to_defaults() {
+        let settings: PersistedSettings =
+            serde_json::from_str(r#"{"speed_factor":0.5,"active_tab":"Graphs"}"#).unwrap();
+        assert_eq!(settings.speed_factor, 0.5);
+        assert_eq!(settings.hover_slowdown, DEFAULT_HOVER_SLOWDOWN);
+        assert!(!settings.particle_trails);
+        assert!(!settings.ring_bloom);
+        assert_eq!(settings.trail_length, DEFAULT_TRAIL_LENGTH);
+        assert_eq!(settings.travel_durations, default_travel_durations().to_vec());
+        assert!(settings.active_tab == ActiveTab::Graphs);
+        assert!(settings.theme == Theme::Dark);
+        assert!(settings.color_schema == ColorSchema::Vivid);
+        assert_eq!(settings.selected_events.len(), 256);
+        assert!(!settings.server_filter_enabled);
+        assert!(settings.show_legend);
+        assert!(settings.peer_lines_colored);
+        assert_eq!(settings.peer_channels, [true, false, false]);
+        assert!(settings.peer_band == SeriesBand::Lines);
+        assert!(settings.rate_mode == RateMode::PerNode);
+        assert!(settings.rate_smoothing == RateSmoothing::Raw);
+        assert_eq!(settings.rate_smoothing_window, DEFAULT_RATE_SMOOTHING_WINDOW);
+        assert!(settings.rate_y_autoscale && !settings.rate_y_log);
+        assert_eq!(settings.rate_y_max, DEFAULT_RATE_Y_MAX);
+        assert!(settings.gas_by_service);
+        assert!(!settings.heatmap_by_category);
+        assert_eq!(settings.reason_event_type, EventType::ConnectInFailed);
+        assert_eq!(settings.time_window, DEFAULT_TIME_WINDOW);
+        assert_eq!(settings.scatter_window, Some(DEFAULT_SCATTER_WINDOW));
+        assert_eq!(settings.ring_radius, DEFAULT_RING_RADIUS);
+        assert_eq!(settings.node_dot_radius, DEFAULT_NODE_DOT_RADIUS);
+        assert!(!settings.ring_order_stable);
+        assert!(settings.ring_transitions);
+        assert!(!settings.hashed_node_indices);
+        assert!(settings.ring_grouping == RingGrouping::None);
+        assert!(!settings.show_node_indices);
+        assert_eq!(settings.toast_event_types, DEFAULT_TOAST_TYPES);
+        assert_eq!(settings.diag_interval, DEFAULT_DIAG_INTERVAL);
+        assert_eq!(settings.pulse_event_types, DEFAULT_PULSE_TYPES);
+        assert_eq!(settings.pulse_duration, DEFAULT_PULSE_DURATION);
+        assert_eq!(settings.pulse_radius, DEFAULT_PULSE_RADIUS);
+        assert!(settings.diag_rate_mode == DiagRateMode::Instant);
+        assert_eq!(settings.stall_threshold, DEFAULT_STALL_THRESHOLD);
+        assert_eq!(settings.retention_secs, DEFAULT_RETENTION_SECS);
+        assert_eq!(settings.max_events_per_node, DEFAULT_MAX_EVENTS_PER_NODE);
+        assert_eq!(settings.scatter_particle_cap, DEFAULT_SCATTER_PARTICLE_CAP);
+        assert_eq!(settings.alert_rules, default_alert_rules());
+        assert!(!settings.alert_sound);
+    }
+
+    #[test]
+    fn palettes_saved_before_the_unknown_category_still_load() {
+        let old = serde_json::to_string(&[[0.1f32, 0.2, 0.3, 1.0]; 12]).unwrap();
+        let json = format!(r#"{{"custom_palette":{0},"color_schema":{{"Custom":{0}}}}}"#, old);
+        let settings: PersistedSettings = serde_json::from_str(&json).unwrap();
+        let vivid = ColorSchema::Vivid.colors();
+        assert_eq!(settings.custom_palette[11], [0.1, 0.2, 0.3, 1.0]);
+        assert_eq!(settings.custom_palette[12], vivid[12]);
+        assert!(settings.color_schema == ColorSchema::Custom(settings.custom_palette));
+    }
+}
@@ -0,0 +1,71 @@
+//! Replay controls — scrub bar, loop toggle and playback speed (native --replay only)
+
+use eframe::egui;
+use crate::theme::colors;
+use super::JamApp;
+
+/// Format seconds as m:ss
+fn format_clock(secs: f64) -> String {
+    let total = secs.max(0.0) as u64;
+    format!("{}:{:02}", total / 60, total % 60)
+}
+
+impl JamApp {
+    pub(crate) fn render_replay_bar(&mut self, ctx: &egui::Context) {
+        let Some(replay) = self.replay.as_ref() else {
+            return;
+        };
+        let duration = replay.duration();
+        let mut position = replay.position();
+        let mut looping = replay.looping;
+        let finished = replay.is_finished();
+
+        let mut seek_to = None;
+        egui::TopBottomPanel::bottom("replay_bar")
+            .frame(egui::Frame::new().fill(colors::bg_primary()).inner_margin(6.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let status = if finished { "Replay ended" } else { "Replay" };
+                    ui.label(egui::RichText::new(status).color(colors::text_muted()));
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "{} / {}",
+                            format_clock(position),
+                            format_clock(duration)
+                        ))
+                        .monospace(),
+                    );
+
+                    ui.checkbox(&mut looping, "Loop");
+
+                    ui.label(
+                        egui::RichText::new(format!("{:.1}x", self.speed_factor))
+                            .color(colors::text_muted()),
+                    );
+                    ui.add(
+                        egui::Slider::new(&mut self.speed_factor, 0.1..=2.0)
+                            .logarithmic(true)
+                            .clamping(egui::SliderClamping::Always)
+                            .show_value(false),
+                    );
+
+                    ui.spacing_mut().slider_width = ui.available_width();
+                    let scrub = ui.add(
+                        egui::Slider::new(&mut position, 0.0..=duration.max(f64::EPSILON))
+                            .show_value(false),
+                    );
+                    // Seek once the user lets go, not on every drag step
+                    if scrub.drag_stopped() || (scrub.changed() && !scrub.dragged()) {
+                        seek_to = Some(position);
+                    }
+                });
+            });
+
+        if let Some(replay) = self.replay.as_mut() {
+            replay.looping = looping;
+        }
+        if let Some(t) = seek_to {
+            self.seek_replay(t);
+        }
+    }
+}
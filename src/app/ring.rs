@@ -1,13 +1,69 @@
 //! Validators ring visualization
 
 use eframe::egui;
-use crate::time::now_seconds;
+use crate::theme::colors;
 use super::{JamApp, with_data};
+use super::ring_layout::RingLayout;
 
 use std::sync::Arc;
 use crate::vring::{FilterBitfield, GpuParticle, RingCallback, Uniforms};
 
+/// Seconds for the hover slowdown to ease in and out
+const HOVER_EASE_SECS: f32 = 0.3;
+/// Fraction of the remaining lag the ring clock makes up per second once the pointer leaves
+const HOVER_CATCH_UP_RATE: f64 = 3.0;
+
+/// Ring clock that falls behind the view clock while the pointer is over the ring,
+/// so particles slow down (or freeze) for reading, then catches up after it leaves
+#[derive(Default)]
+pub(crate) struct HoverClock {
+    /// Seconds the ring clock trails the view clock
+    lag: f64,
+    /// How much of the slowdown applies, easing between 0 (full speed) and 1
+    weight: f32,
+    /// Pointer was over the ring canvas since the last step
+    hovered: bool,
+    /// View time of the last step
+    last: Option<f64>,
+}
+
+impl HoverClock {
+    /// Advance to view time `now`; `slowdown` is the speed reduction while hovered
+    /// (0 = none, 1 = freeze). A frozen view clock (pause) leaves the lag unchanged.
+    pub(crate) fn step(&mut self, now: f64, slowdown: f32) {
+        let dt = self.last.map_or(0.0, |last| (now - last).clamp(0.0, 0.25));
+        self.last = Some(now);
+        let hovered = std::mem::take(&mut self.hovered) && slowdown > 0.0;
+
+        let ease = dt as f32 / HOVER_EASE_SECS;
+        self.weight = if hovered {
+            (self.weight + ease).min(1.0)
+        } else {
+            (self.weight - ease).max(0.0)
+        };
+        if hovered {
+            self.lag += dt * (slowdown * self.weight) as f64;
+        } else {
+            self.lag *= (1.0 - dt * HOVER_CATCH_UP_RATE).max(0.0);
+            if self.lag < 0.001 {
+                self.lag = 0.0;
+            }
+        }
+    }
+
+    /// Ring time for view time `now`
+    pub(crate) fn now(&self, now: f64) -> f64 {
+        now - self.lag
+    }
+}
+
 impl JamApp {
+    /// Clock the ring particles and pulses run on: the view clock, held back by the
+    /// hover slowdown
+    pub(crate) fn ring_now(&self) -> f64 {
+        self.ring_clock.now(self.view_now())
+    }
+
     /// Render the Ring tab — routes to GPU or CPU path.
     pub(crate) fn render_ring_tab(&mut self, ui: &mut egui::Ui) {
         if self.use_cpu {
@@ -20,17 +76,18 @@ impl JamApp {
     /// GPU ring rendering path.
     /// Particles rendered by GPU shader, overlays (ring, dots, legend) drawn by CPU.
     fn render_ring_tab_gpu(&mut self, ui: &mut egui::Ui) {
-        use std::f32::consts::PI;
-
-        let now = now_seconds() as f32;
+        let now = self.ring_now() as f32;
+        self.refresh_ring_layout();
 
         let (particle_max, active_count, num_nodes, new_particles, new_cursor, peer_counts) =
             with_data!(self, |data| {
-                let (particles, cursor, skip) =
-                    data.directed_buffer.get_new_since(self.gpu_upload_cursor);
-                let gpu_particles: Vec<GpuParticle> =
-                    particles.iter().skip(skip).map(GpuParticle::from).collect();
-                let nc = data.events.node_count().max(1);
+                let (particles, cursor) =
+                    data.directed_buffer.get_new_since(self.gpu_upload_cursor, self.focused_node);
+                let gpu_particles: Vec<GpuParticle> = particles
+                    .filter(|p| self.source_visible(p.source_id as u8))
+                    .map(|p| self.ring_target.place(p))
+                    .collect();
+                let nc = data.events.index_span().max(1);
                 let mut counts = vec![0.0f32; nc];
                 for (node_id, node) in data.events.nodes() {
                     let idx = node.index as usize;
@@ -57,12 +114,12 @@ impl JamApp {
 
         // Allocate canvas
         let available = ui.available_size();
-        let (response, painter) = ui.allocate_painter(available, egui::Sense::hover());
+        let (response, painter) = ui.allocate_painter(available, egui::Sense::click());
         let rect = response.rect;
+        self.ring_clock.hovered |= response.hovered();
 
         let center = rect.center();
-        let pixel_radius = 0.75 * rect.height() * 0.5;
-        let num_nodes_f = num_nodes as f32;
+        let pixel_radius = self.ring_pixel_radius(rect);
 
         // Draw ring outline and node dots (CPU overlay, matched to GPU coords)
         painter.circle_stroke(
@@ -70,28 +127,36 @@ impl JamApp {
             pixel_radius,
             egui::Stroke::new(1.0, egui::Color32::from_rgba_unmultiplied(100, 100, 100, 40)),
         );
-        let num_dots = num_nodes.min(256);
+        self.draw_group_separators(&painter, center, pixel_radius);
+        let num_dots = self.dot_span(num_nodes);
         let max_peers = peer_counts.iter().cloned().fold(1.0f32, f32::max);
-        for (i, &count) in peer_counts.iter().enumerate().take(num_dots) {
-            let angle = (i as f32 / num_dots as f32) * 2.0 * PI - PI * 0.5;
-            let pos = center + egui::vec2(angle.cos(), angle.sin()) * pixel_radius;
-            let color = if self.node_brightness_enabled {
+        let dots = peer_counts.iter().enumerate().take(num_dots);
+        for (i, &count) in dots.filter(|&(i, _)| self.ring_layout.contains(i)) {
+            let pos = self.ring_layout.position(center, pixel_radius, i);
+            let color = if self.stalled_nodes.binary_search(&(i as u16)).is_ok() {
+                STALLED_DOT_COLOR
+            } else if self.node_brightness_enabled {
                 let brightness = (count / max_peers).clamp(0.1, 1.0);
                 let gray = (80.0 + brightness * 120.0) as u8;
                 let alpha = (60.0 + brightness * 180.0) as u8;
-                egui::Color32::from_rgba_unmultiplied(gray, gray, gray, alpha)
+                colors::shade(gray, alpha)
             } else {
-                egui::Color32::from_rgba_unmultiplied(150, 150, 150, 100)
+                colors::shade(150, 100)
             };
-            painter.circle_filled(pos, 4.0, color);
+            painter.circle_filled(pos, self.node_dot_radius, color);
         }
+        if self.show_node_indices {
+            self.draw_node_indices(&painter, center, pixel_radius, num_nodes);
+        }
+        self.handle_node_click(&response, &painter, center, pixel_radius, num_dots);
+        self.show_node_tooltip(&response, center, pixel_radius, num_dots);
 
         // Draw collapsing pulse overlays
-        self.draw_pulses(&painter, center, pixel_radius, num_nodes_f, now);
+        self.draw_pulses(&painter, center, pixel_radius, now);
 
         // Draw slot pulse
         if self.slot_pulse_enabled {
-            Self::draw_slot_pulse(&painter, center, pixel_radius);
+            Self::draw_slot_pulse(&painter, center, pixel_radius, self.view_now_unix());
         }
 
         // GPU paint callback for particles
@@ -99,11 +164,13 @@ impl JamApp {
         let aspect_ratio = rect.width() / rect.height();
         let uniforms = Uniforms {
             current_time: now,
-            num_validators: num_nodes as f32,
+            num_validators: self.ring_layout.span(),
             aspect_ratio,
             point_size: 0.005,
             speed_factor: self.speed_factor,
-            _pad: [0.0; 3],
+            ring_radius: self.ring_radius,
+            trail_length: self.effective_trail_length(),
+            _pad: 0.0,
         };
         painter.add(egui_wgpu::Callback::new_paint_callback(
             rect,
@@ -111,8 +178,10 @@ impl JamApp {
                 new_particles: Arc::new(new_particles),
                 uniforms,
                 filter,
-                color_lut: self.color_lut,
-                reset: false,
+                color_lut: self.display_lut(),
+                reset: std::mem::take(&mut self.ring_reset_pending),
+                bloom: self.ring_bloom,
+                rect,
             },
         ));
 
@@ -120,15 +189,17 @@ impl JamApp {
 
     /// CPU ring rendering path (WASM + native --use-cpu fallback)
     fn render_ring_tab_cpu(&mut self, ui: &mut egui::Ui) {
-        use std::f32::consts::PI;
-
-        let now = now_seconds() as f32;
-        let max_age = 5.0_f32;
+        let now = self.ring_now() as f32;
+        // Slowed-down particles live longer; keep them until they finish their path
+        let max_age = 5.0 / self.speed_factor;
+        self.refresh_ring_layout();
 
         let (particle_max, num_nodes, active_particles, peer_counts) =
             with_data!(self, |data| {
-                let particles = data.directed_buffer.get_active_particles(now, max_age);
-                let nc = data.events.node_count().max(1);
+                let mut particles =
+                    data.directed_buffer.get_active_particles(now, max_age, self.focused_node);
+                particles.retain(|p| self.source_visible(p.source_id as u8));
+                let nc = data.events.index_span().max(1);
                 let mut counts = vec![0.0f32; nc];
                 for (node_id, node) in data.events.nodes() {
                     let idx = node.index as usize;
@@ -152,12 +223,12 @@ impl JamApp {
 
         // Allocate canvas
         let available = ui.available_size();
-        let (response, painter) = ui.allocate_painter(available, egui::Sense::hover());
+        let (response, painter) = ui.allocate_painter(available, egui::Sense::click());
         let rect = response.rect;
+        self.ring_clock.hovered |= response.hovered();
 
         let center = rect.center();
-        let radius = rect.width().min(rect.height()) * 0.4;
-        let num_nodes_f = num_nodes as f32;
+        let radius = self.ring_pixel_radius(rect);
 
         // Draw ring outline
         painter.circle_stroke(
@@ -165,59 +236,86 @@ impl JamApp {
             radius,
             egui::Stroke::new(1.0, egui::Color32::from_rgba_unmultiplied(100, 100, 100, 40)),
         );
+        self.draw_group_separators(&painter, center, radius);
 
         // Draw node dots (brightness by peer count)
-        let num_dots = num_nodes.min(256);
+        let num_dots = self.dot_span(num_nodes);
         let max_peers = peer_counts.iter().cloned().fold(1.0f32, f32::max);
-        for (i, &count) in peer_counts.iter().enumerate().take(num_dots) {
-            let angle = (i as f32 / num_dots as f32) * 2.0 * PI - PI * 0.5;
-            let pos = center + egui::vec2(angle.cos(), angle.sin()) * radius;
-            let color = if self.node_brightness_enabled {
+        let dots = peer_counts.iter().enumerate().take(num_dots);
+        for (i, &count) in dots.filter(|&(i, _)| self.ring_layout.contains(i)) {
+            let pos = self.ring_layout.position(center, radius, i);
+            let color = if self.stalled_nodes.binary_search(&(i as u16)).is_ok() {
+                STALLED_DOT_COLOR
+            } else if self.node_brightness_enabled {
                 let brightness = (count / max_peers).clamp(0.1, 1.0);
                 let gray = (80.0 + brightness * 120.0) as u8;
                 let alpha = (60.0 + brightness * 180.0) as u8;
-                egui::Color32::from_rgba_unmultiplied(gray, gray, gray, alpha)
+                colors::shade(gray, alpha)
             } else {
-                egui::Color32::from_rgba_unmultiplied(150, 150, 150, 100)
+                colors::shade(150, 100)
             };
-            painter.circle_filled(pos, 4.0, color);
+            painter.circle_filled(pos, self.node_dot_radius, color);
         }
+        if self.show_node_indices {
+            self.draw_node_indices(&painter, center, radius, num_nodes);
+        }
+        self.handle_node_click(&response, &painter, center, radius, num_dots);
+        self.show_node_tooltip(&response, center, radius, num_dots);
 
         // Draw active particles (CPU path)
+        let lut = self.display_lut();
         const NUM_SAMPLES: usize = 16;
-        const DIRECTED_SPEED: f32 = 8.0;
+        // Must match the direction encoding in vring/shader.wgsl
+        const OUTBOUND_WIDTH_SCALE: f32 = 1.6;
+        const INBOUND_ALPHA_SCALE: f32 = 0.45;
+        const TRAIL_GHOSTS: usize = 4;
+        let trail_length = self.effective_trail_length();
+        let ghosts = if trail_length > 0.0 { TRAIL_GHOSTS } else { 0 };
         for particle in &active_particles {
             let age = now - particle.birth_time;
             let et_idx = particle.event_type as usize;
-            let [r, g, b, a] = self.color_lut.colors[et_idx];
+            let [r, g, b, a] = lut.colors[et_idx];
             let color = egui::Color32::from_rgba_unmultiplied(
                 (r * 255.0) as u8, (g * 255.0) as u8, (b * 255.0) as u8, (a * 255.0) as u8,
             );
 
             if particle.source_index == particle.target_index {
                 // ── Radial: circle particle ──
-                let radial_dur = particle.travel_duration / self.speed_factor;
-                let t = (age / radial_dur).clamp(0.0, 1.0);
+                let radial_dur = radial_duration(particle.travel_duration, self.speed_factor);
                 if age > radial_dur * 1.5 || age < 0.0 {
                     continue;
                 }
-                let angle = (particle.source_index / num_nodes_f) * 2.0 * PI - PI * 0.5;
+                let angle = self.ring_layout.angle(particle.source_index as usize);
                 let dir = egui::vec2(angle.cos(), angle.sin());
-                let r = radius + (radius * 0.2) * t;
-                let pos = center + dir * r;
-
-                let fade_in = (t / 0.1).min(1.0);
-                let fade_out = 1.0 - ((t - 0.9) / 0.1).max(0.0);
-                let alpha = (color.a() as f32 * fade_in * fade_out) as u8;
-                let final_color = egui::Color32::from_rgba_unmultiplied(
-                    color.r(), color.g(), color.b(), alpha,
-                );
-                painter.circle_filled(pos, 3.0, final_color);
+
+                // Ghost k lags the head by k/TRAIL_GHOSTS of the trail; drawn oldest first
+                for k in (0..=ghosts).rev() {
+                    let lag = k as f32 / TRAIL_GHOSTS as f32 * trail_length;
+                    let progress = age / radial_dur - lag;
+                    if progress < 0.0 {
+                        continue;
+                    }
+                    let t = progress.min(1.0);
+                    let r = radius + (radius * 0.2) * t;
+                    let pos = center + dir * r;
+
+                    let fade_in = (t / 0.1).min(1.0);
+                    let fade_out = 1.0 - ((t - 0.9) / 0.1).max(0.0);
+                    let ghost_fade = 1.0 - k as f32 / (TRAIL_GHOSTS + 1) as f32;
+                    let alpha = (color.a() as f32 * fade_in * fade_out * ghost_fade) as u8;
+                    let final_color = egui::Color32::from_rgba_unmultiplied(
+                        color.r(), color.g(), color.b(), alpha,
+                    );
+                    painter.circle_filled(pos, 3.0 * (1.0 - 0.15 * k as f32), final_color);
+                }
             } else {
                 // ── Directed: bezier trail line ──
-                let eff_dur = particle.travel_duration / (DIRECTED_SPEED * self.speed_factor);
+                let eff_dur = directed_duration(particle.travel_duration, self.speed_factor);
                 let t_head = (age / eff_dur).clamp(0.0, 1.0);
-                let t_tail = ((age - eff_dur) / eff_dur).clamp(0.0, 1.0);
+                let mut t_tail = ((age - eff_dur) / eff_dur).clamp(0.0, 1.0);
+                if trail_length > 0.0 {
+                    t_tail = t_tail.max(t_head - trail_length);
+                }
                 if age > eff_dur * 2.5 || age < 0.0 || t_head <= t_tail {
                     continue;
                 }
@@ -227,14 +325,10 @@ impl JamApp {
                 let fade_out = 1.0 - ((overall - 0.95) / 0.05).max(0.0);
                 let base_alpha = color.a() as f32 * fade_in * fade_out;
 
-                let source_angle =
-                    (particle.source_index / num_nodes_f) * 2.0 * PI - PI * 0.5;
-                let target_angle =
-                    (particle.target_index / num_nodes_f) * 2.0 * PI - PI * 0.5;
                 let source_pos =
-                    center + egui::vec2(source_angle.cos(), source_angle.sin()) * radius;
+                    self.ring_layout.position(center, radius, particle.source_index as usize);
                 let target_pos =
-                    center + egui::vec2(target_angle.cos(), target_angle.sin()) * radius;
+                    self.ring_layout.position(center, radius, particle.target_index as usize);
                 let mid = source_pos + (target_pos - source_pos) * 0.5;
                 let diff = target_pos - source_pos;
                 let perp = egui::vec2(-diff.y, diff.x).normalized();
@@ -257,43 +351,169 @@ impl JamApp {
                     })
                     .collect();
 
-                let trail_alpha = (base_alpha * 0.65) as u8;
+                // Outbound (sender's report) leads: wider and brighter than inbound
+                let (width, direction_alpha) = if particle.is_outbound > 0.5 {
+                    (OUTBOUND_WIDTH_SCALE, 1.0)
+                } else {
+                    (1.0, INBOUND_ALPHA_SCALE)
+                };
+                let trail_alpha = (base_alpha * 0.65 * direction_alpha) as u8;
                 let stroke_color = egui::Color32::from_rgba_unmultiplied(
                     color.r(), color.g(), color.b(), trail_alpha,
                 );
                 if points.len() >= 2 {
                     painter.add(egui::Shape::line(
                         points,
-                        egui::Stroke::new(1.0, stroke_color),
+                        egui::Stroke::new(width, stroke_color),
                     ));
                 }
             }
         }
 
         // Draw collapsing pulse overlays
-        self.draw_pulses(&painter, center, radius, num_nodes_f, now);
+        self.draw_pulses(&painter, center, radius, now);
 
         // Draw slot pulse
         if self.slot_pulse_enabled {
-            Self::draw_slot_pulse(&painter, center, radius);
+            Self::draw_slot_pulse(&painter, center, radius, self.view_now_unix());
         }
 
     }
 
 
+    /// Focus the clicked node (same node or empty space clears it), open its detail popup,
+    /// and outline the selected dot. Double-clicking a node inspects its newest event.
+    fn handle_node_click(
+        &mut self,
+        response: &egui::Response,
+        painter: &egui::Painter,
+        center: egui::Pos2,
+        radius: f32,
+        num_dots: usize,
+    ) {
+        // Particles are too transient to pick individually, so inspect the node's latest event
+        const INSPECT_WINDOW: f64 = 5.0;
+
+        let hit_radius = self.node_hit_radius();
+        let clicked = response
+            .interact_pointer_pos()
+            .and_then(|pos| node_at(pos, center, radius, &self.ring_layout, num_dots, hit_radius));
+        if response.double_clicked() {
+            // The first click of the pair already focused the node; keep it focused
+            if let Some(idx) = clicked {
+                self.inspect_event_near(idx, self.view_now(), INSPECT_WINDOW);
+            }
+        } else if response.clicked() {
+            let focus = if clicked == self.focused_node { None } else { clicked };
+            self.set_focused_node(focus);
+            self.selected_node = focus;
+        }
+        if let Some(idx) = self.selected_node.filter(|&i| (i as usize) < num_dots) {
+            let pos = self.ring_layout.position(center, radius, idx as usize);
+            painter.circle_stroke(
+                pos,
+                self.node_dot_radius + 4.0,
+                egui::Stroke::new(1.5, colors::text_primary()),
+            );
+        }
+    }
+
+    /// Tooltip with identity, slots, peers and recent activity for the dot under the pointer.
+    fn show_node_tooltip(
+        &self,
+        response: &egui::Response,
+        center: egui::Pos2,
+        radius: f32,
+        num_dots: usize,
+    ) {
+        const RECENT_WINDOW: f64 = 5.0;
+
+        let hit_radius = self.node_hit_radius();
+        let Some(idx) = response
+            .hover_pos()
+            .and_then(|pos| node_at(pos, center, radius, &self.ring_layout, num_dots, hit_radius))
+        else {
+            return;
+        };
+
+        let cutoff = self.view_now() - RECENT_WINDOW;
+        let info = with_data!(self, |data| {
+            data.events.node_id_at(idx).map(|node_id| {
+                (
+                    node_id.to_string(),
+                    data.blocks.best_for(node_id),
+                    data.blocks.finalized_for(node_id),
+                    data.time_series.latest_value(node_id),
+                    data.events.node(node_id).map_or(0, |n| n.count_since(cutoff)),
+                )
+            })
+        });
+        let Some((node_id, best, finalized, peers, recent)) = info else {
+            return;
+        };
+
+        let label = self.node_label(&node_id);
+        response.clone().on_hover_ui_at_pointer(|ui| {
+            let short_id = if node_id.len() > 16 {
+                format!("{}…", &node_id[..16])
+            } else {
+                node_id.clone()
+            };
+            let or_dash = |v: Option<u64>| v.map_or("-".to_string(), |s| s.to_string());
+
+            ui.label(
+                egui::RichText::new(format!("Node {} · {}", idx, label))
+                    .color(colors::text_primary()),
+            );
+            ui.label(egui::RichText::new(short_id).monospace().color(colors::text_secondary()));
+            ui.label(format!("best {} / final {}", or_dash(best), or_dash(finalized)));
+            ui.label(format!(
+                "peers {}",
+                peers.map_or("-".to_string(), |c| format!("{:.0}", c))
+            ));
+            ui.label(format!("{} events in last {:.0}s", recent, RECENT_WINDOW));
+        });
+    }
+
+    /// Indices below which dots are drawn: the first 256 in-use indices
+    fn dot_span(&self, num_nodes: usize) -> usize {
+        (0..num_nodes)
+            .filter(|&i| self.ring_layout.contains(i))
+            .take(256)
+            .last()
+            .map_or(0, |i| i + 1)
+    }
+
+    /// Small index labels just outside the dots; crowded rings label every Nth node only.
+    fn draw_node_indices(
+        &self,
+        painter: &egui::Painter,
+        center: egui::Pos2,
+        radius: f32,
+        num_nodes: usize,
+    ) {
+        let label_radius = radius + self.node_dot_radius + 10.0;
+        let font = egui::FontId::monospace(9.0);
+        let in_use = (0..num_nodes).filter(|&i| self.ring_layout.contains(i));
+        for i in in_use.step_by(index_label_stride(num_nodes)) {
+            painter.text(
+                self.ring_layout.position(center, label_radius, i),
+                egui::Align2::CENTER_CENTER,
+                i.to_string(),
+                font.clone(),
+                colors::text_muted(),
+            );
+        }
+    }
+
     /// Draw collapsing pulse circles as CPU overlay on the ring.
     pub(crate) fn draw_pulses(
         &self,
         painter: &egui::Painter,
         center: egui::Pos2,
         pixel_radius: f32,
-        num_nodes: f32,
         now: f32,
     ) {
-        use std::f32::consts::PI;
-        const PULSE_DURATION: f32 = 0.4;
-        const MAX_PULSE_RADIUS: f32 = 40.0;
-
         for pulse in &self.active_pulses {
             // Respect event type filter
             let et = pulse.event_type.idx();
@@ -302,16 +522,15 @@ impl JamApp {
             }
 
             let age = now - pulse.birth_time;
-            if !(0.0..PULSE_DURATION).contains(&age) {
+            if !(0.0..self.pulse_duration).contains(&age) {
                 continue;
             }
 
-            let t = age / PULSE_DURATION;
+            let t = age / self.pulse_duration;
             let radius_factor = (1.0 - t) * (1.0 - t);
-            let pulse_radius = MAX_PULSE_RADIUS * radius_factor;
+            let pulse_radius = self.pulse_radius * radius_factor;
 
-            let angle = (pulse.node_index as f32 / num_nodes) * 2.0 * PI - PI * 0.5;
-            let pos = center + egui::vec2(angle.cos(), angle.sin()) * pixel_radius;
+            let pos = self.ring_layout.position(center, pixel_radius, pulse.node_index as usize);
 
             let base_color = self.get_event_color(pulse.event_type);
             let alpha = (180.0 * (1.0 - t)) as u8;
@@ -332,11 +551,11 @@ impl JamApp {
         painter: &egui::Painter,
         center: egui::Pos2,
         pixel_radius: f32,
+        now_unix: f64,
     ) {
         const JAM_EPOCH: f64 = 1_735_732_800.0; // Jan 1 2025 00:00:00 UTC
         const SLOT_DURATION: f64 = 6.0;
 
-        let now_unix = crate::time::now_unix_seconds();
         let phase = ((now_unix - JAM_EPOCH) % SLOT_DURATION / SLOT_DURATION) as f32;
 
         // Ease-out: fast start, slow end (quadratic)
@@ -364,3 +583,152 @@ impl JamApp {
         painter.add(egui::Shape::line(points, egui::Stroke::new(stroke_width, color)));
     }
 }
+
+impl JamApp {
+    /// Ring radius in pixels. Matches the shader: `ring_radius` NDC of half the rect height.
+    fn ring_pixel_radius(&self, rect: egui::Rect) -> f32 {
+        self.ring_radius * rect.height() * 0.5
+    }
+
+    /// Pointer distance from a dot that still counts as hovering it
+    fn node_hit_radius(&self) -> f32 {
+        (self.node_dot_radius + 6.0).max(10.0)
+    }
+
+    /// Trail length handed to both ring paths; 0 when trails are off
+    fn effective_trail_length(&self) -> f32 {
+        if self.particle_trails {
+            self.trail_length
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Directed particles cross the ring this many times faster than their travel duration;
+/// must match DIRECTED_SPEED in vring/shader.wgsl
+const DIRECTED_SPEED: f32 = 8.0;
+
+/// Seconds a radial particle takes to reach the end of its path at `speed_factor`
+fn radial_duration(travel_duration: f32, speed_factor: f32) -> f32 {
+    travel_duration / speed_factor
+}
+
+/// Seconds a directed particle's head takes to reach its target at `speed_factor`
+fn directed_duration(travel_duration: f32, speed_factor: f32) -> f32 {
+    travel_duration / (DIRECTED_SPEED * speed_factor)
+}
+
+/// Dim red for nodes that have gone quiet (see `JamApp::stalled_nodes`)
+const STALLED_DOT_COLOR: egui::Color32 = egui::Color32::from_rgb(130, 60, 60);
+
+/// Most index labels drawn around the ring before thinning out
+const MAX_INDEX_LABELS: usize = 64;
+
+/// Label every Nth node, N from 1, 2, 5, 10, 20, 50, … so labels stay at round numbers
+fn index_label_stride(num_nodes: usize) -> usize {
+    let mut decade = 1;
+    loop {
+        for m in [1, 2, 5] {
+            let stride = decade * m;
+            if num_nodes.div_ceil(stride) <= MAX_INDEX_LABELS {
+                return stride;
+            }
+        }
+        decade *= 10;
+    }
+}
+
+/// Ring node under `pos` among the first `num_dots`, if the pointer is within `hit_radius`
+/// pixels of its dot.
+fn node_at(
+    pos: egui::Pos2,
+    center: egui::Pos2,
+    radius: f32,
+    layout: &RingLayout,
+    num_dots: usize,
+    hit_radius: f32,
+) -> Option<u16> {
+    (0..num_dots)
+        .map(|i| (i, layout.position(center, radius, i).distance(pos)))
+        .filter(|&(_, d)| d <= hit_radius)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(i, _)| i as u16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn node_at_hits_nearest_dot() {
+        let center = egui::pos2(0.0, 0.0);
+        let layout = RingLayout::grouped(&[0, 1, 2, 3], &[(); 4]);
+        // 4 dots: top, right, bottom, left
+        assert_eq!(node_at(egui::pos2(0.0, -95.0), center, 100.0, &layout, 4, 10.0), Some(0));
+        assert_eq!(node_at(egui::pos2(103.0, 2.0), center, 100.0, &layout, 4, 10.0), Some(1));
+        assert_eq!(node_at(egui::pos2(-100.0, 0.0), center, 100.0, &layout, 4, 10.0), Some(3));
+    }
+
+    #[test]
+    fn speed_factor_scales_cpu_particle_progress() {
+        // Radial: t = age / duration, half speed halves the progress
+        assert_eq!(0.5 / radial_duration(1.0, 1.0), 0.5);
+        assert_eq!(0.5 / radial_duration(1.0, 0.5), 0.25);
+        assert_eq!(0.5 / radial_duration(1.0, 2.0), 1.0);
+        // Directed: 8x faster than the travel duration, scaled the same way
+        assert_eq!(0.25 / directed_duration(4.0, 1.0), 0.5);
+        assert_eq!(0.25 / directed_duration(4.0, 0.5), 0.25);
+    }
+
+    #[test]
+    fn hover_slows_the_ring_clock_and_catches_up() {
+        let mut clock = HoverClock::default();
+        clock.step(0.0, 1.0);
+        // Freeze: once eased in, the ring clock stands still
+        for i in 1..=20 {
+            clock.hovered = true;
+            clock.step(i as f64 * 0.1, 1.0);
+        }
+        let frozen = clock.now(2.0);
+        clock.hovered = true;
+        clock.step(2.1, 1.0);
+        assert!((clock.now(2.1) - frozen).abs() < 1e-9);
+        assert!(clock.lag > 1.5);
+
+        // Pointer left: the lag is made up within a couple of seconds
+        for i in 22..=50 {
+            clock.step(i as f64 * 0.1, 1.0);
+        }
+        assert_eq!(clock.lag, 0.0);
+        assert_eq!(clock.now(5.0), 5.0);
+    }
+
+    #[test]
+    fn no_slowdown_keeps_the_view_clock() {
+        let mut clock = HoverClock::default();
+        for i in 0..10 {
+            clock.hovered = true;
+            clock.step(i as f64 * 0.1, 0.0);
+        }
+        assert_eq!(clock.now(1.0), 1.0);
+    }
+
+    #[test]
+    fn index_label_stride_is_round() {
+        assert_eq!(index_label_stride(0), 1);
+        assert_eq!(index_label_stride(64), 1);
+        assert_eq!(index_label_stride(65), 2);
+        assert_eq!(index_label_stride(200), 5);
+        assert_eq!(index_label_stride(1023), 20);
+    }
+
+    #[test]
+    fn node_at_misses_empty_space() {
+        let center = egui::pos2(0.0, 0.0);
+        let layout = RingLayout::grouped(&[0, 1, 2, 3], &[(); 4]);
+        assert_eq!(node_at(center, center, 100.0, &layout, 4, 10.0), None);
+        assert_eq!(node_at(egui::pos2(70.0, -70.0), center, 100.0, &layout, 4, 10.0), None);
+        assert_eq!(node_at(egui::pos2(0.0, -100.0), center, 100.0, &RingLayout::default(), 0, 10.0), None);
+    }
+}
@@ -0,0 +1,337 @@
+//! Ring layout — where each node sits on the ring, shared by the GPU and CPU paths
+
+use eframe::egui;
+use crate::vring::{DirectedParticleInstance, GpuParticle};
+use crate::time::now_seconds;
+use super::{JamApp, RingGrouping, with_data};
+
+/// Empty slots between two groups, as a fraction of the node count (at least one slot)
+const GROUP_GAP: f32 = 0.04;
+/// Seconds nodes take to glide to their new slots after the layout changes
+const TRANSITION_SECS: f64 = 0.3;
+
+/// Ring slot of every node, indexed by `NodeEvents::index`.
+/// Slot 0 is at the top and slots run clockwise; `span` slots make a full turn.
+/// Consecutive nodes of a group are one slot apart, with a wider gap between groups.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct RingLayout {
+    slots: Vec<f32>,
+    span: f32,
+    /// Slot positions of the separators drawn in each gap between groups
+    separators: Vec<f32>,
+}
+
+impl RingLayout {
+    /// Nodes ordered by `(groups[i], keys[i])` (both indexed by node index), with a gap between
+    /// groups. A single group is a plain sorted ring; keys of `0..n` give arrival order.
+    pub(crate) fn grouped<K: Ord, G: Ord>(keys: &[K], groups: &[G]) -> Self {
+        Self::grouped_among(keys, groups, |_| true)
+    }
+
+    /// Like [`grouped`](Self::grouped), placing only the indices for which `present` holds.
+    /// Hashed node indices leave unused indices, which get no slot and no ring space.
+    pub(crate) fn grouped_among<K: Ord, G: Ord>(
+        keys: &[K],
+        groups: &[G],
+        present: impl Fn(usize) -> bool,
+    ) -> Self {
+        let n = keys.len();
+        let mut order: Vec<usize> = (0..n).filter(|&i| present(i)).collect();
+        order.sort_by(|&a, &b| groups[a].cmp(&groups[b]).then_with(|| keys[a].cmp(&keys[b])));
+
+        let gap = (order.len() as f32 * GROUP_GAP).max(1.0);
+        let mut slots = vec![f32::NAN; n];
+        let mut separators = Vec::new();
+        let mut next = 0.0;
+        for (i, &idx) in order.iter().enumerate() {
+            if i > 0 && groups[idx] != groups[order[i - 1]] {
+                // Halfway between the last node of the previous group and the first of this one
+                separators.push(next + (gap - 1.0) * 0.5);
+                next += gap;
+            }
+            slots[idx] = next;
+            next += 1.0;
+        }
+        // The last group also needs a gap before wrapping around to the first
+        if !separators.is_empty() {
+            separators.push(next + (gap - 1.0) * 0.5);
+            next += gap;
+        }
+        Self { slots, span: next.max(1.0), separators }
+    }
+
+    /// Slots in one full turn (the shader's `num_validators`)
+    pub(crate) fn span(&self) -> f32 {
+        self.span
+    }
+
+    /// Ring slot of node `idx`; nodes newer than the layout fall back to their index,
+    /// unused indices inside it are NaN
+    pub(crate) fn slot(&self, idx: usize) -> f32 {
+        self.slots.get(idx).copied().unwrap_or(idx as f32)
+    }
+
+    /// Whether `idx` has a place on the ring (false for unused indices)
+    pub(crate) fn contains(&self, idx: usize) -> bool {
+        !self.slot(idx).is_nan()
+    }
+
+    /// Angle of node `idx` in radians (top of the ring, clockwise)
+    pub(crate) fn angle(&self, idx: usize) -> f32 {
+        self.slot_angle(self.slot(idx))
+    }
+
+    /// Angles of the separators between groups (empty when ungrouped)
+    pub(crate) fn separator_angles(&self) -> impl Iterator<Item = f32> + '_ {
+        self.separators.iter().map(|&slot| self.slot_angle(slot))
+    }
+
+    fn slot_angle(&self, slot: f32) -> f32 {
+        use std::f32::consts::PI;
+        (slot / self.span) * 2.0 * PI - PI * 0.5
+    }
+
+    /// Screen position of node `idx` on a ring of `radius` pixels
+    pub(crate) fn position(&self, center: egui::Pos2, radius: f32, idx: usize) -> egui::Pos2 {
+        let angle = self.angle(idx);
+        center + egui::vec2(angle.cos(), angle.sin()) * radius
+    }
+
+    /// GPU particle with its endpoints moved from node indices to ring slots
+    pub(crate) fn place(&self, p: &DirectedParticleInstance) -> GpuParticle {
+        GpuParticle {
+            source_index: self.slot(p.source_index as usize),
+            target_index: self.slot(p.target_index as usize),
+            ..GpuParticle::from(p)
+        }
+    }
+
+    /// Layout `t` (0..=1) of the way from `self` to `to`: slots and span move linearly, so
+    /// nodes glide along the ring. Nodes new in `to` start at their final slot.
+    fn lerp(&self, to: &RingLayout, t: f32) -> RingLayout {
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+        let slots = to
+            .slots
+            .iter()
+            .enumerate()
+            .map(|(i, &b)| match self.slots.get(i) {
+                Some(&a) if !a.is_nan() && !b.is_nan() => lerp(a, b),
+                _ => b,
+            })
+            .collect();
+        let separators = if self.separators.len() == to.separators.len() {
+            self.separators.iter().zip(&to.separators).map(|(&a, &b)| lerp(a, b)).collect()
+        } else {
+            to.separators.clone()
+        };
+        RingLayout { slots, span: lerp(self.span, to.span), separators }
+    }
+
+    /// Whether particles already placed with `self` sit somewhere else under `next`.
+    /// Appending nodes keeps existing slots, so arrival order never needs a re-upload.
+    pub(crate) fn moves_nodes(&self, next: &RingLayout) -> bool {
+        self.slots
+            .iter()
+            .enumerate()
+            .any(|(i, &slot)| !slot.is_nan() && next.slot(i) != slot)
+    }
+}
+
+/// Eased move from the layout drawn when the node set changed to the new one
+#[derive(Clone, Debug)]
+pub(crate) struct RingTransition {
+    from: RingLayout,
+    /// `now_seconds()` when the move started
+    started: f64,
+}
+
+impl RingTransition {
+    pub(crate) fn new(from: RingLayout, started: f64) -> Self {
+        Self { from, started }
+    }
+
+    /// Layout drawn at `now` on the way to `to`; None once the move is over
+    pub(crate) fn at(&self, to: &RingLayout, now: f64) -> Option<RingLayout> {
+        let t = ((now - self.started) / TRANSITION_SECS) as f32;
+        if !(0.0..1.0).contains(&t) {
+            return None;
+        }
+        // Ease in-out (smoothstep)
+        Some(self.from.lerp(to, t * t * (3.0 - 2.0 * t)))
+    }
+}
+
+impl JamApp {
+    /// Rebuild the ring layout for the current node set, ordering and grouping options, and
+    /// advance the drawn layout toward it (animated when `ring_transitions` is on).
+    /// If existing nodes move, the GPU particle buffer is re-uploaded with the new slots.
+    pub(crate) fn refresh_ring_layout(&mut self) {
+        self.update_ring_target();
+        let now = now_seconds();
+        match self.ring_transition.as_ref().and_then(|tr| tr.at(&self.ring_target, now)) {
+            Some(layout) => self.ring_layout = layout,
+            None => {
+                self.ring_transition = None;
+                if self.ring_layout != self.ring_target {
+                    self.ring_layout = self.ring_target.clone();
+                }
+            }
+        }
+    }
+
+    fn update_ring_target(&mut self) {
+        let now = self.view_now();
+        let layout = with_data!(self, |data| {
+            let num_nodes = data.events.index_span();
+            let mut in_use = vec![false; num_nodes];
+            for (_, node) in data.events.nodes() {
+                in_use[node.index as usize] = true;
+            }
+            let present = |idx: usize| in_use[idx];
+            let groups: Vec<u8> = match self.ring_grouping {
+                RingGrouping::None => vec![0; num_nodes],
+                RingGrouping::SyncStatus => {
+                    let entries = data.sync_status.entries(now);
+                    let mut groups = vec![SyncGroup::Unknown as u8; num_nodes];
+                    for (node_id, node) in data.events.nodes() {
+                        let group = match entries.binary_search_by(|e| e.0.cmp(node_id)) {
+                            Ok(i) => SyncGroup::of(entries[i].1, entries[i].2),
+                            Err(_) => SyncGroup::Unknown,
+                        };
+                        if let Some(slot) = groups.get_mut(node.index as usize) {
+                            *slot = group as u8;
+                        }
+                    }
+                    groups
+                }
+            };
+            if self.ring_order_stable {
+                // Operator label first, peer id as tie-break (and as the label fallback)
+                let mut keys = vec![(String::new(), String::new()); num_nodes];
+                for (node_id, node) in data.events.nodes() {
+                    if let Some(key) = keys.get_mut(node.index as usize) {
+                        *key = (self.node_label(node_id), node_id.to_string());
+                    }
+                }
+                RingLayout::grouped_among(&keys, &groups, present)
+            } else {
+                let arrival: Vec<usize> = (0..num_nodes).collect();
+                RingLayout::grouped_among(&arrival, &groups, present)
+            }
+        });
+        if layout == self.ring_target {
+            return;
+        }
+        if self.ring_target.moves_nodes(&layout) {
+            self.gpu_upload_cursor = 0;
+            self.ring_reset_pending = true;
+        }
+        // Start from what is on screen, so a change in the middle of a move doesn't jump
+        if self.ring_transitions && self.ring_layout.span() > 1.0 {
+            self.ring_transition = Some(RingTransition::new(self.ring_layout.clone(), now_seconds()));
+        }
+        self.ring_target = layout;
+    }
+
+    /// Faint radial ticks in the gaps between node groups
+    pub(crate) fn draw_group_separators(
+        &self,
+        painter: &egui::Painter,
+        center: egui::Pos2,
+        radius: f32,
+    ) {
+        const TICK: f32 = 14.0;
+        let stroke =
+            egui::Stroke::new(1.0, egui::Color32::from_rgba_unmultiplied(100, 100, 100, 60));
+        for angle in self.ring_layout.separator_angles() {
+            let dir = egui::vec2(angle.cos(), angle.sin());
+            painter.line_segment(
+                [center + dir * (radius - TICK), center + dir * (radius + TICK)],
+                stroke,
+            );
+        }
+    }
+}
+
+/// Ring group of a node under `RingGrouping::SyncStatus`, in ring order
+#[derive(Clone, Copy)]
+enum SyncGroup {
+    Synced = 0,
+    Syncing = 1,
+    /// No report yet, or the last one is older than `SYNC_STALE_SECS`
+    Unknown = 2,
+}
+
+impl SyncGroup {
+    fn of(synced: bool, stale: bool) -> Self {
+        match (synced, stale) {
+            (_, true) => SyncGroup::Unknown,
+            (true, false) => SyncGroup::Synced,
+            (false, false) => SyncGroup::Syncing,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sorted_layout_is_independent_of_arrival() {
+        let a = RingLayout::grouped(&["carol", "alice", "bob"], &[(); 3]);
+        let b = RingLayout::grouped(&["bob", "carol", "alice"], &[(); 3]);
+        // alice, bob, carol take slots 0, 1, 2 whatever their node index
+        assert_eq!((a.slot(1), a.slot(2), a.slot(0)), (0.0, 1.0, 2.0));
+        assert_eq!((b.slot(2), b.slot(0), b.slot(1)), (0.0, 1.0, 2.0));
+        assert_eq!(a.span(), 3.0);
+        assert_eq!(a.separator_angles().count(), 0);
+    }
+
+    #[test]
+    fn arrival_growth_keeps_slots() {
+        let small = RingLayout::grouped(&[0, 1, 2], &[(); 3]);
+        assert!(!small.moves_nodes(&RingLayout::grouped(&[0, 1, 2, 3, 4], &[(); 5])));
+        assert!(small.moves_nodes(&RingLayout::grouped(&["c", "b", "a"], &[(); 3])));
+        // Unknown nodes fall back to their index
+        assert_eq!(small.slot(7), 7.0);
+    }
+
+    #[test]
+    fn unused_indices_take_no_slot() {
+        // Hashed indices 0, 3 and 5 in use
+        let layout = RingLayout::grouped_among(&[0, 1, 2, 3, 4, 5], &[(); 6], |i| [0, 3, 5].contains(&i));
+        assert_eq!((layout.slot(0), layout.slot(3), layout.slot(5)), (0.0, 1.0, 2.0));
+        assert_eq!(layout.span(), 3.0);
+        assert!(layout.contains(3) && !layout.contains(1));
+        assert!(!layout.moves_nodes(&layout.clone()));
+        // A node hashed in between shifts the ones after it
+        let grown = RingLayout::grouped_among(&[0, 1, 2, 3, 4, 5], &[(); 6], |i| i != 1 && i != 4);
+        assert!(layout.moves_nodes(&grown));
+    }
+
+    #[test]
+    fn groups_are_contiguous_with_gaps() {
+        // Nodes 0 and 2 in group 0, 1 and 3 in group 1; gap of one slot (4 * 0.04 < 1)
+        let layout = RingLayout::grouped(&[0, 1, 2, 3], &[0, 1, 0, 1]);
+        assert_eq!((layout.slot(0), layout.slot(2)), (0.0, 1.0));
+        assert_eq!((layout.slot(1), layout.slot(3)), (3.0, 4.0));
+        // Two gaps: between the groups and before wrapping around
+        assert_eq!(layout.span(), 6.0);
+        assert_eq!(layout.separators, vec![2.0, 5.0]);
+    }
+
+    #[test]
+    fn transition_glides_to_the_new_layout() {
+        let from = RingLayout::grouped(&[0, 1, 2], &[(); 3]);
+        let to = RingLayout::grouped(&[0, 1, 2, 3], &[(); 4]);
+        let transition = RingTransition::new(from.clone(), 10.0);
+
+        assert_eq!(transition.at(&to, 10.0), Some(from.lerp(&to, 0.0)));
+        let halfway = transition.at(&to, 10.0 + TRANSITION_SECS / 2.0).unwrap();
+        assert_eq!(halfway.span(), 3.5);
+        // The new node is in place from the start; the others close up as the span grows
+        assert_eq!(halfway.slot(3), 3.0);
+        assert!(halfway.angle(2) > to.angle(2) && halfway.angle(2) < from.angle(2));
+        assert_eq!(transition.at(&to, 10.0 + TRANSITION_SECS), None);
+    }
+}
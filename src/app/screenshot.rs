@@ -0,0 +1,195 @@
+//! PNG screenshot of the whole window, with a footer strip naming the time and endpoint.
+//!
+//! eframe answers `ViewportCommand::Screenshot` with an `Event::Screenshot` a frame or two
+//! later, so the capture runs as a small state machine across frames.
+
+use std::sync::Arc;
+
+use eframe::egui;
+use tracing::{error, info, warn};
+use crate::theme::colors;
+use crate::time::{now_seconds, now_unix_seconds, utc_parts};
+use super::JamApp;
+
+/// Height of the footer strip drawn while a capture is pending
+const FOOTER_HEIGHT: f32 = 20.0;
+/// Seconds to wait for the screenshot event before giving up
+const SCREENSHOT_TIMEOUT_SECS: f64 = 3.0;
+
+/// Where a screenshot request stands
+#[derive(Default)]
+pub(crate) enum ScreenshotState {
+    #[default]
+    Idle,
+    /// Footer is on screen; the request goes out at the end of this frame
+    Pending(Capture),
+    /// Request sent at `now_seconds()` = the second field; waiting for the image
+    Requested(Capture, f64),
+}
+
+/// Destination and footer text of one capture
+pub(crate) struct Capture {
+    #[cfg(not(target_arch = "wasm32"))]
+    path: std::path::PathBuf,
+    footer: String,
+}
+
+/// `jam-orbit-20261016-115045.png`
+fn default_file_name(unix_seconds: f64) -> String {
+    let (y, mo, d, h, mi, s) = utc_parts(unix_seconds);
+    format!("jam-orbit-{:04}{:02}{:02}-{:02}{:02}{:02}.png", y, mo, d, h, mi, s)
+}
+
+/// `jam-orbit · 2026-10-16 11:50:45 UTC · ws://…`
+fn footer_text(unix_seconds: f64, url: &str) -> String {
+    let (y, mo, d, h, mi, s) = utc_parts(unix_seconds);
+    format!(
+        "jam-orbit · {:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC · {}",
+        y, mo, d, h, mi, s, url,
+    )
+}
+
+impl JamApp {
+    /// Header button: pick a destination (native) and start a capture
+    pub(crate) fn start_screenshot(&mut self) {
+        if !matches!(self.screenshot, ScreenshotState::Idle) {
+            return;
+        }
+        let now = now_unix_seconds();
+        #[cfg(not(target_arch = "wasm32"))]
+        let Some(path) = rfd::FileDialog::new()
+            .set_file_name(default_file_name(now))
+            .add_filter("PNG", &["png"])
+            .save_file()
+        else {
+            return;
+        };
+        self.screenshot = ScreenshotState::Pending(Capture {
+            #[cfg(not(target_arch = "wasm32"))]
+            path,
+            footer: footer_text(now, &self.ws_url),
+        });
+    }
+
+    /// Once per frame, after the UI is drawn: show the footer, send the request,
+    /// and save the image when it arrives
+    pub(crate) fn drive_screenshot(&mut self, ctx: &egui::Context) {
+        let state = std::mem::take(&mut self.screenshot);
+        self.screenshot = match state {
+            ScreenshotState::Idle => ScreenshotState::Idle,
+            ScreenshotState::Pending(capture) => {
+                draw_footer(ctx, &capture.footer);
+                ctx.send_viewport_cmd(egui::ViewportCommand::Screenshot(Default::default()));
+                ScreenshotState::Requested(capture, now_seconds())
+            }
+            ScreenshotState::Requested(capture, since) => {
+                // The capture is of a frame after the request, so the footer stays up
+                draw_footer(ctx, &capture.footer);
+                let image = ctx.input(|i| {
+                    i.raw.events.iter().find_map(|e| match e {
+                        egui::Event::Screenshot { image, .. } => Some(image.clone()),
+                        _ => None,
+                    })
+                });
+                if let Some(image) = image {
+                    save_screenshot(&capture, image);
+                    ScreenshotState::Idle
+                } else if now_seconds() - since > SCREENSHOT_TIMEOUT_SECS {
+                    warn!("No screenshot received; the renderer may not support it");
+                    ScreenshotState::Idle
+                } else {
+                    ctx.request_repaint();
+                    ScreenshotState::Requested(capture, since)
+                }
+            }
+        };
+    }
+}
+
+/// Dark strip along the bottom edge, above everything else
+fn draw_footer(ctx: &egui::Context, text: &str) {
+    let screen = ctx.screen_rect();
+    let rect = egui::Rect::from_min_max(
+        egui::pos2(screen.left(), screen.bottom() - FOOTER_HEIGHT),
+        screen.max,
+    );
+    let painter = ctx.layer_painter(egui::LayerId::new(
+        egui::Order::Tooltip,
+        egui::Id::new("screenshot_footer"),
+    ));
+    painter.rect_filled(rect, 0.0, colors::bg_primary());
+    painter.text(
+        egui::pos2(rect.left() + 8.0, rect.center().y),
+        egui::Align2::LEFT_CENTER,
+        text,
+        egui::FontId::monospace(11.0),
+        colors::text_secondary(),
+    );
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn save_screenshot(capture: &Capture, shot: Arc<egui::ColorImage>) {
+    let [width, height] = shot.size;
+    let result = image::save_buffer(
+        &capture.path,
+        shot.as_raw(),
+        width as u32,
+        height as u32,
+        image::ExtendedColorType::Rgba8,
+    );
+    match result {
+        Ok(()) => info!(path = %capture.path.display(), width, height, "Saved screenshot"),
+        Err(e) => error!(path = %capture.path.display(), error = %e, "Screenshot save failed"),
+    }
+}
+
+/// Read the pixels back through a 2D canvas and hand its PNG to the browser as a download
+#[cfg(target_arch = "wasm32")]
+fn save_screenshot(_capture: &Capture, shot: Arc<egui::ColorImage>) {
+    use wasm_bindgen::JsCast;
+
+    let [width, height] = shot.size;
+    let result = (|| -> Result<(), wasm_bindgen::JsValue> {
+        let document = web_sys::window()
+            .and_then(|w| w.document())
+            .ok_or("no document")?;
+        let canvas: web_sys::HtmlCanvasElement = document.create_element("canvas")?.dyn_into()?;
+        canvas.set_width(width as u32);
+        canvas.set_height(height as u32);
+        let context: web_sys::CanvasRenderingContext2d = canvas
+            .get_context("2d")?
+            .ok_or("no 2d context")?
+            .dyn_into()?;
+        let pixels = web_sys::ImageData::new_with_u8_clamped_array_and_sh(
+            wasm_bindgen::Clamped(shot.as_raw()),
+            width as u32,
+            height as u32,
+        )?;
+        context.put_image_data(&pixels, 0.0, 0.0)?;
+        let url = canvas.to_data_url_with_type("image/png")?;
+
+        let link: web_sys::HtmlAnchorElement = document.create_element("a")?.dyn_into()?;
+        link.set_href(&url);
+        link.set_download(&default_file_name(now_unix_seconds()));
+        link.click();
+        Ok(())
+    })();
+    match result {
+        Ok(()) => info!(width, height, "Saved screenshot"),
+        Err(e) => error!(error = ?e, "Screenshot save failed"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_name_and_footer_carry_utc_time() {
+        assert_eq!(default_file_name(1_792_151_445.0), "jam-orbit-20261016-115045.png");
+        assert_eq!(
+            footer_text(1_792_151_445.0, "ws://localhost:8080"),
+            "jam-orbit · 2026-10-16 11:50:45 UTC · ws://localhost:8080",
+        );
+    }
+}
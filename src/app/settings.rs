@@ -1,9 +1,29 @@
-//! Settings sidebar — ring visualization toggles, particle speed, color schema
+//! Settings sidebar — ring visualization toggles, particle speed, event rate mode, retention, color schema
 
 use eframe::egui;
-use crate::theme::colors;
-use crate::vring::ColorSchema;
-use super::JamApp;
+use crate::theme::{colors, Theme};
+use crate::core::events::default_travel_durations;
+use crate::core::{event_name, BIDIR_EVENTS, EVENT_CATEGORIES, INBOUND_EVENTS, OUTBOUND_EVENTS};
+use crate::vring::{ColorSchema, CvdMode};
+use super::graphs::PEER_CHANNEL_NAMES;
+use super::{
+    DiagRateMode, JamApp, RateMode, RateSmoothing, RingGrouping, SeriesBand, DEFAULT_DIAG_INTERVAL,
+    DEFAULT_HOVER_SLOWDOWN,
+    DEFAULT_MAX_EVENTS_PER_NODE, DEFAULT_NODE_DOT_RADIUS, DEFAULT_PULSE_DURATION, DEFAULT_PULSE_RADIUS,
+    DEFAULT_PULSE_TYPES, DEFAULT_RATE_SMOOTHING_ALPHA,
+    DEFAULT_RATE_SMOOTHING_WINDOW, DEFAULT_RATE_Y_MAX, DEFAULT_RETENTION_SECS, DEFAULT_RING_RADIUS,
+    DEFAULT_SCATTER_PARTICLE_CAP, DEFAULT_SCATTER_WINDOW, DEFAULT_STALL_THRESHOLD,
+    DEFAULT_TIME_WINDOW, DEFAULT_TRAIL_LENGTH, MAX_TRAVEL_DURATION, MIN_TRAVEL_DURATION, TIME_WINDOW_PRESETS,
+};
+
+/// Window length for labels: whole minutes as "5m", otherwise seconds
+pub(crate) fn format_window(secs: f32) -> String {
+    if secs >= 60.0 && secs % 60.0 == 0.0 {
+        format!("{:.0}m", secs / 60.0)
+    } else {
+        format!("{:.0}s", secs)
+    }
+}
 
 impl JamApp {
     pub(crate) fn render_settings(&mut self, ctx: &egui::Context) {
@@ -12,33 +32,37 @@ impl JamApp {
             .default_width(half_width)
             .min_width(240.0)
             .resizable(true)
-            .frame(egui::Frame::new().fill(colors::BG_PRIMARY).inner_margin(8.0))
+            .frame(egui::Frame::new().fill(colors::bg_primary()).inner_margin(8.0))
             .show(ctx, |ui| {
                 let group_frame = egui::Frame::new()
-                    .stroke(egui::Stroke::new(1.0, colors::TEXT_MUTED.gamma_multiply(0.6)))
+                    .stroke(egui::Stroke::new(1.0, colors::text_muted().gamma_multiply(0.6)))
                     .corner_radius(4.0)
                     .inner_margin(6.0);
 
                 group_frame.show(ui, |ui| {
                     ui.set_min_width(ui.available_width());
-                    ui.label(egui::RichText::new("Ring:").color(colors::TEXT_MUTED));
+                    ui.label(egui::RichText::new("Ring:").color(colors::text_muted()));
 
                     ui.checkbox(&mut self.slot_pulse_enabled, "Slot pulse");
+                    self.pulse_editor(ui);
+                    ui.add_enabled(!self.use_cpu, egui::Checkbox::new(&mut self.ring_bloom, "Glow"))
+                        .on_hover_text("Bloom pass over the particles; costs GPU time")
+                        .on_disabled_hover_text("Needs the GPU renderer");
                     ui.checkbox(
                         &mut self.node_brightness_enabled,
-                        egui::RichText::new("Node brightness").color(colors::TEXT_PRIMARY),
+                        egui::RichText::new("Node brightness").color(colors::text_primary()),
                     );
                     if self.node_brightness_enabled {
                         ui.label(
                             egui::RichText::new("  Dot brightness reflects peer count")
-                                .color(colors::TEXT_MUTED)
+                                .color(colors::text_muted())
                                 .small(),
                         );
                     }
 
                     ui.add_space(4.0);
                     let speed_label = format!("Particle speed: {:.1}x", self.speed_factor);
-                    ui.label(egui::RichText::new(speed_label).color(colors::TEXT_MUTED));
+                    ui.label(egui::RichText::new(speed_label).color(colors::text_muted()));
                     let full_width = ui.available_width();
                     ui.spacing_mut().slider_width = full_width;
                     let speed_response = ui.add(
@@ -50,17 +74,553 @@ impl JamApp {
                     if speed_response.double_clicked() {
                         self.speed_factor = 1.0;
                     }
+                    let hover_label = if self.hover_slowdown >= 1.0 {
+                        "Slow down on hover: freeze".to_string()
+                    } else {
+                        format!("Slow down on hover: {:.0}%", self.hover_slowdown * 100.0)
+                    };
+                    ui.label(egui::RichText::new(hover_label).color(colors::text_muted()))
+                        .on_hover_text("Particles slow down while the pointer is over the ring");
+                    let hover_response = ui.add(
+                        egui::Slider::new(&mut self.hover_slowdown, 0.0..=1.0)
+                            .clamping(egui::SliderClamping::Always)
+                            .show_value(false),
+                    );
+                    if hover_response.double_clicked() {
+                        self.hover_slowdown = DEFAULT_HOVER_SLOWDOWN;
+                    }
+                    self.travel_duration_editor(ui);
+
+                    ui.add_space(4.0);
+                    ui.checkbox(&mut self.particle_trails, "Particle trails");
+                    if self.particle_trails {
+                        let trail_label = format!("Trail length: {:.0}%", self.trail_length * 100.0);
+                        ui.label(egui::RichText::new(trail_label).color(colors::text_muted()));
+                        let trail_response = ui.add(
+                            egui::Slider::new(&mut self.trail_length, 0.05..=1.0)
+                                .clamping(egui::SliderClamping::Always)
+                                .show_value(false),
+                        );
+                        if trail_response.double_clicked() {
+                            self.trail_length = DEFAULT_TRAIL_LENGTH;
+                        }
+                    }
+
+                    ui.add_space(4.0);
+                    let ring_label = format!("Ring size: {:.0}%", self.ring_radius * 100.0);
+                    ui.label(egui::RichText::new(ring_label).color(colors::text_muted()));
+                    let ring_response = ui.add(
+                        egui::Slider::new(&mut self.ring_radius, 0.3..=0.95)
+                            .clamping(egui::SliderClamping::Always)
+                            .show_value(false),
+                    );
+                    if ring_response.double_clicked() {
+                        self.ring_radius = DEFAULT_RING_RADIUS;
+                    }
+
+                    ui.add_space(4.0);
+                    let dot_label = format!("Dot size: {:.1}px", self.node_dot_radius);
+                    ui.label(egui::RichText::new(dot_label).color(colors::text_muted()));
+                    let dot_response = ui.add(
+                        egui::Slider::new(&mut self.node_dot_radius, 1.0..=12.0)
+                            .logarithmic(true)
+                            .clamping(egui::SliderClamping::Always)
+                            .show_value(false),
+                    );
+                    if dot_response.double_clicked() {
+                        self.node_dot_radius = DEFAULT_NODE_DOT_RADIUS;
+                    }
+
+                    ui.add_space(4.0);
+                    ui.checkbox(&mut self.ring_order_stable, "Stable node order")
+                        .on_hover_text(
+                            "Place nodes by label / peer id so they keep their spot across \
+                             sessions (otherwise by arrival order)",
+                        );
+                    ui.checkbox(&mut self.ring_transitions, "Animate node moves")
+                        .on_hover_text(
+                            "Glide nodes to their new spots when validators join or leave or the \
+                             order changes, instead of jumping",
+                        );
+                    if ui
+                        .checkbox(&mut self.hashed_node_indices, "Stable node indices")
+                        .on_hover_text(
+                            "Derive each node's index from its peer id, so it keeps its ring slot \
+                             and scatter column across reconnects. Clears the current data.",
+                        )
+                        .changed()
+                    {
+                        self.clear_data();
+                    }
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("Group by:").color(colors::text_muted()));
+                        ui.radio_value(&mut self.ring_grouping, RingGrouping::None, "None");
+                        ui.radio_value(&mut self.ring_grouping, RingGrouping::SyncStatus, "Sync");
+                    });
+                    ui.checkbox(&mut self.show_node_indices, "Node indices")
+                        .on_hover_text("Label ring nodes with their index (every Nth when crowded)");
+
+                    ui.add_space(4.0);
+                    ui.checkbox(&mut self.show_legend, "Legend");
+
+                    ui.add_space(4.0);
+                    let stall_label = format!("Stalled after: {:.0}s", self.stall_threshold);
+                    ui.label(egui::RichText::new(stall_label).color(colors::text_muted()));
+                    let stall_response = ui.add(
+                        egui::Slider::new(&mut self.stall_threshold, 2.0..=120.0)
+                            .logarithmic(true)
+                            .clamping(egui::SliderClamping::Always)
+                            .show_value(false),
+                    );
+                    if stall_response.double_clicked() {
+                        self.stall_threshold = DEFAULT_STALL_THRESHOLD;
+                    }
+                });
+
+                ui.add_space(8.0);
+
+                group_frame.show(ui, |ui| {
+                    ui.set_min_width(ui.available_width());
+                    ui.label(egui::RichText::new("Event rate:").color(colors::text_muted()));
+                    ui.radio_value(&mut self.rate_mode, RateMode::PerNode, "Per node");
+                    ui.radio_value(&mut self.rate_mode, RateMode::PerCategory, "Per category");
+                    ui.radio_value(&mut self.rate_mode, RateMode::Stacked, "Stacked");
+
+                    ui.add_space(4.0);
+                    ui.label(egui::RichText::new("Per-node smoothing:").color(colors::text_muted()));
+                    ui.horizontal(|ui| {
+                        ui.radio_value(&mut self.rate_smoothing, RateSmoothing::Raw, "Raw");
+                        ui.radio_value(&mut self.rate_smoothing, RateSmoothing::MovingAverage, "Average");
+                        ui.radio_value(&mut self.rate_smoothing, RateSmoothing::Exponential, "EMA");
+                    });
+                    match self.rate_smoothing {
+                        RateSmoothing::Raw => {}
+                        RateSmoothing::MovingAverage => {
+                            let label = format!("Window: {} buckets (centered)", self.rate_smoothing_window);
+                            ui.label(egui::RichText::new(label).color(colors::text_muted()));
+                            ui.spacing_mut().slider_width = ui.available_width();
+                            let response = ui.add(
+                                egui::Slider::new(&mut self.rate_smoothing_window, 3..=15)
+                                    .step_by(2.0)
+                                    .clamping(egui::SliderClamping::Always)
+                                    .show_value(false),
+                            );
+                            if response.double_clicked() {
+                                self.rate_smoothing_window = DEFAULT_RATE_SMOOTHING_WINDOW;
+                            }
+                        }
+                        RateSmoothing::Exponential => {
+                            let label = format!("Alpha: {:.2} (trails raw)", self.rate_smoothing_alpha);
+                            ui.label(egui::RichText::new(label).color(colors::text_muted()));
+                            ui.spacing_mut().slider_width = ui.available_width();
+                            let response = ui.add(
+                                egui::Slider::new(&mut self.rate_smoothing_alpha, 0.05..=0.9)
+                                    .logarithmic(true)
+                                    .clamping(egui::SliderClamping::Always)
+                                    .show_value(false),
+                            );
+                            if response.double_clicked() {
+                                self.rate_smoothing_alpha = DEFAULT_RATE_SMOOTHING_ALPHA;
+                            }
+                        }
+                    }
+
+                    ui.add_space(4.0);
+                    ui.checkbox(&mut self.rate_y_autoscale, "Autoscale Y axis");
+                    if !self.rate_y_autoscale {
+                        let label = format!("Y ceiling: {:.0}/s", self.rate_y_max);
+                        ui.label(egui::RichText::new(label).color(colors::text_muted()));
+                        ui.spacing_mut().slider_width = ui.available_width();
+                        let response = ui.add(
+                            egui::Slider::new(&mut self.rate_y_max, 5.0..=100_000.0)
+                                .logarithmic(true)
+                                .clamping(egui::SliderClamping::Always)
+                                .show_value(false),
+                        );
+                        if response.double_clicked() {
+                            self.rate_y_max = DEFAULT_RATE_Y_MAX;
+                        }
+                    }
+                    ui.checkbox(&mut self.rate_y_log, "Log scale");
+                });
+
+                ui.add_space(8.0);
+
+                group_frame.show(ui, |ui| {
+                    ui.set_min_width(ui.available_width());
+                    ui.label(egui::RichText::new("Node series:").color(colors::text_muted()));
+                    ui.checkbox(&mut self.peer_lines_colored, "Color lines by validator");
+                    ui.label(egui::RichText::new("Peer count:").color(colors::text_muted()));
+                    ui.horizontal_wrapped(|ui| {
+                        for (enabled, name) in self.peer_channels.iter_mut().zip(PEER_CHANNEL_NAMES) {
+                            ui.checkbox(enabled, name);
+                        }
+                    })
+                    .response
+                    .on_hover_text("Validator peers dashed, sync peers dotted");
+                    ui.horizontal(|ui| {
+                        ui.radio_value(&mut self.peer_band, SeriesBand::Lines, "Lines");
+                        ui.radio_value(&mut self.peer_band, SeriesBand::Band, "Band");
+                        ui.radio_value(&mut self.peer_band, SeriesBand::Both, "Both");
+                    })
+                    .response
+                    .on_hover_text("Band: network min/max shaded, median line");
+                });
+
+                ui.add_space(8.0);
+
+                group_frame.show(ui, |ui| {
+                    ui.set_min_width(ui.available_width());
+                    self.render_toast_settings(ui);
+                });
+
+                ui.add_space(8.0);
+
+                group_frame.show(ui, |ui| {
+                    ui.set_min_width(ui.available_width());
+                    self.render_alert_settings(ui);
+                });
+
+                ui.add_space(8.0);
+
+                group_frame.show(ui, |ui| {
+                    ui.set_min_width(ui.available_width());
+                    let interval_label = format!("Diagnostics every: {:.2}s", self.diag_interval);
+                    ui.label(egui::RichText::new(interval_label).color(colors::text_muted()));
+                    ui.spacing_mut().slider_width = ui.available_width();
+                    let interval_response = ui.add(
+                        egui::Slider::new(&mut self.diag_interval, 0.25..=5.0)
+                            .logarithmic(true)
+                            .clamping(egui::SliderClamping::Always)
+                            .show_value(false),
+                    );
+                    if interval_response.double_clicked() {
+                        self.diag_interval = DEFAULT_DIAG_INTERVAL;
+                    }
+                    ui.radio_value(&mut self.diag_rate_mode, DiagRateMode::Instant, "Per interval");
+                    ui.radio_value(&mut self.diag_rate_mode, DiagRateMode::Average, "Moving average");
+                });
+
+                ui.add_space(8.0);
+
+                let scatter_age = self.scatter_max_age();
+                group_frame.show(ui, |ui| {
+                    ui.set_min_width(ui.available_width());
+                    ui.label(egui::RichText::new("Time window:").color(colors::text_muted()));
+                    ui.horizontal(|ui| {
+                        for preset in TIME_WINDOW_PRESETS {
+                            let selected = self.time_window == preset;
+                            if ui.selectable_label(selected, format_window(preset)).clicked() {
+                                self.time_window = preset;
+                            }
+                        }
+                    });
+                    let label = format!("Custom: {}", format_window(self.time_window));
+                    ui.label(egui::RichText::new(label).color(colors::text_muted()));
+                    ui.spacing_mut().slider_width = ui.available_width();
+                    let response = ui.add(
+                        egui::Slider::new(&mut self.time_window, 1.0..=600.0)
+                            .logarithmic(true)
+                            .step_by(1.0)
+                            .clamping(egui::SliderClamping::Always)
+                            .show_value(false),
+                    );
+                    if response.double_clicked() {
+                        self.time_window = DEFAULT_TIME_WINDOW;
+                    }
+                    if self.time_window > self.retention_secs {
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "Only {} of events are kept",
+                                format_window(self.retention_secs.round()),
+                            ))
+                            .color(egui::Color32::from_rgb(200, 200, 100))
+                            .small(),
+                        );
+                    }
+
+                    ui.add_space(4.0);
+                    let mut own_window = self.scatter_window.is_some();
+                    if ui
+                        .checkbox(&mut own_window, "Event Particles: own window")
+                        .on_hover_text("A shorter window keeps the particle scatter readable")
+                        .changed()
+                    {
+                        self.scatter_window = own_window.then_some(DEFAULT_SCATTER_WINDOW);
+                    }
+                    if let Some(ref mut window) = self.scatter_window {
+                        let label = format!("Particles: {}", format_window(*window));
+                        ui.label(egui::RichText::new(label).color(colors::text_muted()));
+                        let response = ui.add(
+                            egui::Slider::new(window, 1.0..=600.0)
+                                .logarithmic(true)
+                                .step_by(1.0)
+                                .clamping(egui::SliderClamping::Always)
+                                .show_value(false),
+                        );
+                        if response.double_clicked() {
+                            *window = DEFAULT_SCATTER_WINDOW;
+                        }
+                    }
+                });
+                // Particles are filtered by age on upload, so a longer window needs a re-upload
+                if self.scatter_max_age() > scatter_age {
+                    self.scatter_upload_cursor = 0;
+                    self.scatter_reset_pending = true;
+                }
+
+                ui.add_space(8.0);
+
+                group_frame.show(ui, |ui| {
+                    ui.set_min_width(ui.available_width());
+                    let retention_label = format!("Keep events: {:.0}s", self.retention_secs);
+                    ui.label(egui::RichText::new(retention_label).color(colors::text_muted()));
+                    ui.spacing_mut().slider_width = ui.available_width();
+                    let retention_response = ui.add(
+                        egui::Slider::new(&mut self.retention_secs, 10.0..=600.0)
+                            .logarithmic(true)
+                            .clamping(egui::SliderClamping::Always)
+                            .show_value(false),
+                    );
+                    if retention_response.double_clicked() {
+                        self.retention_secs = DEFAULT_RETENTION_SECS;
+                    }
+
+                    ui.collapsing("Advanced", |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label(egui::RichText::new("Max events per type:").color(colors::text_muted()));
+                            let response = ui.add(
+                                egui::DragValue::new(&mut self.max_events_per_node)
+                                    .range(1_000..=1_000_000)
+                                    .speed(1_000),
+                            );
+                            if response.double_clicked() {
+                                self.max_events_per_node = DEFAULT_MAX_EVENTS_PER_NODE;
+                            }
+                        });
+                        ui.label(
+                            egui::RichText::new("  Per node; oldest events are dropped beyond this")
+                                .color(colors::text_muted())
+                                .small(),
+                        );
+                        ui.horizontal(|ui| {
+                            ui.label(egui::RichText::new("Max scatter points:").color(colors::text_muted()));
+                            let response = ui.add(
+                                egui::DragValue::new(&mut self.scatter_particle_cap)
+                                    .range(10_000..=5_000_000)
+                                    .speed(10_000),
+                            );
+                            if response.double_clicked() {
+                                self.scatter_particle_cap = DEFAULT_SCATTER_PARTICLE_CAP;
+                            }
+                        });
+                    });
                 });
 
                 ui.add_space(8.0);
 
                 group_frame.show(ui, |ui| {
                     ui.set_min_width(ui.available_width());
-                    ui.label(egui::RichText::new("Color schema:").color(colors::TEXT_MUTED));
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("Theme:").color(colors::text_muted()));
+                        for theme in [Theme::Dark, Theme::Light] {
+                            ui.radio_value(&mut self.theme, theme, theme.label());
+                        }
+                    });
+                    ui.add_space(4.0);
+                    ui.label(egui::RichText::new("Color schema:").color(colors::text_muted()));
                     for &schema in ColorSchema::ALL {
                         ui.radio_value(&mut self.color_schema, schema, schema.label());
                     }
+                    let is_custom = matches!(self.color_schema, ColorSchema::Custom(_));
+                    if ui.radio(is_custom, "Custom").clicked() {
+                        self.color_schema = ColorSchema::Custom(self.custom_palette);
+                    }
+                    if is_custom {
+                        let mut changed = false;
+                        egui::Grid::new("custom_palette").num_columns(2).show(ui, |ui| {
+                            for (color, category) in self.custom_palette.iter_mut().zip(EVENT_CATEGORIES) {
+                                changed |= ui.color_edit_button_rgba_unmultiplied(color).changed();
+                                ui.label(egui::RichText::new(category.name).color(colors::text_secondary()));
+                                ui.end_row();
+                            }
+                        });
+                        if ui.small_button("Copy Vivid").clicked() {
+                            self.custom_palette = *ColorSchema::Vivid.colors();
+                            changed = true;
+                        }
+                        // Picked up by the color_schema change detection in update()
+                        if changed {
+                            self.color_schema = ColorSchema::Custom(self.custom_palette);
+                        }
+                    }
+
+                    ui.add_space(4.0);
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("Preview as:").color(colors::text_muted()));
+                        egui::ComboBox::from_id_salt("cvd_mode")
+                            .selected_text(self.cvd_mode.label())
+                            .show_ui(ui, |ui| {
+                                for &mode in CvdMode::ALL {
+                                    ui.selectable_value(&mut self.cvd_mode, mode, mode.label());
+                                }
+                            });
+                    });
+                    if self.cvd_mode != CvdMode::Off {
+                        ui.checkbox(&mut self.cvd_whole_view, "Apply to particles");
+                        ui.label(
+                            egui::RichText::new("  Simulation only, colors are not changed")
+                                .color(colors::text_muted())
+                                .small(),
+                        );
+                    }
                 });
+
+                ui.add_space(8.0);
+
+                group_frame.show(ui, |ui| {
+                    ui.set_min_width(ui.available_width());
+                    ui.label(egui::RichText::new("Recovery:").color(colors::text_muted()));
+                    ui.checkbox(&mut self.auto_recover_enabled, "Auto clear & reconnect");
+                    ui.label(
+                        egui::RichText::new("  On sustained parse-error spikes")
+                            .color(colors::text_muted())
+                            .small(),
+                    );
+                });
+            });
+    }
+}
+
+impl JamApp {
+    /// Collapsing-pulse duration, size and the event types that trigger one.
+    /// Type changes apply to events received after the change.
+    fn pulse_editor(&mut self, ui: &mut egui::Ui) {
+        let title = format!("Event pulses ({} types)", self.pulse_event_types.len());
+        egui::CollapsingHeader::new(egui::RichText::new(title).color(colors::text_muted()))
+            .id_salt("pulse_settings")
+            .show(ui, |ui| {
+                let duration_label = format!("Duration: {:.2}s", self.pulse_duration);
+                ui.label(egui::RichText::new(duration_label).color(colors::text_muted()));
+                let duration_response = ui.add(
+                    egui::Slider::new(&mut self.pulse_duration, 0.1..=2.0)
+                        .logarithmic(true)
+                        .clamping(egui::SliderClamping::Always)
+                        .show_value(false),
+                );
+                if duration_response.double_clicked() {
+                    self.pulse_duration = DEFAULT_PULSE_DURATION;
+                }
+                let radius_label = format!("Radius: {:.0}px", self.pulse_radius);
+                ui.label(egui::RichText::new(radius_label).color(colors::text_muted()));
+                let radius_response = ui.add(
+                    egui::Slider::new(&mut self.pulse_radius, 5.0..=120.0)
+                        .clamping(egui::SliderClamping::Always)
+                        .show_value(false),
+                );
+                if radius_response.double_clicked() {
+                    self.pulse_radius = DEFAULT_PULSE_RADIUS;
+                }
+
+                ui.add_space(4.0);
+                ui.label(
+                    egui::RichText::new("Pulsing types (no radial particle)")
+                        .color(colors::text_muted())
+                        .small(),
+                );
+                for category in EVENT_CATEGORIES {
+                    let enabled = category
+                        .event_types
+                        .iter()
+                        .filter(|et| self.pulse_event_types.contains(et))
+                        .count();
+                    let title = if enabled > 0 {
+                        format!("{} ({})", category.name, enabled)
+                    } else {
+                        category.name.to_string()
+                    };
+                    egui::CollapsingHeader::new(title)
+                        .id_salt(("pulse_types", category.name))
+                        .show(ui, |ui| {
+                            for &et in category.event_types {
+                                let mut enabled = self.pulse_event_types.contains(&et);
+                                if ui.checkbox(&mut enabled, event_name(et)).changed() {
+                                    if enabled {
+                                        self.pulse_event_types.push(et);
+                                    } else {
+                                        self.pulse_event_types.retain(|&t| t != et);
+                                    }
+                                }
+                            }
+                        });
+                }
+                let customized = self.pulse_event_types != DEFAULT_PULSE_TYPES
+                    || self.pulse_duration != DEFAULT_PULSE_DURATION
+                    || self.pulse_radius != DEFAULT_PULSE_RADIUS;
+                if ui
+                    .add_enabled(customized, egui::Button::new("Reset to defaults").small())
+                    .clicked()
+                {
+                    self.pulse_event_types = DEFAULT_PULSE_TYPES.to_vec();
+                    self.pulse_duration = DEFAULT_PULSE_DURATION;
+                    self.pulse_radius = DEFAULT_PULSE_RADIUS;
+                }
+            });
+    }
+
+    /// Per-type travel time of directed particles, grouped by category.
+    /// Applies to particles created after the change.
+    fn travel_duration_editor(&mut self, ui: &mut egui::Ui) {
+        let defaults = default_travel_durations();
+        let customized = self.travel_durations != defaults;
+        let title = if customized { "Travel durations (custom)" } else { "Travel durations" };
+        egui::CollapsingHeader::new(egui::RichText::new(title).color(colors::text_muted()))
+            .id_salt("travel_durations")
+            .show(ui, |ui| {
+                for category in EVENT_CATEGORIES {
+                    let directed: Vec<_> = category
+                        .event_types
+                        .iter()
+                        .filter(|et| {
+                            OUTBOUND_EVENTS.contains(*et)
+                                || INBOUND_EVENTS.contains(*et)
+                                || BIDIR_EVENTS.contains(*et)
+                        })
+                        .collect();
+                    if directed.is_empty() {
+                        continue;
+                    }
+                    ui.collapsing(category.name, |ui| {
+                        egui::Grid::new(("travel_durations", category.name))
+                            .num_columns(2)
+                            .show(ui, |ui| {
+                                for &&et in &directed {
+                                    let duration = &mut self.travel_durations[et.idx()];
+                                    let response = ui.add(
+                                        egui::DragValue::new(duration)
+                                            .range(MIN_TRAVEL_DURATION..=MAX_TRAVEL_DURATION)
+                                            .speed(0.05)
+                                            .fixed_decimals(1)
+                                            .suffix("s"),
+                                    );
+                                    if response.double_clicked() {
+                                        *duration = defaults[et.idx()];
+                                    }
+                                    ui.label(
+                                        egui::RichText::new(event_name(et))
+                                            .color(colors::text_secondary()),
+                                    );
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                }
+                if ui
+                    .add_enabled(customized, egui::Button::new("Reset to defaults").small())
+                    .clicked()
+                {
+                    self.travel_durations = defaults;
+                }
             });
     }
 }
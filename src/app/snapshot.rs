@@ -0,0 +1,275 @@
+//! Snapshot diff — freeze the per-node metrics at two points in time and compare them
+
+use std::collections::{HashMap, HashSet};
+
+use eframe::egui;
+use crate::theme::colors;
+use crate::time::utc_parts;
+use super::node_list::NodeSnapshot;
+use super::JamApp;
+
+/// Per-node metrics frozen at one moment
+pub(crate) struct Snapshot {
+    /// Unix seconds of the view time the snapshot was taken at
+    taken_at: f64,
+    rows: Vec<NodeSnapshot>,
+}
+
+/// The two stored snapshots
+#[derive(Default)]
+pub(crate) struct SnapshotPair {
+    a: Option<Snapshot>,
+    b: Option<Snapshot>,
+}
+
+/// One node's change from A to B; a metric is `None` when either side lacks it
+#[derive(Debug, PartialEq)]
+struct NodeDelta {
+    index: u16,
+    label: String,
+    node_id: String,
+    peers: Option<f32>,
+    best: Option<i64>,
+    finalized: Option<i64>,
+    events_per_sec: Option<f64>,
+    presence: Presence,
+}
+
+/// Which snapshots saw the node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Presence {
+    Both,
+    OnlyA,
+    OnlyB,
+}
+
+/// Pair rows by node id, ordered by the node's ring index in B (A for nodes that left)
+fn diff(a: &Snapshot, b: &Snapshot) -> Vec<NodeDelta> {
+    let before: HashMap<&str, &NodeSnapshot> =
+        a.rows.iter().map(|r| (r.node_id.as_str(), r)).collect();
+    let delta_u64 = |x: Option<u64>, y: Option<u64>| Some(y? as i64 - x? as i64);
+
+    let mut deltas: Vec<NodeDelta> = b
+        .rows
+        .iter()
+        .map(|after| match before.get(after.node_id.as_str()) {
+            Some(prev) => NodeDelta {
+                index: after.index,
+                label: after.label.clone(),
+                node_id: after.node_id.clone(),
+                peers: after.peers.zip(prev.peers).map(|(y, x)| y - x),
+                best: delta_u64(prev.best, after.best),
+                finalized: delta_u64(prev.finalized, after.finalized),
+                events_per_sec: Some(after.events_per_sec - prev.events_per_sec),
+                presence: Presence::Both,
+            },
+            None => NodeDelta {
+                index: after.index,
+                label: after.label.clone(),
+                node_id: after.node_id.clone(),
+                peers: None,
+                best: None,
+                finalized: None,
+                events_per_sec: None,
+                presence: Presence::OnlyB,
+            },
+        })
+        .collect();
+    let in_b: HashSet<&str> = b.rows.iter().map(|r| r.node_id.as_str()).collect();
+    deltas.extend(a.rows.iter().filter(|r| !in_b.contains(r.node_id.as_str())).map(|prev| {
+        NodeDelta {
+            index: prev.index,
+            label: prev.label.clone(),
+            node_id: prev.node_id.clone(),
+            peers: None,
+            best: None,
+            finalized: None,
+            events_per_sec: None,
+            presence: Presence::OnlyA,
+        }
+    }));
+    deltas.sort_by(|x, y| x.index.cmp(&y.index).then_with(|| x.node_id.cmp(&y.node_id)));
+    deltas
+}
+
+/// `+3`, `-1.5`, `0`, or `-` when there is nothing to compare
+fn format_delta(delta: Option<f64>, decimals: usize) -> String {
+    match delta {
+        None => "-".to_string(),
+        Some(d) if d == 0.0 => "0".to_string(),
+        Some(d) => format!("{:+.*}", decimals, d),
+    }
+}
+
+/// Green for growth, red for decline, muted when unchanged
+fn delta_color(delta: Option<f64>) -> egui::Color32 {
+    match delta {
+        Some(d) if d > 0.0 => egui::Color32::from_rgb(100, 200, 100),
+        Some(d) if d < 0.0 => egui::Color32::from_rgb(200, 100, 100),
+        _ => colors::text_muted(),
+    }
+}
+
+/// `11:50:45 UTC, 24 nodes`
+fn describe(snapshot: &Snapshot) -> String {
+    let (_, _, _, h, mi, s) = utc_parts(snapshot.taken_at);
+    format!("{:02}:{:02}:{:02} UTC, {} nodes", h, mi, s, snapshot.rows.len())
+}
+
+impl JamApp {
+    fn take_snapshot(&self) -> Snapshot {
+        Snapshot {
+            taken_at: self.view_now_unix(),
+            rows: self.node_snapshots(self.view_now()),
+        }
+    }
+
+    /// Header toggle for the snapshot window
+    pub(crate) fn render_snapshot_button(&mut self, ui: &mut egui::Ui) {
+        let color = if self.show_snapshots {
+            colors::text_primary()
+        } else {
+            colors::text_muted()
+        };
+        if ui
+            .selectable_label(self.show_snapshots, egui::RichText::new("Diff").color(color))
+            .on_hover_text("Compare per-node metrics between two snapshots")
+            .clicked()
+        {
+            self.show_snapshots = !self.show_snapshots;
+        }
+    }
+
+    pub(crate) fn render_snapshot_panel(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_snapshots;
+        egui::Window::new("Snapshots")
+            .id(egui::Id::new("snapshot_panel"))
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                for (name, slot) in [("A", 0), ("B", 1)] {
+                    ui.horizontal(|ui| {
+                        if ui.button(format!("Take snapshot {}", name)).clicked() {
+                            let snapshot = Some(self.take_snapshot());
+                            if slot == 0 {
+                                self.snapshots.a = snapshot;
+                            } else {
+                                self.snapshots.b = snapshot;
+                            }
+                        }
+                        let taken = if slot == 0 { &self.snapshots.a } else { &self.snapshots.b };
+                        let text = taken.as_ref().map_or("not taken".to_string(), describe);
+                        ui.label(egui::RichText::new(text).color(colors::text_muted()));
+                    });
+                }
+
+                let (Some(a), Some(b)) = (&self.snapshots.a, &self.snapshots.b) else {
+                    ui.add_space(4.0);
+                    ui.label(
+                        egui::RichText::new("Take both snapshots to see per-node deltas (B − A)")
+                            .color(colors::text_muted()),
+                    );
+                    return;
+                };
+                let deltas = diff(a, b);
+                ui.label(
+                    egui::RichText::new(format!("B − A over {:.0}s", b.taken_at - a.taken_at))
+                        .color(colors::text_secondary()),
+                );
+                ui.add_space(4.0);
+
+                egui::ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                    egui::Grid::new("snapshot_diff_grid")
+                        .num_columns(5)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for title in ["node", "Δ peers", "Δ best", "Δ final", "Δ ev/s"] {
+                                ui.label(egui::RichText::new(title).color(colors::text_secondary()));
+                            }
+                            ui.end_row();
+
+                            for delta in &deltas {
+                                let label = match delta.presence {
+                                    Presence::Both => delta.label.clone(),
+                                    Presence::OnlyA => format!("{} (gone)", delta.label),
+                                    Presence::OnlyB => format!("{} (new)", delta.label),
+                                };
+                                ui.label(egui::RichText::new(label).color(colors::text_muted()))
+                                    .on_hover_text(delta.node_id.as_str());
+                                let cells = [
+                                    (delta.peers.map(f64::from), 0),
+                                    (delta.best.map(|d| d as f64), 0),
+                                    (delta.finalized.map(|d| d as f64), 0),
+                                    (delta.events_per_sec, 1),
+                                ];
+                                for (value, decimals) in cells {
+                                    ui.label(
+                                        egui::RichText::new(format_delta(value, decimals))
+                                            .color(delta_color(value)),
+                                    );
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+
+                if ui.small_button("Clear").clicked() {
+                    self.snapshots = SnapshotPair::default();
+                }
+            });
+        self.show_snapshots = open;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(index: u16, id: &str, peers: Option<f32>, best: Option<u64>, rate: f64) -> NodeSnapshot {
+        NodeSnapshot {
+            index,
+            node_id: id.to_string(),
+            label: id.to_string(),
+            peers,
+            best,
+            finalized: None,
+            synced: None,
+            events_per_sec: rate,
+            last_seen_age: None,
+        }
+    }
+
+    #[test]
+    fn diff_pairs_by_node_id_and_marks_joins_and_leaves() {
+        let a = Snapshot {
+            taken_at: 0.0,
+            rows: vec![row(0, "val-a", Some(5.0), Some(10), 2.0), row(1, "val-b", None, Some(3), 1.0)],
+        };
+        let b = Snapshot {
+            taken_at: 30.0,
+            rows: vec![row(0, "val-a", Some(3.0), Some(14), 2.5), row(2, "val-c", Some(1.0), None, 0.0)],
+        };
+        let deltas = diff(&a, &b);
+        assert_eq!(deltas.len(), 3);
+
+        assert_eq!(deltas[0].node_id, "val-a");
+        assert_eq!(deltas[0].presence, Presence::Both);
+        assert_eq!(deltas[0].peers, Some(-2.0));
+        assert_eq!(deltas[0].best, Some(4));
+        assert_eq!(deltas[0].finalized, None);
+        assert_eq!(deltas[0].events_per_sec, Some(0.5));
+
+        assert_eq!((deltas[1].node_id.as_str(), deltas[1].presence), ("val-b", Presence::OnlyA));
+        assert_eq!((deltas[2].node_id.as_str(), deltas[2].presence), ("val-c", Presence::OnlyB));
+        assert_eq!(deltas[2].best, None);
+    }
+
+    #[test]
+    fn delta_formatting() {
+        assert_eq!(format_delta(Some(3.0), 0), "+3");
+        assert_eq!(format_delta(Some(-1.3), 1), "-1.3");
+        assert_eq!(format_delta(Some(0.0), 1), "0");
+        assert_eq!(format_delta(None, 0), "-");
+    }
+}
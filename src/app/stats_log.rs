@@ -0,0 +1,144 @@
+//! Periodic stats output of the native binary: a tracing line, or one JSON object per
+//! interval on stdout with `--json-stats` (for jq or a log pipeline)
+
+use serde_json::json;
+use tracing::info;
+use crate::core::events::{event_name, EVENT_CATEGORIES, REASON_EVENT_TYPES};
+use crate::core::EventStore;
+use crate::time::now_unix_seconds;
+use super::diagnostics::DiagRates;
+use super::JamApp;
+
+/// Default `--stats-interval`, seconds
+pub const DEFAULT_STATS_INTERVAL: f64 = 1.0;
+/// Error reasons listed per JSON stats object
+const TOP_ERROR_REASONS: usize = 5;
+
+/// `--json-stats` / `--stats-interval`
+#[derive(Clone, Copy, Debug)]
+pub struct StatsOptions {
+    pub json: bool,
+    /// Seconds between two stats outputs
+    pub interval: f64,
+}
+
+/// `(event type, reason, count)` across all types, most frequent first, at most `n`
+fn top_reasons<'a>(
+    per_type: impl IntoIterator<Item = (&'a str, Vec<(String, u64)>)>,
+    n: usize,
+) -> Vec<(&'a str, String, u64)> {
+    let mut all: Vec<(&str, String, u64)> = per_type
+        .into_iter()
+        .flat_map(|(name, reasons)| reasons.into_iter().map(move |(r, c)| (name, r, c)))
+        .collect();
+    all.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.0.cmp(b.0)).then_with(|| a.1.cmp(&b.1)));
+    all.truncate(n);
+    all
+}
+
+/// What one stats output reports; filled by the GUI and by the headless run
+pub(crate) struct StatsSample<'a> {
+    pub rates: DiagRates,
+    pub active_particles: usize,
+    pub events: &'a EventStore,
+    /// Clock the stored events are stamped with
+    pub view_now: f64,
+}
+
+/// Write one stats output: a tracing line, or with `json` an object on stdout whose
+/// counts cover the `interval` since the previous one
+pub(crate) fn emit_stats(options: &StatsOptions, sample: &StatsSample) {
+    let rates = &sample.rates;
+    let nodes = sample.events.node_count();
+    if !options.json {
+        info!(
+            events_per_sec = rates.events,
+            node_dropped_per_sec = rates.node_dropped,
+            server_dropped_per_sec = rates.server_dropped,
+            buffer_evicted_per_sec = rates.buffer_evicted,
+            rx_bytes_per_sec = rates.rx_bytes,
+            parse_errors_per_sec = rates.parse_errors,
+            active_particles = sample.active_particles,
+            nodes,
+            "stats"
+        );
+        return;
+    }
+
+    let (events, view_now, window) = (sample.events, sample.view_now, options.interval);
+    let by_category: serde_json::Map<String, serde_json::Value> = EVENT_CATEGORIES
+        .iter()
+        .map(|cat| (cat.name.to_string(), events.count_events(cat.event_types, view_now - window).into()))
+        .collect();
+    let reasons = top_reasons(
+        REASON_EVENT_TYPES
+            .iter()
+            .map(|&et| (event_name(et), events.reason_distribution(et as u8, view_now, window))),
+        TOP_ERROR_REASONS,
+    );
+    let stats = json!({
+        "timestamp": now_unix_seconds(),
+        "events_per_sec": rates.events,
+        "node_dropped_per_sec": rates.node_dropped,
+        "server_dropped_per_sec": rates.server_dropped,
+        "buffer_evicted_per_sec": rates.buffer_evicted,
+        "rx_bytes_per_sec": rates.rx_bytes,
+        "parse_errors_per_sec": rates.parse_errors,
+        "active_particles": sample.active_particles,
+        "nodes": nodes,
+        "events_by_category": by_category,
+        "top_error_reasons": reasons
+            .iter()
+            .map(|(event, reason, count)| json!({"event": event, "reason": reason, "count": count}))
+            .collect::<Vec<_>>(),
+    });
+    println!("{}", stats);
+}
+
+impl JamApp {
+    /// Housekeeping tick: emit stats once `interval` seconds have passed since the last output
+    pub(crate) fn log_stats(&mut self, now: f64) {
+        if now - self.stats_last_emit < self.stats.interval {
+            return;
+        }
+        self.stats_last_emit = now;
+
+        let sample = StatsSample {
+            rates: DiagRates {
+                events: self.diag_events_sec,
+                node_dropped: self.diag_node_dropped_sec,
+                server_dropped: self.diag_server_dropped_sec,
+                rx_bytes: self.diag_rx_bytes_sec,
+                parse_errors: self.diag_parse_errors_sec,
+                buffer_evicted: self.diag_buffer_evicted_sec,
+            },
+            active_particles: self.data.directed_buffer.active_count(now as f32, 5.0),
+            events: &self.data.events,
+            view_now: self.view_now(),
+        };
+        emit_stats(&self.stats, &sample);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn top_reasons_merges_types_and_truncates() {
+        let per_type = vec![
+            ("Disconnected", vec![("timeout".to_string(), 4), ("reset".to_string(), 1)]),
+            ("ConnectInFailed", vec![("refused".to_string(), 4)]),
+            ("PeerMisbehaved", vec![("bad block".to_string(), 7)]),
+        ];
+        let top = top_reasons(per_type, 3);
+        assert_eq!(
+            top,
+            vec![
+                ("PeerMisbehaved", "bad block".to_string(), 7),
+                ("ConnectInFailed", "refused".to_string(), 4),
+                ("Disconnected", "timeout".to_string(), 4),
+            ]
+        );
+    }
+}
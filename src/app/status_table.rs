@@ -0,0 +1,128 @@
+//! Status table — every field of each validator's latest Status report, one row per node
+
+use eframe::egui;
+use crate::core::LatestStatus;
+use crate::theme::colors;
+use super::graphs::format_bytes;
+use super::{with_data, JamApp};
+
+/// Whether a report received at `updated_at` is older than the time window
+fn is_stale(status: &LatestStatus, now: f64, window: f64) -> bool {
+    now - status.updated_at > window
+}
+
+impl JamApp {
+    /// Header toggle for the status table
+    pub(crate) fn render_status_table_button(&mut self, ui: &mut egui::Ui) {
+        let color = if self.show_status_table {
+            colors::text_primary()
+        } else {
+            colors::text_muted()
+        };
+        if ui
+            .selectable_label(self.show_status_table, egui::RichText::new("Status").color(color))
+            .on_hover_text("Latest Status report of every validator")
+            .clicked()
+        {
+            self.show_status_table = !self.show_status_table;
+        }
+    }
+
+    pub(crate) fn render_status_table(&mut self, ctx: &egui::Context) {
+        let now = self.view_now();
+        let window = self.time_window as f64;
+        // (ring index, node_id, report), ring order
+        let mut rows: Vec<(Option<u16>, String, LatestStatus)> = with_data!(self, |data| {
+            data.latest_status
+                .entries()
+                .into_iter()
+                .map(|(id, status)| (data.events.node_index(id), id.to_string(), status.clone()))
+                .collect()
+        });
+        rows.sort_by_key(|(idx, _, _)| idx.unwrap_or(u16::MAX));
+
+        let mut open = self.show_status_table;
+        egui::Window::new("Status")
+            .id(egui::Id::new("status_table"))
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(560.0)
+            .show(ctx, |ui| {
+                if rows.is_empty() {
+                    ui.label(egui::RichText::new("No Status reports yet").color(colors::text_muted()));
+                    return;
+                }
+                let stale = rows.iter().filter(|(_, _, s)| is_stale(s, now, window)).count();
+                ui.label(
+                    egui::RichText::new(format!(
+                        "{} validators, {} stale (no report in {:.0}s)",
+                        rows.len(),
+                        stale,
+                        window,
+                    ))
+                    .color(colors::text_muted()),
+                );
+                ui.add_space(4.0);
+
+                egui::ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                    egui::Grid::new("status_table_grid")
+                        .num_columns(8)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            let titles = [
+                                "node", "peers", "val", "sync", "shards", "shard size", "preimages",
+                                "preimage size",
+                            ];
+                            for title in titles {
+                                ui.label(egui::RichText::new(title).color(colors::text_secondary()));
+                            }
+                            ui.end_row();
+
+                            for (idx, node_id, status) in &rows {
+                                let stale = is_stale(status, now, window);
+                                let value_color = if stale {
+                                    colors::text_muted()
+                                } else {
+                                    colors::text_primary()
+                                };
+                                let name = match idx {
+                                    Some(idx) => format!("{} {}", idx, self.node_label(node_id)),
+                                    None => self.node_label(node_id),
+                                };
+                                let name = if stale { format!("{} (stale)", name) } else { name };
+                                let age = format!("{}\nlast Status {:.0}s ago", node_id, now - status.updated_at);
+                                ui.label(egui::RichText::new(name).color(colors::text_muted()))
+                                    .on_hover_text(age);
+                                let cells = [
+                                    status.num_peers.to_string(),
+                                    status.num_val_peers.to_string(),
+                                    status.num_sync_peers.to_string(),
+                                    status.num_shards.to_string(),
+                                    format_bytes(status.shards_size as f64),
+                                    status.num_preimages.to_string(),
+                                    format_bytes(status.preimages_size as f64),
+                                ];
+                                for cell in cells {
+                                    ui.label(egui::RichText::new(cell).color(value_color));
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+        self.show_status_table = open;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stale_after_the_time_window() {
+        let status = LatestStatus { updated_at: 100.0, ..Default::default() };
+        assert!(!is_stale(&status, 110.0, 30.0));
+        assert!(!is_stale(&status, 130.0, 30.0));
+        assert!(is_stale(&status, 130.5, 30.0));
+    }
+}
@@ -0,0 +1,168 @@
+//! Server-side event filter — narrow the jamtart subscription to the selected event types
+
+use tracing::{info, warn};
+use crate::core::EventType;
+use crate::ws_state::subscribe_message;
+use super::JamApp;
+
+/// Seconds the selection must stay unchanged before the subscription is re-sent
+const SUBSCRIBE_DEBOUNCE_SECS: f64 = 0.5;
+
+/// What the server was last asked for, and the change waiting out the debounce.
+/// `None` types means the unfiltered `All` subscription.
+#[derive(Default)]
+pub(crate) struct ServerFilter {
+    sent: Option<Vec<u8>>,
+    /// Wanted types and when they were first seen
+    pending: Option<(Option<Vec<u8>>, f64)>,
+    /// The server refused a typed filter on this connection; stay on `All`
+    rejected: bool,
+}
+
+impl ServerFilter {
+    /// Subscription to send at `now`, once `wanted` differs from what the server has
+    /// and has stayed the same for `SUBSCRIBE_DEBOUNCE_SECS`
+    fn poll(&mut self, wanted: Option<Vec<u8>>, now: f64) -> Option<Option<Vec<u8>>> {
+        let wanted = if self.rejected { None } else { wanted };
+        if wanted == self.sent {
+            self.pending = None;
+            return None;
+        }
+        match &self.pending {
+            Some((pending, since)) if *pending == wanted => {
+                if now - since < SUBSCRIBE_DEBOUNCE_SECS {
+                    return None;
+                }
+            }
+            _ => {
+                self.pending = Some((wanted, now));
+                return None;
+            }
+        }
+        self.pending = None;
+        self.sent = wanted.clone();
+        Some(wanted)
+    }
+
+    /// The server answered with an error. Returns true if a typed filter was in effect,
+    /// in which case it falls back to `All` until the next connection.
+    fn reject(&mut self) -> bool {
+        if self.sent.is_none() {
+            return false;
+        }
+        self.rejected = true;
+        self.sent = None;
+        self.pending = None;
+        true
+    }
+
+    /// A new connection starts out subscribed to `All`
+    pub(crate) fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Whether a typed filter is in effect, so server errors may be rejections of it
+    pub(crate) fn is_typed(&self) -> bool {
+        self.sent.is_some()
+    }
+
+    pub(crate) fn is_rejected(&self) -> bool {
+        self.rejected
+    }
+}
+
+/// Wire filter for `selected_events`: `None` when every known type is selected
+fn wanted_types(selected_events: &[bool]) -> Option<Vec<u8>> {
+    let known = (0..=u8::MAX).filter(|&t| EventType::from_u8(t).is_some());
+    let mut types = Vec::new();
+    let mut all = true;
+    for t in known {
+        if selected_events.get(t as usize).copied().unwrap_or(false) {
+            types.push(t);
+        } else {
+            all = false;
+        }
+    }
+    (!all).then_some(types)
+}
+
+impl JamApp {
+    /// Re-send the subscription when the server-side filter setting or the selection changed
+    pub(crate) fn sync_server_filter(&mut self, now: f64) {
+        let wanted = if self.server_filter_enabled {
+            wanted_types(&self.selected_events)
+        } else {
+            None
+        };
+        if let Some(types) = self.server_filter.poll(wanted, now) {
+            self.send_subscription(types.as_deref());
+        }
+    }
+
+    /// An error reply arrived; if it answers a typed filter, go back to `All`
+    /// and keep filtering client-side only
+    pub(crate) fn note_server_error(&mut self) {
+        if self.server_filter.reject() {
+            warn!("Server rejected the typed subscribe filter, subscribing to all events");
+            self.send_subscription(None);
+        }
+    }
+
+    fn send_subscription(&self, types: Option<&[u8]>) {
+        info!(types = ?types, "Updating subscription");
+        #[cfg(target_arch = "wasm32")]
+        if let Some(ref client) = self.ws_client {
+            client.subscribe(subscribe_message(types));
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        for endpoint in &self.endpoints {
+            endpoint.client.subscribe(subscribe_message(types));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changes_are_debounced() {
+        let mut filter = ServerFilter::default();
+        let narrow = Some(vec![11, 28]);
+        assert_eq!(filter.poll(narrow.clone(), 0.0), None);
+        // A different selection restarts the wait
+        assert_eq!(filter.poll(Some(vec![11]), 0.3), None);
+        assert_eq!(filter.poll(narrow.clone(), 0.4), None);
+        assert_eq!(filter.poll(narrow.clone(), 0.8), None);
+        assert_eq!(filter.poll(narrow.clone(), 0.9), Some(narrow.clone()));
+        assert!(filter.is_typed());
+        // Nothing to send while the server already has it
+        assert_eq!(filter.poll(narrow, 5.0), None);
+    }
+
+    #[test]
+    fn rejection_falls_back_to_all() {
+        let mut filter = ServerFilter::default();
+        assert!(!filter.reject());
+        filter.poll(Some(vec![11]), 0.0);
+        filter.poll(Some(vec![11]), 1.0);
+        assert!(filter.reject());
+        assert!(!filter.is_typed());
+        // Typed filters stay off until the next connection
+        assert_eq!(filter.poll(Some(vec![11]), 2.0), None);
+        assert_eq!(filter.poll(Some(vec![11]), 3.0), None);
+        filter.reset();
+        assert!(!filter.is_rejected());
+    }
+
+    #[test]
+    fn wanted_types_from_selection() {
+        assert_eq!(wanted_types(&JamApp::default_selected_events()), None);
+        let mut selected = vec![false; 256];
+        selected[EventType::BestBlockChanged.idx()] = true;
+        selected[EventType::PeerMisbehaved.idx()] = true;
+        // Unknown discriminants are never sent
+        selected[1] = true;
+        assert_eq!(wanted_types(&selected), Some(vec![11, 28]));
+    }
+}
@@ -0,0 +1,121 @@
+//! Sync-status overview — header "synced N/M" indicator and per-validator cell grid
+
+use eframe::egui;
+use crate::core::SYNC_STALE_SECS;
+use crate::theme::colors;
+use super::{JamApp, with_data};
+
+/// Side of one validator cell in the sync panel
+const CELL_SIZE: f32 = 12.0;
+/// Cells per row in the sync panel
+const CELLS_PER_ROW: usize = 32;
+
+/// Cell color: green synced, red not synced, gray when the report is stale
+fn sync_cell_color(synced: bool, stale: bool) -> egui::Color32 {
+    if stale {
+        colors::text_muted()
+    } else if synced {
+        egui::Color32::from_rgb(100, 200, 100)
+    } else {
+        egui::Color32::from_rgb(200, 100, 100)
+    }
+}
+
+impl JamApp {
+    /// Header indicator; clicking it toggles the sync panel. Hidden until a validator reports.
+    pub(crate) fn render_sync_indicator(&mut self, ui: &mut egui::Ui) {
+        let (synced, total) = with_data!(self, |data| data.sync_status.synced_count());
+        if total == 0 {
+            return;
+        }
+
+        let color = if synced == total {
+            egui::Color32::from_rgb(100, 200, 100)
+        } else {
+            egui::Color32::from_rgb(200, 200, 100)
+        };
+        let response = ui
+            .selectable_label(
+                self.show_sync_panel,
+                egui::RichText::new(format!("synced {}/{}", synced, total)).color(color),
+            )
+            .on_hover_text("Validators reporting synced (click for details)");
+        if response.clicked() {
+            self.show_sync_panel = !self.show_sync_panel;
+        }
+        ui.add_space(10.0);
+    }
+
+    /// One cell per validator, colored by its latest SyncStatusChanged
+    pub(crate) fn render_sync_panel(&mut self, ctx: &egui::Context) {
+        let now = self.view_now();
+        // (ring index, node_id, synced, stale), ring order
+        let mut cells: Vec<(Option<u16>, String, bool, bool)> = with_data!(self, |data| {
+            data.sync_status
+                .entries(now)
+                .into_iter()
+                .map(|(id, synced, stale)| {
+                    (data.events.node_index(id), id.to_string(), synced, stale)
+                })
+                .collect()
+        });
+        cells.sort_by_key(|(idx, _, _, _)| idx.unwrap_or(u16::MAX));
+
+        let mut open = self.show_sync_panel;
+        egui::Window::new("Sync Status")
+            .id(egui::Id::new("sync_panel"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let synced = cells.iter().filter(|(_, _, synced, _)| *synced).count();
+                let stale = cells.iter().filter(|(_, _, _, stale)| *stale).count();
+                ui.label(
+                    egui::RichText::new(format!(
+                        "{}/{} synced, {} stale",
+                        synced,
+                        cells.len(),
+                        stale,
+                    ))
+                    .color(colors::text_muted()),
+                );
+                ui.add_space(4.0);
+
+                for row in cells.chunks(CELLS_PER_ROW) {
+                    ui.horizontal(|ui| {
+                        ui.spacing_mut().item_spacing = egui::vec2(2.0, 2.0);
+                        for (idx, node_id, synced, stale) in row {
+                            let (rect, response) = ui.allocate_exact_size(
+                                egui::vec2(CELL_SIZE, CELL_SIZE),
+                                egui::Sense::hover(),
+                            );
+                            ui.painter().rect_filled(rect, 2.0, sync_cell_color(*synced, *stale));
+                            let state = match (*synced, *stale) {
+                                (_, true) => format!("no report in {:.0}s", SYNC_STALE_SECS),
+                                (true, false) => "synced".to_string(),
+                                (false, false) => "not synced".to_string(),
+                            };
+                            let name = match idx {
+                                Some(idx) => format!("node {} ({})", idx, self.node_label(node_id)),
+                                None => self.node_label(node_id),
+                            };
+                            response.on_hover_text(format!("{}: {}", name, state));
+                        }
+                    });
+                }
+            });
+        self.show_sync_panel = open;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stale_cells_are_gray() {
+        assert_eq!(sync_cell_color(true, true), colors::text_muted());
+        assert_eq!(sync_cell_color(false, true), colors::text_muted());
+        assert_ne!(sync_cell_color(true, false), sync_cell_color(false, false));
+    }
+}
@@ -0,0 +1,192 @@
+//! Error toasts — transient corner notifications for notable error events
+
+use std::collections::VecDeque;
+
+use eframe::egui;
+use crate::core::events::ERROR_EVENT_TYPES;
+use crate::core::{event_name, EventType};
+use crate::theme::colors;
+use crate::time::now_seconds;
+use super::JamApp;
+
+/// Error types that raise a toast until the user picks their own set
+pub(crate) const DEFAULT_TOAST_TYPES: &[EventType] = &[
+    EventType::PeerMisbehaved,
+    EventType::BlockExecutionFailed,
+    EventType::SegmentReconstructionFailed,
+];
+
+/// Seconds a toast stays up after its latest occurrence (the last second fades out)
+const TOAST_SECS: f64 = 6.0;
+/// Most toasts on screen; older ones are dropped first
+const MAX_TOASTS: usize = 4;
+
+/// One toast; repeats of the same error type while it is showing bump `count`
+pub(crate) struct Toast {
+    pub event_type: EventType,
+    /// Node of the latest occurrence
+    pub node_index: Option<u16>,
+    pub count: u32,
+    /// App time of the latest occurrence
+    pub last_at: f64,
+}
+
+/// Visible toasts, oldest first
+#[derive(Default)]
+pub(crate) struct ToastQueue {
+    toasts: VecDeque<Toast>,
+}
+
+impl ToastQueue {
+    /// Record an occurrence; a storm of one error type collapses into a single "xN" toast
+    pub(crate) fn push(&mut self, event_type: EventType, node_index: Option<u16>, now: f64) {
+        self.expire(now);
+        if let Some(pos) = self.toasts.iter().position(|t| t.event_type == event_type) {
+            let mut toast = self.toasts.remove(pos).expect("position is in range");
+            toast.count += 1;
+            toast.node_index = node_index;
+            toast.last_at = now;
+            self.toasts.push_back(toast);
+            return;
+        }
+        if self.toasts.len() >= MAX_TOASTS {
+            self.toasts.pop_front();
+        }
+        self.toasts.push_back(Toast { event_type, node_index, count: 1, last_at: now });
+    }
+
+    /// Drop toasts older than `TOAST_SECS`
+    pub(crate) fn expire(&mut self, now: f64) {
+        self.toasts.retain(|t| now - t.last_at < TOAST_SECS);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.toasts.clear();
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.toasts.is_empty()
+    }
+}
+
+/// Opacity of a toast `age` seconds after its latest occurrence
+fn toast_alpha(age: f64) -> f32 {
+    ((TOAST_SECS - age) as f32).clamp(0.0, 1.0)
+}
+
+impl JamApp {
+    /// Queue a toast if `event_type` is one of the user's notable error types
+    pub(crate) fn notify_error_event(&mut self, event_type: EventType, node_index: Option<u16>) {
+        if self.toast_event_types.contains(&event_type) {
+            self.toasts.push(event_type, node_index, now_seconds());
+        }
+    }
+
+    /// Bottom-right stack of toasts; clicking one focuses its node
+    pub(crate) fn render_toasts(&mut self, ctx: &egui::Context) {
+        let now = now_seconds();
+        self.toasts.expire(now);
+        if self.toasts.is_empty() {
+            return;
+        }
+
+        let mut clicked = None;
+        egui::Area::new(egui::Id::new("error_toasts"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-12.0, -12.0))
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                for toast in self.toasts.toasts.iter().rev() {
+                    let alpha = toast_alpha(now - toast.last_at);
+                    let mut text = event_name(toast.event_type).to_string();
+                    if toast.count > 1 {
+                        text.push_str(&format!(" x{}", toast.count));
+                    }
+                    let node = match toast.node_index {
+                        Some(idx) => format!("node {}", idx),
+                        None => "unknown node".to_string(),
+                    };
+                    let response = egui::Frame::new()
+                        .fill(colors::bg_primary().gamma_multiply(alpha))
+                        .stroke(egui::Stroke::new(
+                            1.0,
+                            self.get_event_color(toast.event_type).gamma_multiply(alpha),
+                        ))
+                        .corner_radius(4.0)
+                        .inner_margin(6.0)
+                        .show(ui, |ui| {
+                            ui.label(
+                                egui::RichText::new(text)
+                                    .color(colors::text_primary().gamma_multiply(alpha)),
+                            );
+                            ui.label(
+                                egui::RichText::new(node)
+                                    .color(colors::text_muted().gamma_multiply(alpha))
+                                    .small(),
+                            );
+                        })
+                        .response
+                        .interact(egui::Sense::click())
+                        .on_hover_cursor(egui::CursorIcon::PointingHand);
+                    if response.clicked() {
+                        clicked = Some(toast.node_index);
+                    }
+                    ui.add_space(4.0);
+                }
+            });
+
+        if let Some(node_index) = clicked.flatten() {
+            self.set_focused_node(Some(node_index));
+            self.selected_node = Some(node_index);
+        }
+        // Keep repainting while toasts fade out
+        ctx.request_repaint();
+    }
+
+    /// Settings group: which error types raise a toast (none disables toasts)
+    pub(crate) fn render_toast_settings(&mut self, ui: &mut egui::Ui) {
+        ui.label(egui::RichText::new("Error toasts:").color(colors::text_muted()));
+        let title = format!("Notable types ({})", self.toast_event_types.len());
+        ui.collapsing(title, |ui| {
+            for &et in ERROR_EVENT_TYPES {
+                let mut enabled = self.toast_event_types.contains(&et);
+                if ui.checkbox(&mut enabled, event_name(et)).changed() {
+                    if enabled {
+                        self.toast_event_types.push(et);
+                    } else {
+                        self.toast_event_types.retain(|&t| t != et);
+                    }
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeats_collapse_into_one_toast() {
+        let mut queue = ToastQueue::default();
+        queue.push(EventType::PeerMisbehaved, Some(1), 0.0);
+        queue.push(EventType::BlockExecutionFailed, Some(2), 0.5);
+        queue.push(EventType::PeerMisbehaved, Some(3), 1.0);
+        assert_eq!(queue.toasts.len(), 2);
+        // The repeated type moves to the newest position with the latest node
+        let newest = queue.toasts.back().unwrap();
+        assert_eq!(newest.event_type, EventType::PeerMisbehaved);
+        assert_eq!((newest.count, newest.node_index), (2, Some(3)));
+
+        // Expired toasts start over
+        queue.push(EventType::PeerMisbehaved, Some(4), 1.0 + TOAST_SECS);
+        assert_eq!(queue.toasts.len(), 1);
+        assert_eq!(queue.toasts[0].count, 1);
+    }
+
+    #[test]
+    fn toasts_fade_in_their_last_second() {
+        assert_eq!(toast_alpha(0.0), 1.0);
+        assert!(toast_alpha(TOAST_SECS - 0.5) < 1.0);
+        assert_eq!(toast_alpha(TOAST_SECS), 0.0);
+    }
+}
@@ -0,0 +1,207 @@
+//! Topology — live peer connections between nodes as a graph, partitions set apart
+
+use std::collections::HashMap;
+use std::f32::consts::{FRAC_PI_2, TAU};
+use eframe::egui;
+use crate::theme::colors;
+use super::{with_data, JamApp};
+
+/// Nodes of the largest component
+const MAIN_COLOR: egui::Color32 = egui::Color32::from_rgb(100, 150, 200);
+/// Nodes cut off from the largest component
+const PARTITION_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 200, 100);
+
+/// One vertex of the graph
+struct Vertex {
+    node_id: String,
+    /// Index into the components, largest first
+    component: usize,
+    degree: usize,
+    /// Reports telemetry itself (otherwise only seen as someone's peer)
+    reporting: bool,
+}
+
+/// Angle of every vertex when the components go one after another around a circle, largest
+/// first from the top, clockwise; one empty slot between components sets partitions apart
+fn circle_angles(sizes: &[usize]) -> Vec<Vec<f32>> {
+    let gaps = if sizes.len() > 1 { sizes.len() } else { 0 };
+    let slots = (sizes.iter().sum::<usize>() + gaps).max(1);
+    let step = TAU / slots as f32;
+    let mut slot = 0;
+    sizes
+        .iter()
+        .map(|&size| {
+            let angles = (slot..slot + size).map(|i| i as f32 * step - FRAC_PI_2).collect();
+            slot += size + usize::from(gaps > 0);
+            angles
+        })
+        .collect()
+}
+
+impl JamApp {
+    /// Header toggle for the topology graph
+    pub(crate) fn render_topology_button(&mut self, ui: &mut egui::Ui) {
+        let color = if self.show_topology {
+            colors::text_primary()
+        } else {
+            colors::text_muted()
+        };
+        if ui
+            .selectable_label(self.show_topology, egui::RichText::new("Topology").color(color))
+            .on_hover_text("Who is connected to whom, from the connection events")
+            .clicked()
+        {
+            self.show_topology = !self.show_topology;
+        }
+    }
+
+    pub(crate) fn render_topology(&mut self, ctx: &egui::Context) {
+        let (components, edges) = with_data!(self, |data| {
+            let topology = &data.topology;
+            let components: Vec<Vec<Vertex>> = topology
+                .components(data.events.nodes().map(|(id, _)| id))
+                .into_iter()
+                .enumerate()
+                .map(|(component, ids)| {
+                    ids.into_iter()
+                        .map(|id| Vertex {
+                            node_id: id.to_string(),
+                            component,
+                            degree: topology.degree(id),
+                            reporting: data.events.node_index(id).is_some(),
+                        })
+                        .collect()
+                })
+                .collect();
+            let edges: Vec<(String, String)> =
+                topology.edges().map(|(a, b)| (a.to_string(), b.to_string())).collect();
+            (components, edges)
+        });
+        let labels: HashMap<&str, String> = components
+            .iter()
+            .flatten()
+            .map(|v| (v.node_id.as_str(), self.node_label(&v.node_id)))
+            .collect();
+
+        let mut open = self.show_topology;
+        egui::Window::new("Topology")
+            .id(egui::Id::new("topology"))
+            .open(&mut open)
+            .default_size([480.0, 520.0])
+            .show(ctx, |ui| {
+                if edges.is_empty() {
+                    ui.label(
+                        egui::RichText::new("No connections seen yet (ConnectedIn events)")
+                            .color(colors::text_muted()),
+                    );
+                    return;
+                }
+                ui.horizontal(|ui| {
+                    let nodes: usize = components.iter().map(Vec::len).sum();
+                    ui.label(
+                        egui::RichText::new(format!("{} nodes, {} connections", nodes, edges.len()))
+                            .color(colors::text_muted()),
+                    );
+                    let (text, color) = match (self.partitions.partition(), components.len()) {
+                        (Some(sizes), _) => (
+                            format!("partitioned: {} validator groups", sizes.len()),
+                            egui::Color32::from_rgb(200, 100, 100),
+                        ),
+                        (None, 1) => ("connected".to_string(), egui::Color32::from_rgb(100, 200, 100)),
+                        (None, n) => (format!("{} components", n), PARTITION_COLOR),
+                    };
+                    ui.label(egui::RichText::new(text).color(color)).on_hover_text(
+                        "Groups of nodes with no connection path between them. Validators \
+                         count as partitioned once split for a few seconds; those with fewer \
+                         connections seen than their Status peer count suggests are not counted.",
+                    );
+                });
+                draw_topology(ui, &components, &edges, &labels);
+            });
+        self.show_topology = open;
+    }
+}
+
+/// Circular layout: one chord per connection, one dot per node, area by degree; nodes
+/// outside the largest component highlighted, peers that don't report telemetry hollow
+fn draw_topology(
+    ui: &mut egui::Ui,
+    components: &[Vec<Vertex>],
+    edges: &[(String, String)],
+    labels: &HashMap<&str, String>,
+) {
+    let available = ui.available_size();
+    let side = available.x.min(available.y).max(120.0);
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(side, side), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    let radius = side / 2.0 - 16.0;
+    let sizes: Vec<usize> = components.iter().map(Vec::len).collect();
+    let positions: HashMap<&str, (egui::Pos2, &Vertex)> = components
+        .iter()
+        .zip(circle_angles(&sizes))
+        .flat_map(|(vertices, angles)| vertices.iter().zip(angles))
+        .map(|(v, angle)| {
+            let pos = rect.center() + radius * egui::vec2(angle.cos(), angle.sin());
+            (v.node_id.as_str(), (pos, v))
+        })
+        .collect();
+
+    let edge_stroke = egui::Stroke::new(1.0, colors::shade(255, 40));
+    for (a, b) in edges {
+        if let (Some((a, _)), Some((b, _))) = (positions.get(a.as_str()), positions.get(b.as_str())) {
+            painter.line_segment([*a, *b], edge_stroke);
+        }
+    }
+
+    let dot_radius = |degree: usize| (2.5 + (degree as f32).sqrt()).min(10.0);
+    for (pos, v) in positions.values() {
+        let color = if v.component == 0 { MAIN_COLOR } else { PARTITION_COLOR };
+        if v.reporting {
+            painter.circle_filled(*pos, dot_radius(v.degree), color);
+        } else {
+            painter.circle_stroke(*pos, dot_radius(v.degree), egui::Stroke::new(1.0, color));
+        }
+    }
+
+    if let Some(pointer) = response.hover_pos() {
+        let hovered = positions
+            .values()
+            .map(|(pos, v)| (v, pos.distance(pointer)))
+            .filter(|(v, distance)| *distance <= dot_radius(v.degree).max(6.0))
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((v, _)) = hovered {
+            let label = labels.get(v.node_id.as_str()).map_or(v.node_id.as_str(), String::as_str);
+            let partition = if components.len() > 1 {
+                format!(
+                    "\ncomponent {} of {} ({} nodes)",
+                    v.component + 1,
+                    components.len(),
+                    components[v.component].len(),
+                )
+            } else {
+                String::new()
+            };
+            let reporting = if v.reporting { "" } else { "\nno telemetry from this peer" };
+            response.on_hover_text_at_pointer(format!(
+                "{}\n{} connections{}{}",
+                label, v.degree, partition, reporting,
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn components_are_laid_out_apart() {
+        let angles = circle_angles(&[2, 1]);
+        // 3 nodes and 2 gaps: 5 slots
+        let step = TAU / 5.0;
+        assert_eq!(angles, vec![vec![-FRAC_PI_2, step - FRAC_PI_2], vec![3.0 * step - FRAC_PI_2]]);
+
+        // A single component fills the circle
+        assert_eq!(circle_angles(&[4])[0][2], TAU / 2.0 - FRAC_PI_2);
+    }
+}
@@ -0,0 +1,49 @@
+//! Window state (native) — which sidebars were open, saved through eframe storage and
+//! restored on the next launch. eframe itself persists the window geometry.
+
+use serde::{Deserialize, Serialize};
+use super::JamApp;
+
+/// eframe storage key for [`WindowState`]
+const STORAGE_KEY: &str = "jam_orbit_window";
+
+/// Sidebars that were open when the app closed
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct WindowState {
+    show_settings: bool,
+    show_event_selector: bool,
+}
+
+impl JamApp {
+    /// Reopen the sidebars of the previous session
+    pub(crate) fn restore_window_state(&mut self, storage: Option<&dyn eframe::Storage>) {
+        let Some(state) = storage.and_then(|s| eframe::get_value::<WindowState>(s, STORAGE_KEY))
+        else {
+            return;
+        };
+        self.show_settings = state.show_settings;
+        self.show_event_selector = state.show_event_selector;
+    }
+
+    /// Write the open sidebars to eframe storage
+    pub(crate) fn persist_window_state(&self, storage: &mut dyn eframe::Storage) {
+        let state = WindowState {
+            show_settings: self.show_settings,
+            show_event_selector: self.show_event_selector,
+        };
+        eframe::set_value(storage, STORAGE_KEY, &state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_fields_fall_back_to_defaults() {
+        let state: WindowState = serde_json::from_str(r#"{"show_settings": true}"#).unwrap();
+        assert!(state.show_settings);
+        assert!(!state.show_event_selector);
+    }
+}
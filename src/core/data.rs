@@ -3,10 +3,82 @@
 //! These structures are platform-agnostic (no WASM deps) and shared
 //! between the CLI and dashboard.
 
-use std::collections::{HashMap, VecDeque};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::io::{self, Write};
+use regex::Regex;
 use tracing::trace;
+#[cfg(not(target_arch = "wasm32"))]
+use rayon::prelude::*;
+
+use super::events::{
+    event_name, BlockOutline, Event, EventType, Hash, HeaderHash, PeerAddress, ServiceId, CONNECT_EVENTS,
+    DISCONNECT_EVENTS, EVENT_CATEGORIES, PIPELINE_STAGES, RECOVERY_OUTCOMES,
+};
+
+/// Nodes in the store from which rate scans run on the rayon pool (native only);
+/// below this the thread handoff costs more than the scan
+const PARALLEL_MIN_NODES: usize = 64;
+
+/// Size of the index space hashed node ids are spread over (one slot per possible validator)
+pub const NODE_INDEX_SPACE: usize = 1024;
+
+/// How nodes seen for the first time are given an array / ring index
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NodeIndexing {
+    /// Next free index in arrival order; shuffles when nodes reconnect in a different order
+    #[default]
+    Arrival,
+    /// Hash of the node id within `NODE_INDEX_SPACE`, so a validator keeps its index
+    /// across reconnects; collisions take the next free index
+    Hashed,
+}
+
+/// Hands out node indices below `capacity`. Once every index is used,
+/// further nodes share the last one.
+#[derive(Clone, Debug)]
+struct IndexAllocator {
+    mode: NodeIndexing,
+    capacity: usize,
+    taken: HashSet<usize>,
+    assigned: usize,
+}
+
+impl IndexAllocator {
+    fn new(capacity: usize) -> Self {
+        Self {
+            mode: NodeIndexing::Arrival,
+            capacity: capacity.max(1),
+            taken: HashSet::new(),
+            assigned: 0,
+        }
+    }
+
+    fn assign(&mut self, node_id: &str) -> usize {
+        let last = self.capacity - 1;
+        let idx = match self.mode {
+            NodeIndexing::Arrival => self.assigned.min(last),
+            NodeIndexing::Hashed => {
+                let space = self.capacity.min(NODE_INDEX_SPACE);
+                let start = (fnv1a(node_id.as_bytes()) % space as u64) as usize;
+                (0..space)
+                    .map(|probe| (start + probe) % space)
+                    .find(|idx| !self.taken.contains(idx))
+                    .unwrap_or(last)
+            }
+        };
+        self.taken.insert(idx);
+        self.assigned += 1;
+        idx
+    }
+}
 
-use super::events::Event;
+/// 64-bit FNV-1a: stable across runs and platforms, unlike `DefaultHasher`
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
 
 /// Time series data - stores num_peers over time per validator
 pub struct TimeSeriesData {
@@ -16,6 +88,7 @@ pub struct TimeSeriesData {
     pub max_points: usize,
     /// Maps node_id (hex string) to array index
     node_index: HashMap<String, usize>,
+    indices: IndexAllocator,
 }
 
 impl TimeSeriesData {
@@ -24,9 +97,15 @@ impl TimeSeriesData {
             series: vec![Vec::with_capacity(max_points); num_series],
             max_points,
             node_index: HashMap::new(),
+            indices: IndexAllocator::new(num_series),
         }
     }
 
+    /// Index assignment for validators not seen yet
+    pub fn set_indexing(&mut self, mode: NodeIndexing) {
+        self.indices.mode = mode;
+    }
+
     /// Push a new data point for a validator
     pub fn push(&mut self, node_id: &str, value: f32) {
         let (idx, is_new) = self.get_or_create_index(node_id);
@@ -50,14 +129,13 @@ impl TimeSeriesData {
             return (idx, false);
         }
 
-        // Assign next available index, capped at series capacity
-        let idx = self.node_index.len().min(self.series.len() - 1);
+        // Arrival order or hashed, capped at series capacity
+        let idx = self.indices.assign(node_id);
         self.node_index.insert(node_id.to_string(), idx);
         (idx, true)
     }
 
     /// Length of the longest series
-    #[allow(dead_code)]
     pub fn max_series_len(&self) -> usize {
         self.series.iter().map(|s| s.len()).max().unwrap_or(0)
     }
@@ -68,6 +146,55 @@ impl TimeSeriesData {
         self.series.first().map_or(0, |s| s.len())
     }
 
+    /// Series that have been assigned to a node, as `(node_id, series index, values)`
+    pub fn labeled_series(&self) -> impl Iterator<Item = (&str, usize, &Vec<f32>)> {
+        self.node_index
+            .iter()
+            .filter_map(|(id, &idx)| self.series.get(idx).map(|s| (id.as_str(), idx, s)))
+    }
+
+    /// Sum across validators, aligned on each series' newest point (newest last).
+    /// Status reports arrive at the same cadence on every node, so equal offsets from the
+    /// end are roughly simultaneous; shorter series only contribute to their own span.
+    pub fn aggregate(&self) -> Vec<f32> {
+        let len = self.max_series_len();
+        let mut totals = vec![0.0f32; len];
+        for series in &self.series {
+            let offset = len - series.len();
+            for (total, &value) in totals[offset..].iter_mut().zip(series) {
+                *total += value;
+            }
+        }
+        totals
+    }
+
+    /// `(min, median, max)` across validators at each point, end-aligned like
+    /// [`aggregate`](Self::aggregate): a point only counts the series that reach back to it
+    pub fn spread(&self) -> Vec<(f32, f32, f32)> {
+        let len = self.max_series_len();
+        let mut column: Vec<f32> = Vec::with_capacity(self.series.len());
+        (0..len)
+            .map(|i| {
+                let from_end = len - i;
+                column.clear();
+                column.extend(
+                    self.series
+                        .iter()
+                        .filter(|s| s.len() >= from_end)
+                        .map(|s| s[s.len() - from_end]),
+                );
+                column.sort_by(f32::total_cmp);
+                let n = column.len();
+                let median = if n % 2 == 1 {
+                    column[n / 2]
+                } else {
+                    (column[n / 2 - 1] + column[n / 2]) / 2.0
+                };
+                (column[0], median, column[n - 1])
+            })
+            .collect()
+    }
+
     /// Get the latest value (peer count) for a node_id
     pub fn latest_value(&self, node_id: &str) -> Option<f32> {
         self.node_index.get(node_id)
@@ -76,14 +203,158 @@ impl TimeSeriesData {
     }
 }
 
+/// Availability shard metrics per validator over time (from Status)
+pub struct ShardMetrics {
+    /// Number of shards held
+    pub shard_counts: TimeSeriesData,
+    /// Total size of held shards, in bytes
+    pub shard_sizes: TimeSeriesData,
+}
+
+impl ShardMetrics {
+    pub fn new(num_series: usize, max_points: usize) -> Self {
+        Self {
+            shard_counts: TimeSeriesData::new(num_series, max_points),
+            shard_sizes: TimeSeriesData::new(num_series, max_points),
+        }
+    }
+
+    /// Record one Status report
+    pub fn push(&mut self, node_id: &str, num_shards: u32, shards_size: u64) {
+        self.shard_counts.push(node_id, num_shards as f32);
+        self.shard_sizes.push(node_id, shards_size as f32);
+    }
+}
+
+/// Peer counts by kind per validator over time (from Status); the total is in the
+/// `num_peers` series
+pub struct PeerMetrics {
+    /// Peers that are validators
+    pub val_peers: TimeSeriesData,
+    /// Peers the node syncs from
+    pub sync_peers: TimeSeriesData,
+}
+
+impl PeerMetrics {
+    pub fn new(num_series: usize, max_points: usize) -> Self {
+        Self {
+            val_peers: TimeSeriesData::new(num_series, max_points),
+            sync_peers: TimeSeriesData::new(num_series, max_points),
+        }
+    }
+
+    /// Record one Status report
+    pub fn push(&mut self, node_id: &str, num_val_peers: u32, num_sync_peers: u32) {
+        self.val_peers.push(node_id, num_val_peers as f32);
+        self.sync_peers.push(node_id, num_sync_peers as f32);
+    }
+}
+
+/// Preimage pool metrics per validator over time (from Status)
+pub struct PreimageMetrics {
+    /// Number of preimages held
+    pub preimage_counts: TimeSeriesData,
+    /// Total size of held preimages, in bytes
+    pub preimage_sizes: TimeSeriesData,
+}
+
+impl PreimageMetrics {
+    pub fn new(num_series: usize, max_points: usize) -> Self {
+        Self {
+            preimage_counts: TimeSeriesData::new(num_series, max_points),
+            preimage_sizes: TimeSeriesData::new(num_series, max_points),
+        }
+    }
+
+    /// Record one Status report
+    pub fn push(&mut self, node_id: &str, num_preimages: u32, preimages_size: u32) {
+        self.preimage_counts.push(node_id, num_preimages as f32);
+        self.preimage_sizes.push(node_id, preimages_size as f32);
+    }
+}
+
+/// Tip advances averaged into the block time estimate
+pub const BLOCK_TIME_WINDOW: usize = 20;
+
 /// Best block and finalized block data per validator
 pub struct BestBlockData {
     /// [validator_idx] = best block slot
     pub best_blocks: Vec<u64>,
     /// [validator_idx] = finalized block slot
     pub finalized_blocks: Vec<u64>,
+    /// [validator_idx] = hash of the latest best block (None until first BestBlockChanged)
+    best_hashes: Vec<Option<HeaderHash>>,
+    /// Network-wide block time from advances of the highest best slot
+    pub block_times: BlockTimes,
     /// Maps node_id to array index
     node_index: HashMap<String, usize>,
+    indices: IndexAllocator,
+}
+
+/// Rolling estimate of the network block time from tip advances.
+///
+/// Only a new highest slot counts, so duplicate reports of the tip from other validators and
+/// late (out-of-order) reports of older slots are ignored.
+pub struct BlockTimes {
+    /// (app time, slot) of the current tip
+    tip: Option<(f64, u64)>,
+    /// Seconds between consecutive tip advances, oldest first
+    intervals: VecDeque<f64>,
+    /// Intervals kept for the rolling statistics
+    window: usize,
+}
+
+impl BlockTimes {
+    pub fn new(window: usize) -> Self {
+        Self {
+            tip: None,
+            intervals: VecDeque::with_capacity(window),
+            window,
+        }
+    }
+
+    /// Note a best block report at app time `now`
+    pub fn record(&mut self, now: f64, slot: u64) {
+        match self.tip {
+            Some((_, tip_slot)) if slot <= tip_slot => {}
+            Some((tip_time, _)) => {
+                if self.intervals.len() >= self.window {
+                    self.intervals.pop_front();
+                }
+                self.intervals.push_back((now - tip_time).max(0.0));
+                self.tip = Some((now, slot));
+            }
+            None => self.tip = Some((now, slot)),
+        }
+    }
+
+    /// Mean interval in seconds (None until the tip advanced twice)
+    pub fn mean(&self) -> Option<f64> {
+        if self.intervals.is_empty() {
+            return None;
+        }
+        Some(self.intervals.iter().sum::<f64>() / self.intervals.len() as f64)
+    }
+
+    /// Population variance of the intervals in seconds²
+    pub fn variance(&self) -> Option<f64> {
+        let mean = self.mean()?;
+        let sum_sq: f64 = self.intervals.iter().map(|i| (i - mean).powi(2)).sum();
+        Some(sum_sq / self.intervals.len() as f64)
+    }
+
+    /// Number of intervals behind the current estimate
+    pub fn sample_count(&self) -> usize {
+        self.intervals.len()
+    }
+}
+
+/// Validators disagreeing on the block at one slot
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fork {
+    pub slot: u64,
+    /// (hash, node_ids reporting it), largest group first
+    pub branches: Vec<(HeaderHash, Vec<String>)>,
 }
 
 impl BestBlockData {
@@ -91,12 +362,20 @@ impl BestBlockData {
         Self {
             best_blocks: vec![0; num_validators],
             finalized_blocks: vec![0; num_validators],
+            best_hashes: vec![None; num_validators],
+            block_times: BlockTimes::new(BLOCK_TIME_WINDOW),
             node_index: HashMap::new(),
+            indices: IndexAllocator::new(num_validators),
         }
     }
 
-    /// Update best block for a validator
-    pub fn set_best(&mut self, node_id: &str, slot: u64) {
+    /// Index assignment for validators not seen yet
+    pub fn set_indexing(&mut self, mode: NodeIndexing) {
+        self.indices.mode = mode;
+    }
+
+    /// Update best block (slot and hash) for a validator
+    pub fn set_best(&mut self, node_id: &str, slot: u64, hash: HeaderHash) {
         let (idx, is_new) = self.get_or_create_index(node_id);
 
         if is_new {
@@ -105,6 +384,7 @@ impl BestBlockData {
 
         let prev = self.best_blocks[idx];
         self.best_blocks[idx] = slot;
+        self.best_hashes[idx] = Some(hash);
 
         trace!(node_id, prev_slot = prev, new_slot = slot, "Best block updated");
     }
@@ -123,11 +403,36 @@ impl BestBlockData {
             return (idx, false);
         }
 
-        let idx = self.node_index.len().min(self.best_blocks.len() - 1);
+        let idx = self.indices.assign(node_id);
         self.node_index.insert(node_id.to_string(), idx);
         (idx, true)
     }
 
+    /// Best block slot for a validator (None if never reported)
+    pub fn best_for(&self, node_id: &str) -> Option<u64> {
+        self.node_index
+            .get(node_id)
+            .map(|&idx| self.best_blocks[idx])
+            .filter(|&s| s > 0)
+    }
+
+    /// Finalized block slot for a validator (None if never reported)
+    pub fn finalized_for(&self, node_id: &str) -> Option<u64> {
+        self.node_index
+            .get(node_id)
+            .map(|&idx| self.finalized_blocks[idx])
+            .filter(|&s| s > 0)
+    }
+
+    /// Iterate (node_id, best, finalized) for every validator seen (None = never reported)
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Option<u64>, Option<u64>)> {
+        self.node_index.iter().map(|(node_id, &idx)| {
+            let best = Some(self.best_blocks[idx]).filter(|&s| s > 0);
+            let finalized = Some(self.finalized_blocks[idx]).filter(|&s| s > 0);
+            (node_id.as_str(), best, finalized)
+        })
+    }
+
     /// Number of unique validators seen
     #[allow(dead_code)]
     pub fn validator_count(&self) -> usize {
@@ -139,6 +444,71 @@ impl BestBlockData {
         self.best_blocks.iter().copied().filter(|&s| s > 0).max()
     }
 
+    /// `best - finalized` slots per validator index, for validators that reported both.
+    /// A finalized slot ahead of best (ordering artifact) is clamped to a lag of zero.
+    pub fn finalization_lag(&self) -> Vec<(u16, i64)> {
+        let mut lags: Vec<(u16, i64)> = self
+            .node_index
+            .iter()
+            .filter_map(|(node_id, &idx)| {
+                let best = self.best_blocks[idx];
+                let finalized = self.finalized_blocks[idx];
+                if best == 0 || finalized == 0 {
+                    return None;
+                }
+                let lag = best as i64 - finalized as i64;
+                if lag < 0 {
+                    trace!(node_id, best, finalized, "Finalized ahead of best, clamping lag");
+                }
+                Some((idx as u16, lag.max(0)))
+            })
+            .collect();
+        lags.sort_unstable();
+        lags
+    }
+
+    /// Divergent best blocks at the highest slot that at least two validators have as their tip.
+    ///
+    /// Only each validator's latest (slot, hash) is kept, so this compares tips: returns
+    /// `Some` when those validators report more than one hash for that slot.
+    pub fn forks_at_slot(&self) -> Option<Fork> {
+        // Highest slot shared by two or more tips
+        let mut tip_counts: HashMap<u64, usize> = HashMap::new();
+        for &idx in self.node_index.values() {
+            if self.best_hashes[idx].is_some() {
+                *tip_counts.entry(self.best_blocks[idx]).or_default() += 1;
+            }
+        }
+        let slot = tip_counts
+            .into_iter()
+            .filter(|&(_, count)| count >= 2)
+            .map(|(slot, _)| slot)
+            .max()?;
+
+        let mut groups: HashMap<HeaderHash, Vec<String>> = HashMap::new();
+        for (node_id, &idx) in &self.node_index {
+            if self.best_blocks[idx] != slot {
+                continue;
+            }
+            if let Some(hash) = self.best_hashes[idx] {
+                groups.entry(hash).or_default().push(node_id.clone());
+            }
+        }
+        if groups.len() < 2 {
+            return None;
+        }
+
+        let mut branches: Vec<(HeaderHash, Vec<String>)> = groups
+            .into_iter()
+            .map(|(hash, mut nodes)| {
+                nodes.sort();
+                (hash, nodes)
+            })
+            .collect();
+        branches.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then(a.0.cmp(&b.0)));
+        Some(Fork { slot, branches })
+    }
+
     /// Highest finalized slot across all validators
     #[allow(dead_code)]
     pub fn highest_finalized(&self) -> Option<u64> {
@@ -146,15 +516,433 @@ impl BestBlockData {
     }
 }
 
+/// Guarantee queue depth per core, per validator (latest Status report)
+pub struct GuaranteeQueueData {
+    /// [validator_idx][core] = queued guarantees
+    pub per_validator: Vec<Vec<u8>>,
+    /// Maps node_id to array index
+    node_index: HashMap<String, usize>,
+}
+
+impl GuaranteeQueueData {
+    pub fn new(num_validators: usize) -> Self {
+        Self {
+            per_validator: vec![Vec::new(); num_validators],
+            node_index: HashMap::new(),
+        }
+    }
+
+    /// Replace a validator's per-core queue depths
+    pub fn set(&mut self, node_id: &str, num_guarantees: &[u8]) {
+        let idx = match self.node_index.get(node_id) {
+            Some(&idx) => idx,
+            None => {
+                let idx = self.node_index.len().min(self.per_validator.len() - 1);
+                self.node_index.insert(node_id.to_string(), idx);
+                idx
+            }
+        };
+        self.per_validator[idx] = num_guarantees.to_vec();
+    }
+
+    /// Largest core count reported by any validator
+    pub fn num_cores(&self) -> usize {
+        self.per_validator.iter().map(|q| q.len()).max().unwrap_or(0)
+    }
+
+    /// Queue depth summed over validators, per core
+    pub fn aggregate_per_core(&self) -> Vec<u32> {
+        let mut totals = vec![0u32; self.num_cores()];
+        for queue in &self.per_validator {
+            for (total, &depth) in totals.iter_mut().zip(queue) {
+                *total += depth as u32;
+            }
+        }
+        totals
+    }
+
+    /// (node_id, depths) for every validator seen, in registration order.
+    /// Shorter reports are padded with zeros to [`num_cores`](Self::num_cores).
+    pub fn rows(&self) -> Vec<(&str, Vec<u8>)> {
+        let cores = self.num_cores();
+        let mut rows: Vec<(usize, &str)> =
+            self.node_index.iter().map(|(id, &idx)| (idx, id.as_str())).collect();
+        rows.sort_unstable();
+        rows.into_iter()
+            .map(|(idx, id)| {
+                let mut depths = self.per_validator[idx].clone();
+                depths.resize(cores, 0);
+                (id, depths)
+            })
+            .collect()
+    }
+}
+
+/// Latest availability bitfield per validator (from DistributingAssurance)
+#[derive(Default)]
+pub struct AssuranceData {
+    /// (node_id, bitfield) in registration order
+    latest: Vec<(String, Vec<u8>)>,
+    /// Maps node_id to its position in `latest`
+    node_index: HashMap<String, usize>,
+}
+
+impl AssuranceData {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace a validator's latest bitfield
+    pub fn set(&mut self, node_id: &str, bitfield: &[u8]) {
+        match self.node_index.get(node_id) {
+            Some(&idx) => self.latest[idx].1 = bitfield.to_vec(),
+            None => {
+                self.node_index.insert(node_id.to_string(), self.latest.len());
+                self.latest.push((node_id.to_string(), bitfield.to_vec()));
+            }
+        }
+    }
+
+    /// Widest bitfield reported by any validator, in bits
+    pub fn max_bits(&self) -> usize {
+        self.latest.iter().map(|(_, b)| b.len() * 8).max().unwrap_or(0)
+    }
+
+    /// (node_id, availability per core) for every validator seen, in registration order.
+    /// Bits are read least-significant first; each row is cut or padded (unset) to `cores`.
+    pub fn rows(&self, cores: usize) -> Vec<(&str, Vec<bool>)> {
+        self.latest
+            .iter()
+            .map(|(id, bitfield)| {
+                let bits = (0..cores)
+                    .map(|core| bitfield.get(core / 8).is_some_and(|byte| byte >> (core % 8) & 1 == 1))
+                    .collect();
+                (id.as_str(), bits)
+            })
+            .collect()
+    }
+}
+
+/// Every field of a validator's latest Status report
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LatestStatus {
+    pub num_peers: u32,
+    pub num_val_peers: u32,
+    pub num_sync_peers: u32,
+    pub num_shards: u32,
+    pub shards_size: u64,
+    pub num_preimages: u32,
+    pub preimages_size: u32,
+    /// App time the report arrived
+    pub updated_at: f64,
+}
+
+/// Latest Status report per validator
+#[derive(Default)]
+pub struct LatestStatusData {
+    latest: HashMap<String, LatestStatus>,
+}
+
+impl LatestStatusData {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace a validator's latest report
+    pub fn set(&mut self, node_id: &str, status: LatestStatus) {
+        self.latest.insert(node_id.to_string(), status);
+    }
+
+    /// (node_id, report) for every validator that sent a Status, sorted by node_id
+    pub fn entries(&self) -> Vec<(&str, &LatestStatus)> {
+        let mut entries: Vec<(&str, &LatestStatus)> =
+            self.latest.iter().map(|(id, status)| (id.as_str(), status)).collect();
+        entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        entries
+    }
+}
+
+/// Epochs kept by [`TicketData`]; older ones are dropped as new epochs start
+pub const TICKET_EPOCHS_KEPT: usize = 12;
+
+/// Safrole ticket activity within one epoch, summed over validators
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EpochTickets {
+    /// Validators that started generating tickets (GeneratingTickets)
+    pub generating: u32,
+    /// Ticket ids produced (TicketsGenerated)
+    pub generated: u32,
+    /// Tickets sent to a peer, counted on the sending side (TicketTransferred)
+    pub transferred: u32,
+    /// TicketGenerationFailed and TicketTransferFailed reports
+    pub failed: u32,
+}
+
+/// Per-epoch ticket counts (from the Safrole ticket events).
+///
+/// TicketsGenerated and TicketGenerationFailed carry no epoch; they are credited to the epoch
+/// of the node's latest GeneratingTickets (the current epoch when none was seen). Transfer
+/// failures carry none either and go to the current epoch.
+#[derive(Default)]
+pub struct TicketData {
+    epochs: BTreeMap<u32, EpochTickets>,
+    /// node_id → epoch of its latest GeneratingTickets
+    node_epoch: HashMap<String, u32>,
+}
+
+impl TicketData {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Highest epoch seen
+    pub fn current_epoch(&self) -> Option<u32> {
+        self.epochs.keys().next_back().copied()
+    }
+
+    /// Counts of `epoch`, created on first use. An epoch older than every kept one is not
+    /// recorded (it would be pruned right away).
+    fn epoch_mut(&mut self, epoch: u32) -> Option<&mut EpochTickets> {
+        if self.epochs.len() >= TICKET_EPOCHS_KEPT
+            && !self.epochs.contains_key(&epoch)
+            && self.epochs.keys().next().is_some_and(|&oldest| epoch < oldest)
+        {
+            return None;
+        }
+        self.epochs.entry(epoch).or_default();
+        // Rollover: a new epoch pushes out the oldest
+        while self.epochs.len() > TICKET_EPOCHS_KEPT {
+            self.epochs.pop_first();
+        }
+        self.epochs.get_mut(&epoch)
+    }
+
+    pub fn record_generating(&mut self, node_id: &str, epoch: u32) {
+        self.node_epoch.insert(node_id.to_string(), epoch);
+        if let Some(counts) = self.epoch_mut(epoch) {
+            counts.generating += 1;
+        }
+    }
+
+    /// `failed` for TicketGenerationFailed; otherwise `count` tickets were generated
+    pub fn record_generated(&mut self, node_id: &str, count: u32, failed: bool) {
+        let Some(epoch) = self.node_epoch.get(node_id).copied().or(self.current_epoch()) else {
+            return;
+        };
+        if let Some(counts) = self.epoch_mut(epoch) {
+            if failed {
+                counts.failed += 1;
+            } else {
+                counts.generated += count;
+            }
+        }
+    }
+
+    pub fn record_transferred(&mut self, epoch: u32) {
+        if let Some(counts) = self.epoch_mut(epoch) {
+            counts.transferred += 1;
+        }
+    }
+
+    pub fn record_transfer_failed(&mut self) {
+        if let Some(counts) = self.current_epoch().and_then(|epoch| self.epoch_mut(epoch)) {
+            counts.failed += 1;
+        }
+    }
+
+    /// (epoch, counts) oldest first
+    pub fn epochs(&self) -> impl Iterator<Item = (u32, &EpochTickets)> {
+        self.epochs.iter().map(|(&epoch, counts)| (epoch, counts))
+    }
+}
+
+/// Seconds without a report after which a validator's sync state is shown as unknown
+pub const SYNC_STALE_SECS: f64 = 30.0;
+
+/// Latest sync state per validator (from SyncStatusChanged)
+#[derive(Default)]
+pub struct SyncStatusData {
+    /// node_id → (synced, app time of the last report)
+    latest: HashMap<String, (bool, f64)>,
+}
+
+impl SyncStatusData {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a SyncStatusChanged report
+    pub fn set(&mut self, node_id: &str, synced: bool, now: f64) {
+        self.latest.insert(node_id.to_string(), (synced, now));
+    }
+
+    /// Refresh a known validator's report time without changing its state.
+    /// SyncStatusChanged is only sent on transitions, so periodic Status events keep it fresh.
+    pub fn touch(&mut self, node_id: &str, now: f64) {
+        if let Some((_, updated_at)) = self.latest.get_mut(node_id) {
+            *updated_at = now;
+        }
+    }
+
+    /// (synced, total) over validators that reported a sync state
+    pub fn synced_count(&self) -> (usize, usize) {
+        let synced = self.latest.values().filter(|(synced, _)| *synced).count();
+        (synced, self.latest.len())
+    }
+
+    /// Share of reporting validators that are synced (None before any report)
+    pub fn synced_fraction(&self) -> Option<f64> {
+        let (synced, total) = self.synced_count();
+        (total > 0).then(|| synced as f64 / total as f64)
+    }
+
+    /// (node_id, synced, stale) for every validator that reported, sorted by node_id
+    pub fn entries(&self, now: f64) -> Vec<(&str, bool, bool)> {
+        let mut entries: Vec<(&str, bool, bool)> = self
+            .latest
+            .iter()
+            .map(|(id, &(synced, updated_at))| {
+                (id.as_str(), synced, now - updated_at > SYNC_STALE_SECS)
+            })
+            .collect();
+        entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        entries
+    }
+}
+
+/// Share of its Status-reported peers a validator's observed connections must reach before
+/// its place in the topology counts for partitions
+const TOPOLOGY_COMPLETE_SHARE: f64 = 0.8;
+
+/// Live peer connections: who is connected to whom (from ConnectedIn and Disconnected)
+///
+/// Connections are undirected; both ends may report the same one. Only events naming the
+/// peer count: ConnectedOut names none, just the node-local event id of its ConnectingOut,
+/// and concurrent dials finish in any order, so an outbound connection shows up when the
+/// other end reports its ConnectedIn. Connections to peers that send no telemetry and were
+/// dialled by the node are therefore missing.
+#[derive(Default)]
+pub struct TopologyData {
+    /// node_id → node_ids it is connected to
+    edges: HashMap<String, BTreeSet<String>>,
+}
+
+impl TopologyData {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn connect(&mut self, a: &str, b: &str) {
+        if a == b {
+            return;
+        }
+        self.edges.entry(a.to_string()).or_default().insert(b.to_string());
+        self.edges.entry(b.to_string()).or_default().insert(a.to_string());
+    }
+
+    pub fn disconnect(&mut self, a: &str, b: &str) {
+        for (from, to) in [(a, b), (b, a)] {
+            if let Some(peers) = self.edges.get_mut(from) {
+                peers.remove(to);
+                if peers.is_empty() {
+                    self.edges.remove(from);
+                }
+            }
+        }
+    }
+
+    /// Number of peers `node_id` is connected to
+    pub fn degree(&self, node_id: &str) -> usize {
+        self.edges.get(node_id).map_or(0, BTreeSet::len)
+    }
+
+    /// Every connection once, as (lower node_id, higher node_id)
+    pub fn edges(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.edges.iter().flat_map(|(a, peers)| {
+            peers.iter().filter(move |b| a < *b).map(move |b| (a.as_str(), b.as_str()))
+        })
+    }
+
+    /// Connected components over the connected nodes plus `nodes` (those without connections
+    /// become components of their own), largest first, each sorted by node_id
+    pub fn components<'a>(&'a self, nodes: impl IntoIterator<Item = &'a str>) -> Vec<Vec<&'a str>> {
+        let mut vertices: BTreeSet<&str> = self.edges.keys().map(String::as_str).collect();
+        vertices.extend(nodes);
+        let mut seen: HashSet<&str> = HashSet::new();
+        let mut components = Vec::new();
+        for &start in &vertices {
+            if !seen.insert(start) {
+                continue;
+            }
+            let mut component = vec![start];
+            let mut next = 0;
+            while next < component.len() {
+                let node = component[next];
+                next += 1;
+                for peer in self.edges.get(node).into_iter().flatten() {
+                    if seen.insert(peer.as_str()) {
+                        component.push(peer.as_str());
+                    }
+                }
+            }
+            component.sort_unstable();
+            components.push(component);
+        }
+        // Stable: equal sizes stay in node_id order
+        components.sort_by(|a, b| b.len().cmp(&a.len()));
+        components
+    }
+
+    /// How many of `validators`, given as (node_id, peer count from its latest Status), each
+    /// component holds, largest first. Connections made before the dashboard started are never
+    /// reported, so a validator is left out until the connections seen reach
+    /// `TOPOLOGY_COMPLETE_SHARE` of its reported peers (and at least one): with fewer, its
+    /// component says more about what was missed than about a split.
+    pub fn validator_components<'a>(
+        &self,
+        validators: impl IntoIterator<Item = (&'a str, u32)>,
+    ) -> Vec<usize> {
+        let components = self.components(std::iter::empty());
+        let component_of: HashMap<&str, usize> = components
+            .iter()
+            .enumerate()
+            .flat_map(|(i, ids)| ids.iter().map(move |&id| (id, i)))
+            .collect();
+        let mut sizes = vec![0; components.len()];
+        for (validator, num_peers) in validators {
+            let degree = self.degree(validator);
+            if (degree as f64) < num_peers as f64 * TOPOLOGY_COMPLETE_SHARE {
+                continue;
+            }
+            if let Some(&i) = component_of.get(validator) {
+                sizes[i] += 1;
+            }
+        }
+        sizes.retain(|&size| size > 0);
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes
+    }
+}
+
 // ============================================================================
 // Event Storage (full events, indexed per-node)
 // ============================================================================
 
+/// Whether a hidden-sources mask (bit `id - 1` per source) leaves out source `id`;
+/// untagged events (id 0) are never hidden
+pub fn source_hidden(mask: u64, id: u8) -> bool {
+    id != 0 && mask & (1 << (id - 1)) != 0
+}
+
 /// A stored event with app-relative timestamp
 #[allow(dead_code)]
 pub struct StoredEvent {
     /// When event occurred (app-relative seconds)
     pub timestamp: f64,
+    /// Store-wide insertion sequence number, for incremental consumers (see `get_new_since`)
+    pub seq: u64,
+    /// Endpoint the event came from (0 with a single endpoint, see `ParserContext::source_id`)
+    pub source_id: u8,
     /// Full parsed event with all variant data
     pub event: Event,
 }
@@ -175,6 +963,14 @@ pub struct NodeEvents {
     pub index: u16,
     /// Max events per type (ring buffer capacity)
     max_per_type: usize,
+    /// Events dropped because a type bucket was full (retention pruning not counted)
+    capacity_evictions: u64,
+    /// Connections reported up since the node was first seen (never pruned)
+    connects: u64,
+    /// Connections reported down or failed since the node was first seen (never pruned)
+    disconnects: u64,
+    /// Timestamp of the newest event ever pushed (never pruned)
+    last_seen: f64,
 }
 
 impl NodeEvents {
@@ -183,12 +979,23 @@ impl NodeEvents {
             by_type: HashMap::new(),
             index,
             max_per_type,
+            capacity_evictions: 0,
+            connects: 0,
+            disconnects: 0,
+            last_seen: f64::NEG_INFINITY,
         }
     }
 
     /// Push an event into the appropriate type bucket
-    fn push(&mut self, event: Event, timestamp: f64) {
-        let event_type = event.event_type() as u8;
+    fn push(&mut self, event: Event, timestamp: f64, seq: u64, source_id: u8) {
+        self.last_seen = self.last_seen.max(timestamp);
+        let kind = event.event_type();
+        if CONNECT_EVENTS.contains(&kind) {
+            self.connects += 1;
+        } else if DISCONNECT_EVENTS.contains(&kind) {
+            self.disconnects += 1;
+        }
+        let event_type = kind as u8;
         let max = self.max_per_type;
 
         let bucket = self.by_type.entry(event_type).or_insert_with(|| {
@@ -197,52 +1004,238 @@ impl NodeEvents {
 
         if bucket.len() >= max {
             bucket.pop_front();
+            self.capacity_evictions += 1;
         }
-        bucket.push_back(StoredEvent { timestamp, event });
+        bucket.push_back(StoredEvent { timestamp, seq, source_id, event });
     }
 
     /// Total event count across all types
     pub fn total_events(&self) -> usize {
         self.by_type.values().map(|v| v.len()).sum()
     }
-}
 
-/// Per-node event storage - source of truth for all event visualizations
-pub struct EventStore {
-    /// Events grouped by node: node_id → NodeEvents
-    nodes: HashMap<String, NodeEvents>,
-    /// Max events per node (ring buffer per node)
-    max_events_per_node: usize,
-    /// How long to keep events (seconds)
-    #[allow(dead_code)]
-    pub retention: f64,
-    /// Counter for assigning node indices
-    next_node_index: u16,
-}
+    /// Stored event count per type, sorted by count (descending), then type
+    pub fn type_counts(&self) -> Vec<(u8, usize)> {
+        let mut counts: Vec<(u8, usize)> = self
+            .by_type
+            .iter()
+            .filter(|(_, events)| !events.is_empty())
+            .map(|(&et, events)| (et, events.len()))
+            .collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        counts
+    }
 
-impl EventStore {
-    pub fn new(max_events_per_node: usize, retention: f64) -> Self {
-        Self {
-            nodes: HashMap::new(),
-            max_events_per_node,
-            retention,
-            next_node_index: 0,
-        }
+    /// Number of stored events (all types) with timestamp >= `cutoff`
+    pub fn count_since(&self, cutoff: f64) -> usize {
+        // Buckets are chronological, so count from the back
+        self.by_type
+            .values()
+            .map(|events| events.iter().rev().take_while(|e| e.timestamp >= cutoff).count())
+            .sum()
+    }
+
+    /// Timestamp of the newest event ever pushed, kept after retention pruned it
+    pub fn last_seen(&self) -> f64 {
+        self.last_seen
+    }
+
+    /// Work-package or block hash of the newest `kinds` event at or before `timestamp`: what
+    /// a later event of the same pipeline on this node was about. Event ids aren't tracked,
+    /// so the link is by order rather than by `submission_or_share_id`.
+    fn preceding_hash(&self, kinds: &[EventType], timestamp: f64) -> Option<Hash> {
+        kinds
+            .iter()
+            .filter_map(|&kind| self.by_type.get(&(kind as u8)))
+            .filter_map(|events| {
+                let end = events.partition_point(|e| e.timestamp <= timestamp);
+                end.checked_sub(1).map(|i| &events[i])
+            })
+            .max_by(|a, b| a.timestamp.total_cmp(&b.timestamp))
+            .and_then(|stored| match &stored.event {
+                Event::WorkPackageReceived { outline, .. } => Some(outline.work_package_hash),
+                Event::Authored { outline, .. } | Event::Importing { outline, .. } => Some(outline.hash),
+                _ => None,
+            })
+    }
+
+    /// Remote addresses of the node's latest connection attempts (in and out), newest first,
+    /// each address once
+    pub fn recent_peer_addresses(&self, limit: usize) -> Vec<PeerAddress> {
+        let mut attempts: Vec<(f64, PeerAddress)> = [EventType::ConnectingIn, EventType::ConnectingOut]
+            .into_iter()
+            .filter_map(|et| self.by_type.get(&(et as u8)))
+            .flat_map(|events| events.iter())
+            .filter_map(|stored| match &stored.event {
+                Event::ConnectingIn { from, .. } => Some((stored.timestamp, *from)),
+                Event::ConnectingOut { to, .. } => Some((stored.timestamp, to.peer_address)),
+                _ => None,
+            })
+            .collect();
+        attempts.sort_by(|a, b| b.0.total_cmp(&a.0));
+        let mut addresses: Vec<PeerAddress> = Vec::new();
+        for (_, address) in attempts {
+            if addresses.len() == limit {
+                break;
+            }
+            if !addresses.contains(&address) {
+                addresses.push(address);
+            }
+        }
+        addresses
+    }
+
+    /// Sync status from the most recent stored SyncStatusChanged event
+    pub fn latest_sync_status(&self) -> Option<bool> {
+        self.by_type
+            .get(&(EventType::SyncStatusChanged as u8))
+            .and_then(|events| events.back())
+            .and_then(|stored| match stored.event {
+                Event::SyncStatusChanged { synced, .. } => Some(synced),
+                _ => None,
+            })
+    }
+}
+
+/// Fixed-interval time buckets ending at `now`, shared by the rate computations
+struct RateWindow {
+    aligned_now: f64,
+    oldest_time: f64,
+    bucket_duration: f64,
+    num_buckets: usize,
+}
+
+impl RateWindow {
+    fn new(now: f64, bucket_duration: f64, num_buckets: usize) -> Self {
+        // Align bucket boundaries to fixed time intervals to prevent oscillation
+        // at bucket edges due to floating-point precision issues.
+        // Floor now to the bucket duration so that bucket boundaries are stable.
+        let aligned_now = (now / bucket_duration).floor() * bucket_duration;
+        let oldest_time = aligned_now - (bucket_duration * num_buckets as f64);
+        Self {
+            aligned_now,
+            oldest_time,
+            bucket_duration,
+            num_buckets,
+        }
+    }
+
+    /// Bucket index for `timestamp` (newest at the end), or None outside the window
+    fn bucket(&self, timestamp: f64) -> Option<usize> {
+        if timestamp < self.oldest_time || timestamp >= self.aligned_now {
+            return None;
+        }
+        let age = self.aligned_now - timestamp;
+        let bucket_idx = ((age / self.bucket_duration) as usize).min(self.num_buckets - 1);
+        // Invert so newest is at the end
+        Some(self.num_buckets - 1 - bucket_idx)
+    }
+}
+
+/// What a gas sample was spent on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum GasSource {
+    /// Accumulation of one service in an executed block
+    Accumulate(ServiceId),
+    /// Refinement of a whole work package (all its items summed)
+    Refine,
+}
+
+/// Gas and wall time of one execution, taken from `BlockExecuted` or `Refined`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GasSample {
+    /// When the reporting event occurred (app-relative seconds)
+    pub timestamp: f64,
+    pub source: GasSource,
+    pub gas_used: u64,
+    pub elapsed_ns: u64,
+}
+
+/// Events sharing one normalized reason: how many, and which nodes reported them
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReasonGroup<'a> {
+    pub reason: String,
+    pub count: u64,
+    /// Reporting node_ids, sorted
+    pub nodes: Vec<&'a str>,
+}
+
+/// Reason text folded for grouping: lowercased, trimmed, inner whitespace runs collapsed
+pub fn normalize_reason(reason: &str) -> String {
+    reason.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+/// Per-node event storage - source of truth for all event visualizations
+pub struct EventStore {
+    /// Events grouped by node: node_id → NodeEvents
+    nodes: HashMap<String, NodeEvents>,
+    /// Max events per node (ring buffer per node)
+    max_events_per_node: usize,
+    /// How long to keep events (seconds); takes effect on the next `prune`
+    pub retention: f64,
+    /// Assigns node indices (ring / scatter positions)
+    indices: IndexAllocator,
+    /// Sequence number of the next stored event
+    next_seq: u64,
+    /// Sources left out of queries (see [`source_hidden`])
+    hidden_sources: u64,
+}
+
+impl EventStore {
+    pub fn new(max_events_per_node: usize, retention: f64) -> Self {
+        Self {
+            nodes: HashMap::new(),
+            max_events_per_node,
+            retention,
+            indices: IndexAllocator::new(u16::MAX as usize + 1),
+            next_seq: 0,
+            hidden_sources: 0,
+        }
+    }
+
+    /// Index assignment for nodes not seen yet
+    pub fn set_indexing(&mut self, mode: NodeIndexing) {
+        self.indices.mode = mode;
+    }
+
+    /// Leave the events of these sources out of the scatter, rate and count queries.
+    /// They stay stored, so showing a source again brings its history back.
+    pub fn set_hidden_sources(&mut self, mask: u64) {
+        self.hidden_sources = mask;
+    }
+
+    /// Whether `stored` comes from a source that is not hidden
+    pub fn shown(&self, stored: &StoredEvent) -> bool {
+        !source_hidden(self.hidden_sources, stored.source_id)
+    }
+
+    /// Events of a type bucket newer than `cutoff`, from shown sources
+    fn count_newer(&self, events: &VecDeque<StoredEvent>, cutoff: f64) -> usize {
+        let start = events.partition_point(|e| e.timestamp < cutoff);
+        if self.hidden_sources == 0 {
+            return events.len() - start;
+        }
+        events.range(start..).filter(|e| self.shown(e)).count()
     }
 
     /// Store a new event for a node
     pub fn push(&mut self, node_id: &str, event: Event, timestamp: f64) {
+        self.push_from(node_id, event, timestamp, 0);
+    }
+
+    /// Store a new event for a node, tagged with the endpoint it came from
+    pub fn push_from(&mut self, node_id: &str, event: Event, timestamp: f64, source_id: u8) {
         let max_events = self.max_events_per_node;
-        let next_idx = &mut self.next_node_index;
+        let indices = &mut self.indices;
 
         let node = self.nodes.entry(node_id.to_string()).or_insert_with(|| {
-            let idx = *next_idx;
-            *next_idx = next_idx.saturating_add(1);
+            let idx = indices.assign(node_id) as u16;
             trace!(node_id, idx, "New node registered for events");
             NodeEvents::new(idx, max_events)
         });
 
-        node.push(event, timestamp);
+        node.push(event, timestamp, self.next_seq, source_id);
+        self.next_seq += 1;
 
         trace!(
             node_id,
@@ -257,8 +1250,20 @@ impl EventStore {
         self.nodes.get(node_id).map(|n| n.index)
     }
 
+    /// Look up the node_id registered at a ring index
+    pub fn node_id_at(&self, index: u16) -> Option<&str> {
+        self.nodes
+            .iter()
+            .find(|(_, n)| n.index == index)
+            .map(|(k, _)| k.as_str())
+    }
+
+    /// Get a single node's events
+    pub fn node(&self, node_id: &str) -> Option<&NodeEvents> {
+        self.nodes.get(node_id)
+    }
+
     /// Iterate all nodes
-    #[allow(dead_code)]
     pub fn nodes(&self) -> impl Iterator<Item = (&str, &NodeEvents)> {
         self.nodes.iter().map(|(k, v)| (k.as_str(), v))
     }
@@ -271,11 +1276,367 @@ impl EventStore {
             .and_then(|n| n.by_type.get(&event_type))
     }
 
+    /// Stored event of the node at ring index `node_index` closest in time to `timestamp`,
+    /// if one lies within `tolerance` seconds. Scans every type bucket of that node.
+    pub fn find_event_near(
+        &self,
+        node_index: u16,
+        timestamp: f64,
+        tolerance: f64,
+    ) -> Option<(&str, &StoredEvent)> {
+        let (node_id, node) = self.nodes.iter().find(|(_, n)| n.index == node_index)?;
+        node.by_type
+            .values()
+            .flat_map(|events| {
+                // Buckets are chronological, so only the neighbours of the insertion point
+                // can be the nearest event
+                let i = events.partition_point(|e| e.timestamp < timestamp);
+                [i.checked_sub(1), Some(i)].into_iter().flatten().filter_map(|i| events.get(i))
+            })
+            .map(|e| (e, (e.timestamp - timestamp).abs()))
+            .filter(|&(_, distance)| distance <= tolerance)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(e, _)| (node_id.as_str(), e))
+    }
+
+    /// Events stored since `cursor` (a sequence number from a previous call; 0 = everything
+    /// still stored), as `(node index, event type, event)` in no particular order.
+    ///
+    /// Returns (new events, new cursor). With `focus`, only that node's events are yielded
+    /// (the cursor still advances past all). Evicted or pruned events are simply not returned.
+    pub fn get_new_since(
+        &self,
+        cursor: u64,
+        focus: Option<u16>,
+    ) -> (impl Iterator<Item = (u16, u8, &StoredEvent)> + '_, u64) {
+        let events = self
+            .nodes
+            .values()
+            .filter(move |node| !focus.is_some_and(|f| f != node.index))
+            .flat_map(move |node| {
+                node.by_type.iter().flat_map(move |(&event_type, events)| {
+                    // Sequence numbers only grow within a bucket, so new events are at the back
+                    events
+                        .iter()
+                        .rev()
+                        .take_while(move |e| e.seq >= cursor)
+                        .filter(move |e| self.shown(e))
+                        .map(move |e| (node.index, event_type, e))
+                })
+            });
+        (events, self.next_seq)
+    }
+
+    /// Number of stored events newer than `cutoff` among the types enabled in `event_filter`
+    pub fn count_in_window(&self, cutoff: f64, event_filter: &[bool], focus: Option<u16>) -> usize {
+        self.nodes
+            .values()
+            .filter(|node| !focus.is_some_and(|f| f != node.index))
+            .flat_map(|node| node.by_type.iter())
+            .filter(|(&event_type, _)| event_filter.get(event_type as usize).copied().unwrap_or(false))
+            .map(|(_, events)| self.count_newer(events, cutoff))
+            .sum()
+    }
+
+    /// Number of stored events of `event_types` newer than `cutoff`, across all nodes
+    pub fn count_events(&self, event_types: &[EventType], cutoff: f64) -> usize {
+        self.nodes
+            .values()
+            .flat_map(|node| event_types.iter().filter_map(|&et| node.by_type.get(&(et as u8))))
+            .map(|events| self.count_newer(events, cutoff))
+            .sum()
+    }
+
+    /// Stored events per node and type newer than `now - window`, as (ring index, counts
+    /// indexed by event type) in ring order. Nodes quiet in the window get an all-zero row.
+    pub fn per_node_type_counts(&self, now: f64, window: f64) -> Vec<(u16, [u32; 256])> {
+        let cutoff = now - window;
+        let mut rows: Vec<(u16, [u32; 256])> = self
+            .nodes
+            .values()
+            .map(|node| {
+                let mut counts = [0u32; 256];
+                for (&event_type, events) in &node.by_type {
+                    counts[event_type as usize] = self.count_newer(events, cutoff) as u32;
+                }
+                (node.index, counts)
+            })
+            .collect();
+        rows.sort_by_key(|&(index, _)| index);
+        rows
+    }
+
+    /// (step, successes, failures) of each bundle/segment recovery step in the last `window`
+    /// seconds, in [`RECOVERY_OUTCOMES`] order
+    pub fn recovery_outcome_counts(&self, now: f64, window: f64) -> Vec<(&'static str, usize, usize)> {
+        RECOVERY_OUTCOMES
+            .iter()
+            .map(|&(step, successes, failures)| {
+                (
+                    step,
+                    self.count_events(successes, now - window),
+                    self.count_events(failures, now - window),
+                )
+            })
+            .collect()
+    }
+
+    /// Events reaching each work-package pipeline stage in the last `window` seconds,
+    /// as (stage name, count) in [`PIPELINE_STAGES`] order
+    pub fn pipeline_stage_counts(&self, now: f64, window: f64) -> Vec<(&'static str, usize)> {
+        PIPELINE_STAGES
+            .iter()
+            .map(|&(stage, event_types)| (stage, self.count_events(event_types, now - window)))
+            .collect()
+    }
+
+    /// Up to `limit` nodes with the most disconnects and failed connects, as
+    /// (node_id, connects, disconnects). Nodes that never lost a connection are left out.
+    pub fn top_churning(&self, limit: usize) -> Vec<(&str, u64, u64)> {
+        let mut churning: Vec<(&str, u64, u64)> = self
+            .nodes
+            .iter()
+            .map(|(id, node)| (id.as_str(), node.connects, node.disconnects))
+            .filter(|&(_, _, disconnects)| disconnects > 0)
+            .collect();
+        churning.sort_by(|a, b| b.2.cmp(&a.2).then(b.1.cmp(&a.1)).then(a.0.cmp(b.0)));
+        churning.truncate(limit);
+        churning
+    }
+
+    /// Ring indices of nodes whose newest event is older than `threshold` seconds, pruned or
+    /// not. Nodes that never sent anything are not in the store, so they are never reported.
+    pub fn stalled_nodes(&self, now: f64, threshold: f64) -> Vec<u16> {
+        let mut stalled: Vec<u16> = self
+            .nodes
+            .values()
+            .filter(|node| now - node.last_seen() > threshold)
+            .map(|node| node.index)
+            .collect();
+        stalled.sort_unstable();
+        stalled
+    }
+
+    /// Newest-first events of `event_types` from the last `max_age` seconds, across all nodes.
+    /// When `regex` is set only events whose reason matches are kept (events without a
+    /// reason never match).
+    pub fn recent_errors(
+        &self,
+        event_types: &[EventType],
+        regex: Option<&Regex>,
+        limit: usize,
+        now: f64,
+        max_age: f64,
+    ) -> Vec<(&str, &StoredEvent)> {
+        let cutoff = now - max_age;
+        let mut errors: Vec<(&str, &StoredEvent)> = self
+            .nodes
+            .iter()
+            .flat_map(|(node_id, node)| {
+                event_types
+                    .iter()
+                    .filter_map(|&et| node.by_type.get(&(et as u8)))
+                    .flat_map(|events| events.iter().rev().take_while(|e| e.timestamp >= cutoff))
+                    .map(move |stored| (node_id.as_str(), stored))
+            })
+            .filter(|(_, stored)| match regex {
+                Some(re) => stored.event.reason().is_some_and(|r| re.is_match(&r)),
+                None => true,
+            })
+            .collect();
+        errors.sort_by(|a, b| b.1.timestamp.total_cmp(&a.1.timestamp));
+        errors.truncate(limit);
+        errors
+    }
+
+    /// Outlines of distinct blocks authored or imported in the last `window` seconds, oldest
+    /// first. Every importing node reports the same block, so outlines are deduplicated by hash.
+    pub fn block_outlines(&self, now: f64, window: f64) -> Vec<&BlockOutline> {
+        let cutoff = now - window;
+        let mut outlines: Vec<(f64, &BlockOutline)> = self
+            .nodes
+            .values()
+            .flat_map(|node| {
+                [EventType::Authored, EventType::Importing]
+                    .into_iter()
+                    .filter_map(|et| node.by_type.get(&(et as u8)))
+                    .flat_map(|events| events.iter().rev().take_while(|e| e.timestamp >= cutoff))
+            })
+            .filter_map(|stored| match &stored.event {
+                Event::Authored { outline, .. } | Event::Importing { outline, .. } => {
+                    Some((stored.timestamp, outline))
+                }
+                _ => None,
+            })
+            .collect();
+        outlines.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let mut seen = HashSet::new();
+        outlines
+            .into_iter()
+            .filter(|(_, outline)| seen.insert(outline.hash))
+            .map(|(_, outline)| outline)
+            .collect()
+    }
+
+    /// Gas samples from the last `window` seconds, oldest first: one per service for every
+    /// `BlockExecuted`, one per `Refined` with its work items summed. Every guarantor refines
+    /// the same work package and every importer executes the same block, so each package or
+    /// block counts once, from its first report; reports whose package or block isn't stored
+    /// count on their own.
+    pub fn gas_samples(&self, now: f64, window: f64) -> Vec<GasSample> {
+        let cutoff = now - window;
+        let mut reports: Vec<(f64, Option<(bool, Hash)>, Vec<GasSample>)> = self
+            .nodes
+            .values()
+            .flat_map(|node| {
+                [EventType::BlockExecuted, EventType::Refined]
+                    .into_iter()
+                    .filter_map(|et| node.by_type.get(&(et as u8)))
+                    .flat_map(|events| events.iter().rev().take_while(|e| e.timestamp >= cutoff))
+                    .map(move |stored| (node, stored))
+            })
+            .filter_map(|(node, stored)| {
+                let sample = |source, gas_used, elapsed_ns| GasSample {
+                    timestamp: stored.timestamp,
+                    source,
+                    gas_used,
+                    elapsed_ns,
+                };
+                let (refine, subjects, samples) = match &stored.event {
+                    Event::BlockExecuted { accumulate_costs, .. } => (
+                        false,
+                        &[EventType::Authored, EventType::Importing][..],
+                        accumulate_costs
+                            .iter()
+                            .map(|(service, cost)| {
+                                let total = &cost.total;
+                                sample(GasSource::Accumulate(*service), total.gas_used, total.elapsed_ns)
+                            })
+                            .collect(),
+                    ),
+                    Event::Refined { costs, .. } => (
+                        true,
+                        &[EventType::WorkPackageReceived][..],
+                        vec![sample(
+                            GasSource::Refine,
+                            costs.iter().map(|c| c.total.gas_used).sum(),
+                            costs.iter().map(|c| c.total.elapsed_ns).sum(),
+                        )],
+                    ),
+                    _ => return None,
+                };
+                let key = node.preceding_hash(subjects, stored.timestamp).map(|hash| (refine, hash));
+                Some((stored.timestamp, key, samples))
+            })
+            .collect();
+        reports.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let mut seen = HashSet::new();
+        reports
+            .into_iter()
+            .filter(|(_, key, _)| match key {
+                Some(key) => seen.insert(*key),
+                None => true,
+            })
+            .flat_map(|(_, _, samples)| samples)
+            .collect()
+    }
+
+    /// Remote address of every incoming and outgoing connection attempt in the last `window`
+    /// seconds, one entry per attempt
+    pub fn peer_addresses(&self, now: f64, window: f64) -> Vec<PeerAddress> {
+        let cutoff = now - window;
+        self.nodes
+            .values()
+            .flat_map(|node| {
+                [EventType::ConnectingIn, EventType::ConnectingOut]
+                    .into_iter()
+                    .filter_map(|et| node.by_type.get(&(et as u8)))
+                    .flat_map(|events| events.iter().rev().take_while(|e| e.timestamp >= cutoff))
+            })
+            .filter_map(|stored| match &stored.event {
+                Event::ConnectingIn { from, .. } => Some(*from),
+                Event::ConnectingOut { to, .. } => Some(to.peer_address),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Events of `event_type` from the last `window` seconds grouped by normalized reason,
+    /// most frequent first. Events without a reason are skipped.
+    pub fn reason_groups(&self, event_type: u8, now: f64, window: f64) -> Vec<ReasonGroup<'_>> {
+        let cutoff = now - window;
+        let mut groups: HashMap<String, (u64, Vec<&str>)> = HashMap::new();
+        for (node_id, node) in &self.nodes {
+            let Some(events) = node.by_type.get(&event_type) else {
+                continue;
+            };
+            for stored in events.iter().rev().take_while(|e| e.timestamp >= cutoff) {
+                if let Some(reason) = stored.event.reason() {
+                    let (count, nodes) = groups.entry(normalize_reason(&reason)).or_default();
+                    *count += 1;
+                    nodes.push(node_id.as_str());
+                }
+            }
+        }
+        let mut groups: Vec<ReasonGroup<'_>> = groups
+            .into_iter()
+            .map(|(reason, (count, mut nodes))| {
+                nodes.sort_unstable();
+                nodes.dedup();
+                ReasonGroup { reason, count, nodes }
+            })
+            .collect();
+        groups.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.reason.cmp(&b.reason)));
+        groups
+    }
+
+    /// Counts per normalized reason of `event_type` events over the last `window` seconds,
+    /// most frequent first
+    pub fn reason_distribution(&self, event_type: u8, now: f64, window: f64) -> Vec<(String, u64)> {
+        self.reason_groups(event_type, now, window)
+            .into_iter()
+            .map(|group| (group.reason, group.count))
+            .collect()
+    }
+
+    /// Current per-type ring buffer capacity
+    pub fn max_events_per_node(&self) -> usize {
+        self.max_events_per_node
+    }
+
+    /// Change the per-type ring buffer capacity. Applies to existing nodes too;
+    /// shrinking drops the oldest events right away.
+    pub fn set_max_events_per_node(&mut self, max: usize) {
+        let max = max.max(1);
+        if max == self.max_events_per_node {
+            return;
+        }
+        self.max_events_per_node = max;
+        for node in self.nodes.values_mut() {
+            node.max_per_type = max;
+            for events in node.by_type.values_mut() {
+                let excess = events.len().saturating_sub(max);
+                events.drain(..excess);
+            }
+        }
+    }
+
+    /// Events evicted across all nodes because a ring buffer was full, since the store was created
+    pub fn capacity_evictions(&self) -> u64 {
+        self.nodes.values().map(|n| n.capacity_evictions).sum()
+    }
+
     /// Total node count
     pub fn node_count(&self) -> usize {
         self.nodes.len()
     }
 
+    /// One past the highest node index: the width of index-addressed views.
+    /// Equals `node_count()` in arrival order; hashed indices leave gaps.
+    pub fn index_span(&self) -> usize {
+        self.nodes.values().map(|n| n.index as usize + 1).max().unwrap_or(0)
+    }
+
     /// Compute event rates per node for given event types
     ///
     /// Returns Vec<(node_idx, Vec<count_per_bucket>)>
@@ -287,45 +1648,182 @@ impl EventStore {
         num_buckets: usize,
         event_filter: &[bool],
     ) -> Vec<(u16, Vec<u32>)> {
-        // Align bucket boundaries to fixed time intervals to prevent oscillation
-        // at bucket edges due to floating-point precision issues.
-        // Floor now to the bucket duration so that bucket boundaries are stable.
-        let aligned_now = (now / bucket_duration).floor() * bucket_duration;
-        let oldest_time = aligned_now - (bucket_duration * num_buckets as f64);
+        self.rates_per_node(now, bucket_duration, num_buckets, event_filter, self.parallel())
+    }
 
-        self.nodes
-            .values()
-            .map(|node| {
-                let mut buckets = vec![0u32; num_buckets];
+    fn rates_per_node(
+        &self,
+        now: f64,
+        bucket_duration: f64,
+        num_buckets: usize,
+        event_filter: &[bool],
+        parallel: bool,
+    ) -> Vec<(u16, Vec<u32>)> {
+        let window = RateWindow::new(now, bucket_duration, num_buckets);
 
-                // Only iterate over event types that are selected in the filter
-                for (&event_type, events) in &node.by_type {
-                    if (event_type as usize) >= event_filter.len()
-                        || !event_filter[event_type as usize]
-                    {
-                        continue; // Skip entire event type bucket
-                    }
+        let mut rates = self.map_nodes(parallel, |node| {
+            let mut buckets = vec![0u32; num_buckets];
+
+            // Only iterate over event types that are selected in the filter
+            for (&event_type, events) in &node.by_type {
+                if (event_type as usize) >= event_filter.len()
+                    || !event_filter[event_type as usize]
+                {
+                    continue; // Skip entire event type bucket
+                }
 
-                    for stored in events {
-                        // Skip events outside time window
-                        if stored.timestamp < oldest_time || stored.timestamp >= aligned_now {
-                            continue;
-                        }
-
-                        // Compute bucket index using aligned time
-                        let age = aligned_now - stored.timestamp;
-                        let bucket_idx = ((age / bucket_duration) as usize).min(num_buckets - 1);
-                        // Invert so newest is at the end
-                        let bucket_idx = num_buckets - 1 - bucket_idx;
+                for stored in events.iter().filter(|e| self.shown(e)) {
+                    if let Some(bucket_idx) = window.bucket(stored.timestamp) {
                         buckets[bucket_idx] += 1;
                     }
                 }
+            }
+
+            (node.index, buckets)
+        });
+        // HashMap order differs between runs; callers get nodes in ring order
+        rates.sort_unstable_by_key(|&(index, _)| index);
+        rates
+    }
 
-                (node.index, buckets)
+    /// Compute event rates (events/sec) per EVENT_CATEGORIES entry, summed across nodes
+    ///
+    /// Returns Vec<(category_idx, Vec<rate_per_bucket>)> in EVENT_CATEGORIES order,
+    /// only for categories with at least one selected event type. With `focus`, only that
+    /// node's events count.
+    pub fn compute_category_rates(
+        &self,
+        now: f64,
+        bucket_duration: f64,
+        num_buckets: usize,
+        event_filter: &[bool],
+        focus: Option<u16>,
+    ) -> Vec<(usize, Vec<f64>)> {
+        let window = RateWindow::new(now, bucket_duration, num_buckets);
+
+        // event_type → category index
+        let mut category_of = [None; 256];
+        for (cat_idx, category) in EVENT_CATEGORIES.iter().enumerate() {
+            for &et in category.event_types {
+                category_of[et.idx()] = Some(cat_idx);
+            }
+        }
+
+        let per_node = self.map_nodes(self.parallel(), |node| {
+            let mut counts = vec![vec![0u32; num_buckets]; EVENT_CATEGORIES.len()];
+            if focus.is_some_and(|f| f != node.index) {
+                return counts;
+            }
+            for (&event_type, events) in &node.by_type {
+                if (event_type as usize) >= event_filter.len()
+                    || !event_filter[event_type as usize]
+                {
+                    continue;
+                }
+                let Some(cat_idx) = category_of[event_type as usize] else {
+                    continue;
+                };
+                for stored in events.iter().filter(|e| self.shown(e)) {
+                    if let Some(bucket_idx) = window.bucket(stored.timestamp) {
+                        counts[cat_idx][bucket_idx] += 1;
+                    }
+                }
+            }
+            counts
+        });
+        let mut counts = vec![vec![0u32; num_buckets]; EVENT_CATEGORIES.len()];
+        for node_counts in per_node {
+            for (category, node_category) in counts.iter_mut().zip(node_counts) {
+                for (total, c) in category.iter_mut().zip(node_category) {
+                    *total += c;
+                }
+            }
+        }
+
+        EVENT_CATEGORIES
+            .iter()
+            .enumerate()
+            .filter(|(_, cat)| {
+                cat.event_types
+                    .iter()
+                    .any(|et| et.idx() < event_filter.len() && event_filter[et.idx()])
+            })
+            .map(|(cat_idx, _)| {
+                let rates = counts[cat_idx]
+                    .iter()
+                    .map(|&c| c as f64 / bucket_duration)
+                    .collect();
+                (cat_idx, rates)
             })
             .collect()
     }
 
+    /// Compute total event rate (events/sec) across all nodes, of `event_types` or of every type
+    ///
+    /// Returns one rate per bucket, newest last.
+    pub fn compute_aggregate_rate(
+        &self,
+        now: f64,
+        bucket_duration: f64,
+        num_buckets: usize,
+        event_types: Option<&[EventType]>,
+    ) -> Vec<f64> {
+        self.aggregate_rate(now, bucket_duration, num_buckets, event_types, self.parallel())
+    }
+
+    fn aggregate_rate(
+        &self,
+        now: f64,
+        bucket_duration: f64,
+        num_buckets: usize,
+        event_types: Option<&[EventType]>,
+        parallel: bool,
+    ) -> Vec<f64> {
+        let window = RateWindow::new(now, bucket_duration, num_buckets);
+
+        let per_node = self.map_nodes(parallel, |node| {
+            let mut counts = vec![0u32; num_buckets];
+            let buckets = node.by_type.iter().filter(|(&et, _)| match event_types {
+                Some(types) => types.iter().any(|&t| t as u8 == et),
+                None => true,
+            });
+            for stored in buckets.flat_map(|(_, events)| events).filter(|e| self.shown(e)) {
+                if let Some(bucket_idx) = window.bucket(stored.timestamp) {
+                    counts[bucket_idx] += 1;
+                }
+            }
+            counts
+        });
+        let mut counts = vec![0u32; num_buckets];
+        for node_counts in per_node {
+            for (total, c) in counts.iter_mut().zip(node_counts) {
+                *total += c;
+            }
+        }
+        counts.iter().map(|&c| c as f64 / bucket_duration).collect()
+    }
+
+    /// Whether per-node scans are worth spreading across threads
+    fn parallel(&self) -> bool {
+        self.nodes.len() >= PARALLEL_MIN_NODES
+    }
+
+    /// `f` applied to every node, across the rayon pool when `parallel` (native only).
+    /// Order follows the node map, so callers sort or sum the results.
+    fn map_nodes<T, F>(&self, parallel: bool, f: F) -> Vec<T>
+    where
+        T: Send,
+        F: Fn(&NodeEvents) -> T + Sync + Send,
+    {
+        #[cfg(not(target_arch = "wasm32"))]
+        if parallel {
+            return self.nodes.par_iter().map(|(_, node)| f(node)).collect();
+        }
+        #[cfg(target_arch = "wasm32")]
+        let _ = parallel;
+        self.nodes.values().map(f).collect()
+    }
+
     /// Prune old events beyond retention period
     #[allow(dead_code)]
     pub fn prune(&mut self, now: f64) {
@@ -343,6 +1841,58 @@ impl EventStore {
         }
     }
 
+    /// Write every stored event as CSV, oldest first. Returns the number of rows written.
+    ///
+    /// Columns: `timestamp,node_index,node_id,event_type,event_name,reason,synced_fraction`
+    /// (timestamp in app-relative seconds; reason empty for events without one;
+    /// synced_fraction is the share of validators synced as of that row, replayed from the
+    /// stored SyncStatusChanged events, empty before the first one).
+    pub fn export_csv<W: Write>(&self, w: &mut W) -> io::Result<usize> {
+        let mut rows: Vec<(&str, &NodeEvents, &StoredEvent)> = self
+            .nodes
+            .iter()
+            .flat_map(|(node_id, node)| {
+                node.by_type
+                    .values()
+                    .flatten()
+                    .map(move |stored| (node_id.as_str(), node, stored))
+            })
+            .collect();
+        rows.sort_by(|a, b| a.2.timestamp.total_cmp(&b.2.timestamp));
+
+        writeln!(w, "timestamp,node_index,node_id,event_type,event_name,reason,synced_fraction")?;
+        let mut sync = SyncStatusData::new();
+        for (node_id, node, stored) in &rows {
+            let event_type = stored.event.event_type();
+            let reason = stored.event.reason().unwrap_or_default();
+            if let Event::SyncStatusChanged { synced, .. } = stored.event {
+                sync.set(node_id, synced, stored.timestamp);
+            }
+            let synced_fraction =
+                sync.synced_fraction().map(|f| format!("{:.3}", f)).unwrap_or_default();
+            writeln!(
+                w,
+                "{:.6},{},{},{},{},{},{}",
+                stored.timestamp,
+                node.index,
+                csv_field(node_id),
+                event_type as u8,
+                csv_field(event_name(event_type)),
+                csv_field(&reason),
+                synced_fraction,
+            )?;
+        }
+        Ok(rows.len())
+    }
+}
+
+/// Quote a CSV field if it contains a separator, quote or line break (RFC 4180)
+fn csv_field(value: &str) -> Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(value)
+    }
 }
 
 #[cfg(test)]
@@ -377,13 +1927,220 @@ mod tests {
         assert_eq!(ts.point_count(), 3); // node1 has 3 points
     }
 
+    #[test]
+    fn test_time_series_aggregate() {
+        let mut ts = TimeSeriesData::new(4, 3);
+        assert!(ts.aggregate().is_empty());
+
+        ts.push("aa", 1.0);
+        ts.push("aa", 2.0);
+        ts.push("aa", 3.0);
+        ts.push("bb", 10.0);
+        ts.push("bb", 20.0);
+        assert_eq!(ts.aggregate(), vec![1.0, 12.0, 23.0]);
+
+        // Oldest point evicted from "aa": both series now end-aligned over 3 points
+        ts.push("aa", 4.0);
+        assert_eq!(ts.aggregate(), vec![2.0, 13.0, 24.0]);
+    }
+
+    #[test]
+    fn test_ticket_epochs_roll_over() {
+        let mut tickets = TicketData::new();
+        tickets.record_generated("aa", 3, false); // no epoch known yet: dropped
+        assert_eq!(tickets.current_epoch(), None);
+
+        tickets.record_generating("aa", 5);
+        tickets.record_generating("bb", 5);
+        tickets.record_generated("aa", 3, false);
+        tickets.record_generated("bb", 0, true);
+        tickets.record_transferred(5);
+        tickets.record_generating("aa", 6);
+        tickets.record_generated("aa", 2, false);
+        tickets.record_transfer_failed();
+        assert_eq!(tickets.current_epoch(), Some(6));
+        let epochs: Vec<(u32, EpochTickets)> = tickets.epochs().map(|(e, c)| (e, *c)).collect();
+        assert_eq!(
+            epochs,
+            vec![
+                (5, EpochTickets { generating: 2, generated: 3, transferred: 1, failed: 1 }),
+                (6, EpochTickets { generating: 1, generated: 2, transferred: 0, failed: 1 }),
+            ]
+        );
+
+        // Old epochs drop out as new ones start; a late report for a dropped epoch is ignored
+        for epoch in 7..7 + TICKET_EPOCHS_KEPT as u32 {
+            tickets.record_transferred(epoch);
+        }
+        assert_eq!(tickets.epochs().count(), TICKET_EPOCHS_KEPT);
+        assert_eq!(tickets.epochs().next().map(|(e, _)| e), Some(7));
+        tickets.record_transferred(5);
+        assert_eq!(tickets.epochs().next().map(|(e, _)| e), Some(7));
+    }
+
+    #[test]
+    fn test_time_series_spread_handles_ragged_series() {
+        let mut ts = TimeSeriesData::new(4, 3);
+        assert!(ts.spread().is_empty());
+
+        for v in [1.0, 2.0, 3.0] {
+            ts.push("aa", v);
+        }
+        ts.push("bb", 10.0);
+        ts.push("bb", 20.0);
+        ts.push("cc", 5.0);
+        // Oldest point: only "aa"; newest: all three
+        assert_eq!(ts.spread(), vec![(1.0, 1.0, 1.0), (2.0, 6.0, 10.0), (3.0, 5.0, 20.0)]);
+    }
+
+    #[test]
+    fn test_time_series_labeled_series() {
+        let mut ts = TimeSeriesData::new(4, 3);
+        ts.push("aa", 1.0);
+        ts.push("bb", 2.0);
+        ts.push("bb", 3.0);
+
+        let mut labeled: Vec<_> = ts.labeled_series().map(|(id, idx, s)| (id, idx, s.clone())).collect();
+        labeled.sort_by_key(|(_, idx, _)| *idx);
+        assert_eq!(labeled, vec![("aa", 0, vec![1.0]), ("bb", 1, vec![2.0, 3.0])]);
+    }
+
+    #[test]
+    fn test_guarantee_queue_pads_to_max_cores() {
+        let mut gq = GuaranteeQueueData::new(4);
+        assert_eq!(gq.num_cores(), 0);
+        assert!(gq.aggregate_per_core().is_empty());
+
+        gq.set("a", &[1, 2]);
+        gq.set("b", &[3, 0, 5]);
+        assert_eq!(gq.num_cores(), 3);
+        assert_eq!(gq.aggregate_per_core(), vec![4, 2, 5]);
+        assert_eq!(gq.rows(), vec![("a", vec![1, 2, 0]), ("b", vec![3, 0, 5])]);
+
+        // Latest report replaces the previous one
+        gq.set("a", &[0]);
+        assert_eq!(gq.aggregate_per_core(), vec![3, 0, 5]);
+    }
+
+    #[test]
+    fn test_assurance_rows() {
+        let mut av = AssuranceData::new();
+        av.set("node1", &[0b0000_0101]);
+        av.set("node2", &[0xff, 0b0000_0001]);
+        // Newer statement replaces the old one in place
+        av.set("node1", &[0b0000_0110]);
+        assert_eq!(av.max_bits(), 16);
+
+        let rows = av.rows(10);
+        assert_eq!(rows[0].0, "node1");
+        assert_eq!(rows[0].1, vec![false, true, true, false, false, false, false, false, false, false]);
+        assert_eq!(rows[1].1.iter().filter(|&&b| b).count(), 9);
+        assert_eq!(av.rows(2)[1].1, vec![true, true]);
+    }
+
+    #[test]
+    fn test_sync_status_data() {
+        let mut sync = SyncStatusData::new();
+        assert_eq!(sync.synced_fraction(), None);
+
+        sync.set("b", true, 10.0);
+        sync.set("a", false, 10.0);
+        sync.touch("c", 10.0); // unknown node: ignored
+        assert_eq!(sync.synced_count(), (1, 2));
+        assert_eq!(sync.synced_fraction(), Some(0.5));
+
+        sync.touch("b", 35.0);
+        assert_eq!(sync.entries(45.0), vec![("a", false, true), ("b", true, false)]);
+
+        sync.set("a", true, 50.0);
+        assert_eq!(sync.synced_count(), (2, 2));
+    }
+
+    #[test]
+    fn test_topology_data() {
+        let mut topology = TopologyData::new();
+        topology.connect("a", "b");
+        topology.connect("b", "a"); // reported by both ends
+        topology.connect("c", "a");
+        topology.connect("c", "c"); // self-connection: ignored
+        assert_eq!(topology.edges().count(), 2);
+        assert_eq!(topology.degree("a"), 2);
+        assert_eq!(topology.degree("d"), 0);
+
+        let mut edges: Vec<_> = topology.edges().collect();
+        edges.sort_unstable();
+        assert_eq!(edges, vec![("a", "b"), ("a", "c")]);
+        assert_eq!(topology.components(["e"]), vec![vec!["a", "b", "c"], vec!["e"]]);
+
+        topology.disconnect("b", "a");
+        assert_eq!(topology.degree("b"), 0);
+        assert_eq!(topology.components(["b", "e"]), vec![vec!["a", "c"], vec!["b"], vec!["e"]]);
+    }
+
+    #[test]
+    fn test_topology_validator_components() {
+        let mut topology = TopologyData::new();
+        // v1 - v2 - external - v3, and v4 - v5 apart
+        topology.connect("v1", "v2");
+        topology.connect("v2", "ext");
+        topology.connect("ext", "v3");
+        topology.connect("v4", "v5");
+        topology.connect("ext2", "ext3"); // no validators: not counted
+        let validators = [("v1", 1), ("v2", 2), ("v3", 1), ("v4", 1), ("v5", 1), ("v6", 0)];
+        // v6 has no connection seen and is left out
+        assert_eq!(topology.validator_components(validators), vec![3, 2]);
+
+        topology.connect("v3", "v4");
+        let validators = [("v1", 1), ("v2", 2), ("v3", 2), ("v4", 2), ("v5", 1)];
+        assert_eq!(topology.validator_components(validators), vec![5]);
+    }
+
+    #[test]
+    fn test_topology_validator_components_skip_incomplete() {
+        let mut topology = TopologyData::new();
+        // Two reconnects seen since start, while every validator reports 10 peers
+        topology.connect("v1", "v2");
+        topology.connect("v3", "v4");
+        let validators = [("v1", 10), ("v2", 10), ("v3", 10), ("v4", 10)];
+        assert!(topology.validator_components(validators).is_empty());
+
+        // v1 and v2 fully observed, v3 and v4 still not: no split reported
+        for peer in ["p1", "p2", "p3", "p4", "p5", "p6", "p7", "p8"] {
+            topology.connect("v1", peer);
+            topology.connect("v2", peer);
+        }
+        assert_eq!(topology.validator_components(validators), vec![2]);
+    }
+
+    #[test]
+    fn test_block_times() {
+        let mut bt = BlockTimes::new(3);
+        assert_eq!(bt.mean(), None);
+
+        bt.record(0.0, 10);
+        assert_eq!(bt.mean(), None);
+        bt.record(6.0, 11);
+        bt.record(6.5, 11); // duplicate tip from another validator
+        bt.record(7.0, 9); // late report of an older slot
+        bt.record(14.0, 12);
+        assert_eq!(bt.sample_count(), 2);
+        assert_eq!(bt.mean(), Some(7.0));
+        assert_eq!(bt.variance(), Some(1.0));
+
+        // Window keeps the newest intervals only
+        bt.record(20.0, 13);
+        bt.record(26.0, 15);
+        assert_eq!(bt.sample_count(), 3);
+        assert_eq!(bt.mean(), Some(20.0 / 3.0));
+    }
+
     #[test]
     fn test_best_block_data() {
         let mut bbd = BestBlockData::new(10);
 
         // Set best blocks for two nodes
-        bbd.set_best("node1", 100);
-        bbd.set_best("node2", 150);
+        bbd.set_best("node1", 100, [1; 32]);
+        bbd.set_best("node2", 150, [2; 32]);
 
         // Set finalized for one node
         bbd.set_finalized("node1", 90);
@@ -399,6 +2156,45 @@ mod tests {
         assert_eq!(bbd.highest_finalized(), Some(95));
     }
 
+    #[test]
+    fn test_hashed_indices_ignore_arrival_order() {
+        let ids: Vec<String> = (0..50).map(|i| format!("{:064x}", i * 7919)).collect();
+        let assign = |order: &mut dyn Iterator<Item = &String>| {
+            let mut store = EventStore::new(100, 60.0);
+            store.set_indexing(NodeIndexing::Hashed);
+            let synced = Event::SyncStatusChanged { timestamp: 0, synced: true };
+            for id in order {
+                store.push(id, synced.clone(), 1.0);
+            }
+            ids.iter().map(|id| store.node_index(id).unwrap()).collect::<Vec<_>>()
+        };
+        let forward = assign(&mut ids.iter());
+        assert_eq!(forward, assign(&mut ids.iter().rev()));
+        assert!(forward.iter().all(|&idx| (idx as usize) < NODE_INDEX_SPACE));
+        let unique: HashSet<u16> = forward.iter().copied().collect();
+        assert_eq!(unique.len(), ids.len());
+    }
+
+    #[test]
+    fn test_index_allocator_probes_and_caps() {
+        // Three ids fill a space of three, probing past collisions; a fourth shares the last index
+        let mut indices = IndexAllocator::new(3);
+        indices.mode = NodeIndexing::Hashed;
+        let first = indices.assign("a");
+        let taken: HashSet<usize> = ["b", "c"].iter().map(|id| indices.assign(id)).collect();
+        assert!(!taken.contains(&first) && taken.len() == 2);
+        assert_eq!(indices.assign("d"), 2);
+
+        let mut arrival = IndexAllocator::new(2);
+        assert_eq!(["a", "b", "c"].map(|id| arrival.assign(id)), [0, 1, 1]);
+
+        let mut ts = TimeSeriesData::new(NODE_INDEX_SPACE, 3);
+        ts.set_indexing(NodeIndexing::Hashed);
+        ts.push("node", 1.0);
+        let (_, idx, _) = ts.labeled_series().next().unwrap();
+        assert_eq!(idx as u64, fnv1a(b"node") % NODE_INDEX_SPACE as u64);
+    }
+
     #[test]
     fn test_compute_rates_per_node() {
         let mut store = EventStore::new(100, 60.0);
@@ -445,6 +2241,608 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_compute_category_rates() {
+        let mut store = EventStore::new(100, 60.0);
+        let now = 60.0;
+
+        let status_event = Event::Status {
+            timestamp: 0,
+            num_peers: 1,
+            num_val_peers: 0,
+            num_sync_peers: 0,
+            num_guarantees: vec![],
+            num_shards: 0,
+            shards_size: 0,
+            num_preimages: 0,
+            preimages_size: 0,
+        };
+        store.push("node1", status_event.clone(), now - 1.5);
+        store.push("node2", status_event.clone(), now - 1.5);
+        store.push("node2", status_event, now - 3.5);
+
+        // Bucket duration 2s → counts are halved into per-second rates
+        let rates = store.compute_category_rates(now, 2.0, 5, &[true; 256], None);
+        assert_eq!(rates.len(), EVENT_CATEGORIES.len());
+        let (cat_idx, status) = &rates[0];
+        assert_eq!(EVENT_CATEGORIES[*cat_idx].name, "Status");
+        assert_eq!(status, &vec![0.0, 0.0, 0.0, 0.5, 1.0]);
+        assert!(rates[1..].iter().all(|(_, r)| r.iter().all(|&v| v == 0.0)));
+
+        // Focus counts only that node
+        let node2 = store.node_index("node2");
+        let rates = store.compute_category_rates(now, 2.0, 5, &[true; 256], node2);
+        assert_eq!(rates[0].1, vec![0.0, 0.0, 0.0, 0.5, 0.5]);
+
+        // Only categories with a selected type are returned
+        let mut filter = [false; 256];
+        filter[EventType::BestBlockChanged.idx()] = true;
+        let rates = store.compute_category_rates(now, 2.0, 5, &filter, None);
+        assert_eq!(rates.len(), 1);
+        assert!(rates[0].1.iter().all(|&v| v == 0.0));
+
+        // Aggregate rate sums every node and type
+        let synced = Event::SyncStatusChanged { timestamp: 0, synced: true };
+        store.push("node1", synced, now - 1.5);
+        assert_eq!(store.compute_aggregate_rate(now, 2.0, 5, None), vec![0.0, 0.0, 0.0, 0.5, 1.5]);
+        let only_sync = [EventType::SyncStatusChanged];
+        assert_eq!(
+            store.compute_aggregate_rate(now, 2.0, 5, Some(&only_sync)),
+            vec![0.0, 0.0, 0.0, 0.0, 0.5]
+        );
+    }
+
+    /// Synthetic store: `nodes` validators with a status and a sync event every 100ms for a minute
+    fn synthetic_store(nodes: usize) -> EventStore {
+        let mut store = EventStore::new(1_000, 60.0);
+        let status = Event::Status {
+            timestamp: 0,
+            num_peers: 1,
+            num_val_peers: 0,
+            num_sync_peers: 0,
+            num_guarantees: vec![],
+            num_shards: 0,
+            shards_size: 0,
+            num_preimages: 0,
+            preimages_size: 0,
+        };
+        let synced = Event::SyncStatusChanged { timestamp: 0, synced: true };
+        for n in 0..nodes {
+            let node_id = format!("node{}", n);
+            for i in 0..600 {
+                let t = i as f64 * 0.1 + n as f64 * 0.001;
+                store.push(&node_id, status.clone(), t);
+                store.push(&node_id, synced.clone(), t);
+            }
+        }
+        store
+    }
+
+    #[test]
+    fn test_parallel_rates_match_serial() {
+        let store = synthetic_store(PARALLEL_MIN_NODES * 2);
+        assert!(store.parallel());
+        let filter = [true; 256];
+        let serial = store.rates_per_node(60.0, 1.0, 60, &filter, false);
+        let parallel = store.rates_per_node(60.0, 1.0, 60, &filter, true);
+        assert_eq!(serial, parallel);
+        assert!(parallel.windows(2).all(|w| w[0].0 < w[1].0));
+        assert_eq!(
+            store.aggregate_rate(60.0, 1.0, 60, None, false),
+            store.aggregate_rate(60.0, 1.0, 60, None, true),
+        );
+    }
+
+    /// Serial vs parallel rate scans over 1000 nodes: `cargo test --release -- --ignored --nocapture`
+    #[test]
+    #[ignore]
+    fn bench_parallel_rates() {
+        let store = synthetic_store(1_000);
+        let filter = [true; 256];
+        let runs = 20;
+        for parallel in [false, true] {
+            let start = std::time::Instant::now();
+            for _ in 0..runs {
+                std::hint::black_box(store.rates_per_node(60.0, 1.0, 60, &filter, parallel));
+                std::hint::black_box(store.aggregate_rate(60.0, 1.0, 60, None, parallel));
+            }
+            println!(
+                "{}: {:?} per frame",
+                if parallel { "parallel" } else { "serial" },
+                start.elapsed() / runs,
+            );
+        }
+    }
+
+    #[test]
+    fn test_export_csv() {
+        use crate::core::events::Reason;
+
+        let mut store = EventStore::new(100, 60.0);
+        let status_event = Event::Status {
+            timestamp: 0,
+            num_peers: 1,
+            num_val_peers: 0,
+            num_sync_peers: 0,
+            num_guarantees: vec![],
+            num_shards: 0,
+            shards_size: 0,
+            num_preimages: 0,
+            preimages_size: 0,
+        };
+        store.push("node1", status_event.clone(), 2.0);
+        store.push(
+            "node2",
+            Event::AuthoringFailed {
+                timestamp: 0,
+                authoring_id: 7,
+                reason: Reason("bad \"seal\", retrying".to_string()),
+            },
+            1.5,
+        );
+        store.push("node2", status_event, 0.5);
+        store.push("node1", Event::SyncStatusChanged { timestamp: 0, synced: true }, 1.0);
+
+        let mut out = Vec::new();
+        assert_eq!(store.export_csv(&mut out).unwrap(), 4);
+        let csv = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "timestamp,node_index,node_id,event_type,event_name,reason,synced_fraction"
+        );
+        assert_eq!(lines[1], "0.500000,1,node2,10,Status,,");
+        assert_eq!(lines[2], "1.000000,0,node1,13,SyncStatusChanged,,1.000");
+        assert_eq!(
+            lines[3],
+            "1.500000,1,node2,41,AuthoringFailed,\"bad \"\"seal\"\", retrying\",1.000"
+        );
+        assert_eq!(lines[4], "2.000000,0,node1,10,Status,,1.000");
+    }
+
+    #[test]
+    fn test_stalled_nodes() {
+        let mut store = EventStore::new(100, 60.0);
+        let synced = Event::SyncStatusChanged { timestamp: 0, synced: true };
+        let best = Event::BestBlockChanged { timestamp: 0, slot: 1, hash: [0u8; 32] };
+
+        // node1 last heard at 5.0 (newest event in a different bucket than the oldest)
+        store.push("node1", synced.clone(), 1.0);
+        store.push("node1", best.clone(), 5.0);
+        store.push("node2", synced, 18.0);
+
+        assert!(store.stalled_nodes(5.0, 10.0).is_empty());
+        assert_eq!(store.stalled_nodes(20.0, 10.0), vec![0]);
+        assert_eq!(store.stalled_nodes(40.0, 10.0), vec![0, 1]);
+        assert!(EventStore::new(100, 60.0).stalled_nodes(1000.0, 10.0).is_empty());
+
+        // Still stalled once retention has pruned every event of the node
+        store.prune(100.0);
+        assert_eq!(store.node("node1").unwrap().total_events(), 0);
+        assert_eq!(store.stalled_nodes(100.0, 10.0), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_find_event_near() {
+        let mut store = EventStore::new(100, 60.0);
+        let synced = Event::SyncStatusChanged { timestamp: 0, synced: true };
+        let best = Event::BestBlockChanged { timestamp: 0, slot: 1, hash: [0u8; 32] };
+        store.push("node1", synced.clone(), 1.0);
+        store.push("node1", synced.clone(), 4.0);
+        store.push("node1", best, 2.5);
+        store.push("node2", synced, 3.0);
+
+        // Nearest across buckets of node 0 only (node2's event at 3.0 is ignored)
+        let (node_id, stored) = store.find_event_near(0, 3.0, 1.0).unwrap();
+        assert_eq!(node_id, "node1");
+        assert_eq!(stored.timestamp, 2.5);
+        assert_eq!(store.find_event_near(0, 3.8, 1.0).unwrap().1.timestamp, 4.0);
+        assert_eq!(store.find_event_near(0, 0.0, 1.0).unwrap().1.timestamp, 1.0);
+        // Outside the tolerance, or an unknown node
+        assert!(store.find_event_near(0, 10.0, 1.0).is_none());
+        assert!(store.find_event_near(7, 3.0, 1.0).is_none());
+    }
+
+    #[test]
+    fn test_finalization_lag() {
+        let mut bbd = BestBlockData::new(10);
+        bbd.set_best("node1", 100, [1; 32]);
+        bbd.set_finalized("node1", 97);
+        bbd.set_best("node2", 100, [1; 32]);
+        // node3 has only a best block: no lag reported
+        bbd.set_best("node3", 100, [1; 32]);
+        // node2's finalized arrives ahead of its best update
+        bbd.set_finalized("node2", 101);
+
+        assert_eq!(bbd.finalization_lag(), vec![(0, 3), (1, 0)]);
+    }
+
+    #[test]
+    fn test_forks_at_slot() {
+        let mut bbd = BestBlockData::new(10);
+        assert_eq!(bbd.forks_at_slot(), None);
+
+        bbd.set_best("node1", 100, [1; 32]);
+        bbd.set_best("node2", 100, [1; 32]);
+        bbd.set_best("node3", 101, [3; 32]);
+        // node3 alone at 101; node1/node2 agree at 100
+        assert_eq!(bbd.forks_at_slot(), None);
+
+        bbd.set_best("node2", 101, [4; 32]);
+        let fork = bbd.forks_at_slot().unwrap();
+        assert_eq!(fork.slot, 101);
+        assert_eq!(
+            fork.branches,
+            vec![([3; 32], vec!["node3".to_string()]), ([4; 32], vec!["node2".to_string()])]
+        );
+
+        // node2 switches to node3's block: fork resolved
+        bbd.set_best("node2", 101, [3; 32]);
+        assert_eq!(bbd.forks_at_slot(), None);
+    }
+
+    #[test]
+    fn test_recent_errors_matching() {
+        use crate::core::events::Reason;
+
+        let mut store = EventStore::new(100, 60.0);
+        let failed = |reason: &str| Event::ConnectInFailed {
+            timestamp: 0,
+            connecting_id: 0,
+            reason: Reason(reason.to_string()),
+        };
+        store.push("node1", failed("handshake timeout"), 10.0);
+        store.push("node2", failed("connection reset"), 20.0);
+        store.push("node1", failed("timeout waiting for peer"), 30.0);
+        store.push("node2", Event::SyncStatusChanged { timestamp: 0, synced: true }, 40.0);
+        let types = [EventType::ConnectInFailed];
+
+        let all = store.recent_errors(&types, None, 10, 40.0, 60.0);
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].1.timestamp, 30.0);
+
+        // Match: newest first, limited
+        let re = Regex::new("time(d )?out").unwrap();
+        let hits = store.recent_errors(&types, Some(&re), 10, 40.0, 60.0);
+        assert_eq!(hits.iter().map(|(n, e)| (*n, e.timestamp)).collect::<Vec<_>>(),
+            vec![("node1", 30.0), ("node1", 10.0)]);
+        assert_eq!(store.recent_errors(&types, Some(&re), 1, 40.0, 60.0).len(), 1);
+
+        // Max age still applies
+        assert_eq!(store.recent_errors(&types, Some(&re), 10, 40.0, 15.0).len(), 1);
+
+        // No match
+        let re = Regex::new("^refused$").unwrap();
+        assert!(store.recent_errors(&types, Some(&re), 10, 40.0, 60.0).is_empty());
+    }
+
+    #[test]
+    fn test_block_outlines() {
+        let outline = |size_bytes: u32, hash: u8| BlockOutline {
+            size_bytes,
+            hash: [hash; 32],
+            num_tickets: 0,
+            num_preimages: 0,
+            total_preimages_size: 0,
+            num_guarantees: 0,
+            num_assurances: 0,
+            num_dispute_verdicts: 0,
+        };
+        let mut store = EventStore::new(100, 60.0);
+        store.push("node1", Event::Authored { timestamp: 0, authoring_id: 0, outline: outline(100, 1) }, 10.0);
+        // Same block imported by other nodes counts once
+        store.push("node2", Event::Importing { timestamp: 0, slot: 1, outline: outline(100, 1) }, 10.5);
+        store.push("node3", Event::Importing { timestamp: 0, slot: 1, outline: outline(100, 1) }, 10.6);
+        store.push("node2", Event::Importing { timestamp: 0, slot: 2, outline: outline(250, 2) }, 16.0);
+        store.push("node1", Event::Importing { timestamp: 0, slot: 3, outline: outline(400, 3) }, 22.0);
+
+        let sizes = |now, window| -> Vec<u32> {
+            store.block_outlines(now, window).iter().map(|o| o.size_bytes).collect()
+        };
+        assert_eq!(sizes(22.0, 60.0), vec![100, 250, 400]);
+        assert_eq!(sizes(22.0, 10.0), vec![250, 400]);
+        assert!(sizes(100.0, 10.0).is_empty());
+    }
+
+    #[test]
+    fn test_gas_samples() {
+        use crate::core::events::{AccumulateCost, ExecCost, RefineCost};
+
+        let cost = |gas_used, elapsed_ns| ExecCost { gas_used, elapsed_ns };
+        let accumulate = |service, gas, ns| {
+            (service, AccumulateCost { num_calls: 1, num_transfers: 0, num_items: 1, total: cost(gas, ns), load_ns: 0 })
+        };
+        let mut store = EventStore::new(100, 60.0);
+        store.push("node1", Event::Refined {
+            timestamp: 0,
+            submission_or_share_id: 0,
+            costs: vec![
+                RefineCost { total: cost(100, 10), load_ns: 0 },
+                RefineCost { total: cost(50, 5), load_ns: 0 },
+            ],
+        }, 10.0);
+        store.push("node2", Event::BlockExecuted {
+            timestamp: 0,
+            authoring_or_importing_id: 0,
+            accumulate_costs: vec![accumulate(7, 1_000, 200), accumulate(9, 3_000, 400)],
+        }, 20.0);
+
+        let samples = store.gas_samples(20.0, 60.0);
+        let summary: Vec<(f64, GasSource, u64, u64)> = samples
+            .iter()
+            .map(|s| (s.timestamp, s.source, s.gas_used, s.elapsed_ns))
+            .collect();
+        assert_eq!(summary, vec![
+            (10.0, GasSource::Refine, 150, 15),
+            (20.0, GasSource::Accumulate(7), 1_000, 200),
+            (20.0, GasSource::Accumulate(9), 3_000, 400),
+        ]);
+        assert_eq!(store.gas_samples(20.0, 5.0).len(), 2);
+    }
+
+    #[test]
+    fn test_gas_samples_count_each_package_once() {
+        use crate::core::events::{ExecCost, RefineCost, WorkPackageOutline};
+
+        let received = |hash: u8| Event::WorkPackageReceived {
+            timestamp: 0,
+            submission_or_share_id: 0,
+            core: 0,
+            outline: WorkPackageOutline {
+                work_package_size: 0,
+                work_package_hash: [hash; 32],
+                anchor: [0; 32],
+                lookup_anchor_slot: 0,
+            },
+        };
+        let refined = |gas_used| Event::Refined {
+            timestamp: 0,
+            submission_or_share_id: 0,
+            costs: vec![RefineCost { total: ExecCost { gas_used, elapsed_ns: 1 }, load_ns: 0 }],
+        };
+        let mut store = EventStore::new(100, 60.0);
+        // Three guarantors refine package 1; the first report counts
+        for (i, node) in ["node1", "node2", "node3"].into_iter().enumerate() {
+            store.push(node, received(1), 1.0);
+            store.push(node, refined(100 + i as u64), 2.0 + i as f64);
+        }
+        // node1 then refines package 2
+        store.push("node1", received(2), 6.0);
+        store.push("node1", refined(300), 7.0);
+        // A report whose package isn't stored counts on its own
+        store.push("node4", refined(50), 8.0);
+
+        let gas: Vec<u64> = store.gas_samples(10.0, 60.0).iter().map(|s| s.gas_used).collect();
+        assert_eq!(gas, vec![100, 300, 50]);
+    }
+
+    #[test]
+    fn test_pipeline_stage_counts() {
+        let mut store = EventStore::new(100, 60.0);
+        let refined = Event::Refined { timestamp: 0, submission_or_share_id: 0, costs: vec![] };
+        store.push("node1", refined.clone(), 5.0);
+        store.push("node1", refined.clone(), 15.0);
+        store.push("node2", refined, 18.0);
+        store.push("node2", Event::SyncStatusChanged { timestamp: 0, synced: true }, 18.0);
+
+        let counts = store.pipeline_stage_counts(20.0, 10.0);
+        assert_eq!(counts.len(), PIPELINE_STAGES.len());
+        assert_eq!(counts[2], ("Refined", 2));
+        assert!(counts.iter().filter(|(stage, _)| *stage != "Refined").all(|(_, n)| *n == 0));
+        assert_eq!(store.count_events(&[EventType::Refined], 0.0), 3);
+    }
+
+    #[test]
+    fn test_per_node_type_counts() {
+        let mut store = EventStore::new(100, 60.0);
+        let synced = Event::SyncStatusChanged { timestamp: 0, synced: true };
+        let best = Event::BestBlockChanged { timestamp: 0, slot: 1, hash: [0u8; 32] };
+        store.push("node1", synced.clone(), 5.0);
+        store.push("node1", synced.clone(), 15.0);
+        store.push("node1", best, 16.0);
+        store.push("node2", synced, 2.0);
+
+        let rows = store.per_node_type_counts(20.0, 10.0);
+        assert_eq!(rows.len(), 2);
+        let (index, counts) = &rows[0];
+        assert_eq!(*index, 0);
+        assert_eq!(counts[EventType::SyncStatusChanged.idx()], 1);
+        assert_eq!(counts[EventType::BestBlockChanged.idx()], 1);
+        assert_eq!(counts.iter().sum::<u32>(), 2);
+        // node2 is known but quiet in the window
+        assert_eq!(rows[1].0, 1);
+        assert!(rows[1].1.iter().all(|&c| c == 0));
+    }
+
+    #[test]
+    fn test_peer_addresses() {
+        use crate::core::events::PeerDetails;
+        let address = |last: u8| PeerAddress { ipv6: [last; 16], port: 30333 };
+        let mut store = EventStore::new(100, 60.0);
+        store.push("node1", Event::ConnectingIn { timestamp: 0, from: address(1) }, 5.0);
+        store.push("node1", Event::ConnectingIn { timestamp: 0, from: address(2) }, 15.0);
+        let to = PeerDetails { peer_id: [0u8; 32], peer_address: address(3) };
+        store.push("node2", Event::ConnectingOut { timestamp: 0, to }, 18.0);
+
+        let mut seen: Vec<u8> = store.peer_addresses(20.0, 10.0).iter().map(|a| a.ipv6[0]).collect();
+        seen.sort_unstable();
+        assert_eq!(seen, vec![2, 3]);
+        assert_eq!(store.peer_addresses(20.0, 30.0).len(), 3);
+
+        store.push("node1", Event::ConnectingIn { timestamp: 0, from: address(1) }, 19.0);
+        let recent = store.node("node1").unwrap().recent_peer_addresses(5);
+        assert_eq!(recent, vec![address(1), address(2)]);
+        assert_eq!(store.node("node1").unwrap().recent_peer_addresses(1), vec![address(1)]);
+    }
+
+    #[test]
+    fn test_connection_churn() {
+        use crate::core::events::Reason;
+
+        let mut store = EventStore::new(100, 60.0);
+        let connected = Event::ConnectedIn { timestamp: 0, connecting_id: 0, peer_id: [0; 32] };
+        let failed = Event::ConnectInFailed { timestamp: 0, connecting_id: 0, reason: Reason("reset".to_string()) };
+        store.push("node1", connected.clone(), 1.0);
+        store.push("node1", connected.clone(), 2.0);
+        store.push("node1", failed.clone(), 3.0);
+        store.push("node2", failed.clone(), 3.0);
+        store.push("node2", failed, 4.0);
+        store.push("node3", connected, 4.0);
+
+        // node3 never lost a connection
+        assert_eq!(store.top_churning(5), vec![("node2", 0, 2), ("node1", 2, 1)]);
+        assert_eq!(store.top_churning(1).len(), 1);
+    }
+
+    #[test]
+    fn test_misbehavior_reason_groups() {
+        use crate::core::events::Reason;
+
+        let misbehaved = |reason: &str| Event::PeerMisbehaved {
+            timestamp: 0,
+            peer: [0; 32],
+            reason: Reason(reason.to_string()),
+        };
+        let mut store = EventStore::new(100, 60.0);
+        store.push("node2", misbehaved("Invalid  block"), 10.0);
+        store.push("node1", misbehaved("invalid block "), 11.0);
+        store.push("node1", misbehaved("INVALID BLOCK"), 12.0);
+        store.push("node3", misbehaved("spam"), 13.0);
+        store.push("node3", misbehaved("old"), 1.0);
+
+        assert_eq!(
+            store.reason_distribution(EventType::PeerMisbehaved as u8, 20.0, 15.0),
+            vec![("invalid block".to_string(), 3), ("spam".to_string(), 1)]
+        );
+        let groups = store.reason_groups(EventType::PeerMisbehaved as u8, 20.0, 15.0);
+        assert_eq!(groups[0].nodes, vec!["node1", "node2"]);
+        assert_eq!(normalize_reason("  Peer\tsent   GARBAGE "), "peer sent garbage");
+    }
+
+    #[test]
+    fn test_reason_distribution() {
+        use crate::core::events::Reason;
+
+        let mut store = EventStore::new(100, 60.0);
+        let connect_failed = |reason: &str| Event::ConnectInFailed {
+            timestamp: 0,
+            connecting_id: 0,
+            reason: Reason(reason.to_string()),
+        };
+        let wp_failed = |reason: &str| Event::WorkPackageFailed {
+            timestamp: 0,
+            submission_or_share_id: 0,
+            reason: Reason(reason.to_string()),
+        };
+        store.push("node1", connect_failed("timeout"), 10.0);
+        store.push("node2", connect_failed("Timeout"), 11.0);
+        store.push("node2", connect_failed("connection reset"), 12.0);
+        store.push("node1", wp_failed("authorization failed"), 12.0);
+        store.push("node3", wp_failed("bad  core index"), 13.0);
+        store.push("node3", wp_failed("Authorization failed"), 14.0);
+
+        assert_eq!(
+            store.reason_distribution(EventType::ConnectInFailed as u8, 20.0, 60.0),
+            vec![("timeout".to_string(), 2), ("connection reset".to_string(), 1)]
+        );
+        assert_eq!(
+            store.reason_distribution(EventType::WorkPackageFailed as u8, 20.0, 60.0),
+            vec![("authorization failed".to_string(), 2), ("bad core index".to_string(), 1)]
+        );
+        // Window and types without stored events
+        assert_eq!(store.reason_distribution(EventType::ConnectInFailed as u8, 20.0, 8.5).len(), 1);
+        assert!(store.reason_distribution(EventType::GuaranteeDiscarded as u8, 20.0, 60.0).is_empty());
+    }
+
+    #[test]
+    fn test_get_new_since() {
+        let mut store = EventStore::new(100, 60.0);
+        let synced = Event::SyncStatusChanged { timestamp: 0, synced: true };
+        store.push("node1", synced.clone(), 1.0);
+        store.push("node2", synced.clone(), 2.0);
+
+        let (events, cursor) = store.get_new_since(0, None);
+        assert_eq!(events.count(), 2);
+        assert_eq!(cursor, 2);
+
+        store.push("node1", synced.clone(), 3.0);
+        store.push("node2", synced, 4.0);
+        let (events, cursor) = store.get_new_since(2, None);
+        let mut times: Vec<f64> = events.map(|(_, _, e)| e.timestamp).collect();
+        times.sort_by(f64::total_cmp);
+        assert_eq!(times, vec![3.0, 4.0]);
+        assert_eq!(cursor, 4);
+
+        // Focus limits the events but not the cursor
+        let (events, cursor) = store.get_new_since(2, Some(0));
+        assert_eq!(events.map(|(idx, _, _)| idx).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(cursor, 4);
+        assert_eq!(store.get_new_since(4, None).0.count(), 0);
+
+        // Windowed count with filter and focus
+        let filter = [true; 256];
+        assert_eq!(store.count_in_window(2.5, &filter, None), 2);
+        assert_eq!(store.count_in_window(0.0, &filter, Some(1)), 2);
+        assert_eq!(store.count_in_window(0.0, &[false; 256], None), 0);
+    }
+
+    #[test]
+    fn test_hidden_sources() {
+        let mut store = EventStore::new(100, 60.0);
+        let synced = Event::SyncStatusChanged { timestamp: 0, synced: true };
+        store.push_from("node1", synced.clone(), 1.0, 1);
+        store.push_from("node1", synced.clone(), 2.0, 2);
+        store.push("node1", synced, 3.0);
+
+        // Hiding source 2 leaves its events out; untagged events always show
+        store.set_hidden_sources(0b10);
+        let filter = [true; 256];
+        assert_eq!(store.count_in_window(0.0, &filter, None), 2);
+        let mut times: Vec<f64> = store.get_new_since(0, None).0.map(|(_, _, e)| e.timestamp).collect();
+        times.sort_by(f64::total_cmp);
+        assert_eq!(times, vec![1.0, 3.0]);
+        let et = EventType::SyncStatusChanged as usize;
+        assert_eq!(store.per_node_type_counts(4.0, 10.0)[0].1[et], 2);
+        assert_eq!(store.count_events(&[EventType::SyncStatusChanged], 0.0), 2);
+        let rates = store.compute_rates_per_node(4.0, 1.0, 4, &filter);
+        assert_eq!(rates[0].1.iter().sum::<u32>(), 2);
+
+        // Still stored: showing the source again brings it back
+        store.set_hidden_sources(0);
+        assert_eq!(store.count_in_window(0.0, &filter, None), 3);
+        assert_eq!(store.count_events(&[EventType::SyncStatusChanged], 0.0), 3);
+        assert!(!source_hidden(u64::MAX, 0));
+    }
+
+    #[test]
+    fn test_runtime_limits() {
+        let mut store = EventStore::new(100, 60.0);
+        let synced = Event::SyncStatusChanged { timestamp: 0, synced: true };
+        for t in 0..10 {
+            store.push("node1", synced.clone(), t as f64 * 10.0);
+        }
+
+        // Shorter retention prunes on the next call
+        store.retention = 30.0;
+        store.prune(90.0);
+        assert_eq!(store.node("node1").unwrap().total_events(), 4);
+
+        // Longer retention doesn't bring anything back
+        store.retention = 600.0;
+        store.prune(90.0);
+        assert_eq!(store.node("node1").unwrap().total_events(), 4);
+        assert_eq!(store.capacity_evictions(), 0);
+
+        // Shrinking capacity keeps the newest events, and sticks for new pushes
+        store.set_max_events_per_node(2);
+        let node = store.node("node1").unwrap();
+        assert_eq!(node.total_events(), 2);
+        assert_eq!(node.last_seen(), 90.0);
+        store.push("node1", synced, 100.0);
+        assert_eq!(store.node("node1").unwrap().total_events(), 2);
+        assert_eq!(store.capacity_evictions(), 1);
+    }
+
     #[test]
     fn test_prune() {
         let mut store = EventStore::new(100, 30.0);
@@ -476,5 +2874,41 @@ mod tests {
         let remaining = store.node_events("node1", 10).unwrap();
         assert_eq!(remaining.len(), 1);
     }
+    #[test]
+    fn test_node_summary_accessors() {
+        let mut store = EventStore::new(100, 60.0);
+
+        let synced = |synced| Event::SyncStatusChanged { timestamp: 0, synced };
+        let best = Event::BestBlockChanged { timestamp: 0, slot: 1, hash: [0u8; 32] };
+
+        store.push("node1", best.clone(), 1.0);
+        store.push("node2", best.clone(), 1.0);
+        store.push("node2", best.clone(), 2.0);
+        store.push("node2", synced(false), 3.0);
+        store.push("node2", synced(true), 4.0);
+
+        assert_eq!(store.node_id_at(0), Some("node1"));
+        assert_eq!(store.node_id_at(1), Some("node2"));
+        assert_eq!(store.node_id_at(2), None);
+
+        let node2 = store.node("node2").unwrap();
+        // BestBlockChanged (11) x2 before SyncStatusChanged (13) x2: tie broken by type
+        assert_eq!(node2.type_counts(), vec![(11, 2), (13, 2)]);
+        assert_eq!(node2.latest_sync_status(), Some(true));
+        assert_eq!(store.node("node1").unwrap().latest_sync_status(), None);
+        assert_eq!(node2.count_since(2.0), 3);
+        assert_eq!(node2.count_since(5.0), 0);
+        assert_eq!(node2.last_seen(), 4.0);
+
+        let mut bbd = BestBlockData::new(10);
+        bbd.set_best("node1", 100, [1; 32]);
+        assert_eq!(bbd.best_for("node1"), Some(100));
+        assert_eq!(bbd.finalized_for("node1"), None);
+        assert_eq!(bbd.best_for("node2"), None);
+        bbd.set_finalized("node2", 90);
+        let mut all: Vec<_> = bbd.iter().collect();
+        all.sort();
+        assert_eq!(all, vec![("node1", Some(100), None), ("node2", None, Some(90))]);
+    }
 
 }
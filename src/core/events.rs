@@ -5,7 +5,10 @@
 //! - Event categories for UI grouping
 //! - Event names and colors for display
 
+use parity_scale_codec::{Decode, Encode};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::net::{IpAddr, Ipv6Addr, SocketAddr};
 
 // ============================================================================
 // Basic Types (subset of jamtart types.rs, no encoding)
@@ -32,34 +35,52 @@ pub type TicketAttempt = u8;
 #[allow(dead_code)]
 pub type ImportSegmentId = u16;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
 pub struct Reason(#[allow(dead_code)] pub String);
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Encode, Decode)]
 pub struct PeerAddress {
     pub ipv6: [u8; 16],
     pub port: u16,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl PeerAddress {
+    /// IPv4 for IPv4-mapped addresses (`::ffff:a.b.c.d`), IPv6 otherwise
+    pub fn ip(&self) -> IpAddr {
+        let v6 = Ipv6Addr::from(self.ipv6);
+        match v6.to_ipv4_mapped() {
+            Some(v4) => IpAddr::V4(v4),
+            None => IpAddr::V6(v6),
+        }
+    }
+}
+
+/// Socket address form: `a.b.c.d:port` for IPv4-mapped addresses, `[v6]:port` otherwise
+impl std::fmt::Display for PeerAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", SocketAddr::new(self.ip(), self.port))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
 pub struct PeerDetails {
     pub peer_id: PeerId,
     pub peer_address: PeerAddress,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Encode, Decode)]
 pub enum ConnectionSide {
     Local = 0,
     Remote = 1,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Encode, Decode)]
 pub enum BlockRequestDirection {
     Ascending = 0,
     Descending = 1,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Encode, Decode)]
 pub enum GuaranteeDiscardReason {
     PackageReportedOnChain = 0,
     ReplacedByBetter = 1,
@@ -69,7 +90,7 @@ pub enum GuaranteeDiscardReason {
 }
 
 #[allow(dead_code)]
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Encode, Decode)]
 pub enum AnnouncedPreimageForgetReason {
     ProvidedOnChain = 0,
     NotRequestedOnChain = 1,
@@ -80,7 +101,7 @@ pub enum AnnouncedPreimageForgetReason {
 }
 
 #[allow(dead_code)]
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Encode, Decode)]
 pub enum PreimageDiscardReason {
     ProvidedOnChain = 0,
     NotRequestedOnChain = 1,
@@ -89,14 +110,14 @@ pub enum PreimageDiscardReason {
 }
 
 #[allow(dead_code)]
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Encode, Decode)]
 pub enum ReconstructionKind {
     NonTrivial = 0,
     Trivial = 1,
 }
 
 // Simplified outline types (just the essential fields)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
 pub struct BlockOutline {
     pub size_bytes: u32,
     pub hash: HeaderHash,
@@ -108,7 +129,7 @@ pub struct BlockOutline {
     pub num_dispute_verdicts: u32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
 pub struct WorkPackageOutline {
     pub work_package_size: u32,
     pub work_package_hash: WorkPackageHash,
@@ -116,7 +137,7 @@ pub struct WorkPackageOutline {
     pub lookup_anchor_slot: Slot,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
 pub struct WorkReportOutline {
     pub work_report_hash: WorkReportHash,
     pub bundle_size: u32,
@@ -124,40 +145,40 @@ pub struct WorkReportOutline {
     pub segments_root: SegmentsRoot,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
 pub struct GuaranteeOutline {
     pub work_report_hash: WorkReportHash,
     pub slot: Slot,
     pub guarantors: Vec<ValidatorIndex>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
 pub struct AvailabilityStatement {
     pub anchor: HeaderHash,
     pub bitfield: Vec<u8>,
 }
 
 // Simplified cost types
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
 pub struct ExecCost {
     pub gas_used: Gas,
     pub elapsed_ns: u64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
 pub struct IsAuthorizedCost {
     pub total: ExecCost,
     pub load_ns: u64,
     pub host_call: ExecCost,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
 pub struct RefineCost {
     pub total: ExecCost,
     pub load_ns: u64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
 pub struct AccumulateCost {
     pub num_calls: u32,
     pub num_transfers: u32,
@@ -315,6 +336,10 @@ pub enum EventType {
     PreimageRequestReceived = 197,
     PreimageTransferred = 198,
     PreimageDiscarded = 199,
+
+    /// Any discriminant this build doesn't know (see [`Event::Unknown`]).
+    /// Not returned by [`EventType::from_u8`], which only accepts JIP-3 types.
+    Unknown = 255,
 }
 
 impl EventType {
@@ -344,15 +369,50 @@ impl EventType {
             _ => None,
         }
     }
+
+    /// Get the default travel duration for this event type (in seconds).
+    /// Longer durations make motion visible at high event rates.
+    pub fn travel_duration(self) -> f32 {
+        match self {
+            // Fast events (guarantees, assurances)
+            EventType::SendingGuarantee
+            | EventType::ReceivingGuarantee
+            | EventType::AssuranceSent
+            | EventType::AssuranceReceived => 2.0,
+
+            // Medium events (shard requests, blocks)
+            EventType::SendingShardRequest
+            | EventType::ReceivingShardRequest
+            | EventType::SendingBlockRequest
+            | EventType::ReceivingBlockRequest => 2.0,
+
+            // Slow events (connections, work package sharing)
+            EventType::ConnectingOut
+            | EventType::ConnectedIn
+            | EventType::WorkPackageBeingShared => 3.0,
+
+            // Default (includes WorkPackageSubmission — pulse handles visual emphasis)
+            _ => 2.0,
+        }
+    }
+}
+
+/// Directed-particle travel duration per event type (seconds), indexed by [`EventType::idx`]
+pub type TravelDurations = [f32; 256];
+
+/// [`EventType::travel_duration`] for every discriminant; unknown ones get the default
+pub fn default_travel_durations() -> TravelDurations {
+    std::array::from_fn(|i| EventType::from_u8(i as u8).map_or(2.0, EventType::travel_duration))
 }
 
 // ============================================================================
 // Event Enum (full JIP-3 specification)
 // ============================================================================
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
 pub enum Event {
     // Meta events
+    #[codec(index = 0)]
     Dropped {
         timestamp: Timestamp,
         last_timestamp: Timestamp,
@@ -360,6 +420,7 @@ pub enum Event {
     },
 
     // Status
+    #[codec(index = 10)]
     Status {
         timestamp: Timestamp,
         num_peers: u32,
@@ -372,62 +433,73 @@ pub enum Event {
         preimages_size: u32,
     },
 
+    #[codec(index = 11)]
     BestBlockChanged {
         timestamp: Timestamp,
         slot: Slot,
         hash: HeaderHash,
     },
 
+    #[codec(index = 12)]
     FinalizedBlockChanged {
         timestamp: Timestamp,
         slot: Slot,
         hash: HeaderHash,
     },
 
+    #[codec(index = 13)]
     SyncStatusChanged {
         timestamp: Timestamp,
         synced: bool,
     },
 
     // Connection events
+    #[codec(index = 20)]
     ConnectionRefused {
         timestamp: Timestamp,
         from: PeerAddress,
     },
 
+    #[codec(index = 21)]
     ConnectingIn {
         timestamp: Timestamp,
         from: PeerAddress,
     },
 
+    #[codec(index = 22)]
     ConnectInFailed {
         timestamp: Timestamp,
         connecting_id: EventId,
         reason: Reason,
     },
 
+    #[codec(index = 23)]
     ConnectedIn {
         timestamp: Timestamp,
         connecting_id: EventId,
         peer_id: PeerId,
     },
 
+    #[codec(index = 24)]
     ConnectingOut {
         timestamp: Timestamp,
         to: PeerDetails,
     },
 
+    #[codec(index = 25)]
     ConnectOutFailed {
         timestamp: Timestamp,
         connecting_id: EventId,
         reason: Reason,
     },
 
+    #[codec(index = 26)]
     ConnectedOut {
         timestamp: Timestamp,
         connecting_id: EventId,
     },
 
+    #[codec(index = 27)]
     Disconnected {
         timestamp: Timestamp,
         peer: PeerId,
@@ -435,6 +507,7 @@ pub enum Event {
         reason: Reason,
     },
 
+    #[codec(index = 28)]
     PeerMisbehaved {
         timestamp: Timestamp,
         peer: PeerId,
@@ -442,47 +515,55 @@ pub enum Event {
     },
 
     // Authoring events
+    #[codec(index = 40)]
     Authoring {
         timestamp: Timestamp,
         slot: Slot,
         parent: HeaderHash,
     },
 
+    #[codec(index = 41)]
     AuthoringFailed {
         timestamp: Timestamp,
         authoring_id: EventId,
         reason: Reason,
     },
 
+    #[codec(index = 42)]
     Authored {
         timestamp: Timestamp,
         authoring_id: EventId,
         outline: BlockOutline,
     },
 
+    #[codec(index = 43)]
     Importing {
         timestamp: Timestamp,
         slot: Slot,
         outline: BlockOutline,
     },
 
+    #[codec(index = 44)]
     BlockVerificationFailed {
         timestamp: Timestamp,
         importing_id: EventId,
         reason: Reason,
     },
 
+    #[codec(index = 45)]
     BlockVerified {
         timestamp: Timestamp,
         importing_id: EventId,
     },
 
+    #[codec(index = 46)]
     BlockExecutionFailed {
         timestamp: Timestamp,
         authoring_or_importing_id: EventId,
         reason: Reason,
     },
 
+    #[codec(index = 47)]
     BlockExecuted {
         timestamp: Timestamp,
         authoring_or_importing_id: EventId,
@@ -490,12 +571,14 @@ pub enum Event {
     },
 
     // Block distribution
+    #[codec(index = 60)]
     BlockAnnouncementStreamOpened {
         timestamp: Timestamp,
         peer: PeerId,
         opener: ConnectionSide,
     },
 
+    #[codec(index = 61)]
     BlockAnnouncementStreamClosed {
         timestamp: Timestamp,
         peer: PeerId,
@@ -503,6 +586,7 @@ pub enum Event {
         reason: Reason,
     },
 
+    #[codec(index = 62)]
     BlockAnnounced {
         timestamp: Timestamp,
         peer: PeerId,
@@ -511,6 +595,7 @@ pub enum Event {
         hash: HeaderHash,
     },
 
+    #[codec(index = 63)]
     SendingBlockRequest {
         timestamp: Timestamp,
         recipient: PeerId,
@@ -519,22 +604,26 @@ pub enum Event {
         max_blocks: u32,
     },
 
+    #[codec(index = 64)]
     ReceivingBlockRequest {
         timestamp: Timestamp,
         sender: PeerId,
     },
 
+    #[codec(index = 65)]
     BlockRequestFailed {
         timestamp: Timestamp,
         request_id: EventId,
         reason: Reason,
     },
 
+    #[codec(index = 66)]
     BlockRequestSent {
         timestamp: Timestamp,
         request_id: EventId,
     },
 
+    #[codec(index = 67)]
     BlockRequestReceived {
         timestamp: Timestamp,
         request_id: EventId,
@@ -543,6 +632,7 @@ pub enum Event {
         max_blocks: u32,
     },
 
+    #[codec(index = 68)]
     BlockTransferred {
         timestamp: Timestamp,
         request_id: EventId,
@@ -552,23 +642,27 @@ pub enum Event {
     },
 
     // Ticket events
+    #[codec(index = 80)]
     GeneratingTickets {
         timestamp: Timestamp,
         epoch: EpochIndex,
     },
 
+    #[codec(index = 81)]
     TicketGenerationFailed {
         timestamp: Timestamp,
         generating_id: EventId,
         reason: Reason,
     },
 
+    #[codec(index = 82)]
     TicketsGenerated {
         timestamp: Timestamp,
         generating_id: EventId,
         ids: Vec<TicketId>,
     },
 
+    #[codec(index = 83)]
     TicketTransferFailed {
         timestamp: Timestamp,
         peer: PeerId,
@@ -577,6 +671,7 @@ pub enum Event {
         reason: Reason,
     },
 
+    #[codec(index = 84)]
     TicketTransferred {
         timestamp: Timestamp,
         peer: PeerId,
@@ -588,23 +683,27 @@ pub enum Event {
     },
 
     // Work package events (simplified - add full fields as needed)
+    #[codec(index = 90)]
     WorkPackageSubmission {
         timestamp: Timestamp,
         builder: PeerId,
         bundle: bool,
     },
 
+    #[codec(index = 91)]
     WorkPackageBeingShared {
         timestamp: Timestamp,
         primary: PeerId,
     },
 
+    #[codec(index = 92)]
     WorkPackageFailed {
         timestamp: Timestamp,
         submission_or_share_id: EventId,
         reason: Reason,
     },
 
+    #[codec(index = 93)]
     DuplicateWorkPackage {
         timestamp: Timestamp,
         submission_or_share_id: EventId,
@@ -612,6 +711,7 @@ pub enum Event {
         hash: WorkPackageHash,
     },
 
+    #[codec(index = 94)]
     WorkPackageReceived {
         timestamp: Timestamp,
         submission_or_share_id: EventId,
@@ -619,28 +719,33 @@ pub enum Event {
         outline: WorkPackageOutline,
     },
 
+    #[codec(index = 95)]
     Authorized {
         timestamp: Timestamp,
         submission_or_share_id: EventId,
         cost: IsAuthorizedCost,
     },
 
+    #[codec(index = 96)]
     ExtrinsicDataReceived {
         timestamp: Timestamp,
         submission_or_share_id: EventId,
     },
 
+    #[codec(index = 97)]
     ImportsReceived {
         timestamp: Timestamp,
         submission_or_share_id: EventId,
     },
 
+    #[codec(index = 98)]
     SharingWorkPackage {
         timestamp: Timestamp,
         submission_id: EventId,
         secondary: PeerId,
     },
 
+    #[codec(index = 99)]
     WorkPackageSharingFailed {
         timestamp: Timestamp,
         submission_id: EventId,
@@ -648,80 +753,94 @@ pub enum Event {
         reason: Reason,
     },
 
+    #[codec(index = 100)]
     BundleSent {
         timestamp: Timestamp,
         submission_id: EventId,
         secondary: PeerId,
     },
 
+    #[codec(index = 101)]
     Refined {
         timestamp: Timestamp,
         submission_or_share_id: EventId,
         costs: Vec<RefineCost>,
     },
 
+    #[codec(index = 102)]
     WorkReportBuilt {
         timestamp: Timestamp,
         submission_or_share_id: EventId,
         outline: WorkReportOutline,
     },
 
+    #[codec(index = 103)]
     WorkReportSignatureSent {
         timestamp: Timestamp,
         share_id: EventId,
     },
 
+    #[codec(index = 104)]
     WorkReportSignatureReceived {
         timestamp: Timestamp,
         submission_id: EventId,
         secondary: PeerId,
     },
 
+    #[codec(index = 105)]
     GuaranteeBuilt {
         timestamp: Timestamp,
         submission_id: EventId,
         outline: GuaranteeOutline,
     },
 
+    #[codec(index = 106)]
     SendingGuarantee {
         timestamp: Timestamp,
         built_id: EventId,
         recipient: PeerId,
     },
 
+    #[codec(index = 107)]
     GuaranteeSendFailed {
         timestamp: Timestamp,
         sending_id: EventId,
         reason: Reason,
     },
 
+    #[codec(index = 108)]
     GuaranteeSent {
         timestamp: Timestamp,
         sending_id: EventId,
     },
 
+    #[codec(index = 109)]
     GuaranteesDistributed {
         timestamp: Timestamp,
         submission_id: EventId,
     },
 
+    #[codec(index = 110)]
     ReceivingGuarantee {
         timestamp: Timestamp,
         sender: PeerId,
     },
 
+    #[codec(index = 111)]
     GuaranteeReceiveFailed {
         timestamp: Timestamp,
         receiving_id: EventId,
         reason: Reason,
     },
 
+    #[codec(index = 112)]
     GuaranteeReceived {
         timestamp: Timestamp,
         receiving_id: EventId,
         outline: GuaranteeOutline,
     },
 
+    #[codec(index = 113)]
     GuaranteeDiscarded {
         timestamp: Timestamp,
         outline: GuaranteeOutline,
@@ -729,6 +848,7 @@ pub enum Event {
     },
 
     // Availability distribution (simplified)
+    #[codec(index = 120)]
     SendingShardRequest {
         timestamp: Timestamp,
         guarantor: PeerId,
@@ -736,22 +856,26 @@ pub enum Event {
         shard: ShardIndex,
     },
 
+    #[codec(index = 121)]
     ReceivingShardRequest {
         timestamp: Timestamp,
         assurer: PeerId,
     },
 
+    #[codec(index = 122)]
     ShardRequestFailed {
         timestamp: Timestamp,
         request_id: EventId,
         reason: Reason,
     },
 
+    #[codec(index = 123)]
     ShardRequestSent {
         timestamp: Timestamp,
         request_id: EventId,
     },
 
+    #[codec(index = 124)]
     ShardRequestReceived {
         timestamp: Timestamp,
         request_id: EventId,
@@ -759,16 +883,19 @@ pub enum Event {
         shard: ShardIndex,
     },
 
+    #[codec(index = 125)]
     ShardsTransferred {
         timestamp: Timestamp,
         request_id: EventId,
     },
 
+    #[codec(index = 126)]
     DistributingAssurance {
         timestamp: Timestamp,
         statement: AvailabilityStatement,
     },
 
+    #[codec(index = 127)]
     AssuranceSendFailed {
         timestamp: Timestamp,
         distributing_id: EventId,
@@ -776,23 +903,27 @@ pub enum Event {
         reason: Reason,
     },
 
+    #[codec(index = 128)]
     AssuranceSent {
         timestamp: Timestamp,
         distributing_id: EventId,
         recipient: PeerId,
     },
 
+    #[codec(index = 129)]
     AssuranceDistributed {
         timestamp: Timestamp,
         distributing_id: EventId,
     },
 
+    #[codec(index = 130)]
     AssuranceReceiveFailed {
         timestamp: Timestamp,
         sender: PeerId,
         reason: Reason,
     },
 
+    #[codec(index = 131)]
     AssuranceReceived {
         timestamp: Timestamp,
         sender: PeerId,
@@ -800,53 +931,101 @@ pub enum Event {
     },
 
     // Bundle recovery (simplified stubs)
+    #[codec(index = 140)]
     SendingBundleShardRequest { timestamp: Timestamp },
+    #[codec(index = 141)]
     ReceivingBundleShardRequest { timestamp: Timestamp },
+    #[codec(index = 142)]
     BundleShardRequestFailed { timestamp: Timestamp },
+    #[codec(index = 143)]
     BundleShardRequestSent { timestamp: Timestamp },
+    #[codec(index = 144)]
     BundleShardRequestReceived { timestamp: Timestamp },
+    #[codec(index = 145)]
     BundleShardTransferred { timestamp: Timestamp },
+    #[codec(index = 146)]
     ReconstructingBundle { timestamp: Timestamp },
+    #[codec(index = 147)]
     BundleReconstructed { timestamp: Timestamp },
+    #[codec(index = 148)]
     SendingBundleRequest { timestamp: Timestamp },
+    #[codec(index = 149)]
     ReceivingBundleRequest { timestamp: Timestamp },
+    #[codec(index = 150)]
     BundleRequestFailed { timestamp: Timestamp },
+    #[codec(index = 151)]
     BundleRequestSent { timestamp: Timestamp },
+    #[codec(index = 152)]
     BundleRequestReceived { timestamp: Timestamp },
+    #[codec(index = 153)]
     BundleTransferred { timestamp: Timestamp },
 
     // Segment events (simplified stubs)
+    #[codec(index = 160)]
     WorkPackageHashMapped { timestamp: Timestamp },
+    #[codec(index = 161)]
     SegmentsRootMapped { timestamp: Timestamp },
+    #[codec(index = 162)]
     SendingSegmentShardRequest { timestamp: Timestamp },
+    #[codec(index = 163)]
     ReceivingSegmentShardRequest { timestamp: Timestamp },
+    #[codec(index = 164)]
     SegmentShardRequestFailed { timestamp: Timestamp },
+    #[codec(index = 165)]
     SegmentShardRequestSent { timestamp: Timestamp },
+    #[codec(index = 166)]
     SegmentShardRequestReceived { timestamp: Timestamp },
+    #[codec(index = 167)]
     SegmentShardsTransferred { timestamp: Timestamp },
+    #[codec(index = 168)]
     ReconstructingSegments { timestamp: Timestamp },
+    #[codec(index = 169)]
     SegmentReconstructionFailed { timestamp: Timestamp },
+    #[codec(index = 170)]
     SegmentsReconstructed { timestamp: Timestamp },
+    #[codec(index = 171)]
     SegmentVerificationFailed { timestamp: Timestamp },
+    #[codec(index = 172)]
     SegmentsVerified { timestamp: Timestamp },
+    #[codec(index = 173)]
     SendingSegmentRequest { timestamp: Timestamp },
+    #[codec(index = 174)]
     ReceivingSegmentRequest { timestamp: Timestamp },
+    #[codec(index = 175)]
     SegmentRequestFailed { timestamp: Timestamp },
+    #[codec(index = 176)]
     SegmentRequestSent { timestamp: Timestamp },
+    #[codec(index = 177)]
     SegmentRequestReceived { timestamp: Timestamp },
+    #[codec(index = 178)]
     SegmentsTransferred { timestamp: Timestamp },
 
     // Preimage events (simplified stubs)
+    #[codec(index = 190)]
     PreimageAnnouncementFailed { timestamp: Timestamp },
+    #[codec(index = 191)]
     PreimageAnnounced { timestamp: Timestamp },
+    #[codec(index = 192)]
     AnnouncedPreimageForgotten { timestamp: Timestamp },
+    #[codec(index = 193)]
     SendingPreimageRequest { timestamp: Timestamp },
+    #[codec(index = 194)]
     ReceivingPreimageRequest { timestamp: Timestamp },
+    #[codec(index = 195)]
     PreimageRequestFailed { timestamp: Timestamp },
+    #[codec(index = 196)]
     PreimageRequestSent { timestamp: Timestamp },
+    #[codec(index = 197)]
     PreimageRequestReceived { timestamp: Timestamp },
+    #[codec(index = 198)]
     PreimageTransferred { timestamp: Timestamp },
+    #[codec(index = 199)]
     PreimageDiscarded { timestamp: Timestamp },
+
+    /// Event whose payload this build can't decode: a type it doesn't know (e.g. from a newer
+    /// jamtart), or a known type whose fields changed. Only the type and timestamp are kept.
+    #[codec(index = 255)]
+    Unknown { type_id: u8, timestamp: Timestamp },
 }
 
 impl Event {
@@ -968,6 +1147,9 @@ impl Event {
             Event::PreimageRequestReceived { .. } => EventType::PreimageRequestReceived,
             Event::PreimageTransferred { .. } => EventType::PreimageTransferred,
             Event::PreimageDiscarded { .. } => EventType::PreimageDiscarded,
+            Event::Unknown { type_id, .. } => {
+                EventType::from_u8(*type_id).unwrap_or(EventType::Unknown)
+            }
         }
     }
 
@@ -1089,7 +1271,34 @@ impl Event {
             | Event::PreimageRequestSent { timestamp, .. }
             | Event::PreimageRequestReceived { timestamp, .. }
             | Event::PreimageTransferred { timestamp, .. }
-            | Event::PreimageDiscarded { timestamp, .. } => *timestamp,
+            | Event::PreimageDiscarded { timestamp, .. }
+            | Event::Unknown { timestamp, .. } => *timestamp,
+        }
+    }
+
+    /// Human-readable failure reason, for events that carry one
+    pub fn reason(&self) -> Option<Cow<'_, str>> {
+        match self {
+            Event::ConnectInFailed { reason, .. }
+            | Event::ConnectOutFailed { reason, .. }
+            | Event::Disconnected { reason, .. }
+            | Event::PeerMisbehaved { reason, .. }
+            | Event::AuthoringFailed { reason, .. }
+            | Event::BlockVerificationFailed { reason, .. }
+            | Event::BlockExecutionFailed { reason, .. }
+            | Event::BlockAnnouncementStreamClosed { reason, .. }
+            | Event::BlockRequestFailed { reason, .. }
+            | Event::TicketGenerationFailed { reason, .. }
+            | Event::TicketTransferFailed { reason, .. }
+            | Event::WorkPackageFailed { reason, .. }
+            | Event::WorkPackageSharingFailed { reason, .. }
+            | Event::GuaranteeSendFailed { reason, .. }
+            | Event::GuaranteeReceiveFailed { reason, .. }
+            | Event::ShardRequestFailed { reason, .. }
+            | Event::AssuranceSendFailed { reason, .. }
+            | Event::AssuranceReceiveFailed { reason, .. } => Some(Cow::Borrowed(&reason.0)),
+            Event::GuaranteeDiscarded { reason, .. } => Some(Cow::Owned(format!("{:?}", reason))),
+            _ => None,
         }
     }
 }
@@ -1166,6 +1375,26 @@ pub const EVENT_CATEGORIES: &[EventCategory] = &[
         PreimageRequestSent, PreimageRequestReceived, PreimageTransferred, PreimageDiscarded,
     ]},
     EventCategory { name: "Meta", event_types: &[Dropped] },
+    EventCategory { name: "Unknown", event_types: &[Unknown] },
+];
+
+/// Work-package pipeline stages in order, with the events marking a package reaching each
+pub const PIPELINE_STAGES: &[(&str, &[EventType])] = &[
+    ("Submitted", &[WorkPackageSubmission]),
+    ("Shared", &[WorkPackageBeingShared]),
+    ("Refined", &[Refined]),
+    ("Report built", &[WorkReportBuilt]),
+    ("Guaranteed", &[GuaranteeBuilt]),
+];
+
+/// Bundle and segment recovery steps, with the events marking success and failure of each
+pub const RECOVERY_OUTCOMES: &[(&str, &[EventType], &[EventType])] = &[
+    ("Bundle shards", &[BundleShardTransferred], &[BundleShardRequestFailed]),
+    ("Bundles", &[BundleTransferred], &[BundleRequestFailed]),
+    ("Segment shards", &[SegmentShardsTransferred], &[SegmentShardRequestFailed]),
+    ("Segment reconstruction", &[SegmentsReconstructed], &[SegmentReconstructionFailed]),
+    ("Segment verification", &[SegmentsVerified], &[SegmentVerificationFailed]),
+    ("Segments", &[SegmentsTransferred], &[SegmentRequestFailed]),
 ];
 
 /// Event types representing errors, failures, disconnections, and discards.
@@ -1183,6 +1412,23 @@ pub const ERROR_EVENT_TYPES: &[EventType] = &[
     PreimageAnnouncementFailed, PreimageRequestFailed, PreimageDiscarded,
 ];
 
+/// Event types whose events carry a failure reason (see [`Event::reason`])
+pub const REASON_EVENT_TYPES: &[EventType] = &[
+    ConnectInFailed, ConnectOutFailed, Disconnected, PeerMisbehaved,
+    AuthoringFailed, BlockVerificationFailed, BlockExecutionFailed,
+    BlockAnnouncementStreamClosed, BlockRequestFailed,
+    TicketGenerationFailed, TicketTransferFailed,
+    WorkPackageFailed, WorkPackageSharingFailed,
+    GuaranteeSendFailed, GuaranteeReceiveFailed, GuaranteeDiscarded,
+    ShardRequestFailed, AssuranceSendFailed, AssuranceReceiveFailed,
+];
+
+/// Event types marking a peer connection coming up
+pub const CONNECT_EVENTS: &[EventType] = &[ConnectedIn, ConnectedOut];
+
+/// Event types marking a peer connection going down or failing to come up
+pub const DISCONNECT_EVENTS: &[EventType] = &[Disconnected, ConnectOutFailed, ConnectInFailed];
+
 /// Events where `directed_peer()` returns `is_outbound: true` (statically known).
 pub const OUTBOUND_EVENTS: &[EventType] = &[
     EventType::ConnectingOut,
@@ -1338,7 +1584,33 @@ pub fn event_name(et: EventType) -> &'static str {
         PreimageRequestReceived => "PreimageRequestReceived",
         PreimageTransferred => "PreimageTransferred",
         PreimageDiscarded => "PreimageDiscarded",
+        Unknown => "Unknown",
+    }
+}
+
+/// Parse a comma-separated list of event names (case-insensitive) or numeric ids,
+/// e.g. `PeerMisbehaved,Disconnected,11`. Duplicates are kept once, in list order.
+#[allow(dead_code)]
+pub fn parse_event_list(list: &str) -> Result<Vec<EventType>, String> {
+    let known: Vec<EventType> = (0..=u8::MAX).filter_map(EventType::from_u8).collect();
+    let mut types = Vec::new();
+    for item in list.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        let et = match item.parse::<u8>() {
+            Ok(id) => EventType::from_u8(id),
+            Err(_) => known.iter().copied().find(|&et| event_name(et).eq_ignore_ascii_case(item)),
+        };
+        let Some(et) = et else {
+            let names: Vec<&str> = known.iter().map(|&et| event_name(et)).collect();
+            return Err(format!("unknown event type '{}'; valid types: {}", item, names.join(", ")));
+        };
+        if !types.contains(&et) {
+            types.push(et);
+        }
     }
+    if types.is_empty() {
+        return Err("empty event list".to_string());
+    }
+    Ok(types)
 }
 
 /// Get color for event type (for visualization) as RGB tuple
@@ -1346,7 +1618,7 @@ pub fn event_name(et: EventType) -> &'static str {
 pub fn event_color_rgb(et: EventType) -> (u8, u8, u8) {
     let v = et as u8;
     match v {
-        0 => (128, 128, 128),       // Meta - gray
+        0 | 255 => (128, 128, 128), // Meta, unknown types - gray
         10..=13 => (100, 200, 100),  // Status - green
         20..=28 => (100, 150, 255),  // Connection - blue
         40..=47 => (255, 200, 100),  // Block auth - orange
@@ -1514,39 +1786,29 @@ impl Event {
             _ => None,
         }
     }
-
-
-    /// Get the default travel duration for this event type (in seconds).
-    /// Longer durations make motion visible at high event rates.
-    pub fn travel_duration(&self) -> f32 {
-        match self.event_type() {
-            // Fast events (guarantees, assurances)
-            EventType::SendingGuarantee
-            | EventType::ReceivingGuarantee
-            | EventType::AssuranceSent
-            | EventType::AssuranceReceived => 2.0,
-
-            // Medium events (shard requests, blocks)
-            EventType::SendingShardRequest
-            | EventType::ReceivingShardRequest
-            | EventType::SendingBlockRequest
-            | EventType::ReceivingBlockRequest => 2.0,
-
-            // Slow events (connections, work package sharing)
-            EventType::ConnectingOut
-            | EventType::ConnectedIn
-            | EventType::WorkPackageBeingShared => 3.0,
-
-            // Default (includes WorkPackageSubmission — pulse handles visual emphasis)
-            _ => 2.0,
-        }
-    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_peer_address_display() {
+        let mut mapped = [0u8; 16];
+        mapped[10..12].copy_from_slice(&[0xff, 0xff]);
+        mapped[12..].copy_from_slice(&[192, 168, 1, 20]);
+        let v4 = PeerAddress { ipv6: mapped, port: 30333 };
+        assert_eq!(v4.to_string(), "192.168.1.20:30333");
+
+        let v6 = PeerAddress { ipv6: "2001:db8::7".parse::<Ipv6Addr>().unwrap().octets(), port: 40000 };
+        assert_eq!(v6.to_string(), "[2001:db8::7]:40000");
+
+        let loopback = PeerAddress { ipv6: Ipv6Addr::LOCALHOST.octets(), port: 1 };
+        assert_eq!(loopback.to_string(), "[::1]:1");
+        mapped[12..].copy_from_slice(&[127, 0, 0, 1]);
+        assert_eq!(PeerAddress { ipv6: mapped, port: 9000 }.to_string(), "127.0.0.1:9000");
+    }
+
     #[test]
     fn test_event_type_from_u8_valid_ranges() {
         assert_eq!(EventType::from_u8(0), Some(EventType::Dropped));
@@ -1587,6 +1849,22 @@ mod tests {
         assert_eq!(event_name(EventType::Status), "Status");
         assert_eq!(event_name(EventType::SendingGuarantee), "SendingGuarantee");
         assert_eq!(event_name(EventType::PreimageDiscarded), "PreimageDiscarded");
+        assert_eq!(event_name(EventType::Unknown), "Unknown");
+    }
+
+    #[test]
+    fn test_parse_event_list() {
+        assert_eq!(
+            parse_event_list("PeerMisbehaved, disconnected,11,28"),
+            Ok(vec![EventType::PeerMisbehaved, EventType::Disconnected, EventType::BestBlockChanged])
+        );
+        let err = parse_event_list("PeerMisbehaved,Bogus").unwrap_err();
+        assert!(err.starts_with("unknown event type 'Bogus'"));
+        assert!(err.contains("BestBlockChanged"));
+        // Unknown is not a subscribable type, neither is an unassigned id
+        assert!(parse_event_list("Unknown").is_err());
+        assert!(parse_event_list("200").is_err());
+        assert!(parse_event_list(" , ").is_err());
     }
 
     #[test]
@@ -1595,6 +1873,7 @@ mod tests {
         assert_eq!(event_color_rgb(EventType::Status), (100, 200, 100));
         assert_eq!(event_color_rgb(EventType::ConnectionRefused), (100, 150, 255));
         assert_eq!(event_color_rgb(EventType::SendingGuarantee), (255, 100, 200));
+        assert_eq!(event_color_rgb(EventType::Unknown), (128, 128, 128));
     }
 
     #[test]
@@ -1638,13 +1917,21 @@ mod tests {
     #[test]
     fn test_travel_duration() {
         let slow = Event::ConnectingOut { timestamp: 0, to: PeerDetails { peer_id: [0u8; 32], peer_address: PeerAddress { ipv6: [0u8; 16], port: 0 } } };
-        assert_eq!(slow.travel_duration(), 3.0);
+        assert_eq!(slow.event_type().travel_duration(), 3.0);
 
         let medium = Event::SendingGuarantee { timestamp: 0, built_id: 0, recipient: [0u8; 32] };
-        assert_eq!(medium.travel_duration(), 2.0);
+        assert_eq!(medium.event_type().travel_duration(), 2.0);
 
         // Default case
         let default_event = Event::Authored { timestamp: 0, authoring_id: 0, outline: BlockOutline { size_bytes: 0, hash: [0u8; 32], num_tickets: 0, num_preimages: 0, total_preimages_size: 0, num_guarantees: 0, num_assurances: 0, num_dispute_verdicts: 0 } };
-        assert_eq!(default_event.travel_duration(), 2.0);
+        assert_eq!(default_event.event_type().travel_duration(), 2.0);
+    }
+
+    #[test]
+    fn test_default_travel_durations() {
+        let durations = default_travel_durations();
+        assert_eq!(durations[EventType::ConnectingOut.idx()], 3.0);
+        assert_eq!(durations[EventType::SendingGuarantee.idx()], 2.0);
+        assert_eq!(durations[EventType::Unknown.idx()], 2.0);
     }
 }
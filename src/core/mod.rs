@@ -3,8 +3,13 @@
 pub mod data;
 pub mod events;
 pub mod parser;
+pub mod scale;
 
-pub use data::{BestBlockData, EventStore, TimeSeriesData};
+pub use data::{
+    AssuranceData, BestBlockData, EventStore, Fork, GasSample, GasSource, GuaranteeQueueData, PreimageMetrics,
+    LatestStatus, LatestStatusData, NodeIndexing, PeerMetrics, ReasonGroup, ShardMetrics, SyncStatusData, TicketData, TimeSeriesData,
+    EpochTickets, TopologyData, SYNC_STALE_SECS,
+};
 #[allow(unused_imports)]
-pub use events::{Event, EventType, EVENT_CATEGORIES, event_color_rgb, event_name, OUTBOUND_EVENTS, INBOUND_EVENTS, BIDIR_EVENTS};
-pub use parser::{parse_event, ParseResult, ParserContext};
+pub use events::{Event, EventType, PeerAddress, EVENT_CATEGORIES, event_color_rgb, event_name, parse_event_list, OUTBOUND_EVENTS, INBOUND_EVENTS, BIDIR_EVENTS};
+pub use parser::{parse_message, Codec, ParseError, ParseResult, ParserContext};
@@ -1,14 +1,21 @@
 //! Event parser for jamtart WebSocket messages
 //!
-//! Parses all JIP-3 events and stores them in EventStore.
+//! Parses all JIP-3 events and stores them in EventStore. Messages arrive either as
+//! JSON text or as SCALE-encoded binary frames (see [`ScaleFrame`]).
 //! Special handling for Status, BestBlockChanged, FinalizedBlockChanged.
 //! Directed events populate the vring visualization buffers.
 
-use super::{BestBlockData, Event, EventStore, TimeSeriesData};
-use super::events::EventType;
+use super::{
+    AssuranceData, BestBlockData, Event, EventStore, GuaranteeQueueData, LatestStatus,
+    LatestStatusData, PeerMetrics, PreimageMetrics, ShardMetrics, SyncStatusData, TicketData,
+    TimeSeriesData, TopologyData,
+};
+use super::events::{event_name, ConnectionSide, EventType, TravelDurations, EVENT_CATEGORIES};
+use super::scale::ScaleFrame;
 use crate::vring::{DirectedEventBuffer, DirectedParticleInstance, PulseEvent};
 use serde_json::Value;
-use tracing::{trace, warn};
+use std::borrow::Cow;
+use tracing::trace;
 
 /// Mutable references to all data stores updated during event parsing.
 ///
@@ -19,77 +26,298 @@ pub struct ParserContext<'a> {
     pub time_series: &'a mut TimeSeriesData,
     /// Best and finalized block slots per validator.
     pub blocks: &'a mut BestBlockData,
+    /// Per-core guarantee queue depth per validator. Fed by Status events.
+    pub guarantee_queues: &'a mut GuaranteeQueueData,
+    /// Latest sync state per validator. Fed by SyncStatusChanged, refreshed by Status.
+    pub sync_status: &'a mut SyncStatusData,
+    /// Validator and sync peer count series per validator. Fed by Status events.
+    pub peers: &'a mut PeerMetrics,
+    /// Shard count and size series per validator. Fed by Status events.
+    pub shards: &'a mut ShardMetrics,
+    /// Preimage count and size series per validator. Fed by Status events.
+    pub preimages: &'a mut PreimageMetrics,
     /// Central event repository and node_id → ring-index registry.
     pub events: &'a mut EventStore,
     /// GPU particle buffer for directed (node→node) events on the ring.
     pub directed_buffer: &'a mut DirectedEventBuffer,
-    /// Collapsing pulse overlay events (Authoring, WorkPackageSubmission).
+    /// Collapsing pulse overlay events, one per event of a type in `pulse_types`.
     pub pulse_events: &'a mut Vec<PulseEvent>,
+    /// Latest availability bitfield per validator. Fed by DistributingAssurance events.
+    pub assurances: &'a mut AssuranceData,
+    /// Every field of the latest Status per validator. Fed by Status events.
+    pub latest_status: &'a mut LatestStatusData,
+    /// Safrole ticket counts per epoch. Fed by the ticket events.
+    pub tickets: &'a mut TicketData,
+    /// Live peer connections between nodes. Fed by the connection events.
+    pub topology: &'a mut TopologyData,
+    /// Directed-particle travel duration per event type (settings overrides included).
+    pub travel_durations: &'a TravelDurations,
+    /// Event types that emit a collapsing pulse at the reporting node (settings).
+    /// Non-directed events of these types get no radial particle.
+    pub pulse_types: &'a [EventType],
+    /// Tag of the endpoint the message came from when several are connected; node ids
+    /// are namespaced with it (see [`namespaced_node_id`]) so two networks don't collide.
+    pub source: Option<&'a str>,
+    /// Provenance id stamped on stored events and particles: the endpoint's position
+    /// (1-based) when `source` is set, 0 otherwise.
+    pub source_id: u8,
+    /// Only events of these types are stored (`--events` when headless); `None` keeps all.
+    /// Skipped events still come back in the [`ParseResult`] so callers can tell them apart.
+    pub stored_types: Option<&'a [EventType]>,
+}
+
+/// `node_id@source` when a source tag is given, `node_id` unchanged otherwise
+pub fn namespaced_node_id<'a>(node_id: &'a str, source: Option<&str>) -> Cow<'a, str> {
+    match source {
+        Some(source) => Cow::Owned(format!("{}@{}", node_id, source)),
+        None => Cow::Borrowed(node_id),
+    }
+}
+
+/// Split a possibly namespaced node id into the jamtart id and the source tag
+pub fn split_source(node_id: &str) -> (&str, Option<&str>) {
+    match node_id.split_once('@') {
+        Some((id, source)) => (id, Some(source)),
+        None => (node_id, None),
+    }
+}
+
+/// Wire encoding of a message, given by the WebSocket frame type it arrived in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Codec {
+    /// JSON text (`{"type": "event", ...}`), sent in text frames
+    Json,
+    /// SCALE-encoded [`ScaleFrame`], sent in binary frames
+    Scale,
 }
 
 /// Result from parsing a single event message.
 pub struct ParseResult {
     /// Global event ID from jamtart (`data.id`), used for gap detection.
     pub event_id: Option<u64>,
+    /// Codec the event was decoded with.
+    pub codec: Codec,
+    /// Events the node itself reported dropping (`Event::Dropped.num`), 0 for other events.
+    pub node_dropped: u64,
+    /// Type of the parsed event.
+    pub event_type: EventType,
+    /// Ring index of the reporting node.
+    pub node_index: Option<u16>,
+    /// The reporting node, namespaced like the stores key it (see [`namespaced_node_id`]).
+    pub node_id: String,
+    /// A known event type whose payload did not decode; only its type, timestamp and id
+    /// were kept (the stored event is an [`Event::Unknown`] of that type).
+    pub partial: bool,
 }
 
-/// Parse a WebSocket message and update data structures.
+/// Reason a message could not be turned into an event, with the decoder's own error text.
+/// Only built on failure, so the happy path does not allocate for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// Message is not valid JSON (or not UTF-8).
+    InvalidJson(String),
+    /// `event` message without a `data.node_id` string.
+    MissingNodeId,
+    /// `data.event` does not decode into an [`Event`] and names no event type.
+    InvalidEvent(String),
+    /// Binary frame is not a valid SCALE-encoded [`ScaleFrame`].
+    InvalidScale(String),
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::InvalidJson(e) => write!(f, "invalid JSON: {}", e),
+            ParseError::MissingNodeId => write!(f, "missing data.node_id"),
+            ParseError::InvalidEvent(e) => write!(f, "invalid event: {}", e),
+            ParseError::InvalidScale(e) => write!(f, "invalid SCALE frame: {}", e),
+        }
+    }
+}
+
+/// Parse a message of either codec. The codec comes from the frame type, never from the
+/// payload: a SCALE frame may well start with `{`.
+pub fn parse_message(
+    msg: &[u8],
+    codec: Codec,
+    ctx: &mut ParserContext,
+    now: f64,
+) -> Result<Option<ParseResult>, ParseError> {
+    match codec {
+        Codec::Json => {
+            let text =
+                std::str::from_utf8(msg).map_err(|e| ParseError::InvalidJson(e.to_string()))?;
+            parse_event(text, ctx, now)
+        }
+        Codec::Scale => parse_scale_event(msg, ctx, now).map(Some),
+    }
+}
+
+/// Parse a SCALE-encoded binary frame and update data structures.
+pub fn parse_scale_event(
+    msg: &[u8],
+    ctx: &mut ParserContext,
+    now: f64,
+) -> Result<ParseResult, ParseError> {
+    trace!(len = msg.len(), "Parsing SCALE frame");
+
+    let frame = ScaleFrame::decode_frame(msg).map_err(|e| {
+        trace!(error = %e, "Failed to decode SCALE frame");
+        ParseError::InvalidScale(e.to_string())
+    })?;
+    let node_id = hex::encode(frame.node_id);
+    let node_id = namespaced_node_id(&node_id, ctx.source);
+    let node_dropped = node_dropped(&frame.event);
+    let event_type = frame.event.event_type();
+    if ctx.stores(event_type) {
+        apply_event(&node_id, frame.event, ctx, now);
+    }
+
+    Ok(ParseResult {
+        event_id: Some(frame.id),
+        codec: Codec::Scale,
+        node_dropped,
+        event_type,
+        node_index: ctx.events.node_index(&node_id),
+        node_id: node_id.into_owned(),
+        partial: false,
+    })
+}
+
+/// Parse a JSON WebSocket message and update data structures.
 ///
-/// Returns `Some(ParseResult)` if an event was successfully parsed, `None` otherwise.
-pub fn parse_event(msg: &str, ctx: &mut ParserContext, now: f64) -> Option<ParseResult> {
+/// Returns `Ok(Some(ParseResult))` if an event was parsed, `Ok(None)` for
+/// non-event messages ("connected", "subscribed", "stats"), and `Err` for
+/// messages that could not be decoded.
+pub fn parse_event(
+    msg: &str,
+    ctx: &mut ParserContext,
+    now: f64,
+) -> Result<Option<ParseResult>, ParseError> {
     trace!(len = msg.len(), "Parsing message");
 
-    let json: Value = serde_json::from_str(msg)
-        .map_err(|e| {
-            warn!(error = %e, "Failed to parse JSON");
-        })
-        .ok()?;
+    let json: Value = serde_json::from_str(msg).map_err(|e| {
+        trace!(error = %e, "Failed to parse JSON");
+        ParseError::InvalidJson(e.to_string())
+    })?;
 
     // Only process "event" type messages
-    let msg_type = json["type"].as_str()?;
+    let Some(msg_type) = json["type"].as_str() else {
+        return Ok(None);
+    };
     if msg_type != "event" {
         // Not an event (could be "connected", "subscribed", "stats")
-        return None;
+        return Ok(None);
     }
 
     let event_id = json["data"]["id"].as_u64();
-    let node_id = json["data"]["node_id"].as_str()?;
+    let node_id = json["data"]["node_id"]
+        .as_str()
+        .ok_or(ParseError::MissingNodeId)?;
+    let node_id = namespaced_node_id(node_id, ctx.source);
 
-    // Parse the full Event enum from the "event" field
+    // Parse the full Event enum from the "event" field.
+    // If that fails, keep what every event has so it still counts toward rates and gaps
     let event_json = &json["data"]["event"];
-    let event: Event = serde_json::from_value(event_json.clone())
-        .map_err(|e| {
-            trace!(error = %e, "Failed to parse Event enum");
-        })
-        .ok()?;
+    let (event, partial): (Event, bool) = match serde_json::from_value(event_json.clone()) {
+        Ok(event) => (event, false),
+        Err(e) => {
+            let event = undecoded_event(&json["data"]).ok_or_else(|| {
+                trace!(error = %e, "Failed to parse Event enum");
+                ParseError::InvalidEvent(e.to_string())
+            })?;
+            let partial = event.event_type() != EventType::Unknown;
+            if partial {
+                trace!(error = %e, event_type = ?event.event_type(), "Partially parsed event");
+            }
+            (event, partial)
+        }
+    };
 
-    // Store full event for all visualizations
-    ctx.events.push(node_id, event.clone(), now);
+    let node_dropped = node_dropped(&event);
+    let event_type = event.event_type();
+    if ctx.stores(event_type) {
+        apply_event(&node_id, event, ctx, now);
+    }
 
-    // Emit collapsing-pulse for Authoring and WorkPackageSubmission
-    match event.event_type() {
-        EventType::Authoring | EventType::WorkPackageSubmission => {
-            if let Some(node_index) = ctx.events.node_index(node_id) {
-                trace!(
-                    event_type = ?event.event_type(),
-                    node_id = &node_id[..8],
-                    node_index,
-                    "PULSE emitted"
-                );
-                ctx.pulse_events.push(PulseEvent {
-                    node_index,
-                    event_type: event.event_type(),
-                    birth_time: now as f32,
-                });
-            }
+    Ok(Some(ParseResult {
+        event_id,
+        codec: Codec::Json,
+        node_dropped,
+        event_type,
+        node_index: ctx.events.node_index(&node_id),
+        node_id: node_id.into_owned(),
+        partial,
+    }))
+}
+
+impl ParserContext<'_> {
+    /// Whether events of `event_type` pass [`stored_types`](Self::stored_types)
+    fn stores(&self, event_type: EventType) -> bool {
+        self.stored_types.is_none_or(|types| types.contains(&event_type))
+    }
+}
+
+/// Number of events a `Dropped` report says the node lost
+fn node_dropped(event: &Event) -> u64 {
+    match event {
+        Event::Dropped { num, .. } => *num,
+        _ => 0,
+    }
+}
+
+/// [`Event::Unknown`] for an event that did not decode: a type added in a newer jamtart, or a
+/// known type with new or renamed fields. The type comes from `data.event_type`, or else from
+/// the variant name; `None` if neither identifies one.
+fn undecoded_event(data: &Value) -> Option<Event> {
+    // `{"Variant": {"timestamp": ..., ...}}`
+    let (name, fields) = data["event"].as_object().and_then(|variant| variant.iter().next())?;
+    let type_id = match data["event_type"].as_u64() {
+        Some(id) => u8::try_from(id).ok()?,
+        None => event_type_by_name(name)? as u8,
+    };
+    let timestamp = fields["timestamp"].as_u64().unwrap_or(0);
+    Some(Event::Unknown { type_id, timestamp })
+}
+
+/// Known event type with the serde variant name `name`
+fn event_type_by_name(name: &str) -> Option<EventType> {
+    EVENT_CATEGORIES
+        .iter()
+        .flat_map(|category| category.event_types)
+        .copied()
+        .find(|&et| et != EventType::Unknown && event_name(et) == name)
+}
+
+/// Store a decoded event and feed the derived visualization buffers.
+fn apply_event(node_id: &str, event: Event, ctx: &mut ParserContext, now: f64) {
+    // Store full event for all visualizations
+    ctx.events.push_from(node_id, event.clone(), now, ctx.source_id);
+
+    // Emit collapsing-pulse for the configured pulse types
+    let pulsed = ctx.pulse_types.contains(&event.event_type());
+    if pulsed {
+        if let Some(node_index) = ctx.events.node_index(node_id) {
+            trace!(
+                event_type = ?event.event_type(),
+                node_id = &node_id[..8],
+                node_index,
+                "PULSE emitted"
+            );
+            ctx.pulse_events.push(PulseEvent {
+                node_index,
+                event_type: event.event_type(),
+                birth_time: now as f32,
+            });
         }
-        _ => {}
     }
 
     // Handle directed events for vring visualization
     if let Some(directed) = event.directed_peer() {
-        // Resolve peer_id to node_id via hex encoding (jamtart uses hex::encode(peer_id) as node_id)
-        let peer_node_id = hex::encode(directed.peer_id);
+        // Resolve peer_id to node_id via hex encoding (jamtart uses hex::encode(peer_id) as node_id);
+        // the peer is on the same network as the emitter
+        let peer_hex = hex::encode(directed.peer_id);
+        let peer_node_id = namespaced_node_id(&peer_hex, ctx.source);
         if let Some(peer_index) = ctx.events.node_index(&peer_node_id) {
             if let Some(node_index) = ctx.events.node_index(node_id) {
                 let (source, target) = if directed.is_outbound {
@@ -126,20 +354,18 @@ pub fn parse_event(msg: &str, ctx: &mut ParserContext, now: f64) -> Option<Parse
                     source,
                     target,
                     now as f32,
-                    event.travel_duration(),
+                    ctx.travel_durations[event.event_type().idx()],
                     event.event_type() as u8,
                     curve_seed,
-                );
+                    directed.is_outbound,
+                )
+                .with_source(ctx.source_id);
                 ctx.directed_buffer.push(particle);
             }
         }
     } else {
         // Non-directed event: radial particle (source == target = radial sentinel)
-        let dominated_by_pulse = matches!(
-            event.event_type(),
-            EventType::Authoring | EventType::WorkPackageSubmission
-        );
-        if !dominated_by_pulse {
+        if !pulsed {
             if let Some(node_index) = ctx.events.node_index(node_id) {
                 let particle = DirectedParticleInstance::new(
                     node_index,
@@ -148,7 +374,9 @@ pub fn parse_event(msg: &str, ctx: &mut ParserContext, now: f64) -> Option<Parse
                     1.0, // fixed 1.0s
                     event.event_type() as u8,
                     0.0, // no curve
-                );
+                    false,
+                )
+                .with_source(ctx.source_id);
                 ctx.directed_buffer.push(particle);
             }
         }
@@ -159,41 +387,108 @@ pub fn parse_event(msg: &str, ctx: &mut ParserContext, now: f64) -> Option<Parse
         Event::Dropped { num, .. } => {
             trace!(node_id, num, "Dropped event");
         }
-        Event::Status { num_peers, .. } => {
+        Event::Status {
+            num_peers,
+            num_val_peers,
+            num_sync_peers,
+            num_guarantees,
+            num_shards,
+            shards_size,
+            num_preimages,
+            preimages_size,
+            ..
+        } => {
             trace!(node_id, num_peers, "Status event");
             ctx.time_series.push(node_id, *num_peers as f32);
+            ctx.peers.push(node_id, *num_val_peers, *num_sync_peers);
+            ctx.shards.push(node_id, *num_shards, *shards_size);
+            ctx.preimages.push(node_id, *num_preimages, *preimages_size);
+            ctx.guarantee_queues.set(node_id, num_guarantees);
+            ctx.sync_status.touch(node_id, now);
+            ctx.latest_status.set(
+                node_id,
+                LatestStatus {
+                    num_peers: *num_peers,
+                    num_val_peers: *num_val_peers,
+                    num_sync_peers: *num_sync_peers,
+                    num_shards: *num_shards,
+                    shards_size: *shards_size,
+                    num_preimages: *num_preimages,
+                    preimages_size: *preimages_size,
+                    updated_at: now,
+                },
+            );
         }
-        Event::BestBlockChanged { slot, .. } => {
+        Event::BestBlockChanged { slot, hash, .. } => {
             trace!(node_id, slot, "BestBlockChanged event");
-            ctx.blocks.set_best(node_id, *slot as u64);
+            ctx.blocks.set_best(node_id, *slot as u64, *hash);
+            ctx.blocks.block_times.record(now, *slot as u64);
         }
         Event::FinalizedBlockChanged { slot, .. } => {
             trace!(node_id, slot, "FinalizedBlockChanged event");
             ctx.blocks.set_finalized(node_id, *slot as u64);
         }
-        Event::SyncStatusChanged { .. } => {
-            // Stored in EventStore, no special handling needed
+        Event::SyncStatusChanged { synced, .. } => {
+            trace!(node_id, synced, "SyncStatusChanged event");
+            ctx.sync_status.set(node_id, *synced, now);
+        }
+        Event::GeneratingTickets { epoch, .. } => {
+            trace!(node_id, epoch, "GeneratingTickets event");
+            ctx.tickets.record_generating(node_id, *epoch);
+        }
+        Event::TicketsGenerated { ids, .. } => {
+            ctx.tickets.record_generated(node_id, ids.len() as u32, false);
+        }
+        Event::TicketGenerationFailed { .. } => {
+            ctx.tickets.record_generated(node_id, 0, true);
+        }
+        Event::TicketTransferred { epoch, sender: ConnectionSide::Local, .. } => {
+            ctx.tickets.record_transferred(*epoch);
+        }
+        Event::TicketTransferFailed { .. } => {
+            ctx.tickets.record_transfer_failed();
+        }
+        // ConnectedOut names no peer; the other end's ConnectedIn records the connection
+        Event::ConnectedIn { peer_id, .. } => {
+            let peer_hex = hex::encode(peer_id);
+            ctx.topology.connect(node_id, &namespaced_node_id(&peer_hex, ctx.source));
+        }
+        Event::Disconnected { peer, .. } => {
+            let peer_hex = hex::encode(peer);
+            ctx.topology.disconnect(node_id, &namespaced_node_id(&peer_hex, ctx.source));
+        }
+        Event::DistributingAssurance { statement, .. } => {
+            trace!(node_id, bytes = statement.bitfield.len(), "DistributingAssurance event");
+            ctx.assurances.set(node_id, &statement.bitfield);
         }
         _ => {
             // Other events stored but not specially handled
             trace!(event_type = ?event.event_type(), "Event stored");
         }
     }
-
-    Some(ParseResult { event_id })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    fn make_test_ctx() -> (TimeSeriesData, BestBlockData, EventStore, DirectedEventBuffer, Vec<PulseEvent>) {
+    #[allow(clippy::type_complexity)]
+    fn make_test_ctx() -> (TimeSeriesData, BestBlockData, GuaranteeQueueData, SyncStatusData, PeerMetrics, ShardMetrics, PreimageMetrics, EventStore, DirectedEventBuffer, Vec<PulseEvent>, AssuranceData, LatestStatusData, TicketData, TopologyData) {
         (
             TimeSeriesData::new(10, 100),
             BestBlockData::new(10),
+            GuaranteeQueueData::new(10),
+            SyncStatusData::new(),
+            PeerMetrics::new(10, 100),
+            ShardMetrics::new(10, 100),
+            PreimageMetrics::new(10, 100),
             EventStore::new(100, 60.0),
             DirectedEventBuffer::default(),
             Vec::new(),
+            AssuranceData::new(),
+            LatestStatusData::new(),
+            TicketData::new(),
+            TopologyData::new(),
         )
     }
 
@@ -201,20 +496,35 @@ mod tests {
     /// Stores must be declared as `let (mut ts, mut blocks, ...)` and this
     /// macro borrows them all mutably into a ParserContext.
     macro_rules! ctx {
-        ($ts:expr, $blocks:expr, $events:expr, $db:expr, $pe:expr) => {
+        ($ts:expr, $blocks:expr, $gq:expr, $sync:expr, $peers:expr, $shards:expr, $preimages:expr, $events:expr, $db:expr, $pe:expr, $av:expr, $ls:expr, $tk:expr, $topo:expr) => {
             ParserContext {
                 time_series: &mut $ts,
                 blocks: &mut $blocks,
+                guarantee_queues: &mut $gq,
+                sync_status: &mut $sync,
+                peers: &mut $peers,
+                shards: &mut $shards,
+                preimages: &mut $preimages,
                 events: &mut $events,
                 directed_buffer: &mut $db,
                 pulse_events: &mut $pe,
+                assurances: &mut $av,
+                latest_status: &mut $ls,
+                tickets: &mut $tk,
+                topology: &mut $topo,
+                travel_durations: &crate::core::events::default_travel_durations(),
+                pulse_types: &[EventType::Authoring, EventType::WorkPackageSubmission],
+                source: None,
+                source_id: 0,
+                stored_types: None,
             }
         };
     }
 
     #[test]
     fn test_parse_status_event() {
-        let (mut ts, mut blocks, mut events, mut db, mut pe) = make_test_ctx();
+        let (mut ts, mut blocks, mut gq, mut sync, mut peers, mut shards, mut preimages, mut events, mut db, mut pe, mut av, mut ls, mut tk, mut topo) =
+            make_test_ctx();
 
         let msg = r#"{
             "type": "event",
@@ -224,11 +534,11 @@ mod tests {
                         "num_peers": 42,
                         "num_val_peers": 2,
                         "num_sync_peers": 1,
-                        "num_guarantees": [],
-                        "num_shards": 0,
-                        "shards_size": 0,
-                        "num_preimages": 0,
-                        "preimages_size": 0,
+                        "num_guarantees": [0, 3],
+                        "num_shards": 12,
+                        "shards_size": 4096,
+                        "num_preimages": 3,
+                        "preimages_size": 900,
                         "timestamp": 12345
                     }
                 },
@@ -237,15 +547,26 @@ mod tests {
             }
         }"#;
 
-        let result = parse_event(msg, &mut ctx!(ts, blocks, events, db, pe), 0.0);
-        assert!(result.is_some());
+        let result = parse_event(msg, &mut ctx!(ts, blocks, gq, sync, peers, shards, preimages, events, db, pe, av, ls, tk, topo), 0.0);
+        assert!(matches!(result, Ok(Some(_))));
         assert!(ts.latest_value("abc123").is_some());
+        assert_eq!(gq.aggregate_per_core(), vec![0, 3]);
+        assert_eq!(peers.val_peers.latest_value("abc123"), Some(2.0));
+        assert_eq!(peers.sync_peers.latest_value("abc123"), Some(1.0));
+        assert_eq!(shards.shard_counts.latest_value("abc123"), Some(12.0));
+        assert_eq!(shards.shard_sizes.latest_value("abc123"), Some(4096.0));
+        assert_eq!(preimages.preimage_counts.latest_value("abc123"), Some(3.0));
+        assert_eq!(preimages.preimage_sizes.latest_value("abc123"), Some(900.0));
+        let status = ls.entries()[0].1;
+        assert_eq!((status.num_peers, status.num_val_peers, status.num_sync_peers), (42, 2, 1));
+        assert_eq!((status.num_shards, status.shards_size), (12, 4096));
         assert_eq!(events.node_count(), 1);
     }
 
     #[test]
     fn test_parse_best_block_event() {
-        let (mut ts, mut blocks, mut events, mut db, mut pe) = make_test_ctx();
+        let (mut ts, mut blocks, mut gq, mut sync, mut peers, mut shards, mut preimages, mut events, mut db, mut pe, mut av, mut ls, mut tk, mut topo) =
+            make_test_ctx();
 
         let msg = r#"{
             "type": "event",
@@ -262,25 +583,158 @@ mod tests {
             }
         }"#;
 
-        let result = parse_event(msg, &mut ctx!(ts, blocks, events, db, pe), 0.0);
-        assert!(result.is_some());
+        let result = parse_event(msg, &mut ctx!(ts, blocks, gq, sync, peers, shards, preimages, events, db, pe, av, ls, tk, topo), 0.0);
+        assert!(matches!(result, Ok(Some(_))));
         assert_eq!(blocks.highest_slot(), Some(5662737));
         assert_eq!(events.node_count(), 1);
     }
 
+    #[test]
+    fn test_source_namespaces_node_ids() {
+        let (mut ts, mut blocks, mut gq, mut sync, mut peers, mut shards, mut preimages, mut events, mut db, mut pe, mut av, mut ls, mut tk, mut topo) =
+            make_test_ctx();
+
+        let msg = r#"{"type": "event", "data": {"event": {"BestBlockChanged": {
+            "slot": 7, "hash": [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+            "timestamp": 1}}, "event_type": 11, "node_id": "abc123"}}"#;
+        for (source_id, source) in [(1, "s1"), (2, "s2")] {
+            let mut ctx = ctx!(ts, blocks, gq, sync, peers, shards, preimages, events, db, pe, av, ls, tk, topo);
+            ctx.source = Some(source);
+            ctx.source_id = source_id;
+            assert!(matches!(parse_event(msg, &mut ctx, 0.0), Ok(Some(_))));
+        }
+        // The same id on two endpoints is two nodes
+        assert_eq!(events.node_count(), 2);
+        assert!(events.node_index("abc123@s2").is_some());
+        assert!(events.node_index("abc123").is_none());
+        // Stored events and particles keep their provenance
+        for (node_id, node) in events.nodes() {
+            let expected = if node_id.ends_with("@s1") { 1 } else { 2 };
+            assert!(node.by_type.values().flatten().all(|e| e.source_id == expected));
+        }
+        let mut particle_sources: Vec<f32> =
+            db.get_active_particles(1.0, 5.0, None).iter().map(|p| p.source_id).collect();
+        particle_sources.sort_by(f32::total_cmp);
+        assert_eq!(particle_sources, vec![1.0, 2.0]);
+        assert_eq!(split_source("abc123@s2"), ("abc123", Some("s2")));
+        assert_eq!(split_source("abc123"), ("abc123", None));
+    }
+
+    #[test]
+    fn test_parse_distributing_assurance() {
+        let (mut ts, mut blocks, mut gq, mut sync, mut peers, mut shards, mut preimages, mut events, mut db, mut pe, mut av, mut ls, mut tk, mut topo) =
+            make_test_ctx();
+
+        let msg = r#"{
+            "type": "event",
+            "data": {
+                "event": {
+                    "DistributingAssurance": {
+                        "statement": {
+                            "anchor": [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+                            "bitfield": [5]
+                        },
+                        "timestamp": 12345
+                    }
+                },
+                "event_type": 126,
+                "node_id": "abc123"
+            }
+        }"#;
+
+        let result = parse_event(msg, &mut ctx!(ts, blocks, gq, sync, peers, shards, preimages, events, db, pe, av, ls, tk, topo), 0.0);
+        assert!(matches!(result, Ok(Some(_))));
+        assert_eq!(av.rows(3), vec![("abc123", vec![true, false, true])]);
+    }
+
+    #[test]
+    fn test_parse_sync_status_event() {
+        let (mut ts, mut blocks, mut gq, mut sync, mut peers, mut shards, mut preimages, mut events, mut db, mut pe, mut av, mut ls, mut tk, mut topo) =
+            make_test_ctx();
+
+        let msg = r#"{
+            "type": "event",
+            "data": {
+                "event": { "SyncStatusChanged": { "synced": true, "timestamp": 12345 } },
+                "event_type": 13,
+                "node_id": "abc123"
+            }
+        }"#;
+
+        let result = parse_event(msg, &mut ctx!(ts, blocks, gq, sync, peers, shards, preimages, events, db, pe, av, ls, tk, topo), 3.0);
+        assert!(matches!(result, Ok(Some(_))));
+        assert_eq!(sync.synced_count(), (1, 1));
+        assert_eq!(sync.entries(3.0), vec![("abc123", true, false)]);
+    }
+
+    #[test]
+    fn test_parse_message_follows_codec() {
+        use parity_scale_codec::Encode;
+
+        let (mut ts, mut blocks, mut gq, mut sync, mut peers, mut shards, mut preimages, mut events, mut db, mut pe, mut av, mut ls, mut tk, mut topo) =
+            make_test_ctx();
+
+        let frame = ScaleFrame {
+            id: 7,
+            node_id: [0xab; 32],
+            event: Event::BestBlockChanged { timestamp: 1, slot: 99, hash: [0; 32] },
+        };
+        let result = parse_message(&frame.encode(), Codec::Scale, &mut ctx!(ts, blocks, gq, sync, peers, shards, preimages, events, db, pe, av, ls, tk, topo), 0.0)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.codec, Codec::Scale);
+        assert_eq!(result.event_id, Some(7));
+        assert_eq!(blocks.highest_slot(), Some(99));
+        assert!(events.node_index(&"ab".repeat(32)).is_some());
+
+        // Event id 0x7b encodes to a leading `{`; still a SCALE frame
+        let frame = ScaleFrame { id: 0x7b, ..frame };
+        let bytes = frame.encode();
+        assert_eq!(bytes[0], b'{');
+        let result = parse_message(&bytes, Codec::Scale, &mut ctx!(ts, blocks, gq, sync, peers, shards, preimages, events, db, pe, av, ls, tk, topo), 0.0)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.event_id, Some(0x7b));
+
+        let json = br#" {"type": "connected", "data": {}}"#;
+        let result = parse_message(json, Codec::Json, &mut ctx!(ts, blocks, gq, sync, peers, shards, preimages, events, db, pe, av, ls, tk, topo), 0.0);
+        assert!(matches!(result, Ok(None)));
+
+        let result = parse_message(&[0xff, 0x01], Codec::Scale, &mut ctx!(ts, blocks, gq, sync, peers, shards, preimages, events, db, pe, av, ls, tk, topo), 0.0);
+        assert!(matches!(result, Err(ParseError::InvalidScale(_))));
+    }
+
+    #[test]
+    fn test_parse_dropped_reports_count() {
+        let (mut ts, mut blocks, mut gq, mut sync, mut peers, mut shards, mut preimages, mut events, mut db, mut pe, mut av, mut ls, mut tk, mut topo) =
+            make_test_ctx();
+
+        let msg = r#"{"type":"event","data":{"id":7,"node_id":"abc123","event":{"Dropped":{"timestamp":1,"last_timestamp":2,"num":13}}}}"#;
+        let result = parse_event(msg, &mut ctx!(ts, blocks, gq, sync, peers, shards, preimages, events, db, pe, av, ls, tk, topo), 0.0).unwrap().unwrap();
+        assert_eq!(result.node_dropped, 13);
+        assert_eq!(result.event_type, EventType::Dropped);
+        assert_eq!(result.node_index, Some(0));
+
+        let msg = r#"{"type":"event","data":{"node_id":"abc123","event":{"SyncStatusChanged":{"timestamp":3,"synced":true}}}}"#;
+        let result = parse_event(msg, &mut ctx!(ts, blocks, gq, sync, peers, shards, preimages, events, db, pe, av, ls, tk, topo), 0.0).unwrap().unwrap();
+        assert_eq!(result.node_dropped, 0);
+    }
+
     #[test]
     fn test_ignore_non_event() {
-        let (mut ts, mut blocks, mut events, mut db, mut pe) = make_test_ctx();
+        let (mut ts, mut blocks, mut gq, mut sync, mut peers, mut shards, mut preimages, mut events, mut db, mut pe, mut av, mut ls, mut tk, mut topo) =
+            make_test_ctx();
 
         let msg = r#"{"type": "connected", "data": {"message": "hello"}}"#;
 
-        let result = parse_event(msg, &mut ctx!(ts, blocks, events, db, pe), 0.0);
-        assert!(result.is_none());
+        let result = parse_event(msg, &mut ctx!(ts, blocks, gq, sync, peers, shards, preimages, events, db, pe, av, ls, tk, topo), 0.0);
+        assert!(matches!(result, Ok(None)));
     }
 
     #[test]
     fn test_parse_directed_event() {
-        let (mut ts, mut blocks, mut events, mut db, mut pe) = make_test_ctx();
+        let (mut ts, mut blocks, mut gq, mut sync, mut peers, mut shards, mut preimages, mut events, mut db, mut pe, mut av, mut ls, mut tk, mut topo) =
+            make_test_ctx();
 
         // The recipient peer_id [1,2,3,...,32] hex-encodes to this node_id.
         // We must pre-register this node in EventStore so the parser can resolve it.
@@ -300,7 +754,7 @@ mod tests {
                 "node_id": "{}"
             }}
         }}"#, recipient_node_id);
-        parse_event(&dummy_msg, &mut ctx!(ts, blocks, events, db, pe), 0.0);
+        parse_event(&dummy_msg, &mut ctx!(ts, blocks, gq, sync, peers, shards, preimages, events, db, pe, av, ls, tk, topo), 0.0).unwrap();
 
         // SendingGuarantee is a directed event (outbound to recipient peer)
         let msg = r#"{
@@ -318,14 +772,14 @@ mod tests {
             }
         }"#;
 
-        let result = parse_event(msg, &mut ctx!(ts, blocks, events, db, pe), 1.5);
-        assert!(result.is_some());
+        let result = parse_event(msg, &mut ctx!(ts, blocks, gq, sync, peers, shards, preimages, events, db, pe, av, ls, tk, topo), 1.5);
+        assert!(matches!(result, Ok(Some(_))));
 
         // 2 particles: 1 radial from dummy Status + 1 directed from SendingGuarantee
         assert_eq!(db.len(), 2);
 
         // Verify directed particle properties (find by event_type 106)
-        let particles = db.get_active_particles(2.0, 5.0);
+        let particles = db.get_active_particles(2.0, 5.0, None);
         let p = particles.iter().find(|p| p.event_type == 106.0).expect("directed particle");
         assert_eq!(p.source_index, 1.0); // node_abc = second node registered (index 1)
         assert_eq!(p.target_index, 0.0); // recipient = first node registered (index 0)
@@ -335,4 +789,99 @@ mod tests {
         let r = particles.iter().find(|p| p.event_type == 10.0).expect("radial particle");
         assert_eq!(r.source_index, r.target_index); // radial sentinel
     }
+
+    #[test]
+    fn test_connection_events_update_topology() {
+        let (mut ts, mut blocks, mut gq, mut sync, mut peers, mut shards, mut preimages, mut events, mut db, mut pe, mut av, mut ls, mut tk, mut topo) =
+            make_test_ctx();
+        let peer = format!("{:?}", [7u8; 32]).replace(' ', "");
+        let peer_node_id = hex::encode([7u8; 32]);
+
+        // An outbound dial names its peer only in ConnectingOut: no connection from it
+        let messages = [
+            format!(r#"{{"ConnectingOut":{{"timestamp":1,"to":{{"peer_id":{},"peer_address":{{"ipv6":{:?},"port":1}}}}}}}}"#, peer, [0u8; 16]),
+            r#"{"ConnectedOut":{"timestamp":2,"connecting_id":0}}"#.to_string(),
+        ];
+        for event in &messages {
+            let msg = format!(r#"{{"type":"event","data":{{"node_id":"abc123","event":{}}}}}"#, event);
+            parse_event(&msg, &mut ctx!(ts, blocks, gq, sync, peers, shards, preimages, events, db, pe, av, ls, tk, topo), 0.0).unwrap();
+        }
+        assert_eq!(topo.degree("abc123"), 0);
+
+        let msg = format!(
+            r#"{{"type":"event","data":{{"node_id":"abc123","event":{{"ConnectedIn":{{"timestamp":2,"connecting_id":0,"peer_id":{}}}}}}}}}"#,
+            peer,
+        );
+        parse_event(&msg, &mut ctx!(ts, blocks, gq, sync, peers, shards, preimages, events, db, pe, av, ls, tk, topo), 0.0).unwrap();
+        assert_eq!(topo.degree("abc123"), 1);
+        assert_eq!(topo.degree(&peer_node_id), 1);
+
+        let msg = format!(
+            r#"{{"type":"event","data":{{"node_id":"abc123","event":{{"Disconnected":{{"timestamp":3,"peer":{},"terminator":null,"reason":"bye"}}}}}}}}"#,
+            peer,
+        );
+        parse_event(&msg, &mut ctx!(ts, blocks, gq, sync, peers, shards, preimages, events, db, pe, av, ls, tk, topo), 0.0).unwrap();
+        assert_eq!(topo.edges().count(), 0);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let (mut ts, mut blocks, mut gq, mut sync, mut peers, mut shards, mut preimages, mut events, mut db, mut pe, mut av, mut ls, mut tk, mut topo) =
+            make_test_ctx();
+
+        let result = parse_event("not json", &mut ctx!(ts, blocks, gq, sync, peers, shards, preimages, events, db, pe, av, ls, tk, topo), 0.0);
+        assert!(matches!(result, Err(ParseError::InvalidJson(_))));
+
+        let msg = r#"{"type": "event", "data": {"event": {"Status": {}}}}"#;
+        let result = parse_event(msg, &mut ctx!(ts, blocks, gq, sync, peers, shards, preimages, events, db, pe, av, ls, tk, topo), 0.0);
+        assert!(matches!(result, Err(ParseError::MissingNodeId)));
+
+        let msg = r#"{"type": "event", "data": {"event": {"NoSuchEvent": {}}, "node_id": "abc123"}}"#;
+        let result = parse_event(msg, &mut ctx!(ts, blocks, gq, sync, peers, shards, preimages, events, db, pe, av, ls, tk, topo), 0.0);
+        let Err(err) = result else { panic!("expected an error") };
+        assert!(matches!(err, ParseError::InvalidEvent(_)));
+        // The serde error names what went wrong
+        assert!(err.to_string().contains("NoSuchEvent"), "{}", err);
+        assert_eq!(events.node_count(), 0);
+    }
+
+    #[test]
+    fn test_parse_unknown_event_type() {
+        let (mut ts, mut blocks, mut gq, mut sync, mut peers, mut shards, mut preimages, mut events, mut db, mut pe, mut av, mut ls, mut tk, mut topo) =
+            make_test_ctx();
+
+        let msg = r#"{"type":"event","data":{"id":3,"node_id":"abc123","event_type":230,"event":{"FutureEvent":{"timestamp":42,"extra":[1,2]}}}}"#;
+        let result = parse_event(msg, &mut ctx!(ts, blocks, gq, sync, peers, shards, preimages, events, db, pe, av, ls, tk, topo), 0.0)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.event_type, EventType::Unknown);
+        assert_eq!(result.event_id, Some(3));
+        assert_eq!(events.node_count(), 1);
+
+        assert!(!result.partial);
+    }
+
+    #[test]
+    fn test_parse_partial_event() {
+        let (mut ts, mut blocks, mut gq, mut sync, mut peers, mut shards, mut preimages, mut events, mut db, mut pe, mut av, mut ls, mut tk, mut topo) =
+            make_test_ctx();
+
+        // Known type, renamed field: counted as its own type, flagged partial
+        let msg = r#"{"type":"event","data":{"id":9,"node_id":"abc123","event_type":11,"event":{"BestBlockChanged":{"timestamp":5,"slot_index":3}}}}"#;
+        let result = parse_event(msg, &mut ctx!(ts, blocks, gq, sync, peers, shards, preimages, events, db, pe, av, ls, tk, topo), 0.0)
+            .unwrap()
+            .unwrap();
+        assert!(result.partial);
+        assert_eq!(result.event_type, EventType::BestBlockChanged);
+        assert_eq!(result.event_id, Some(9));
+
+        // Without `event_type`, the variant name identifies the type
+        let msg = r#"{"type":"event","data":{"node_id":"abc123","event":{"Authoring":{"timestamp":6}}}}"#;
+        let result = parse_event(msg, &mut ctx!(ts, blocks, gq, sync, peers, shards, preimages, events, db, pe, av, ls, tk, topo), 0.0)
+            .unwrap()
+            .unwrap();
+        assert!(result.partial);
+        assert_eq!(result.event_type, EventType::Authoring);
+        assert_eq!(events.node_count(), 1);
+    }
 }
@@ -0,0 +1,135 @@
+//! SCALE-encoded event frames (binary WebSocket messages from jamtart)
+//!
+//! A frame is the SCALE encoding of [`ScaleFrame`]: the jamtart event id, the emitting
+//! node's peer id, then the [`Event`] itself. Event variant indices are the JIP-3
+//! discriminants from [`EventType`]. Frames carrying a discriminant this build doesn't know
+//! decode to [`Event::Unknown`].
+
+use parity_scale_codec::{Decode, Encode};
+use super::events::{Event, EventId, EventType, PeerId, Timestamp};
+
+/// One event as carried in a binary frame
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct ScaleFrame {
+    /// Global event id (same as `data.id` in JSON messages)
+    pub id: EventId,
+    /// Emitting node; hex-encoded it is the JSON `data.node_id`
+    pub node_id: PeerId,
+    pub event: Event,
+}
+
+impl ScaleFrame {
+    /// Decode a whole frame; trailing bytes are an error
+    pub fn decode_frame(bytes: &[u8]) -> Result<Self, parity_scale_codec::Error> {
+        let mut input = bytes;
+        let result = match Self::decode(&mut input) {
+            Ok(frame) if input.is_empty() => return Ok(frame),
+            Ok(_) => Err("trailing bytes after SCALE frame".into()),
+            Err(e) => Err(e),
+        };
+        result.or_else(|e| Self::decode_unknown(bytes).ok_or(e))
+    }
+
+    /// Frame with an unknown event discriminant: keep the id, node and timestamp
+    /// (the first field of every event) and skip the rest of the payload
+    fn decode_unknown(mut bytes: &[u8]) -> Option<Self> {
+        let (id, node_id, type_id, timestamp) =
+            <(EventId, PeerId, u8, Timestamp)>::decode(&mut bytes).ok()?;
+        if EventType::from_u8(type_id).is_some() {
+            return None;
+        }
+        Some(Self { id, node_id, event: Event::Unknown { type_id, timestamp } })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::events::{
+        ConnectionSide, GuaranteeDiscardReason, GuaranteeOutline, PeerAddress, PeerDetails, Reason,
+    };
+
+    fn round_trip(event: Event) -> Event {
+        let frame = ScaleFrame { id: 42, node_id: [7; 32], event };
+        let bytes = frame.encode();
+        let decoded = ScaleFrame::decode_frame(&bytes).unwrap();
+        assert_eq!(decoded.id, 42);
+        assert_eq!(decoded.node_id, [7; 32]);
+        assert_eq!(decoded.event.encode(), frame.event.encode());
+        decoded.event
+    }
+
+    #[test]
+    fn variant_index_is_jip3_discriminant() {
+        let event = Event::BestBlockChanged { timestamp: 1, slot: 9, hash: [3; 32] };
+        assert_eq!(event.encode()[0], event.event_type() as u8);
+        assert_eq!(event.encode()[0], 11);
+    }
+
+    #[test]
+    fn round_trip_representative_events() {
+        let status = round_trip(Event::Status {
+            timestamp: 12345,
+            num_peers: 42,
+            num_val_peers: 2,
+            num_sync_peers: 1,
+            num_guarantees: vec![1, 2, 3],
+            num_shards: 4,
+            shards_size: 1 << 40,
+            num_preimages: 0,
+            preimages_size: 0,
+        });
+        assert!(matches!(status, Event::Status { num_peers: 42, ref num_guarantees, .. } if num_guarantees == &[1, 2, 3]));
+
+        let connecting = round_trip(Event::ConnectingOut {
+            timestamp: 1,
+            to: PeerDetails {
+                peer_id: [9; 32],
+                peer_address: PeerAddress { ipv6: [1; 16], port: 30333 },
+            },
+        });
+        assert!(matches!(connecting, Event::ConnectingOut { to: PeerDetails { peer_address: PeerAddress { port: 30333, .. }, .. }, .. }));
+
+        let disconnected = round_trip(Event::Disconnected {
+            timestamp: 2,
+            peer: [5; 32],
+            terminator: Some(ConnectionSide::Remote),
+            reason: Reason("timeout".to_string()),
+        });
+        assert_eq!(disconnected.reason().as_deref(), Some("timeout"));
+
+        let discarded = round_trip(Event::GuaranteeDiscarded {
+            timestamp: 3,
+            outline: GuaranteeOutline { work_report_hash: [2; 32], slot: 77, guarantors: vec![1, 4] },
+            reason: GuaranteeDiscardReason::TooManyGuarantees,
+        });
+        assert_eq!(discarded.event_type() as u8, 113);
+    }
+
+    #[test]
+    fn rejects_truncated_and_trailing_bytes() {
+        let frame = ScaleFrame {
+            id: 1,
+            node_id: [0; 32],
+            event: Event::SyncStatusChanged { timestamp: 1, synced: true },
+        };
+        let mut bytes = frame.encode();
+        assert!(ScaleFrame::decode_frame(&bytes[..bytes.len() - 1]).is_err());
+        bytes.push(0);
+        assert!(ScaleFrame::decode_frame(&bytes).is_err());
+    }
+
+    #[test]
+    fn unknown_discriminant_decodes_to_unknown() {
+        // id, node id, discriminant 230, timestamp, then a payload this build can't read
+        let mut bytes = (5u64, [1u8; 32], 230u8, 777u64).encode();
+        bytes.extend_from_slice(&[9, 9, 9]);
+        let frame = ScaleFrame::decode_frame(&bytes).unwrap();
+        assert_eq!((frame.id, frame.node_id), (5, [1; 32]));
+        assert!(matches!(frame.event, Event::Unknown { type_id: 230, timestamp: 777 }));
+
+        // A known discriminant with a broken payload is still an error
+        let bytes = (5u64, [1u8; 32], 11u8, 777u64).encode();
+        assert!(ScaleFrame::decode_frame(&bytes).is_err());
+    }
+}
@@ -1,6 +1,23 @@
 //! JAM Orbit - Native Desktop App
 //!
 //! Run with: cargo run --bin jam-orbit
+//!
+//! Flags:
+//!   --use-cpu          CPU rendering instead of wgpu
+//!   --url <ws-url>     jamtart endpoint; repeat to aggregate several (default: JAMTART_WS,
+//!                      itself a comma-separated list, or ws://127.0.0.1:38080/api/ws)
+//!   --record <path>    Save every received message to an NDJSON file
+//!   --replay <path>    Play back a recorded NDJSON file instead of connecting
+//!   --labels <path>    JSON map of hex peer id → display name
+//!   --export-csv-on-exit <path>  Write all stored events as CSV when the window closes
+//!   --metrics-addr <addr>  Serve Prometheus metrics on http://<addr>/metrics (e.g. 127.0.0.1:9100)
+//!   --json-stats       Run headless, printing stats as one JSON object per line on stdout
+//!                      (logs go to stderr); ends with the replay, or runs until killed
+//!   --stats-interval <secs>  Seconds between stats outputs (default 1)
+//!   --events <list>    Only subscribe to and show these event types (names or ids, comma-separated)
+//!   --geoip <path>     MaxMind City database (.mmdb) placing connecting peers on the peer map
+//!   --reconnect-delay-ms <ms>  Delay before the first reconnect, doubling per attempt (default 250)
+//!   --reconnect-attempts <n>   Give up after this many failed reconnects (default: retry forever)
 
 #[cfg(not(target_arch = "wasm32"))]
 mod app;
@@ -15,6 +32,12 @@ mod scatter;
 #[cfg(not(target_arch = "wasm32"))]
 mod vring;
 #[cfg(not(target_arch = "wasm32"))]
+mod metrics_server;
+#[cfg(not(target_arch = "wasm32"))]
+mod recorder;
+#[cfg(not(target_arch = "wasm32"))]
+mod replay;
+#[cfg(not(target_arch = "wasm32"))]
 mod websocket_native;
 #[cfg(not(target_arch = "wasm32"))]
 mod ws_state;
@@ -23,11 +46,228 @@ mod ws_state;
 fn main() -> eframe::Result<()> {
     use tracing_subscriber::{fmt, EnvFilter};
 
+    let args: Vec<String> = std::env::args().collect();
+    let json_stats = args.iter().any(|a| a == "--json-stats");
+
     let filter = EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| EnvFilter::new("info,jam_orbit=debug"));
-    fmt().with_env_filter(filter).with_target(true).init();
+    // Keep stdout for the JSON stats stream
+    if json_stats {
+        fmt().with_env_filter(filter).with_target(true).with_writer(std::io::stderr).init();
+    } else {
+        fmt().with_env_filter(filter).with_target(true).init();
+    }
+
+    let use_cpu = args.iter().any(|a| a == "--use-cpu");
+
+    let ws_urls = match app::native_ws_urls(&args) {
+        Ok(urls) => urls,
+        Err(e) => {
+            eprintln!("Invalid endpoint URL {}", e);
+            std::process::exit(2);
+        }
+    };
+
+    let replay_path = match args.iter().position(|a| a == "--replay") {
+        Some(i) => {
+            let Some(path) = args.get(i + 1) else {
+                eprintln!("--replay requires a file path");
+                std::process::exit(2);
+            };
+            let live = std::env::var("JAMTART_WS").is_ok()
+                || args.iter().any(|a| a == "--record" || a == "--url");
+            if live {
+                eprintln!("--replay cannot be combined with a live source (JAMTART_WS, --url or --record)");
+                std::process::exit(2);
+            }
+            Some(path)
+        }
+        None => None,
+    };
+    let replay = match replay_path {
+        Some(path) => match replay::ReplaySource::open(std::path::Path::new(path)) {
+            Ok(source) => Some(source),
+            Err(e) => {
+                eprintln!("Failed to read recording {}: {}", path, e);
+                std::process::exit(1);
+            }
+        },
+        None => None,
+    };
+
+    let peer_labels = match args.iter().position(|a| a == "--labels") {
+        Some(i) => {
+            let Some(path) = args.get(i + 1) else {
+                eprintln!("--labels requires a file path");
+                std::process::exit(2);
+            };
+            match app::load_peer_labels(std::path::Path::new(path)) {
+                Ok(labels) => labels,
+                Err(e) => {
+                    eprintln!("Failed to load peer labels {}: {}", path, e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        None => Default::default(),
+    };
+
+    let geoip = match args.iter().position(|a| a == "--geoip") {
+        Some(i) => {
+            let Some(path) = args.get(i + 1) else {
+                eprintln!("--geoip requires a .mmdb file path");
+                std::process::exit(2);
+            };
+            match app::GeoIp::open(std::path::Path::new(path)) {
+                Ok(geoip) => Some(geoip),
+                Err(e) => {
+                    eprintln!("Failed to open GeoIP database {}: {}", path, e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        None => None,
+    };
+
+    let export_csv_on_exit = match args.iter().position(|a| a == "--export-csv-on-exit") {
+        Some(i) => {
+            let Some(path) = args.get(i + 1) else {
+                eprintln!("--export-csv-on-exit requires a file path");
+                std::process::exit(2);
+            };
+            Some(std::path::PathBuf::from(path))
+        }
+        None => None,
+    };
+
+    let live_metrics = match args.iter().position(|a| a == "--metrics-addr") {
+        Some(i) => {
+            let Some(addr) = args.get(i + 1) else {
+                eprintln!("--metrics-addr requires an address (e.g. 127.0.0.1:9100)");
+                std::process::exit(2);
+            };
+            let addr: std::net::SocketAddr = match addr.parse() {
+                Ok(addr) => addr,
+                Err(e) => {
+                    eprintln!("Invalid --metrics-addr {}: {}", addr, e);
+                    std::process::exit(2);
+                }
+            };
+            let metrics = std::sync::Arc::new(metrics_server::LiveMetrics::default());
+            if let Err(e) = metrics_server::serve(addr, metrics.clone()) {
+                eprintln!("Failed to serve metrics on {}: {}", addr, e);
+                std::process::exit(1);
+            }
+            Some(metrics)
+        }
+        None => None,
+    };
+
+    let stats_interval = match args.iter().position(|a| a == "--stats-interval") {
+        Some(i) => match args.get(i + 1).map(|s| s.parse::<f64>()) {
+            Some(Ok(secs)) if secs.is_finite() && secs > 0.0 => secs,
+            _ => {
+                eprintln!("--stats-interval requires a positive number of seconds");
+                std::process::exit(2);
+            }
+        },
+        None => app::DEFAULT_STATS_INTERVAL,
+    };
+    let stats = app::StatsOptions { json: json_stats, interval: stats_interval };
+
+    let events = match args.iter().position(|a| a == "--events") {
+        Some(i) => {
+            let Some(list) = args.get(i + 1) else {
+                eprintln!("--events requires a comma-separated list (e.g. PeerMisbehaved,Disconnected)");
+                std::process::exit(2);
+            };
+            match core::parse_event_list(list) {
+                Ok(types) => Some(types),
+                Err(e) => {
+                    eprintln!("Invalid --events: {}", e);
+                    std::process::exit(2);
+                }
+            }
+        }
+        None => None,
+    };
+
+    let default_backoff = ws_state::Backoff::default();
+    let base_delay = match args.iter().position(|a| a == "--reconnect-delay-ms") {
+        Some(i) => match args.get(i + 1).map(|s| s.parse::<u64>()) {
+            Some(Ok(ms)) if ms > 0 => std::time::Duration::from_millis(ms),
+            _ => {
+                eprintln!("--reconnect-delay-ms requires a positive number of milliseconds");
+                std::process::exit(2);
+            }
+        },
+        None => default_backoff.base_delay,
+    };
+    let max_attempts = match args.iter().position(|a| a == "--reconnect-attempts") {
+        Some(i) => match args.get(i + 1).map(|s| s.parse::<u32>()) {
+            Some(Ok(n)) if n > 0 => Some(n),
+            _ => {
+                eprintln!("--reconnect-attempts requires a positive number");
+                std::process::exit(2);
+            }
+        },
+        None => default_backoff.max_attempts,
+    };
+    let backoff = ws_state::Backoff { base_delay, max_attempts, ..default_backoff };
+
+    let recorder = match args.iter().position(|a| a == "--record") {
+        Some(i) => {
+            let Some(path) = args.get(i + 1) else {
+                eprintln!("--record requires a file path");
+                std::process::exit(2);
+            };
+            match recorder::Recorder::start(std::path::Path::new(path), &ws_urls.join(",")) {
+                Ok(rec) => Some(rec),
+                Err(e) => {
+                    eprintln!("Failed to open recording file {}: {}", path, e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        None => None,
+    };
 
-    let use_cpu = std::env::args().any(|a| a == "--use-cpu");
+    // Ctrl-C: flush the recording before exiting so the last events aren't lost
+    if let Some(rec) = recorder.clone() {
+        std::thread::spawn(move || {
+            let rt = match tokio::runtime::Builder::new_current_thread().enable_all().build() {
+                Ok(rt) => rt,
+                Err(e) => {
+                    tracing::error!(error = %e, "Failed to install Ctrl-C handler");
+                    return;
+                }
+            };
+            if rt.block_on(tokio::signal::ctrl_c()).is_ok() {
+                tracing::info!("Ctrl-C received, closing recording");
+                rec.finish();
+                std::process::exit(130);
+            }
+        });
+    }
+
+    let launch = app::LaunchOptions {
+        use_cpu,
+        ws_urls,
+        recorder: recorder.clone(),
+        replay,
+        peer_labels,
+        export_csv_on_exit,
+        live_metrics,
+        stats,
+        backoff,
+    };
+    if json_stats {
+        app::run_headless(launch, events.as_deref());
+        if let Some(rec) = recorder {
+            rec.finish();
+        }
+        return Ok(());
+    }
 
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
@@ -35,12 +275,25 @@ fn main() -> eframe::Result<()> {
             .with_title("JAM Orbit"),
         ..Default::default()
     };
-
-    eframe::run_native(
+    let result = eframe::run_native(
         "JAM Orbit",
         options,
-        Box::new(move |cc| Ok(Box::new(app::JamApp::new(cc, use_cpu)))),
-    )
+        Box::new(move |cc| {
+            let mut app = app::JamApp::new(cc, launch);
+            if let Some(types) = &events {
+                app.apply_event_selection(types);
+            }
+            if let Some(geoip) = geoip {
+                app.set_geoip(geoip);
+            }
+            Ok(Box::new(app))
+        }),
+    );
+
+    if let Some(rec) = recorder {
+        rec.finish();
+    }
+    result
 }
 
 #[cfg(target_arch = "wasm32")]
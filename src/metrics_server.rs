@@ -0,0 +1,177 @@
+//! Prometheus metrics endpoint (native only, --metrics-addr)
+//!
+//! The UI publishes its aggregates into [`LiveMetrics`] on the housekeeping tick; a
+//! dedicated thread serves them as Prometheus text on `GET /metrics`. Every value is an
+//! atomic, so neither side ever waits on the other.
+
+use std::fmt::Write as _;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// A client that doesn't send its request line within this long is dropped
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Aggregates exported on `/metrics`, as seen on the latest tick
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MetricsSnapshot {
+    pub events_total: u64,
+    pub events_per_sec: f64,
+    /// Node-reported and server-side drops combined
+    pub dropped_per_sec: f64,
+    pub nodes: usize,
+    pub validators: usize,
+    pub highest_slot: Option<u64>,
+    /// Highest best slot minus highest finalized slot
+    pub finalization_lag: Option<u64>,
+}
+
+/// Lock-free cell holding the latest [`MetricsSnapshot`]; optional values are stored as `u64::MAX`
+#[derive(Default)]
+pub struct LiveMetrics {
+    events_total: AtomicU64,
+    events_per_sec: AtomicU64,
+    dropped_per_sec: AtomicU64,
+    nodes: AtomicU64,
+    validators: AtomicU64,
+    highest_slot: AtomicU64,
+    finalization_lag: AtomicU64,
+}
+
+impl LiveMetrics {
+    pub fn publish(&self, snapshot: &MetricsSnapshot) {
+        let store = |cell: &AtomicU64, value: u64| cell.store(value, Ordering::Relaxed);
+        store(&self.events_total, snapshot.events_total);
+        store(&self.events_per_sec, snapshot.events_per_sec.to_bits());
+        store(&self.dropped_per_sec, snapshot.dropped_per_sec.to_bits());
+        store(&self.nodes, snapshot.nodes as u64);
+        store(&self.validators, snapshot.validators as u64);
+        store(&self.highest_slot, snapshot.highest_slot.unwrap_or(u64::MAX));
+        store(&self.finalization_lag, snapshot.finalization_lag.unwrap_or(u64::MAX));
+    }
+
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        let load = |cell: &AtomicU64| cell.load(Ordering::Relaxed);
+        let optional = |cell: &AtomicU64| Some(load(cell)).filter(|&v| v != u64::MAX);
+        MetricsSnapshot {
+            events_total: load(&self.events_total),
+            events_per_sec: f64::from_bits(load(&self.events_per_sec)),
+            dropped_per_sec: f64::from_bits(load(&self.dropped_per_sec)),
+            nodes: load(&self.nodes) as usize,
+            validators: load(&self.validators) as usize,
+            highest_slot: optional(&self.highest_slot),
+            finalization_lag: optional(&self.finalization_lag),
+        }
+    }
+}
+
+/// Prometheus text exposition of `m`; slot gauges are omitted until a block was reported
+fn render(m: &MetricsSnapshot) -> String {
+    let mut out = String::new();
+    let mut metric = |name: &str, kind: &str, help: &str, value: String| {
+        let _ = writeln!(out, "# HELP {} {}", name, help);
+        let _ = writeln!(out, "# TYPE {} {}", name, kind);
+        let _ = writeln!(out, "{} {}", name, value);
+    };
+    metric("jam_orbit_events_total", "counter", "Telemetry events received", m.events_total.to_string());
+    metric("jam_orbit_events_per_second", "gauge", "Telemetry events per second", m.events_per_sec.to_string());
+    metric(
+        "jam_orbit_dropped_per_second",
+        "gauge",
+        "Events dropped per second by nodes and the server",
+        m.dropped_per_sec.to_string(),
+    );
+    metric("jam_orbit_nodes", "gauge", "Nodes that sent at least one event", m.nodes.to_string());
+    metric("jam_orbit_validators", "gauge", "Validators that reported a block", m.validators.to_string());
+    if let Some(slot) = m.highest_slot {
+        metric("jam_orbit_highest_slot", "gauge", "Highest best block slot", slot.to_string());
+    }
+    if let Some(lag) = m.finalization_lag {
+        metric(
+            "jam_orbit_finalization_lag_slots",
+            "gauge",
+            "Highest best slot minus highest finalized slot",
+            lag.to_string(),
+        );
+    }
+    out
+}
+
+/// Bind `addr` and serve `metrics` from a background thread
+pub fn serve(addr: SocketAddr, metrics: Arc<LiveMetrics>) -> io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+    info!(%addr, "Serving Prometheus metrics on /metrics");
+    std::thread::Builder::new()
+        .name("metrics-server".into())
+        .spawn(move || {
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        if let Err(e) = respond(stream, &metrics) {
+                            debug!(error = %e, "Metrics request failed");
+                        }
+                    }
+                    Err(e) => warn!(error = %e, "Failed to accept metrics connection"),
+                }
+            }
+        })?;
+    Ok(())
+}
+
+/// Answer one request; only `GET /metrics` is served
+fn respond(stream: TcpStream, metrics: &LiveMetrics) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Drain the headers so the client sees a clean close
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (status, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => ("200 OK", render(&metrics.snapshot())),
+        _ => ("404 Not Found", "Not found\n".to_string()),
+    };
+    let mut stream = &stream;
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_round_trips_and_renders() {
+        let live = LiveMetrics::default();
+        let snapshot = MetricsSnapshot {
+            events_total: 1200,
+            events_per_sec: 42.5,
+            dropped_per_sec: 0.0,
+            nodes: 6,
+            validators: 5,
+            highest_slot: Some(810),
+            finalization_lag: None,
+        };
+        live.publish(&snapshot);
+        assert_eq!(live.snapshot(), snapshot);
+
+        let text = render(&snapshot);
+        assert!(text.contains("# TYPE jam_orbit_events_total counter\njam_orbit_events_total 1200\n"));
+        assert!(text.contains("jam_orbit_events_per_second 42.5\n"));
+        assert!(text.contains("jam_orbit_highest_slot 810\n"));
+        assert!(!text.contains("finalization_lag"));
+    }
+}
@@ -0,0 +1,223 @@
+//! Session recorder for offline analysis (native only)
+//!
+//! Writes every received WebSocket message as one line of an NDJSON file: text messages
+//! as they are, binary (SCALE) frames wrapped as `{"type":"binary","base64":"..."}`.
+//! With several endpoints each line also carries its endpoint's `"source"` tag.
+//! The first line is a header with the capture start time and source URL.
+//! Writing happens on a dedicated thread so the WebSocket read loop never blocks on disk I/O.
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use serde_json::{json, Value};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::{error, info, warn};
+
+/// How often buffered lines are flushed to disk
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+/// How long `finish()` waits for the writer thread to flush
+const FINISH_TIMEOUT: Duration = Duration::from_secs(5);
+/// `type` of the lines wrapping a binary frame
+pub const BINARY_LINE_TYPE: &str = "binary";
+/// Field holding the endpoint's source tag (`s1`, `s2`, …) in multi-endpoint recordings
+pub const SOURCE_FIELD: &str = "source";
+
+/// Raw newlines can only be insignificant JSON whitespace; keep one message per line
+fn single_line(msg: &str) -> String {
+    if msg.contains(['\n', '\r']) {
+        msg.replace(['\n', '\r'], " ")
+    } else {
+        msg.to_string()
+    }
+}
+
+enum RecordMsg {
+    Line(String),
+    /// Flush, close the file and acknowledge
+    Finish(Sender<()>),
+}
+
+/// Handle to the recording thread. Cheap to clone; all clones feed the same file.
+#[derive(Clone)]
+pub struct Recorder {
+    tx: Sender<RecordMsg>,
+    /// Source tag written into every line recorded through this handle
+    source: Option<String>,
+}
+
+impl Recorder {
+    /// Create `path`, write the header line and start the writer thread
+    pub fn start(path: &Path, url: &str) -> io::Result<Self> {
+        let mut out = BufWriter::new(File::create(path)?);
+        let header = json!({
+            "type": "recording_header",
+            "version": 1,
+            "started_at_unix_ms": unix_ms(),
+            "url": url,
+        });
+        writeln!(out, "{}", header)?;
+        out.flush()?;
+
+        let (tx, rx) = mpsc::channel();
+        let path_display = path.display().to_string();
+        std::thread::Builder::new()
+            .name("recorder".into())
+            .spawn(move || run_writer(out, rx, &path_display))?;
+
+        info!(path = %path.display(), url, "Recording session");
+        Ok(Self { tx, source: None })
+    }
+
+    /// Handle on the same file that tags its lines with an endpoint's `source`
+    pub fn with_source(&self, source: Option<&str>) -> Self {
+        Self { tx: self.tx.clone(), source: source.map(str::to_string) }
+    }
+
+    /// Queue one received message (never blocks)
+    pub fn record(&self, msg: &str) {
+        let line = match &self.source {
+            // serde_json never writes raw newlines
+            Some(source) => match serde_json::from_str::<Value>(msg) {
+                Ok(Value::Object(mut fields)) => {
+                    fields.insert(SOURCE_FIELD.into(), source.as_str().into());
+                    Value::Object(fields).to_string()
+                }
+                _ => single_line(msg),
+            },
+            None => single_line(msg),
+        };
+        let _ = self.tx.send(RecordMsg::Line(line));
+    }
+
+    /// Queue one received binary frame, base64-encoded (never blocks)
+    pub fn record_binary(&self, bytes: &[u8]) {
+        let mut line = json!({ "type": BINARY_LINE_TYPE, "base64": BASE64.encode(bytes) });
+        if let Some(source) = &self.source {
+            line[SOURCE_FIELD] = source.as_str().into();
+        }
+        let _ = self.tx.send(RecordMsg::Line(line.to_string()));
+    }
+
+    /// Note that the source endpoint changed mid-recording
+    pub fn mark_source(&self, url: &str) {
+        let marker = json!({
+            "type": "recording_source",
+            "at_unix_ms": unix_ms(),
+            "url": url,
+        });
+        let _ = self.tx.send(RecordMsg::Line(marker.to_string()));
+    }
+
+    /// Flush and close the file, waiting briefly for the writer thread.
+    /// Messages recorded afterwards are discarded.
+    pub fn finish(&self) {
+        let (ack_tx, ack_rx) = mpsc::channel();
+        if self.tx.send(RecordMsg::Finish(ack_tx)).is_err() {
+            return; // already finished
+        }
+        if ack_rx.recv_timeout(FINISH_TIMEOUT).is_err() {
+            warn!("Timed out waiting for recording to flush");
+        }
+    }
+}
+
+fn run_writer(mut out: BufWriter<File>, rx: Receiver<RecordMsg>, path: &str) {
+    let mut lines = 0u64;
+    let mut last_flush = Instant::now();
+    loop {
+        match rx.recv_timeout(FLUSH_INTERVAL) {
+            Ok(RecordMsg::Line(line)) => {
+                if let Err(e) = writeln!(out, "{}", line) {
+                    error!(error = %e, path, "Failed to write recording, stopping");
+                    return;
+                }
+                lines += 1;
+            }
+            Ok(RecordMsg::Finish(ack)) => {
+                if let Err(e) = out.flush() {
+                    error!(error = %e, path, "Failed to flush recording");
+                }
+                info!(lines, path, "Recording closed");
+                let _ = ack.send(());
+                return;
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+        if last_flush.elapsed() >= FLUSH_INTERVAL {
+            if let Err(e) = out.flush() {
+                error!(error = %e, path, "Failed to flush recording");
+            }
+            last_flush = Instant::now();
+        }
+    }
+    if let Err(e) = out.flush() {
+        error!(error = %e, path, "Failed to flush recording");
+    }
+    info!(lines, path, "Recording closed");
+}
+
+fn unix_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recording_header_and_lines() {
+        let path = std::env::temp_dir()
+            .join(format!("jam-orbit-recorder-test-{}.ndjson", std::process::id()));
+        let rec = Recorder::start(&path, "ws://127.0.0.1:38080/api/ws").unwrap();
+        rec.record(r#"{"type":"event","data":{"id":1}}"#);
+        rec.record("{\"type\":\"event\",\n\"data\":{\"id\":2}}");
+        rec.record_binary(&[0x7b, 0x00, 0xff]);
+        rec.finish();
+        rec.record("after finish is dropped");
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        let mut lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 4);
+
+        let binary: serde_json::Value = serde_json::from_str(lines.pop().unwrap()).unwrap();
+        assert_eq!(binary["type"], BINARY_LINE_TYPE);
+        assert_eq!(binary["base64"], "ewD/");
+
+        let header: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(header["type"], "recording_header");
+        assert_eq!(header["url"], "ws://127.0.0.1:38080/api/ws");
+        assert!(header["started_at_unix_ms"].as_u64().unwrap() > 0);
+
+        for line in &lines[1..] {
+            let msg: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert_eq!(msg["type"], "event");
+        }
+    }
+
+    #[test]
+    fn test_recording_source_tags() {
+        let path = std::env::temp_dir()
+            .join(format!("jam-orbit-recorder-source-test-{}.ndjson", std::process::id()));
+        let rec = Recorder::start(&path, "ws://a/api/ws,ws://b/api/ws").unwrap();
+        rec.with_source(Some("s2")).record(r#"{"type":"event","data":{"id":1}}"#);
+        rec.with_source(Some("s1")).record_binary(&[1, 2]);
+        rec.with_source(None).record(r#"{"type":"event","data":{"id":2}}"#);
+        rec.finish();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        let lines: Vec<serde_json::Value> =
+            contents.lines().skip(1).map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!((&lines[0]["source"], &lines[0]["data"]["id"]), (&json!("s2"), &json!(1)));
+        assert_eq!((&lines[1]["source"], &lines[1]["type"]), (&json!("s1"), &json!("binary")));
+        assert!(lines[2].get("source").is_none());
+    }
+}
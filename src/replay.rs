@@ -0,0 +1,262 @@
+//! Replay of recorded NDJSON sessions (native only)
+//!
+//! Reads a file written by `--record` and hands the event messages back to the app
+//! at the pace given by their JIP-3 `timestamp` deltas, so the same parser path as the
+//! live clients sees the same timing. Binary frames come back as binary messages.
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use serde_json::Value;
+use std::io;
+use std::path::Path;
+use tracing::{info, warn};
+use crate::core::scale::ScaleFrame;
+use crate::recorder::{BINARY_LINE_TYPE, SOURCE_FIELD};
+use crate::ws_state::WsMessage;
+
+/// JIP-3 timestamps are in microseconds
+const TIMESTAMP_UNITS_PER_SEC: f64 = 1_000_000.0;
+
+/// A loaded recording plus its playback cursor
+pub struct ReplaySource {
+    /// Event messages with their endpoint's source tag (multi-endpoint recordings), in file order
+    lines: Vec<(WsMessage, Option<String>)>,
+    /// Seconds since the first event, per line (non-decreasing)
+    offsets: Vec<f64>,
+    /// Next line to hand out
+    cursor: usize,
+    /// Playback position in seconds since the first event
+    position: f64,
+    /// Wall-clock time of the last `advance()` call
+    last_now: Option<f64>,
+    /// Restart from the beginning at end-of-file
+    pub looping: bool,
+}
+
+impl ReplaySource {
+    /// Load a recording from disk
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        let source = Self::from_lines(contents.lines());
+        info!(
+            path = %path.display(),
+            events = source.lines.len(),
+            duration_secs = source.duration(),
+            "Loaded recording"
+        );
+        Ok(source)
+    }
+
+    /// Build from NDJSON lines. Non-event lines (recording header, source markers) are skipped.
+    pub fn from_lines<'a>(lines: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut kept = Vec::new();
+        let mut offsets = Vec::new();
+        let mut first_ts: Option<u64> = None;
+        let mut last_offset = 0.0f64;
+        let mut skipped = 0usize;
+
+        for line in lines {
+            let Ok(json) = serde_json::from_str::<Value>(line) else {
+                skipped += 1;
+                continue;
+            };
+            let (msg, timestamp) = match json["type"].as_str() {
+                Some("event") => (WsMessage::Text(line.to_string()), event_timestamp(&json)),
+                Some(BINARY_LINE_TYPE) => {
+                    let Some(bytes) = json["base64"].as_str().and_then(|b| BASE64.decode(b).ok())
+                    else {
+                        skipped += 1;
+                        continue;
+                    };
+                    let timestamp = ScaleFrame::decode_frame(&bytes).ok().map(|f| f.event.timestamp());
+                    (WsMessage::Binary(bytes), timestamp)
+                }
+                _ => continue,
+            };
+            // Events without a timestamp, or out of order, replay together with the previous one
+            if let Some(ts) = timestamp {
+                let first = *first_ts.get_or_insert(ts);
+                let offset = ts.saturating_sub(first) as f64 / TIMESTAMP_UNITS_PER_SEC;
+                last_offset = last_offset.max(offset);
+            }
+            let source = json[SOURCE_FIELD].as_str().map(str::to_string);
+            kept.push((msg, source));
+            offsets.push(last_offset);
+        }
+
+        if skipped > 0 {
+            warn!(skipped, "Skipped malformed lines in recording");
+        }
+
+        Self {
+            lines: kept,
+            offsets,
+            cursor: 0,
+            position: 0.0,
+            last_now: None,
+            looping: false,
+        }
+    }
+
+    /// Length of the recording in seconds
+    pub fn duration(&self) -> f64 {
+        self.offsets.last().copied().unwrap_or(0.0)
+    }
+
+    /// Current playback position in seconds
+    pub fn position(&self) -> f64 {
+        self.position
+    }
+
+    /// All events have been handed out and playback is not looping
+    pub fn is_finished(&self) -> bool {
+        !self.looping && self.cursor >= self.lines.len()
+    }
+
+    /// Move the playback clock forward by the wall-clock time since the last call, scaled by `speed`.
+    /// Returns true when playback wrapped around to the start (the caller should clear its data).
+    pub fn advance(&mut self, now: f64, speed: f64) -> bool {
+        let dt = self.last_now.map_or(0.0, |last| (now - last).max(0.0));
+        self.last_now = Some(now);
+        self.position = (self.position + dt * speed).min(self.duration());
+
+        if self.looping && self.cursor >= self.lines.len() && !self.lines.is_empty() {
+            self.cursor = 0;
+            self.position = 0.0;
+            return true;
+        }
+        false
+    }
+
+    /// Next message due at the current position, with its source tag and how long ago
+    /// (in recording seconds) it was due.
+    pub fn next_due(&mut self) -> Option<(&WsMessage, Option<&str>, f64)> {
+        let offset = *self.offsets.get(self.cursor)?;
+        if offset > self.position {
+            return None;
+        }
+        let (msg, source) = &self.lines[self.cursor];
+        self.cursor += 1;
+        Some((msg, source.as_deref(), self.position - offset))
+    }
+
+    /// Jump to `t` seconds. Events from `lookback` seconds before `t` are handed out again
+    /// so the visualizations have recent history after the caller clears its data.
+    pub fn seek(&mut self, t: f64, lookback: f64) {
+        self.position = t.clamp(0.0, self.duration());
+        let from = self.position - lookback;
+        self.cursor = self.offsets.partition_point(|&o| o < from);
+    }
+}
+
+/// `data.event.<Variant>.timestamp` of a jamtart event message
+fn event_timestamp(json: &Value) -> Option<u64> {
+    json["data"]["event"]
+        .as_object()?
+        .values()
+        .next()?
+        .get("timestamp")?
+        .as_u64()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event_line(id: u64, ts: u64) -> String {
+        format!(
+            r#"{{"type":"event","data":{{"id":{},"node_id":"aa","event":{{"BestBlockChanged":{{"timestamp":{},"slot":1,"hash":[]}}}}}}}}"#,
+            id, ts
+        )
+    }
+
+    fn sample() -> ReplaySource {
+        let lines = vec![
+            r#"{"type":"recording_header","version":1,"url":"ws://x"}"#.to_string(),
+            event_line(1, 10_000_000),
+            event_line(2, 10_500_000),
+            "not json".to_string(),
+            event_line(3, 12_000_000),
+        ];
+        ReplaySource::from_lines(lines.iter().map(String::as_str))
+    }
+
+    fn drain(replay: &mut ReplaySource) -> usize {
+        let mut n = 0;
+        while replay.next_due().is_some() {
+            n += 1;
+        }
+        n
+    }
+
+    #[test]
+    fn test_replay_offsets_and_pacing() {
+        let mut replay = sample();
+        assert_eq!(replay.duration(), 2.0);
+
+        replay.advance(100.0, 1.0);
+        assert_eq!(drain(&mut replay), 1);
+
+        replay.advance(100.6, 1.0);
+        assert_eq!(drain(&mut replay), 1);
+
+        // Double speed: 0.75s wall → 1.5s recording time
+        replay.advance(101.35, 2.0);
+        assert_eq!(drain(&mut replay), 1);
+        assert!(replay.is_finished());
+    }
+
+    #[test]
+    fn test_replay_looping_and_seek() {
+        let mut replay = sample();
+        replay.looping = true;
+        replay.advance(0.0, 1.0);
+        replay.advance(5.0, 1.0);
+        assert_eq!(drain(&mut replay), 3);
+        assert!(!replay.is_finished());
+        assert!(replay.advance(5.1, 1.0));
+        assert_eq!(replay.position(), 0.0);
+
+        replay.seek(1.0, 0.0);
+        assert_eq!(drain(&mut replay), 0);
+        replay.seek(2.0, 0.0);
+        assert_eq!(drain(&mut replay), 1);
+        replay.seek(2.0, 10.0);
+        assert_eq!(drain(&mut replay), 3);
+    }
+
+    #[test]
+    fn test_replay_binary_frames() {
+        use crate::core::Event;
+        use parity_scale_codec::Encode;
+
+        let frame = ScaleFrame {
+            id: 0x7b,
+            node_id: [1; 32],
+            event: Event::BestBlockChanged { timestamp: 11_000_000, slot: 2, hash: [0; 32] },
+        };
+        let bytes = frame.encode();
+        let binary = format!(r#"{{"type":"binary","base64":"{}"}}"#, BASE64.encode(&bytes));
+        let lines = [event_line(1, 10_000_000), binary, r#"{"type":"binary","base64":"%"}"#.to_string()];
+        let mut replay = ReplaySource::from_lines(lines.iter().map(String::as_str));
+        assert_eq!(replay.duration(), 1.0);
+
+        replay.advance(0.0, 1.0);
+        replay.advance(1.0, 1.0);
+        assert!(matches!(replay.next_due(), Some((WsMessage::Text(_), None, _))));
+        match replay.next_due() {
+            Some((WsMessage::Binary(replayed), _, _)) => assert_eq!(replayed, &bytes),
+            other => panic!("expected the binary frame, got {:?}", other),
+        }
+        assert!(replay.next_due().is_none());
+    }
+
+    #[test]
+    fn test_replay_keeps_source_tags() {
+        let tagged = event_line(2, 10_000_000).replacen('{', r#"{"source":"s2","#, 1);
+        let lines = [event_line(1, 10_000_000), tagged];
+        let mut replay = ReplaySource::from_lines(lines.iter().map(String::as_str));
+        replay.advance(0.0, 1.0);
+        assert_eq!(replay.next_due().map(|(_, source, _)| source), Some(None));
+        assert_eq!(replay.next_due().map(|(_, source, _)| source), Some(Some("s2")));
+    }
+}
@@ -7,11 +7,10 @@ use bytemuck::{Pod, Zeroable};
 use egui_wgpu::wgpu::{self, util::DeviceExt};
 use std::sync::Arc;
 
-use crate::vring::{ColorLut, FilterBitfield};
+use crate::vring::{ColorLut, FilterBitfield, InstanceRing};
 
 const BUFFER_CAPACITY: usize = 2_500_000; // 2.5M particles per buffer
 const NUM_BUFFERS: usize = 2; // 2 buffers = 5M particles total
-const MAX_INSTANCES: usize = BUFFER_CAPACITY * NUM_BUFFERS;
 
 /// GPU-compatible scatter particle (12 bytes)
 #[repr(C)]
@@ -44,11 +43,9 @@ pub struct ScatterRenderer {
     color_lut_buffer: wgpu::Buffer,
     filter_buffer: wgpu::Buffer,
     instance_buffers: Vec<wgpu::Buffer>,
-    buffer_counts: Vec<u32>,
 
-    // Incremental upload tracking
-    gpu_write_head: usize,
-    total_instances: u32,
+    // Incremental upload tracking (wraps around; expiry is done in the shader by age)
+    ring: InstanceRing,
 
     // Render target texture
     texture: wgpu::Texture,
@@ -227,9 +224,7 @@ impl ScatterRenderer {
             color_lut_buffer,
             filter_buffer,
             instance_buffers,
-            buffer_counts: vec![0; NUM_BUFFERS],
-            gpu_write_head: 0,
-            total_instances: 0,
+            ring: InstanceRing::new(BUFFER_CAPACITY, NUM_BUFFERS),
             texture,
             texture_view,
             target_format,
@@ -239,9 +234,7 @@ impl ScatterRenderer {
     }
 
     pub fn reset(&mut self) {
-        self.gpu_write_head = 0;
-        self.total_instances = 0;
-        self.buffer_counts.fill(0);
+        self.ring.reset();
     }
 
     fn create_texture(
@@ -296,44 +289,14 @@ impl ScatterRenderer {
             self.texture_view = view;
         }
 
-        // Upload new particles across multiple buffers
-        if !new_particles.is_empty() {
-            let particle_size = std::mem::size_of::<ScatterParticle>();
-            let mut remaining = new_particles;
-            let mut write_pos = self.gpu_write_head;
-
-            while !remaining.is_empty() {
-                let buffer_idx = write_pos / BUFFER_CAPACITY;
-                let offset_in_buffer = write_pos % BUFFER_CAPACITY;
-                let space_in_buffer = BUFFER_CAPACITY - offset_in_buffer;
-                let to_write = remaining.len().min(space_in_buffer);
-
-                let (chunk, rest) = remaining.split_at(to_write);
-                remaining = rest;
-
-                let byte_offset = (offset_in_buffer * particle_size) as u64;
-                queue.write_buffer(
-                    &self.instance_buffers[buffer_idx % NUM_BUFFERS],
-                    byte_offset,
-                    bytemuck::cast_slice(chunk),
-                );
-
-                let new_end = offset_in_buffer + to_write;
-                self.buffer_counts[buffer_idx % NUM_BUFFERS] =
-                    self.buffer_counts[buffer_idx % NUM_BUFFERS].max(new_end as u32);
-
-                write_pos = (write_pos + to_write) % MAX_INSTANCES;
-            }
-
-            self.gpu_write_head = write_pos;
-            self.total_instances =
-                (self.total_instances + new_particles.len() as u32).min(MAX_INSTANCES as u32);
-
-            if self.total_instances >= MAX_INSTANCES as u32 {
-                for count in &mut self.buffer_counts {
-                    *count = BUFFER_CAPACITY as u32;
-                }
-            }
+        // Append new particles; once full, the oldest are overwritten
+        let particle_size = std::mem::size_of::<ScatterParticle>();
+        for write in self.ring.advance(new_particles.len()) {
+            queue.write_buffer(
+                &self.instance_buffers[write.buffer],
+                (write.offset * particle_size) as u64,
+                bytemuck::cast_slice(&new_particles[write.src]),
+            );
         }
 
         queue.write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(uniforms));
@@ -361,7 +324,7 @@ impl ScatterRenderer {
             render_pass.set_bind_group(0, &self.bind_group, &[]);
 
             for (i, buffer) in self.instance_buffers.iter().enumerate() {
-                let count = self.buffer_counts[i];
+                let count = self.ring.counts()[i];
                 if count > 0 {
                     render_pass.set_vertex_buffer(0, buffer.slice(..));
                     render_pass.draw(0..6, 0..count);
@@ -1,71 +1,156 @@
-//! Minimal black & white theme inspired by polkadot.com
+//! Minimal black & white theme inspired by polkadot.com, with a light variant
+
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use egui::Color32;
+use serde::{Deserialize, Serialize};
+
+/// Which palette the UI is drawn with
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Theme {
+    #[default]
+    Dark,
+    Light,
+}
 
-/// Minimal black & white palette
-/// No colors - only black, white, and greys
-pub mod colors {
-    use super::Color32;
+impl Theme {
+    pub fn label(self) -> &'static str {
+        match self {
+            Theme::Dark => "Dark",
+            Theme::Light => "Light",
+        }
+    }
+}
+
+/// Greyscale palette; the light one mirrors the dark one around mid-grey
+pub struct Palette {
+    pub bg_primary: Color32,
+    pub bg_elevated: Color32,
+    pub bg_hover: Color32,
+    pub text_primary: Color32,
+    pub text_secondary: Color32,
+    pub text_muted: Color32,
+    pub border: Color32,
+    /// Resting widget fill (buttons, slider rails)
+    pub widget: Color32,
+    pub selection: Color32,
+}
 
-    // === Backgrounds (black to dark grey) ===
-    pub const BG_PRIMARY: Color32 = Color32::from_rgb(0, 0, 0);           // #000000 - pure black
-    pub const BG_ELEVATED: Color32 = Color32::from_rgb(12, 12, 12);       // #0C0C0C - subtle elevation
-    pub const BG_HOVER: Color32 = Color32::from_rgb(24, 24, 24);          // #181818 - hover states
+pub const DARK: Palette = Palette {
+    bg_primary: Color32::from_rgb(0, 0, 0),           // #000000 - pure black
+    bg_elevated: Color32::from_rgb(12, 12, 12),       // #0C0C0C - subtle elevation
+    bg_hover: Color32::from_rgb(24, 24, 24),          // #181818 - hover states
+    text_primary: Color32::from_rgb(255, 255, 255),   // #FFFFFF - primary text
+    text_secondary: Color32::from_rgb(160, 160, 160), // #A0A0A0 - secondary
+    text_muted: Color32::from_rgb(80, 80, 80),        // #505050 - muted/disabled
+    border: Color32::from_rgb(40, 40, 40),            // #282828 - subtle borders
+    widget: Color32::from_rgb(40, 40, 40),
+    selection: Color32::from_rgb(60, 60, 60),
+};
+
+pub const LIGHT: Palette = Palette {
+    bg_primary: Color32::from_rgb(255, 255, 255),     // #FFFFFF - paper white
+    bg_elevated: Color32::from_rgb(243, 243, 243),    // #F3F3F3
+    bg_hover: Color32::from_rgb(231, 231, 231),       // #E7E7E7
+    text_primary: Color32::from_rgb(0, 0, 0),         // #000000
+    text_secondary: Color32::from_rgb(85, 85, 85),    // #555555
+    text_muted: Color32::from_rgb(150, 150, 150),     // #969696 - same contrast as dark muted
+    border: Color32::from_rgb(215, 215, 215),         // #D7D7D7
+    widget: Color32::from_rgb(215, 215, 215),
+    selection: Color32::from_rgb(195, 195, 195),
+};
+
+/// Process-wide theme; free drawing functions read it through [`colors`]
+static LIGHT_THEME: AtomicBool = AtomicBool::new(false);
+
+pub fn current_theme() -> Theme {
+    if LIGHT_THEME.load(Ordering::Relaxed) {
+        Theme::Light
+    } else {
+        Theme::Dark
+    }
+}
 
-    // === Text (white to grey) ===
-    pub const TEXT_PRIMARY: Color32 = Color32::from_rgb(255, 255, 255);   // #FFFFFF - primary text
-    pub const TEXT_SECONDARY: Color32 = Color32::from_rgb(160, 160, 160); // #A0A0A0 - secondary
-    pub const TEXT_MUTED: Color32 = Color32::from_rgb(80, 80, 80);        // #505050 - muted/disabled
+/// Switch the palette behind [`colors`]; pair with `ctx.set_visuals(visuals(theme))`
+pub fn set_theme(theme: Theme) {
+    LIGHT_THEME.store(theme == Theme::Light, Ordering::Relaxed);
+}
 
-    // === Lines & Borders ===
-    pub const BORDER: Color32 = Color32::from_rgb(40, 40, 40);            // #282828 - subtle borders
+pub fn palette() -> &'static Palette {
+    match current_theme() {
+        Theme::Dark => &DARK,
+        Theme::Light => &LIGHT,
+    }
+}
+
+/// Minimal black & white palette of the current theme
+/// No colors - only black, white, and greys
+pub mod colors {
+    use super::{palette, Color32, Theme};
+
+    // === Backgrounds ===
+    pub fn bg_primary() -> Color32 { palette().bg_primary }
+
+    // === Text ===
+    pub fn text_primary() -> Color32 { palette().text_primary }
+    pub fn text_secondary() -> Color32 { palette().text_secondary }
+    pub fn text_muted() -> Color32 { palette().text_muted }
+
+    /// Grey `value` steps away from the background: `shade(255, a)` is white on dark
+    /// and black on light, `shade(20, a)` a backdrop just off the background
+    pub fn shade(value: u8, alpha: u8) -> Color32 {
+        let v = match super::current_theme() {
+            Theme::Dark => value,
+            Theme::Light => 255 - value,
+        };
+        Color32::from_rgba_unmultiplied(v, v, v, alpha)
+    }
 
     // === Data Lines ===
-    // Pure white lines with low alpha for the "overlap = solid" effect
+    // Foreground lines with low alpha for the "overlap = solid" effect
     pub const LINE_ALPHA: u8 = 1;  // 0.5% opacity (1/255 ≈ 0.004, closest to 0.5%)
 }
 
-/// Create minimal black & white egui Visuals
-pub fn minimal_visuals() -> egui::Visuals {
-    use colors::*;
-
-    let mut visuals = egui::Visuals::dark();
+/// Create minimal black & white egui Visuals for `theme`
+pub fn visuals(theme: Theme) -> egui::Visuals {
+    let (mut visuals, p) = match theme {
+        Theme::Dark => (egui::Visuals::dark(), &DARK),
+        Theme::Light => (egui::Visuals::light(), &LIGHT),
+    };
 
-    // Pure black backgrounds
-    visuals.panel_fill = BG_PRIMARY;
-    visuals.window_fill = BG_PRIMARY;
-    visuals.extreme_bg_color = BG_PRIMARY;
-    visuals.faint_bg_color = BG_ELEVATED;
+    // Flat backgrounds
+    visuals.panel_fill = p.bg_primary;
+    visuals.window_fill = p.bg_primary;
+    visuals.extreme_bg_color = p.bg_primary;
+    visuals.faint_bg_color = p.bg_elevated;
 
-    // White text
-    visuals.override_text_color = Some(TEXT_PRIMARY);
+    visuals.override_text_color = Some(p.text_primary);
 
     // Minimal widget styling - all greyscale
-    visuals.widgets.noninteractive.bg_fill = BG_PRIMARY;
-    visuals.widgets.noninteractive.fg_stroke = egui::Stroke::new(1.0, TEXT_MUTED);
-    visuals.widgets.noninteractive.bg_stroke = egui::Stroke::new(1.0, BORDER);
-
-    visuals.widgets.inactive.bg_fill = Color32::from_rgb(40, 40, 40);
-    visuals.widgets.inactive.fg_stroke = egui::Stroke::new(1.0, TEXT_SECONDARY);
-    visuals.widgets.inactive.bg_stroke = egui::Stroke::new(1.0, TEXT_MUTED);
-    visuals.widgets.inactive.weak_bg_fill = BG_ELEVATED;
-
-    visuals.widgets.hovered.bg_fill = BG_ELEVATED;
-    visuals.widgets.hovered.fg_stroke = egui::Stroke::new(1.0, TEXT_PRIMARY);
-    visuals.widgets.hovered.bg_stroke = egui::Stroke::new(1.0, TEXT_MUTED);
-    visuals.widgets.hovered.weak_bg_fill = BG_ELEVATED;
-
-    visuals.widgets.active.bg_fill = BG_HOVER;
-    visuals.widgets.active.fg_stroke = egui::Stroke::new(1.0, TEXT_PRIMARY);
-    visuals.widgets.active.bg_stroke = egui::Stroke::new(1.0, TEXT_SECONDARY);
-    visuals.widgets.active.weak_bg_fill = BG_HOVER;
-
-    // Selection - white on dark
-    visuals.selection.bg_fill = Color32::from_rgb(60, 60, 60);
-    visuals.selection.stroke = egui::Stroke::new(1.0, TEXT_PRIMARY);
-
-    // Hyperlinks - white, underlined
-    visuals.hyperlink_color = TEXT_PRIMARY;
+    visuals.widgets.noninteractive.bg_fill = p.bg_primary;
+    visuals.widgets.noninteractive.fg_stroke = egui::Stroke::new(1.0, p.text_muted);
+    visuals.widgets.noninteractive.bg_stroke = egui::Stroke::new(1.0, p.border);
+
+    visuals.widgets.inactive.bg_fill = p.widget;
+    visuals.widgets.inactive.fg_stroke = egui::Stroke::new(1.0, p.text_secondary);
+    visuals.widgets.inactive.bg_stroke = egui::Stroke::new(1.0, p.text_muted);
+    visuals.widgets.inactive.weak_bg_fill = p.bg_elevated;
+
+    visuals.widgets.hovered.bg_fill = p.bg_elevated;
+    visuals.widgets.hovered.fg_stroke = egui::Stroke::new(1.0, p.text_primary);
+    visuals.widgets.hovered.bg_stroke = egui::Stroke::new(1.0, p.text_muted);
+    visuals.widgets.hovered.weak_bg_fill = p.bg_elevated;
+
+    visuals.widgets.active.bg_fill = p.bg_hover;
+    visuals.widgets.active.fg_stroke = egui::Stroke::new(1.0, p.text_primary);
+    visuals.widgets.active.bg_stroke = egui::Stroke::new(1.0, p.text_secondary);
+    visuals.widgets.active.weak_bg_fill = p.bg_hover;
+
+    visuals.selection.bg_fill = p.selection;
+    visuals.selection.stroke = egui::Stroke::new(1.0, p.text_primary);
+
+    // Hyperlinks - same as text, underlined
+    visuals.hyperlink_color = p.text_primary;
 
     // No shadows - flat design
     visuals.window_shadow = egui::Shadow::NONE;
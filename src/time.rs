@@ -33,3 +33,33 @@ pub fn now_unix_seconds() -> f64 {
         .unwrap_or_default()
         .as_secs_f64()
 }
+
+/// UTC calendar time of a unix timestamp: (year, month, day, hour, minute, second)
+pub fn utc_parts(unix_seconds: f64) -> (i64, u32, u32, u32, u32, u32) {
+    let secs = unix_seconds.floor() as i64;
+    let (days, rem) = (secs.div_euclid(86_400), secs.rem_euclid(86_400));
+    // Civil date from days since 1970-01-01 (proleptic Gregorian, eras of 400 years)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day, (rem / 3_600) as u32, (rem % 3_600 / 60) as u32, (rem % 60) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn utc_parts_of_known_dates() {
+        assert_eq!(utc_parts(0.0), (1970, 1, 1, 0, 0, 0));
+        assert_eq!(utc_parts(951_782_400.0), (2000, 2, 29, 0, 0, 0));
+        assert_eq!(utc_parts(1_792_151_445.9), (2026, 10, 16, 11, 50, 45));
+        assert_eq!(utc_parts(-1.0), (1969, 12, 31, 23, 59, 59));
+    }
+}
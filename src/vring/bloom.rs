@@ -0,0 +1,311 @@
+//! Bloom post-process for the ring
+//!
+//! Particles are drawn a second time into a half-resolution target, blurred
+//! horizontally then vertically, and added on top of the ring in egui's render pass.
+
+use bytemuck::{Pod, Zeroable};
+use egui_wgpu::wgpu::{self, util::DeviceExt};
+
+/// Channel peak (premultiplied) below which a pixel doesn't glow
+const BLOOM_THRESHOLD: f32 = 0.15;
+/// Gain of the blurred glow when added back
+const BLOOM_INTENSITY: f32 = 1.4;
+
+/// Uniform buffer layout for `bloom.wgsl`
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct BloomParams {
+    texel: [f32; 2],
+    threshold: f32,
+    intensity: f32,
+}
+
+/// Offscreen textures and the bind groups reading them, rebuilt on resize
+struct BloomTargets {
+    size: [u32; 2],
+    /// Particles at half resolution
+    scene: wgpu::TextureView,
+    /// After the horizontal pass
+    blurred_h: wgpu::TextureView,
+    /// After the vertical pass, composited into the ring
+    blurred: wgpu::TextureView,
+    horizontal_bind_group: wgpu::BindGroup,
+    vertical_bind_group: wgpu::BindGroup,
+    composite_bind_group: wgpu::BindGroup,
+}
+
+/// Offscreen glow pass, owned by [`RingRenderer`](super::RingRenderer)
+pub struct Bloom {
+    blur_pipeline: wgpu::RenderPipeline,
+    composite_pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    horizontal_params: wgpu::Buffer,
+    vertical_params: wgpu::Buffer,
+    composite_params: wgpu::Buffer,
+    format: wgpu::TextureFormat,
+    targets: Option<BloomTargets>,
+}
+
+impl Bloom {
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("ring_bloom_shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("bloom.wgsl").into()),
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("ring_bloom_bind_group_layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("ring_bloom_pipeline_layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let fullscreen_pipeline = |label: &str, entry_point: &str, blend: Option<wgpu::BlendState>| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some("vs_fullscreen"),
+                    buffers: &[],
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some(entry_point),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format,
+                        blend,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+                cache: None,
+            })
+        };
+
+        let blur_pipeline = fullscreen_pipeline("ring_bloom_blur_pipeline", "fs_blur", None);
+        // Add the glow's color, leave the destination alpha alone
+        let additive = wgpu::BlendState {
+            color: wgpu::BlendComponent {
+                src_factor: wgpu::BlendFactor::One,
+                dst_factor: wgpu::BlendFactor::One,
+                operation: wgpu::BlendOperation::Add,
+            },
+            alpha: wgpu::BlendComponent {
+                src_factor: wgpu::BlendFactor::Zero,
+                dst_factor: wgpu::BlendFactor::One,
+                operation: wgpu::BlendOperation::Add,
+            },
+        };
+        let composite_pipeline =
+            fullscreen_pipeline("ring_bloom_composite_pipeline", "fs_composite", Some(additive));
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("ring_bloom_sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let params_buffer = |label: &str| {
+            device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some(label),
+                contents: bytemuck::bytes_of(&BloomParams::zeroed()),
+                usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::UNIFORM,
+            })
+        };
+
+        Self {
+            blur_pipeline,
+            composite_pipeline,
+            bind_group_layout,
+            sampler,
+            horizontal_params: params_buffer("ring_bloom_horizontal_params"),
+            vertical_params: params_buffer("ring_bloom_vertical_params"),
+            composite_params: params_buffer("ring_bloom_composite_params"),
+            format,
+            targets: None,
+        }
+    }
+
+    /// Make the offscreen targets half of `size` (physical pixels of the ring rect)
+    pub fn resize(&mut self, device: &wgpu::Device, size: [u32; 2]) {
+        let size = [(size[0] / 2).max(1), (size[1] / 2).max(1)];
+        if self.targets.as_ref().is_some_and(|t| t.size == size) {
+            return;
+        }
+        let scene = self.create_target(device, "ring_bloom_scene", size);
+        let blurred_h = self.create_target(device, "ring_bloom_blurred_h", size);
+        let blurred = self.create_target(device, "ring_bloom_blurred", size);
+        self.targets = Some(BloomTargets {
+            size,
+            horizontal_bind_group: self.bind_group(device, &scene, &self.horizontal_params),
+            vertical_bind_group: self.bind_group(device, &blurred_h, &self.vertical_params),
+            composite_bind_group: self.bind_group(device, &blurred, &self.composite_params),
+            scene,
+            blurred_h,
+            blurred,
+        });
+    }
+
+    fn create_target(&self, device: &wgpu::Device, label: &str, size: [u32; 2]) -> wgpu::TextureView {
+        device
+            .create_texture(&wgpu::TextureDescriptor {
+                label: Some(label),
+                size: wgpu::Extent3d {
+                    width: size[0],
+                    height: size[1],
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: self.format,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::RENDER_ATTACHMENT,
+                view_formats: &[],
+            })
+            .create_view(&wgpu::TextureViewDescriptor::default())
+    }
+
+    fn bind_group(
+        &self,
+        device: &wgpu::Device,
+        view: &wgpu::TextureView,
+        params: &wgpu::Buffer,
+    ) -> wgpu::BindGroup {
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("ring_bloom_bind_group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: params.as_entire_binding(),
+                },
+            ],
+        })
+    }
+
+    /// Draw the particles with `draw_particles` into the scene target and blur it.
+    /// Call after [`Bloom::resize`]; does nothing before the first resize.
+    pub fn render(
+        &self,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        draw_particles: impl FnOnce(&mut wgpu::RenderPass<'_>),
+    ) {
+        let Some(targets) = &self.targets else {
+            return;
+        };
+        let texel = [1.0 / targets.size[0] as f32, 1.0 / targets.size[1] as f32];
+        let params = |texel, threshold, intensity| BloomParams { texel, threshold, intensity };
+        queue.write_buffer(
+            &self.horizontal_params,
+            0,
+            bytemuck::bytes_of(&params([texel[0], 0.0], BLOOM_THRESHOLD, 1.0)),
+        );
+        queue.write_buffer(
+            &self.vertical_params,
+            0,
+            bytemuck::bytes_of(&params([0.0, texel[1]], -1.0, 1.0)),
+        );
+        queue.write_buffer(
+            &self.composite_params,
+            0,
+            bytemuck::bytes_of(&params([0.0, 0.0], -1.0, BLOOM_INTENSITY)),
+        );
+
+        {
+            let mut pass = begin_pass(encoder, "ring_bloom_scene_pass", &targets.scene);
+            draw_particles(&mut pass);
+        }
+        let passes = [
+            ("ring_bloom_horizontal_pass", &targets.blurred_h, &targets.horizontal_bind_group),
+            ("ring_bloom_vertical_pass", &targets.blurred, &targets.vertical_bind_group),
+        ];
+        for (label, target, bind_group) in passes {
+            let mut pass = begin_pass(encoder, label, target);
+            pass.set_pipeline(&self.blur_pipeline);
+            pass.set_bind_group(0, bind_group, &[]);
+            pass.draw(0..3, 0..1);
+        }
+    }
+
+    /// Add the blurred glow over the ring (inside egui's render pass, viewport = ring rect)
+    pub fn composite(&self, render_pass: &mut wgpu::RenderPass<'_>) {
+        let Some(targets) = &self.targets else {
+            return;
+        };
+        render_pass.set_pipeline(&self.composite_pipeline);
+        render_pass.set_bind_group(0, &targets.composite_bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
+
+/// Render pass over a whole offscreen target, cleared to transparent
+fn begin_pass<'a>(
+    encoder: &'a mut wgpu::CommandEncoder,
+    label: &str,
+    view: &'a wgpu::TextureView,
+) -> wgpu::RenderPass<'a> {
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some(label),
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view,
+            resolve_target: None,
+            ops: wgpu::Operations {
+                load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                store: wgpu::StoreOp::Store,
+            },
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    })
+}
@@ -0,0 +1,58 @@
+// Ring bloom: separable gaussian blur of the half-resolution particle target,
+// then an additive composite into egui's render pass.
+//
+// All passes draw one fullscreen triangle (3 vertices, no vertex buffer).
+
+struct BloomParams {
+    texel: vec2<f32>,      // blur step in UV units (one axis is zero)
+    threshold: f32,        // channel peak below which pixels don't glow; < 0 keeps everything
+    intensity: f32,        // composite gain
+}
+
+@group(0) @binding(0)
+var src_tex: texture_2d<f32>;
+@group(0) @binding(1)
+var src_sampler: sampler;
+@group(0) @binding(2)
+var<uniform> params: BloomParams;
+
+struct FullscreenOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@vertex
+fn vs_fullscreen(@builtin(vertex_index) vertex_index: u32) -> FullscreenOutput {
+    // (0,0), (2,0), (0,2) in UV covers the viewport with a single triangle
+    let uv = vec2(f32((vertex_index << 1u) & 2u), f32(vertex_index & 2u));
+    var out: FullscreenOutput;
+    out.clip_position = vec4(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, 0.0, 1.0);
+    out.uv = uv;
+    return out;
+}
+
+// 9-tap gaussian, center + 4 on each side
+const WEIGHTS = array<f32, 5>(0.227027, 0.1945946, 0.1216216, 0.054054, 0.016216);
+
+// Premultiplied sample, faded out below the threshold
+fn bright(uv: vec2<f32>) -> vec4<f32> {
+    let c = textureSample(src_tex, src_sampler, uv);
+    let peak = max(c.r, max(c.g, c.b));
+    return c * smoothstep(params.threshold, params.threshold + 0.25, peak);
+}
+
+@fragment
+fn fs_blur(in: FullscreenOutput) -> @location(0) vec4<f32> {
+    var sum = bright(in.uv) * WEIGHTS[0];
+    for (var i = 1; i < 5; i++) {
+        // Taps 1.5 texels apart widen the glow at the same cost
+        let offset = params.texel * f32(i) * 1.5;
+        sum += (bright(in.uv + offset) + bright(in.uv - offset)) * WEIGHTS[i];
+    }
+    return sum;
+}
+
+@fragment
+fn fs_composite(in: FullscreenOutput) -> @location(0) vec4<f32> {
+    return textureSample(src_tex, src_sampler, in.uv) * params.intensity;
+}
@@ -0,0 +1,151 @@
+//! Color vision deficiency (CVD) simulation for previewing color schemas
+//!
+//! Protanopia/deuteranopia use the Viénot et al. (1999) single-plane projection, tritanopia the
+//! Brettel et al. (1997) two half-plane method, both applied in linear RGB. Preview only —
+//! the schema colors themselves are never modified.
+
+use super::ColorLut;
+
+/// Simulated color vision for the preview
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum CvdMode {
+    #[default]
+    Off,
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+}
+
+type Mat3 = [[f32; 3]; 3];
+
+const PROTAN_VIENOT: Mat3 = [
+    [0.112_38, 0.887_62, 0.0],
+    [0.112_38, 0.887_62, 0.0],
+    [0.004_01, -0.004_01, 1.0],
+];
+
+const DEUTAN_VIENOT: Mat3 = [
+    [0.292_75, 0.707_25, 0.0],
+    [0.292_75, 0.707_25, 0.0],
+    [-0.022_34, 0.022_34, 1.0],
+];
+
+const TRITAN_BRETTEL_1: Mat3 = [
+    [1.012_77, 0.135_48, -0.148_26],
+    [-0.012_43, 0.868_12, 0.144_31],
+    [0.075_89, 0.805_00, 0.119_11],
+];
+
+const TRITAN_BRETTEL_2: Mat3 = [
+    [0.936_78, 0.189_79, -0.126_57],
+    [0.061_54, 0.815_26, 0.123_20],
+    [-0.375_62, 1.127_67, 0.247_96],
+];
+
+/// Normal of the plane separating the two Brettel tritan half-planes
+const TRITAN_SEPARATION: [f32; 3] = [0.039_01, -0.027_88, -0.011_13];
+
+impl CvdMode {
+    pub const ALL: &[CvdMode] = &[
+        CvdMode::Off,
+        CvdMode::Protanopia,
+        CvdMode::Deuteranopia,
+        CvdMode::Tritanopia,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Protanopia => "Protanopia",
+            Self::Deuteranopia => "Deuteranopia",
+            Self::Tritanopia => "Tritanopia",
+        }
+    }
+
+    /// How an sRGB(A) color in [0, 1] appears under this mode (alpha unchanged)
+    pub fn simulate(self, rgba: [f32; 4]) -> [f32; 4] {
+        let linear = [
+            srgb_to_linear(rgba[0]),
+            srgb_to_linear(rgba[1]),
+            srgb_to_linear(rgba[2]),
+        ];
+        let m = match self {
+            Self::Off => return rgba,
+            Self::Protanopia => &PROTAN_VIENOT,
+            Self::Deuteranopia => &DEUTAN_VIENOT,
+            Self::Tritanopia => {
+                let side: f32 = linear.iter().zip(TRITAN_SEPARATION).map(|(c, n)| c * n).sum();
+                if side >= 0.0 { &TRITAN_BRETTEL_1 } else { &TRITAN_BRETTEL_2 }
+            }
+        };
+        let out = |row: &[f32; 3]| {
+            linear_to_srgb(row[0] * linear[0] + row[1] * linear[1] + row[2] * linear[2])
+        };
+        [out(&m[0]), out(&m[1]), out(&m[2]), rgba[3]]
+    }
+
+    /// Apply [`simulate`](Self::simulate) to every LUT entry
+    pub fn simulate_lut(self, lut: &ColorLut) -> ColorLut {
+        ColorLut { colors: lut.colors.map(|c| self.simulate(c)) }
+    }
+}
+
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.040_45 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(c: f32) -> f32 {
+    let c = c.clamp(0.0, 1.0);
+    if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: [f32; 4], b: [f32; 4]) -> bool {
+        a.iter().zip(b).all(|(x, y)| (x - y).abs() < 0.01)
+    }
+
+    #[test]
+    fn off_is_identity_and_grays_are_preserved() {
+        let c = [0.9, 0.2, 0.4, 0.8];
+        assert_eq!(CvdMode::Off.simulate(c), c);
+        for &mode in CvdMode::ALL {
+            let gray = [0.5, 0.5, 0.5, 0.8];
+            assert!(close(mode.simulate(gray), gray), "{:?}", mode);
+        }
+    }
+
+    #[test]
+    fn red_green_collapse_for_protan_and_deutan() {
+        let red = [1.0, 0.0, 0.0, 1.0];
+        let green = [0.0, 1.0, 0.0, 1.0];
+        for mode in [CvdMode::Protanopia, CvdMode::Deuteranopia] {
+            let (r, g) = (mode.simulate(red), mode.simulate(green));
+            // Both end up on the yellow–blue axis: red and green channels equal
+            assert!((r[0] - r[1]).abs() < 0.01 && (g[0] - g[1]).abs() < 0.01, "{:?}", mode);
+            assert_eq!(r[3], 1.0);
+        }
+        // Tritanopes still tell red from green
+        let (r, g) = (CvdMode::Tritanopia.simulate(red), CvdMode::Tritanopia.simulate(green));
+        assert!(r[0] > r[1] && g[1] > g[0]);
+    }
+
+    #[test]
+    fn simulate_lut_maps_every_entry() {
+        let mut lut = ColorLut { colors: [[0.0; 4]; 256] };
+        lut.colors[7] = [1.0, 0.0, 0.0, 0.8];
+        let sim = CvdMode::Deuteranopia.simulate_lut(&lut);
+        assert_eq!(sim.colors[7], CvdMode::Deuteranopia.simulate(lut.colors[7]));
+        assert_eq!(sim.colors[0], [0.0, 0.0, 0.0, 0.0]);
+    }
+}
@@ -1,6 +1,6 @@
 //! Data structures for validators ring visualization
 //!
-//! - DirectedParticleInstance: GPU-ready particle data (24 bytes)
+//! - DirectedParticleInstance: GPU-ready particle data (32 bytes)
 //! - DirectedEventBuffer: CPU-side ring buffer for directed events
 
 use std::collections::VecDeque;
@@ -12,7 +12,8 @@ use crate::core::events::EventType;
 // ============================================================================
 
 /// GPU-ready particle instance for directed event visualization.
-/// 24 bytes, suitable for GPU instancing.
+/// 32 bytes, suitable for GPU instancing. Field order must match `GpuParticle` and the
+/// vertex attributes in `shader.wgsl`.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default)]
 pub struct DirectedParticleInstance {
@@ -28,6 +29,12 @@ pub struct DirectedParticleInstance {
     pub event_type: f32,
     /// Path deviation seed [-1..1] for curved trajectories
     pub curve_seed: f32,
+    /// 1.0 if the reporting node sent the message (outbound), 0.0 if it received it
+    /// or the particle is radial
+    pub is_outbound: f32,
+    /// Endpoint the event came from (0 with a single endpoint); filtered on the CPU, the
+    /// shader doesn't read it
+    pub source_id: f32,
 }
 
 // bytemuck traits will be implemented when GPU renderer is added
@@ -43,6 +50,7 @@ impl DirectedParticleInstance {
         travel_duration: f32,
         event_type: u8,
         curve_seed: f32,
+        is_outbound: bool,
     ) -> Self {
         Self {
             source_index: source_index as f32,
@@ -51,6 +59,25 @@ impl DirectedParticleInstance {
             travel_duration,
             event_type: event_type as f32,
             curve_seed,
+            is_outbound: if is_outbound { 1.0 } else { 0.0 },
+            source_id: 0.0,
+        }
+    }
+
+    /// Tag the particle with the endpoint it came from
+    pub fn with_source(self, source_id: u8) -> Self {
+        Self { source_id: source_id as f32, ..self }
+    }
+
+    /// Whether this particle starts or ends at `focus` (always true without a focus)
+    #[inline]
+    pub fn involves(&self, focus: Option<u16>) -> bool {
+        match focus {
+            Some(node) => {
+                let node = node as f32;
+                self.source_index == node || self.target_index == node
+            }
+            None => true,
         }
     }
 }
@@ -121,17 +148,26 @@ impl DirectedEventBuffer {
     }
 
     /// Get particles added since `cursor` for incremental GPU upload.
-    /// Returns (particles deque, new cursor, number of items to skip).
-    /// Caller should iterate `particles.iter().skip(skip)` to get only new items.
-    /// If cursor is stale (evicted), returns all buffered particles (skip=0).
-    pub fn get_new_since(&self, cursor: u64) -> (&VecDeque<DirectedParticleInstance>, u64, usize) {
+    /// Returns (new particles, new cursor). With `focus`, only particles
+    /// touching that node index are yielded (the cursor still advances past all).
+    /// If cursor is stale (evicted), returns all buffered particles.
+    pub fn get_new_since(
+        &self,
+        cursor: u64,
+        focus: Option<u16>,
+    ) -> (impl Iterator<Item = &DirectedParticleInstance> + '_, u64) {
         let oldest = self.total_pushed.saturating_sub(self.particles.len() as u64);
         let skip = if cursor >= oldest {
             (cursor - oldest) as usize
         } else {
             0
         };
-        (&self.particles, self.total_pushed, skip)
+        let particles = self
+            .particles
+            .iter()
+            .skip(skip)
+            .filter(move |p| p.involves(focus));
+        (particles, self.total_pushed)
     }
 
     /// Enable or disable an event type
@@ -166,9 +202,17 @@ impl DirectedEventBuffer {
         self.enabled_types = enabled;
     }
 
-    /// Get active particles within time window.
-    /// Event type filtering already happens in `push()`, so only time-based filtering here.
-    pub fn get_active_particles(&self, now: f32, max_age: f32) -> Vec<DirectedParticleInstance> {
+    /// Get active particles within time window, optionally only those touching `focus`.
+    /// Event type filtering already happens in `push()`, so only time/node filtering here.
+    pub fn get_active_particles(
+        &self,
+        now: f32,
+        max_age: f32,
+        focus: Option<u16>,
+    ) -> Vec<DirectedParticleInstance> {
+        if let Some(node_index) = focus {
+            return self.get_active_particles_for_node(now, max_age, node_index);
+        }
         let cutoff = now - max_age;
         self.particles
             .iter()
@@ -177,6 +221,23 @@ impl DirectedEventBuffer {
             .collect()
     }
 
+    /// Active particles whose source or target is `node_index` (sent, received, or radial).
+    /// Indices are stored as f32, so the node is converted once and compared exactly.
+    pub fn get_active_particles_for_node(
+        &self,
+        now: f32,
+        max_age: f32,
+        node_index: u16,
+    ) -> Vec<DirectedParticleInstance> {
+        let cutoff = now - max_age;
+        let node = node_index as f32;
+        self.particles
+            .iter()
+            .filter(|p| p.birth_time >= cutoff && (p.source_index == node || p.target_index == node))
+            .copied()
+            .collect()
+    }
+
     /// Count active particles within time window (no allocation)
     pub fn active_count(&self, now: f32, max_age: f32) -> usize {
         let cutoff = now - max_age;
@@ -229,14 +290,42 @@ impl DirectedEventBuffer {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_active_particles_for_node() {
+        let mut buffer = DirectedEventBuffer::new(16);
+        buffer.push(DirectedParticleInstance::new(1, 2, 1.0, 1.0, 106, 0.0, false)); // into 2
+        buffer.push(DirectedParticleInstance::new(2, 3, 1.0, 1.0, 106, 0.0, false)); // out of 2
+        buffer.push(DirectedParticleInstance::new(2, 2, 1.0, 1.0, 10, 0.0, false)); // radial at 2
+        buffer.push(DirectedParticleInstance::new(3, 4, 1.0, 1.0, 106, 0.0, false)); // unrelated
+        buffer.push(DirectedParticleInstance::new(0, 2, -5.0, 1.0, 106, 0.0, false)); // expired
+
+        let endpoints = |node| -> Vec<(f32, f32)> {
+            buffer
+                .get_active_particles_for_node(2.0, 2.0, node)
+                .iter()
+                .map(|p| (p.source_index, p.target_index))
+                .collect()
+        };
+        // Outbound-only: node 1 only ever sends
+        assert_eq!(endpoints(1), vec![(1.0, 2.0)]);
+        // Inbound-only: node 4 only ever receives
+        assert_eq!(endpoints(4), vec![(3.0, 4.0)]);
+        // Both directions plus its own radial particle
+        assert_eq!(endpoints(2), vec![(1.0, 2.0), (2.0, 3.0), (2.0, 2.0)]);
+        assert!(endpoints(9).is_empty());
+
+        // The focus path of get_active_particles matches
+        assert_eq!(buffer.get_active_particles(2.0, 2.0, Some(2)).len(), 3);
+    }
+
     #[test]
     fn test_directed_event_buffer() {
         let mut buffer = DirectedEventBuffer::new(3);
 
-        let p1 = DirectedParticleInstance::new(0, 1, 0.0, 1.0, 106, 0.5);
-        let p2 = DirectedParticleInstance::new(1, 2, 1.0, 1.0, 128, -0.5);
-        let p3 = DirectedParticleInstance::new(2, 3, 2.0, 1.0, 131, 0.0);
-        let p4 = DirectedParticleInstance::new(3, 4, 3.0, 1.0, 106, 0.3);
+        let p1 = DirectedParticleInstance::new(0, 1, 0.0, 1.0, 106, 0.5, false);
+        let p2 = DirectedParticleInstance::new(1, 2, 1.0, 1.0, 128, -0.5, false);
+        let p3 = DirectedParticleInstance::new(2, 3, 2.0, 1.0, 131, 0.0, false);
+        let p4 = DirectedParticleInstance::new(3, 4, 3.0, 1.0, 106, 0.3, false);
 
         buffer.push(p1);
         buffer.push(p2);
@@ -248,17 +337,21 @@ mod tests {
         assert_eq!(buffer.len(), 3);
 
         // Filter by time
-        let active = buffer.get_active_particles(3.5, 2.0);
+        let active = buffer.get_active_particles(3.5, 2.0, None);
         assert_eq!(active.len(), 2); // p3 and p4 (birth_time >= 1.5)
 
+        // Focus on node 4: only p4 (3 → 4) touches it
+        let active = buffer.get_active_particles(3.5, 2.0, Some(4));
+        assert_eq!(active.len(), 1);
+
         // Disable event type 106 — new pushes of type 106 are dropped
         buffer.set_type_enabled(106, false);
-        let p5 = DirectedParticleInstance::new(4, 5, 3.5, 1.0, 106, 0.1);
+        let p5 = DirectedParticleInstance::new(4, 5, 3.5, 1.0, 106, 0.1, false);
         buffer.push(p5); // dropped because type 106 is disabled
         assert_eq!(buffer.len(), 3); // still 3 (p5 was filtered out)
 
         // Already-buffered type 106 particles remain (filtering is at push time)
-        let active = buffer.get_active_particles(3.5, 2.0);
+        let active = buffer.get_active_particles(3.5, 2.0, None);
         assert_eq!(active.len(), 2); // p3 and p4 still present
     }
 
@@ -285,34 +378,36 @@ mod tests {
     #[test]
     fn test_get_new_since_empty() {
         let buffer = DirectedEventBuffer::new(10);
-        let (particles, cursor, skip) = buffer.get_new_since(0);
-        assert!(particles.is_empty());
+        let (particles, cursor) = buffer.get_new_since(0, None);
+        assert_eq!(particles.count(), 0);
         assert_eq!(cursor, 0);
-        assert_eq!(skip, 0);
     }
 
     #[test]
     fn test_get_new_since_not_full() {
         let mut buffer = DirectedEventBuffer::new(10);
-        buffer.push(DirectedParticleInstance::new(0, 1, 0.0, 1.0, 10, 0.0));
-        buffer.push(DirectedParticleInstance::new(1, 2, 1.0, 1.0, 11, 0.0));
+        buffer.push(DirectedParticleInstance::new(0, 1, 0.0, 1.0, 10, 0.0, false));
+        buffer.push(DirectedParticleInstance::new(1, 2, 1.0, 1.0, 11, 0.0, false));
 
-        // cursor=0 → get all 2 items (skip=0)
-        let (_, cursor, skip) = buffer.get_new_since(0);
+        // cursor=0 → get all 2 items
+        let (particles, cursor) = buffer.get_new_since(0, None);
         assert_eq!(cursor, 2);
-        assert_eq!(skip, 0);
+        assert_eq!(particles.count(), 2);
 
         // cursor=1 → skip first, get 1 new
-        let (particles, cursor, skip) = buffer.get_new_since(1);
+        let (particles, cursor) = buffer.get_new_since(1, None);
         assert_eq!(cursor, 2);
-        assert_eq!(skip, 1);
-        assert_eq!(particles.iter().skip(skip).count(), 1);
+        assert_eq!(particles.count(), 1);
 
         // cursor=2 → nothing new
-        let (particles, cursor, skip) = buffer.get_new_since(2);
+        let (particles, cursor) = buffer.get_new_since(2, None);
+        assert_eq!(cursor, 2);
+        assert_eq!(particles.count(), 0);
+
+        // Focus on node 0: only the first particle (0 → 1) touches it
+        let (particles, cursor) = buffer.get_new_since(0, Some(0));
         assert_eq!(cursor, 2);
-        assert_eq!(skip, 2);
-        assert_eq!(particles.iter().skip(skip).count(), 0);
+        assert_eq!(particles.count(), 1);
     }
 
     #[test]
@@ -320,27 +415,24 @@ mod tests {
         let mut buffer = DirectedEventBuffer::new(3);
         // Push 5 items into capacity-3 buffer → items 0,1 evicted
         for i in 0..5u16 {
-            buffer.push(DirectedParticleInstance::new(i, i + 1, i as f32, 1.0, 10, 0.0));
+            buffer.push(DirectedParticleInstance::new(i, i + 1, i as f32, 1.0, 10, 0.0, false));
         }
 
         assert_eq!(buffer.len(), 3); // items 2,3,4 remain
         // total_pushed=5, oldest=5-3=2
 
         // Stale cursor (cursor=0, but oldest=2) → skip=0, get all 3
-        let (particles, cursor, skip) = buffer.get_new_since(0);
+        let (particles, cursor) = buffer.get_new_since(0, None);
         assert_eq!(cursor, 5);
-        assert_eq!(skip, 0);
-        assert_eq!(particles.iter().skip(skip).count(), 3);
+        assert_eq!(particles.count(), 3);
 
         // cursor=3 → skip 1, get 2 new
-        let (particles, cursor, skip) = buffer.get_new_since(3);
+        let (particles, cursor) = buffer.get_new_since(3, None);
         assert_eq!(cursor, 5);
-        assert_eq!(skip, 1);
-        assert_eq!(particles.iter().skip(skip).count(), 2);
+        assert_eq!(particles.count(), 2);
 
         // cursor=5 → nothing new
-        let (particles, _, skip) = buffer.get_new_since(5);
-        assert_eq!(skip, 3);
-        assert_eq!(particles.iter().skip(skip).count(), 0);
+        let (particles, _) = buffer.get_new_since(5, None);
+        assert_eq!(particles.count(), 0);
     }
 }
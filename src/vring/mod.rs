@@ -3,12 +3,17 @@
 //! Renders directed events as particles traveling between validators
 //! arranged on a circle.
 
+mod bloom;
+mod cvd;
 mod data;
 mod renderer;
 
+pub use cvd::CvdMode;
+
 pub use data::{DirectedEventBuffer, DirectedParticleInstance, PulseEvent};
 
 pub use renderer::{ColorLut, ColorSchema, FilterBitfield, GpuParticle, RingCallback, RingRenderer, Uniforms};
+pub(crate) use renderer::{deserialize_palette, InstanceRing};
 
 #[allow(unused_imports)]
 pub use renderer::CATEGORY_COLORS;
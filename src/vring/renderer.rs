@@ -2,17 +2,94 @@
 //!
 //! Renders directed events as particles traveling between validators
 //! arranged on a circle using GPU instancing. Draws directly into
-//! egui's render pass via CallbackTrait.
+//! egui's render pass via CallbackTrait, optionally followed by a bloom pass
+//! (see [`Bloom`]).
 
 use bytemuck::{Pod, Zeroable};
 use egui_wgpu::wgpu::{self, util::DeviceExt};
 use std::sync::Arc;
 
+use super::bloom::Bloom;
 use super::DirectedParticleInstance;
 
 const BUFFER_CAPACITY: usize = 5_000_000; // 5M particles per buffer
 const NUM_BUFFERS: usize = 4; // 4 buffers = 20M particles total
-const MAX_INSTANCES: usize = BUFFER_CAPACITY * NUM_BUFFERS;
+
+/// Write-head bookkeeping for instance data spread over `num_buffers` GPU buffers of
+/// `buffer_capacity` slots each, used as one circular buffer.
+///
+/// Once the head passes the last slot it wraps to 0 and the oldest instances are overwritten.
+/// Particles are independent (the shader ages them by `birth_time`), so the draw range of each
+/// buffer is simply its high-water mark — after a wrap every buffer is drawn in full and the
+/// overwritten slots hold the newer particles.
+pub(crate) struct InstanceRing {
+    buffer_capacity: usize,
+    num_buffers: usize,
+    head: usize,
+    counts: Vec<u32>,
+}
+
+/// One contiguous copy produced by [`InstanceRing::advance`]
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct RingWrite {
+    /// Destination buffer index
+    pub buffer: usize,
+    /// First destination slot within that buffer
+    pub offset: usize,
+    /// Source range within the uploaded batch
+    pub src: std::ops::Range<usize>,
+}
+
+impl InstanceRing {
+    pub fn new(buffer_capacity: usize, num_buffers: usize) -> Self {
+        Self {
+            buffer_capacity,
+            num_buffers,
+            head: 0,
+            counts: vec![0; num_buffers],
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        self.buffer_capacity * self.num_buffers
+    }
+
+    pub fn reset(&mut self) {
+        self.head = 0;
+        self.counts.fill(0);
+    }
+
+    /// Reserve slots for a batch of `len` instances and return where each chunk goes.
+    /// A batch larger than the whole ring only keeps its newest `capacity` instances.
+    pub fn advance(&mut self, len: usize) -> Vec<RingWrite> {
+        let capacity = self.capacity();
+        let skip = len.saturating_sub(capacity);
+        if skip > 0 {
+            // The skipped prefix would have been overwritten within this same batch
+            self.head = (self.head + skip) % capacity;
+        }
+
+        let mut writes = Vec::new();
+        let mut src = skip;
+        while src < len {
+            let buffer = self.head / self.buffer_capacity;
+            let offset = self.head % self.buffer_capacity;
+            let n = (len - src).min(self.buffer_capacity - offset);
+
+            writes.push(RingWrite { buffer, offset, src: src..src + n });
+            self.counts[buffer] = self.counts[buffer].max((offset + n) as u32);
+
+            src += n;
+            self.head = (self.head + n) % capacity;
+        }
+        writes
+    }
+
+    /// Instances to draw from each buffer (`0..count`)
+    pub fn counts(&self) -> &[u32] {
+        &self.counts
+    }
+}
 
 /// GPU-compatible particle instance (must match DirectedParticleInstance layout)
 #[repr(C)]
@@ -24,6 +101,8 @@ pub struct GpuParticle {
     pub travel_duration: f32,
     pub event_type: f32,
     pub curve_seed: f32,
+    pub is_outbound: f32,
+    pub source_id: f32,
 }
 
 impl From<&DirectedParticleInstance> for GpuParticle {
@@ -35,6 +114,8 @@ impl From<&DirectedParticleInstance> for GpuParticle {
             travel_duration: p.travel_duration,
             event_type: p.event_type,
             curve_seed: p.curve_seed,
+            is_outbound: p.is_outbound,
+            source_id: p.source_id,
         }
     }
 }
@@ -48,7 +129,11 @@ pub struct Uniforms {
     pub aspect_ratio: f32,
     pub point_size: f32,
     pub speed_factor: f32,
-    pub _pad: [f32; 3],
+    /// Ring radius in NDC (fraction of half the viewport height)
+    pub ring_radius: f32,
+    /// Afterglow length in units of particle progress; 0 disables trails
+    pub trail_length: f32,
+    pub _pad: f32,
 }
 
 impl Default for Uniforms {
@@ -59,7 +144,9 @@ impl Default for Uniforms {
             aspect_ratio: 1.0,
             point_size: 0.005,
             speed_factor: 1.0,
-            _pad: [0.0; 3],
+            ring_radius: 0.75,
+            trail_length: 0.0,
+            _pad: 0.0,
         }
     }
 }
@@ -81,17 +168,20 @@ impl Default for ColorLut {
     }
 }
 
-/// Predefined color schema for event categories and per-event distinct palettes.
-#[derive(Clone, Copy, PartialEq, Eq, Default)]
+/// Color schema for event categories and per-event distinct palettes.
+#[derive(Clone, Copy, PartialEq, Default, serde::Serialize, serde::Deserialize)]
 pub enum ColorSchema {
     #[default]
     Vivid,
     Accessible,
     Pipeline,
     Monochrome,
+    /// User-picked category colors, in EVENT_CATEGORIES order
+    Custom(#[serde(deserialize_with = "deserialize_palette")] [[f32; 4]; 13]),
 }
 
 impl ColorSchema {
+    /// Built-in schemas (Custom is offered separately with its color pickers)
     pub const ALL: &[ColorSchema] = &[
         ColorSchema::Vivid,
         ColorSchema::Accessible,
@@ -105,19 +195,21 @@ impl ColorSchema {
             Self::Accessible => "Accessible",
             Self::Pipeline => "Pipeline",
             Self::Monochrome => "Mono",
+            Self::Custom(_) => "Custom",
         }
     }
 
     /// Category colors [f32; 4] RGBA, matching EVENT_CATEGORIES order:
     /// Status, Connection, Block Auth/Import, Block Distribution, Safrole Tickets,
     /// Work Package, Guaranteeing, Availability, Bundle Recovery, Segment Recovery,
-    /// Preimages, Meta
-    pub fn colors(self) -> &'static [[f32; 4]; 12] {
+    /// Preimages, Meta, Unknown
+    pub fn colors(&self) -> &[[f32; 4]; 13] {
         match self {
             Self::Vivid => &VIVID_COLORS,
             Self::Accessible => &ACCESSIBLE_COLORS,
             Self::Pipeline => &PIPELINE_COLORS,
             Self::Monochrome => &MONOCHROME_COLORS,
+            Self::Custom(colors) => colors,
         }
     }
 
@@ -171,6 +263,17 @@ impl ColorSchema {
                     [r, g, b, 0.8]
                 }).collect()
             }
+            Self::Custom(colors) => {
+                // Walk around the category colors, blending between neighbours
+                (0..n).map(|i| {
+                    let pos = i as f32 * colors.len() as f32 / n as f32;
+                    let a = colors[pos as usize];
+                    let b = colors[(pos as usize + 1) % colors.len()];
+                    let t = pos.fract();
+                    let mix = |k: usize| a[k] + (b[k] - a[k]) * t;
+                    [mix(0), mix(1), mix(2), 0.8]
+                }).collect()
+            }
         }
     }
 }
@@ -182,7 +285,7 @@ impl std::fmt::Display for ColorSchema {
 }
 
 /// Vivid: infrastructure=cool/muted, consensus=warm/gold, pipeline=saturated primaries, verification=warm
-const VIVID_COLORS: [[f32; 4]; 12] = [
+const VIVID_COLORS: [[f32; 4]; 13] = [
     [0.40, 0.80, 0.40, 0.8], // Status - muted green
     [0.40, 0.60, 1.00, 0.8], // Connection - steel blue
     [1.00, 0.80, 0.40, 0.8], // Block Auth/Import - amber/gold
@@ -195,10 +298,11 @@ const VIVID_COLORS: [[f32; 4]; 12] = [
     [0.80, 0.40, 0.47, 0.8], // Segment Recovery - rose
     [0.80, 0.80, 0.80, 0.8], // Preimages - light gray
     [0.50, 0.50, 0.50, 0.8], // Meta - gray
+    [0.60, 0.60, 0.60, 0.8], // Unknown - neutral gray
 ];
 
 /// Accessible: Okabe-Ito palette, safe for deuteranopia/protanopia/tritanopia
-const ACCESSIBLE_COLORS: [[f32; 4]; 12] = [
+const ACCESSIBLE_COLORS: [[f32; 4]; 13] = [
     [0.34, 0.71, 0.91, 0.8], // Status - sky blue
     [0.00, 0.45, 0.70, 0.8], // Connection - blue
     [0.90, 0.62, 0.00, 0.8], // Block Auth/Import - orange
@@ -211,10 +315,11 @@ const ACCESSIBLE_COLORS: [[f32; 4]; 12] = [
     [0.60, 0.60, 0.20, 0.8], // Segment Recovery - olive
     [0.73, 0.73, 0.73, 0.8], // Preimages - light gray
     [0.53, 0.53, 0.53, 0.8], // Meta - gray
+    [0.60, 0.60, 0.60, 0.8], // Unknown - neutral gray
 ];
 
 /// Pipeline: cool-to-warm gradient encoding lifecycle stage, infrastructure grayed out
-const PIPELINE_COLORS: [[f32; 4]; 12] = [
+const PIPELINE_COLORS: [[f32; 4]; 13] = [
     [0.47, 0.47, 0.47, 0.8], // Status - neutral gray
     [0.53, 0.53, 0.53, 0.8], // Connection - neutral gray
     [0.67, 0.67, 0.67, 0.8], // Block Auth/Import - light gray
@@ -227,10 +332,11 @@ const PIPELINE_COLORS: [[f32; 4]; 12] = [
     [0.87, 0.27, 0.27, 0.8], // Segment Recovery - red (recovery)
     [0.40, 0.40, 0.40, 0.8], // Preimages - dark gray
     [0.33, 0.33, 0.33, 0.8], // Meta - dark gray
+    [0.50, 0.50, 0.50, 0.8], // Unknown - neutral gray
 ];
 
 /// Monochrome: single cyan hue, luminance differentiation only
-const MONOCHROME_COLORS: [[f32; 4]; 12] = [
+const MONOCHROME_COLORS: [[f32; 4]; 13] = [
     [0.20, 0.40, 0.40, 0.8], // Status - darkest
     [0.27, 0.47, 0.47, 0.8], // Connection
     [0.33, 0.53, 0.53, 0.8], // Block Auth/Import
@@ -243,11 +349,26 @@ const MONOCHROME_COLORS: [[f32; 4]; 12] = [
     [0.27, 0.60, 0.60, 0.8], // Segment Recovery
     [0.27, 0.47, 0.47, 0.8], // Preimages
     [0.20, 0.40, 0.40, 0.8], // Meta - darkest
+    [0.50, 0.50, 0.50, 0.8], // Unknown - neutral gray
 ];
 
+/// Category palette that may have been saved before the last category was added: entries
+/// it lacks come from [`ColorSchema::Vivid`], so older settings keep loading
+pub(crate) fn deserialize_palette<'de, D>(deserializer: D) -> Result<[[f32; 4]; 13], D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let stored = <Vec<[f32; 4]> as serde::Deserialize>::deserialize(deserializer)?;
+    let mut palette = VIVID_COLORS;
+    for (slot, color) in palette.iter_mut().zip(stored) {
+        *slot = color;
+    }
+    Ok(palette)
+}
+
 /// Backward-compatible alias
 #[allow(dead_code)]
-pub const CATEGORY_COLORS: [[f32; 4]; 12] = VIVID_COLORS;
+pub const CATEGORY_COLORS: [[f32; 4]; 13] = VIVID_COLORS;
 
 fn hsl_to_rgb(h: f32, s: f32, l: f32) -> (f32, f32, f32) {
     let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
@@ -290,7 +411,7 @@ impl FilterBitfield {
 }
 
 /// GPU renderer for the validators ring.
-/// Renders directly into egui's render pass (no intermediate texture).
+/// Renders directly into egui's render pass; only the optional bloom uses offscreen textures.
 pub struct RingRenderer {
     pipeline: wgpu::RenderPipeline,
     bind_group: wgpu::BindGroup,
@@ -298,11 +419,11 @@ pub struct RingRenderer {
     color_lut_buffer: wgpu::Buffer,
     filter_buffer: wgpu::Buffer,
     instance_buffers: Vec<wgpu::Buffer>,
-    buffer_counts: Vec<u32>,
 
-    // Incremental upload tracking
-    gpu_write_head: usize,
-    total_instances: u32,
+    // Incremental upload tracking (wraps around, overwriting the oldest particles)
+    ring: InstanceRing,
+
+    bloom: Bloom,
 }
 
 impl RingRenderer {
@@ -394,6 +515,11 @@ impl RingRenderer {
                             shader_location: 5,
                             format: wgpu::VertexFormat::Float32, // curve_seed
                         },
+                        wgpu::VertexAttribute {
+                            offset: 24,
+                            shader_location: 6,
+                            format: wgpu::VertexFormat::Float32, // is_outbound
+                        },
                     ],
                 }],
                 compilation_options: Default::default(),
@@ -478,16 +604,39 @@ impl RingRenderer {
             color_lut_buffer,
             filter_buffer,
             instance_buffers,
-            buffer_counts: vec![0; NUM_BUFFERS],
-            gpu_write_head: 0,
-            total_instances: 0,
+            ring: InstanceRing::new(BUFFER_CAPACITY, NUM_BUFFERS),
+            bloom: Bloom::new(device, target_format),
         }
     }
 
     pub fn reset(&mut self) {
-        self.gpu_write_head = 0;
-        self.total_instances = 0;
-        self.buffer_counts.fill(0);
+        self.ring.reset();
+    }
+
+    /// Draw every uploaded particle into `render_pass`
+    pub fn draw_particles(&self, render_pass: &mut wgpu::RenderPass<'_>) {
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+
+        for (i, buffer) in self.instance_buffers.iter().enumerate() {
+            let count = self.ring.counts()[i];
+            if count > 0 {
+                render_pass.set_vertex_buffer(0, buffer.slice(..));
+                render_pass.draw(0..96, 0..count);
+            }
+        }
+    }
+
+    /// Render the glow for a ring rect of `size` physical pixels; run after `upload_data`
+    pub fn prepare_bloom(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        size: [u32; 2],
+    ) {
+        self.bloom.resize(device, size);
+        self.bloom.render(queue, encoder, |pass| self.draw_particles(pass));
     }
 
     /// Upload new particles, uniforms, and filter to GPU buffers.
@@ -501,43 +650,13 @@ impl RingRenderer {
         color_lut: &ColorLut,
     ) {
         // Upload new particles
-        if !new_particles.is_empty() {
-            let particle_size = std::mem::size_of::<GpuParticle>();
-            let mut remaining = new_particles;
-            let mut write_pos = self.gpu_write_head;
-
-            while !remaining.is_empty() {
-                let buffer_idx = write_pos / BUFFER_CAPACITY;
-                let offset_in_buffer = write_pos % BUFFER_CAPACITY;
-                let space_in_buffer = BUFFER_CAPACITY - offset_in_buffer;
-                let to_write = remaining.len().min(space_in_buffer);
-
-                let (chunk, rest) = remaining.split_at(to_write);
-                remaining = rest;
-
-                let byte_offset = (offset_in_buffer * particle_size) as u64;
-                queue.write_buffer(
-                    &self.instance_buffers[buffer_idx % NUM_BUFFERS],
-                    byte_offset,
-                    bytemuck::cast_slice(chunk),
-                );
-
-                let new_end = offset_in_buffer + to_write;
-                self.buffer_counts[buffer_idx % NUM_BUFFERS] =
-                    self.buffer_counts[buffer_idx % NUM_BUFFERS].max(new_end as u32);
-
-                write_pos = (write_pos + to_write) % MAX_INSTANCES;
-            }
-
-            self.gpu_write_head = write_pos;
-            self.total_instances =
-                (self.total_instances + new_particles.len() as u32).min(MAX_INSTANCES as u32);
-
-            if self.total_instances >= MAX_INSTANCES as u32 {
-                for count in &mut self.buffer_counts {
-                    *count = BUFFER_CAPACITY as u32;
-                }
-            }
+        let particle_size = std::mem::size_of::<GpuParticle>();
+        for write in self.ring.advance(new_particles.len()) {
+            queue.write_buffer(
+                &self.instance_buffers[write.buffer],
+                (write.offset * particle_size) as u64,
+                bytemuck::cast_slice(&new_particles[write.src]),
+            );
         }
 
         queue.write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(uniforms));
@@ -545,21 +664,6 @@ impl RingRenderer {
         queue.write_buffer(&self.color_lut_buffer, 0, bytemuck::bytes_of(color_lut));
     }
 
-    pub fn pipeline(&self) -> &wgpu::RenderPipeline {
-        &self.pipeline
-    }
-
-    pub fn bind_group(&self) -> &wgpu::BindGroup {
-        &self.bind_group
-    }
-
-    pub fn instance_buffers(&self) -> &[wgpu::Buffer] {
-        &self.instance_buffers
-    }
-
-    pub fn buffer_counts(&self) -> &[u32] {
-        &self.buffer_counts
-    }
 }
 
 /// Callback for egui integration
@@ -569,15 +673,18 @@ pub struct RingCallback {
     pub filter: FilterBitfield,
     pub color_lut: ColorLut,
     pub reset: bool,
+    /// Add the bloom glow; needs the ring rect to size its offscreen targets
+    pub bloom: bool,
+    pub rect: egui::Rect,
 }
 
 impl egui_wgpu::CallbackTrait for RingCallback {
     fn prepare(
         &self,
-        _device: &wgpu::Device,
+        device: &wgpu::Device,
         queue: &wgpu::Queue,
-        _screen_descriptor: &egui_wgpu::ScreenDescriptor,
-        _encoder: &mut wgpu::CommandEncoder,
+        screen_descriptor: &egui_wgpu::ScreenDescriptor,
+        encoder: &mut wgpu::CommandEncoder,
         callback_resources: &mut egui_wgpu::CallbackResources,
     ) -> Vec<wgpu::CommandBuffer> {
         let Some(renderer): Option<&mut RingRenderer> = callback_resources.get_mut() else {
@@ -589,6 +696,10 @@ impl egui_wgpu::CallbackTrait for RingCallback {
         }
 
         renderer.upload_data(queue, &self.new_particles, &self.uniforms, &self.filter, &self.color_lut);
+        if self.bloom {
+            let size = self.rect.size() * screen_descriptor.pixels_per_point;
+            renderer.prepare_bloom(device, queue, encoder, [size.x as u32, size.y as u32]);
+        }
         vec![]
     }
 
@@ -602,15 +713,82 @@ impl egui_wgpu::CallbackTrait for RingCallback {
             return;
         };
 
-        render_pass.set_pipeline(renderer.pipeline());
-        render_pass.set_bind_group(0, renderer.bind_group(), &[]);
+        renderer.draw_particles(render_pass);
+        if self.bloom {
+            renderer.bloom.composite(render_pass);
+        }
+    }
+}
 
-        for (i, buffer) in renderer.instance_buffers().iter().enumerate() {
-            let count = renderer.buffer_counts()[i];
-            if count > 0 {
-                render_pass.set_vertex_buffer(0, buffer.slice(..));
-                render_pass.draw(0..96, 0..count);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gpu_particle_matches_instance_layout() {
+        use std::mem::size_of;
+        assert_eq!(size_of::<GpuParticle>(), 32);
+        assert_eq!(size_of::<GpuParticle>(), size_of::<DirectedParticleInstance>());
+
+        let p = DirectedParticleInstance::new(3, 7, 1.5, 2.0, 106, -0.25, true);
+        let gpu = GpuParticle::from(&p);
+        assert_eq!(gpu.is_outbound, 1.0);
+        assert_eq!(bytemuck::bytes_of(&gpu)[24..28], 1.0f32.to_ne_bytes());
+    }
+
+    #[test]
+    fn instance_ring_wraps_past_capacity() {
+        // 2 buffers × 4 slots
+        let mut ring = InstanceRing::new(4, 2);
+
+        // Fill part of buffer 0, then spill into buffer 1
+        assert_eq!(ring.advance(3), vec![RingWrite { buffer: 0, offset: 0, src: 0..3 }]);
+        assert_eq!(
+            ring.advance(3),
+            vec![
+                RingWrite { buffer: 0, offset: 3, src: 0..1 },
+                RingWrite { buffer: 1, offset: 0, src: 1..3 },
+            ]
+        );
+        assert_eq!(ring.counts(), &[4, 2]);
+
+        // Past the end: wrap to slot 0 and overwrite the oldest
+        assert_eq!(
+            ring.advance(4),
+            vec![
+                RingWrite { buffer: 1, offset: 2, src: 0..2 },
+                RingWrite { buffer: 0, offset: 0, src: 2..4 },
+            ]
+        );
+        assert_eq!(ring.counts(), &[4, 4]);
+
+        // Simulate a long session: the head stays in range and every write fits its buffer
+        for batch in [1, 7, 8, 3, 5, 2, 8, 6] {
+            let writes = ring.advance(batch);
+            assert_eq!(writes.iter().map(|w| w.src.len()).sum::<usize>(), batch.min(8));
+            for w in &writes {
+                assert!(w.buffer < 2 && w.offset + w.src.len() <= 4);
             }
+            assert_eq!(ring.counts(), &[4, 4]);
         }
     }
+
+    #[test]
+    fn instance_ring_oversized_batch_keeps_newest() {
+        let mut ring = InstanceRing::new(4, 2);
+        let writes = ring.advance(11);
+        // Only the last 8 of 11 land; they start where the skipped 3 would have ended
+        assert_eq!(
+            writes,
+            vec![
+                RingWrite { buffer: 0, offset: 3, src: 3..4 },
+                RingWrite { buffer: 1, offset: 0, src: 4..8 },
+                RingWrite { buffer: 0, offset: 0, src: 8..11 },
+            ]
+        );
+
+        ring.reset();
+        assert_eq!(ring.counts(), &[0, 0]);
+        assert!(ring.advance(0).is_empty());
+    }
 }
@@ -7,8 +7,9 @@
 // instance in a single draw call. Splitting radial vs directed into separate draw calls
 // would require separate buffers and complicate the incremental upload system.
 //
-// Radial events (source == target): Only the first 6 vertices form a circle quad.
-//   Vertices 6–95 are moved off-screen (early discard in VS, cheap).
+// Radial events (source == target): The first 6 vertices form a circle quad; with trails
+//   on, segments 1..=TRAIL_GHOSTS repeat it as fading dots lagging behind in t.
+//   The remaining vertices are moved off-screen (early discard in VS, cheap).
 //   quad_uv carries [-1,1] coords so the FS can discard outside the unit circle.
 //
 // Directed events (source != target): All 96 vertices used. Each group of 6 forms a
@@ -21,8 +22,8 @@ struct Uniforms {
     aspect_ratio: f32,
     point_size: f32,       // line half-width in NDC
     speed_factor: f32,
-    _pad1: f32,
-    _pad2: f32,
+    ring_radius: f32,      // ring radius in NDC (fraction of half the viewport height)
+    trail_length: f32,     // afterglow length in units of progress t; 0 = off
     _pad3: f32,
 }
 
@@ -50,9 +51,19 @@ const VERTS_PER_INSTANCE: u32 = NUM_SEGMENTS * 6u;  // 96 vertices
 // Directed events travel 4x faster than their travel_duration
 const DIRECTED_SPEED: f32 = 8.0;
 
+// Faded copies drawn behind a radial particle when trail_length > 0
+// IMPORTANT: CPU fallback in ring.rs must match.
+const TRAIL_GHOSTS: u32 = 4u;
+
 // Line half-width in NDC for directed trail lines
 const LINE_HALF_WIDTH: f32 = 0.0015;
 
+// Direction encoding: a message reported by its sender (outbound) is drawn wider and at full
+// alpha; the receiver's report of it (inbound) is thinner and dimmer, trailing behind.
+// IMPORTANT: CPU fallback in ring.rs must match these values.
+const OUTBOUND_WIDTH_SCALE: f32 = 1.6;
+const INBOUND_ALPHA_SCALE: f32 = 0.45;
+
 // Quad vertex offsets for 2 triangles (6 vertices per quad segment)
 // Triangle 1: 0,1,2  Triangle 2: 2,1,3
 const QUAD_POS = array<vec2<f32>, 6>(
@@ -61,16 +72,15 @@ const QUAD_POS = array<vec2<f32>, 6>(
 );
 
 const PI: f32 = 3.14159265359;
-// IMPORTANT: CPU overlay (validator dots, ring outline) in app.rs must match this value.
-// NDC-to-pixel conversion: pixel_radius = RING_RADIUS * rect.height() * 0.5
+// IMPORTANT: CPU overlay (validator dots, ring outline) in ring.rs must match this geometry.
+// NDC-to-pixel conversion: pixel_radius = uniforms.ring_radius * rect.height() * 0.5
 // Angle formula: (index / num_validators) * 2π - π/2  (top = index 0)
-const RING_RADIUS: f32 = 0.75;
 
 // Get position on validator ring (circle)
 fn validator_position(index: f32) -> vec2<f32> {
     let angle = (index / uniforms.num_validators) * 2.0 * PI - PI * 0.5;
     // Negate Y: wgpu NDC is Y-up, but egui screen coords (CPU overlay) are Y-down.
-    return vec2(cos(angle), -sin(angle)) * RING_RADIUS;
+    return vec2(cos(angle), -sin(angle)) * uniforms.ring_radius;
 }
 
 // Quadratic bezier interpolation
@@ -99,6 +109,7 @@ fn vs_main(
     @location(3) travel_duration: f32,
     @location(4) event_type: f32,
     @location(5) curve_seed: f32,
+    @location(6) is_outbound: f32,
 ) -> VertexOutput {
     var out: VertexOutput;
     out.quad_uv = vec2(0.0);
@@ -126,29 +137,35 @@ fn vs_main(
 
     // ── Radial events: rendered as circles (only first segment = 6 verts) ──
     if !is_directed {
-        // Discard extra segments — radial only uses segment 0
-        if segment_idx > 0u {
+        // Discard extra segments — radial uses segment 0, plus the trail ghosts when enabled
+        let ghosts = select(0u, TRAIL_GHOSTS, uniforms.trail_length > 0.0);
+        if segment_idx > ghosts {
             out.clip_position = vec4(2.0, 2.0, 0.0, 1.0);
             out.color = vec4(0.0);
             return out;
         }
 
         let radial_dur = travel_duration / uniforms.speed_factor;
-        let t = clamp(age / radial_dur, 0.0, 1.0);
-        if age > radial_dur * 1.5 || age < 0.0 {
+        // Ghost k trails the head by k/TRAIL_GHOSTS of the trail length
+        let lag = f32(segment_idx) / f32(TRAIL_GHOSTS) * uniforms.trail_length;
+        let progress = age / radial_dur - lag;
+        let t = clamp(progress, 0.0, 1.0);
+        if age > radial_dur * 1.5 || age < 0.0 || progress < 0.0 {
             out.clip_position = vec4(2.0, 2.0, 0.0, 1.0);
             out.color = vec4(0.0);
             return out;
         }
+        let ghost_fade = 1.0 - f32(segment_idx) / f32(TRAIL_GHOSTS + 1u);
+        let size = uniforms.point_size * (1.0 - 0.15 * f32(segment_idx));
 
         let angle = (source_index / uniforms.num_validators) * 2.0 * PI - PI * 0.5;
         let dir = vec2(cos(angle), -sin(angle));
-        let r = mix(RING_RADIUS, RING_RADIUS * 1.44, t);
+        let r = mix(uniforms.ring_radius, uniforms.ring_radius * 1.44, t);
         let pos = dir * r;
 
         let corrected_pos = vec2(
-            (pos.x + quad_offset.x * uniforms.point_size) / uniforms.aspect_ratio,
-            pos.y + quad_offset.y * uniforms.point_size
+            (pos.x + quad_offset.x * size) / uniforms.aspect_ratio,
+            pos.y + quad_offset.y * size
         );
         out.clip_position = vec4(corrected_pos, 0.0, 1.0);
         out.quad_uv = quad_offset;
@@ -156,7 +173,7 @@ fn vs_main(
         var color = get_event_color(event_type);
         let fade_in = smoothstep(0.0, 0.1, t);
         let fade_out = 1.0 - smoothstep(0.6, 1.0, t);
-        color.a *= fade_in * fade_out;
+        color.a *= fade_in * fade_out * ghost_fade;
         out.color = color;
         return out;
     }
@@ -166,9 +183,13 @@ fn vs_main(
     // Effective duration with speed multiplier
     let eff_dur = travel_duration / (DIRECTED_SPEED * uniforms.speed_factor);
 
-    // Animation: head 0→1 in eff_dur, then tail 0→1 in eff_dur
+    // Animation: head 0→1 in eff_dur, then tail 0→1 in eff_dur.
+    // With trails on, the line shortens to a streak of trail_length behind the head.
     let t_head = clamp(age / eff_dur, 0.0, 1.0);
-    let t_tail = clamp((age - eff_dur) / eff_dur, 0.0, 1.0);
+    var t_tail = clamp((age - eff_dur) / eff_dur, 0.0, 1.0);
+    if uniforms.trail_length > 0.0 {
+        t_tail = max(t_tail, t_head - uniforms.trail_length);
+    }
 
     if age > eff_dur * 2.5 || age < 0.0 || t_head <= t_tail {
         out.clip_position = vec4(2.0, 2.0, 0.0, 1.0);
@@ -205,7 +226,9 @@ fn vs_main(
         normal = vec2(0.0, 1.0);
     }
 
-    let offset_pos = pos + normal * side * LINE_HALF_WIDTH;
+    let outbound = is_outbound > 0.5;
+    let half_width = LINE_HALF_WIDTH * select(1.0, OUTBOUND_WIDTH_SCALE, outbound);
+    let offset_pos = pos + normal * side * half_width;
 
     let corrected_pos = vec2(
         offset_pos.x / uniforms.aspect_ratio,
@@ -219,7 +242,8 @@ fn vs_main(
     let overall_progress = age / (eff_dur * 2.0);
     let fade_in = smoothstep(0.0, 0.05, overall_progress);
     let fade_out = 1.0 - smoothstep(0.95, 1.0, overall_progress);
-    color.a *= trail_alpha * fade_in * fade_out;
+    let direction_alpha = select(INBOUND_ALPHA_SCALE, 1.0, outbound);
+    color.a *= trail_alpha * fade_in * fade_out * direction_alpha;
 
     out.color = color;
     return out;
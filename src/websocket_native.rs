@@ -1,32 +1,75 @@
 //! Native WebSocket client for connecting to jamtart
 //!
 //! Uses tokio-tungstenite in a background thread, with channel-based message passing.
+//! Dropped connections are reopened with exponential backoff (see [`Backoff`]).
+//! The subscribe request can be replaced at any time with [`NativeWsClient::subscribe`].
 
-use crate::ws_state::WsState;
+use crate::recorder::Recorder;
+use crate::ws_state::{subscribe_message, Backoff, WsMessage, WsState};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{self, Receiver, Sender};
 use std::sync::Arc;
 use parking_lot::Mutex;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use tokio::sync::Notify;
 use tracing::{error, info, warn};
 
 /// Native WebSocket client that runs in a background thread
 pub struct NativeWsClient {
     /// Receiver for incoming messages
-    pub rx: Receiver<String>,
+    pub rx: Receiver<WsMessage>,
     /// Shared connection state
     pub state: Arc<Mutex<WsState>>,
+    /// Reconnect policy (base delay, max attempts); read on every reconnect, so changes
+    /// apply from the next one
+    pub backoff: Arc<Mutex<Backoff>>,
+    /// Set on drop so the background thread stops reconnecting
+    shutdown: Arc<AtomicBool>,
+    /// Fired on drop to cut a pending reconnect delay short
+    wake: Arc<Notify>,
+    /// Subscribe request sent on every (re)connect
+    subscription: Arc<Mutex<String>>,
+    /// Text messages for the open socket; closes on drop, which ends an idle session
+    outgoing: UnboundedSender<String>,
+}
+
+/// How a single connection session ended
+enum SessionEnd {
+    /// Client dropped — stop for good
+    Stopped,
+    /// Socket failed or closed — eligible for reconnect
+    Dropped { was_connected: bool },
 }
 
 impl NativeWsClient {
-    /// Connect to a WebSocket endpoint
+    /// Connect to a WebSocket endpoint, reconnecting with the default [`Backoff`]
+    /// until [`backoff`](Self::backoff) is changed.
     ///
     /// Spawns a background thread with a tokio runtime to handle the connection.
     /// Messages are sent through the returned receiver.
-    pub fn connect(url: &str) -> Self {
-        let (tx, rx): (Sender<String>, Receiver<String>) = mpsc::channel();
+    /// Every received message is also passed to `recorder`, if given
+    /// (binary frames are recorded base64-encoded).
+    pub fn connect(url: &str, recorder: Option<Recorder>) -> Self {
+        let (tx, rx): (Sender<WsMessage>, Receiver<WsMessage>) = mpsc::channel();
         let state = Arc::new(Mutex::new(WsState::Connecting));
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let wake = Arc::new(Notify::new());
+        let backoff = Arc::new(Mutex::new(Backoff::default()));
+        let subscription = Arc::new(Mutex::new(subscribe_message(None)));
+        let (outgoing, outgoing_rx) = tokio::sync::mpsc::unbounded_channel();
 
         let url = url.to_string();
         let state_clone = state.clone();
+        let session = Session {
+            tx,
+            state: state.clone(),
+            shutdown: shutdown.clone(),
+            wake: wake.clone(),
+            backoff: backoff.clone(),
+            subscription: subscription.clone(),
+            outgoing: outgoing_rx,
+            recorder,
+        };
 
         std::thread::spawn(move || {
             let rt = match tokio::runtime::Runtime::new() {
@@ -38,60 +81,143 @@ impl NativeWsClient {
                 }
             };
             rt.block_on(async move {
-                Self::run_websocket(&url, tx, state_clone).await;
+                Self::run_with_reconnect(&url, session).await;
             });
         });
 
-        Self { rx, state }
+        Self { rx, state, backoff, shutdown, wake, subscription, outgoing }
+    }
+
+    /// Replace the subscribe request: sent now if connected, and on every later reconnect
+    pub fn subscribe(&self, message: String) {
+        *self.subscription.lock() = message.clone();
+        // Not connected (or the thread has exited): the next connect sends it anyway
+        let _ = self.outgoing.send(message);
+    }
+
+    async fn run_with_reconnect(url: &str, mut session: Session) {
+        let shutdown = session.shutdown.clone();
+        let wake = session.wake.clone();
+        let backoff = session.backoff.clone();
+        let state = session.state.clone();
+        let mut attempt = 0u32;
+        loop {
+            match Self::run_websocket(url, &mut session).await {
+                SessionEnd::Stopped => return,
+                SessionEnd::Dropped { was_connected } => {
+                    if was_connected {
+                        attempt = 0;
+                    }
+                }
+            }
+            if shutdown.load(Ordering::Relaxed) {
+                return;
+            }
+
+            attempt += 1;
+            let policy = backoff.lock().clone();
+            if policy.exhausted(attempt) {
+                warn!(attempt, "Giving up reconnecting");
+                return;
+            }
+
+            let delay = policy.delay(attempt, jitter_sample());
+            info!(attempt, delay_ms = delay.as_millis() as u64, "Reconnecting");
+            *state.lock() = WsState::Reconnecting { attempt };
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                // Client dropped while waiting
+                _ = wake.notified() => return,
+            }
+        }
     }
 
-    async fn run_websocket(url: &str, tx: Sender<String>, state: Arc<Mutex<WsState>>) {
+    async fn run_websocket(url: &str, session: &mut Session) -> SessionEnd {
         use futures_util::{SinkExt, StreamExt};
         use tokio_tungstenite::{connect_async, tungstenite::Message};
 
         info!(url, "Connecting to WebSocket");
 
+        let Session { tx, state, shutdown, subscription, outgoing, recorder, .. } = session;
         let ws_stream = match connect_async(url).await {
             Ok((stream, _)) => {
                 info!("WebSocket connected");
-                *state.lock() = WsState::Connected;
                 stream
             }
             Err(e) => {
                 error!(error = %e, "Failed to connect");
                 *state.lock() = WsState::Error(e.to_string());
-                return;
+                return SessionEnd::Dropped { was_connected: false };
             }
         };
 
         let (mut write, mut read) = ws_stream.split();
 
-        // Subscribe to all events
-        let subscribe = r#"{"type":"Subscribe","filter":{"type":"All"}}"#;
+        // Subscribe (re-sent on every reconnect). Queued updates predate this connect
+        // and are already covered by the current subscription.
+        while outgoing.try_recv().is_ok() {}
+        let subscribe = subscription.lock().clone();
         if let Err(e) = write.send(Message::Text(subscribe.into())).await {
             error!(error = %e, "Failed to send subscribe message");
             *state.lock() = WsState::Error(e.to_string());
-            return;
+            return SessionEnd::Dropped { was_connected: false };
+        }
+        *state.lock() = WsState::Connected;
+        // Ordered with the frames, so the app restarts gap detection at the right message
+        if tx.send(WsMessage::Connected).is_err() {
+            return SessionEnd::Stopped;
         }
 
-        // Read messages and send through channel
-        while let Some(msg) = read.next().await {
+        // Read messages and send through channel; forward outgoing messages to the socket
+        loop {
+            let msg = tokio::select! {
+                msg = read.next() => msg,
+                text = outgoing.recv() => {
+                    // Sender gone: the client was dropped, even if the socket stays quiet
+                    let Some(text) = text else {
+                        return SessionEnd::Stopped;
+                    };
+                    if let Err(e) = write.send(Message::Text(text.into())).await {
+                        error!(error = %e, "Failed to send message");
+                        *state.lock() = WsState::Error(e.to_string());
+                        return SessionEnd::Dropped { was_connected: true };
+                    }
+                    continue;
+                }
+            };
+            let Some(msg) = msg else {
+                break;
+            };
+            if shutdown.load(Ordering::Relaxed) {
+                return SessionEnd::Stopped;
+            }
             match msg {
                 Ok(Message::Text(text)) => {
-                    if tx.send(text.to_string()).is_err() {
+                    if let Some(recorder) = recorder.as_ref() {
+                        recorder.record(&text);
+                    }
+                    if tx.send(WsMessage::Text(text)).is_err() {
                         // Receiver dropped, exit
-                        break;
+                        return SessionEnd::Stopped;
+                    }
+                }
+                Ok(Message::Binary(bytes)) => {
+                    if let Some(recorder) = recorder.as_ref() {
+                        recorder.record_binary(&bytes);
+                    }
+                    if tx.send(WsMessage::Binary(bytes)).is_err() {
+                        return SessionEnd::Stopped;
                     }
                 }
                 Ok(Message::Close(_)) => {
                     warn!("WebSocket closed by server");
                     *state.lock() = WsState::Disconnected;
-                    break;
+                    return SessionEnd::Dropped { was_connected: true };
                 }
                 Err(e) => {
                     error!(error = %e, "WebSocket error");
                     *state.lock() = WsState::Error(e.to_string());
-                    break;
+                    return SessionEnd::Dropped { was_connected: true };
                 }
                 _ => {}
             }
@@ -99,5 +225,43 @@ impl NativeWsClient {
 
         warn!("WebSocket stream ended");
         *state.lock() = WsState::Disconnected;
+        SessionEnd::Dropped { was_connected: true }
     }
 }
+
+/// Everything a connection session needs, kept across reconnects
+struct Session {
+    tx: Sender<WsMessage>,
+    state: Arc<Mutex<WsState>>,
+    shutdown: Arc<AtomicBool>,
+    wake: Arc<Notify>,
+    backoff: Arc<Mutex<Backoff>>,
+    subscription: Arc<Mutex<String>>,
+    outgoing: UnboundedReceiver<String>,
+    recorder: Option<Recorder>,
+}
+
+impl Drop for NativeWsClient {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        // Stores a permit if no delay is pending yet, so the next one returns at once
+        self.wake.notify_one();
+    }
+}
+
+/// Jitter source in [0, 1). `RandomState` keys are seeded randomly per process and change
+/// on every call, so clients started together still spread their retries even when the
+/// clock only ticks in microseconds.
+fn jitter_sample() -> f64 {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+    use std::time::{SystemTime, UNIX_EPOCH};
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u32(nanos);
+    // Top 53 bits fill an f64 mantissa exactly
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
@@ -1,8 +1,10 @@
 //! WASM WebSocket client for connecting to jamtart
+//!
+//! Dropped connections are reopened with exponential backoff (see [`Backoff`]).
+//! The subscribe request can be replaced at any time with [`WsClient::subscribe`].
 
-use crate::ws_state::WsState;
-use std::cell::RefCell;
-use std::collections::VecDeque;
+use crate::ws_state::{subscribe_message, Backoff, MessageQueue, WsMessage, WsState};
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 use tracing::{debug, error, info, warn};
 use wasm_bindgen::prelude::*;
@@ -10,18 +12,46 @@ use wasm_bindgen::JsCast;
 use web_sys::{CloseEvent, ErrorEvent, MessageEvent, WebSocket};
 
 /// Shared message buffer — WS callback pushes, app drains in update()
-pub type MessageBuffer = Rc<RefCell<VecDeque<String>>>;
+pub type MessageBuffer = Rc<RefCell<MessageQueue>>;
+
+/// Connection internals shared with the socket callbacks, which outlive `connect()`
+struct Inner {
+    url: String,
+    msg_buffer: MessageBuffer,
+    state: Rc<RefCell<WsState>>,
+    backoff: Rc<RefCell<Backoff>>,
+    /// Consecutive reconnect attempts (reset once a socket opens)
+    attempt: Cell<u32>,
+    /// Set by `close()` so pending reconnects are abandoned
+    closed: Cell<bool>,
+    /// Currently open socket, replaced on every reconnect
+    ws: RefCell<Option<WebSocket>>,
+    /// Handlers attached to `ws`; replaced with it, and dropped by `close()` to release
+    /// the `Rc<Inner>` they hold
+    callbacks: RefCell<Option<Callbacks>>,
+    /// Subscribe request sent on every (re)connect
+    subscription: RefCell<String>,
+}
+
+/// Socket event handlers, owned here instead of leaked with `forget()`
+struct Callbacks {
+    _on_open: Closure<dyn Fn(JsValue)>,
+    _on_msg: Closure<dyn Fn(MessageEvent)>,
+    _on_err: Closure<dyn Fn(ErrorEvent)>,
+    _on_close: Closure<dyn Fn(CloseEvent)>,
+}
 
 /// WASM WebSocket client
 pub struct WsClient {
-    #[allow(dead_code)]
-    ws: WebSocket,
-    #[allow(dead_code)]
-    state: Rc<RefCell<WsState>>,
+    inner: Rc<Inner>,
+    /// Reconnect policy (base delay, max attempts); read on every reconnect, so changes
+    /// apply from the next one
+    pub backoff: Rc<RefCell<Backoff>>,
 }
 
 impl WsClient {
-    /// Connect to a WebSocket endpoint
+    /// Connect to a WebSocket endpoint, reconnecting with the default [`Backoff`]
+    /// until [`backoff`](Self::backoff) is changed.
     ///
     /// Messages are buffered into `msg_buffer` for the app to drain with a time budget.
     pub fn connect(
@@ -31,62 +61,178 @@ impl WsClient {
     ) -> Result<Self, JsValue> {
         info!(url, "Connecting to WebSocket");
 
-        let ws = WebSocket::new(url)?;
+        let backoff = Rc::new(RefCell::new(Backoff::default()));
+        let inner = Rc::new(Inner {
+            url: url.to_string(),
+            msg_buffer,
+            state,
+            backoff: backoff.clone(),
+            attempt: Cell::new(0),
+            closed: Cell::new(false),
+            ws: RefCell::new(None),
+            callbacks: RefCell::new(None),
+            subscription: RefCell::new(subscribe_message(None)),
+        });
+        open_socket(&inner)?;
 
-        // On open - update state and send subscribe message
-        let ws_clone = ws.clone();
-        let state_clone = state.clone();
-        let on_open = Closure::wrap(Box::new(move |_| {
-            info!("WebSocket connected");
-            *state_clone.borrow_mut() = WsState::Connected;
+        Ok(Self { inner, backoff })
+    }
 
-            // Subscribe to all events
-            let subscribe = r#"{"type":"Subscribe","filter":{"type":"All"}}"#;
-            debug!(subscribe, "Sending subscribe message");
-            if let Err(e) = ws_clone.send_with_str(subscribe) {
-                error!(?e, "Failed to send subscribe message");
+    /// Detach callbacks and close the socket so it stops feeding the message buffer
+    pub fn close(&self) {
+        self.inner.closed.set(true);
+        if let Some(ws) = self.inner.ws.borrow_mut().take() {
+            detach(&ws);
+            if let Err(e) = ws.close() {
+                warn!(?e, "Failed to close WebSocket");
             }
-        }) as Box<dyn Fn(JsValue)>);
-        ws.set_onopen(Some(on_open.as_ref().unchecked_ref()));
-        on_open.forget();
-
-        // On message - push to buffer (processed in app update())
-        let on_msg = Closure::wrap(Box::new(move |e: MessageEvent| {
-            if let Ok(txt) = e.data().dyn_into::<js_sys::JsString>() {
-                let msg: String = txt.into();
-                msg_buffer.borrow_mut().push_back(msg);
+        }
+        self.inner.callbacks.borrow_mut().take();
+    }
+
+    /// Replace the subscribe request: sent now if the socket is open, and on every later reconnect
+    pub fn subscribe(&self, message: String) {
+        if let Some(ws) = self.inner.ws.borrow().as_ref() {
+            if ws.ready_state() == WebSocket::OPEN {
+                debug!(subscribe = %message, "Sending subscribe message");
+                if let Err(e) = ws.send_with_str(&message) {
+                    error!(?e, "Failed to send subscribe message");
+                }
             }
-        }) as Box<dyn Fn(MessageEvent)>);
-        ws.set_onmessage(Some(on_msg.as_ref().unchecked_ref()));
-        on_msg.forget();
-
-        // On error
-        let state_clone = state.clone();
-        let on_err = Closure::wrap(Box::new(move |e: ErrorEvent| {
-            let msg = e.message();
-            error!(error = %msg, "WebSocket error");
-            *state_clone.borrow_mut() = WsState::Error(msg);
-        }) as Box<dyn Fn(ErrorEvent)>);
-        ws.set_onerror(Some(on_err.as_ref().unchecked_ref()));
-        on_err.forget();
-
-        // On close
-        let state_clone = state.clone();
-        let on_close = Closure::wrap(Box::new(move |e: CloseEvent| {
-            let code = e.code();
-            let reason = e.reason();
-            warn!(code, reason = %reason, "WebSocket closed");
-            *state_clone.borrow_mut() = WsState::Disconnected;
-        }) as Box<dyn Fn(CloseEvent)>);
-        ws.set_onclose(Some(on_close.as_ref().unchecked_ref()));
-        on_close.forget();
-
-        Ok(Self { ws, state })
+        }
+        *self.inner.subscription.borrow_mut() = message;
     }
 
     /// Get the current connection state
     #[allow(dead_code)]
     pub fn state(&self) -> WsState {
-        self.state.borrow().clone()
+        self.inner.state.borrow().clone()
+    }
+}
+
+impl Drop for WsClient {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+/// Open a new socket for `inner.url` and wire up its callbacks
+fn open_socket(inner: &Rc<Inner>) -> Result<(), JsValue> {
+    let ws = WebSocket::new(&inner.url)?;
+    // Binary (SCALE) frames as ArrayBuffer rather than Blob, so they can be read synchronously
+    ws.set_binary_type(web_sys::BinaryType::Arraybuffer);
+
+    // On open - update state, reset backoff and (re-)send subscribe message
+    let ws_clone = ws.clone();
+    let inner_clone = inner.clone();
+    let on_open = Closure::wrap(Box::new(move |_| {
+        info!("WebSocket connected");
+        inner_clone.attempt.set(0);
+        *inner_clone.state.borrow_mut() = WsState::Connected;
+        inner_clone.msg_buffer.borrow_mut().push(WsMessage::Connected);
+
+        let subscribe = inner_clone.subscription.borrow().clone();
+        debug!(subscribe = %subscribe, "Sending subscribe message");
+        if let Err(e) = ws_clone.send_with_str(&subscribe) {
+            error!(?e, "Failed to send subscribe message");
+        }
+    }) as Box<dyn Fn(JsValue)>);
+    ws.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+
+    // On message - push to buffer (processed in app update())
+    let msg_buffer = inner.msg_buffer.clone();
+    let on_msg = Closure::wrap(Box::new(move |e: MessageEvent| {
+        let data = e.data();
+        if let Some(txt) = data.as_string() {
+            msg_buffer.borrow_mut().push(WsMessage::Text(txt));
+        } else if let Ok(buf) = data.dyn_into::<js_sys::ArrayBuffer>() {
+            let bytes = js_sys::Uint8Array::new(&buf).to_vec();
+            msg_buffer.borrow_mut().push(WsMessage::Binary(bytes));
+        }
+    }) as Box<dyn Fn(MessageEvent)>);
+    ws.set_onmessage(Some(on_msg.as_ref().unchecked_ref()));
+
+    // On error
+    let state_clone = inner.state.clone();
+    let on_err = Closure::wrap(Box::new(move |e: ErrorEvent| {
+        let msg = e.message();
+        error!(error = %msg, "WebSocket error");
+        *state_clone.borrow_mut() = WsState::Error(msg);
+    }) as Box<dyn Fn(ErrorEvent)>);
+    ws.set_onerror(Some(on_err.as_ref().unchecked_ref()));
+
+    // On close - schedule a reconnect
+    let inner_clone = inner.clone();
+    let on_close = Closure::wrap(Box::new(move |e: CloseEvent| {
+        let code = e.code();
+        let reason = e.reason();
+        warn!(code, reason = %reason, "WebSocket closed");
+        *inner_clone.state.borrow_mut() = WsState::Disconnected;
+        schedule_reconnect(&inner_clone);
+    }) as Box<dyn Fn(CloseEvent)>);
+    ws.set_onclose(Some(on_close.as_ref().unchecked_ref()));
+
+    // The previous socket has closed; unhook it before its handlers are dropped
+    let previous = inner.ws.borrow_mut().replace(ws);
+    if let Some(previous) = previous {
+        detach(&previous);
+    }
+    *inner.callbacks.borrow_mut() = Some(Callbacks {
+        _on_open: on_open,
+        _on_msg: on_msg,
+        _on_err: on_err,
+        _on_close: on_close,
+    });
+    Ok(())
+}
+
+/// Remove every handler from `ws`, so it never calls a dropped closure
+fn detach(ws: &WebSocket) {
+    ws.set_onopen(None);
+    ws.set_onmessage(None);
+    ws.set_onerror(None);
+    ws.set_onclose(None);
+}
+
+/// Reopen the socket after a backoff delay, unless closed or out of attempts
+fn schedule_reconnect(inner: &Rc<Inner>) {
+    if inner.closed.get() {
+        return;
+    }
+
+    let attempt = inner.attempt.get() + 1;
+    inner.attempt.set(attempt);
+    let backoff = inner.backoff.borrow().clone();
+    if backoff.exhausted(attempt) {
+        warn!(attempt, "Giving up reconnecting");
+        return;
+    }
+
+    let delay = backoff.delay(attempt, js_sys::Math::random());
+    info!(attempt, delay_ms = delay.as_millis() as u64, "Reconnecting");
+    *inner.state.borrow_mut() = WsState::Reconnecting { attempt };
+
+    let inner_clone = inner.clone();
+    let retry = Closure::once_into_js(move || {
+        if inner_clone.closed.get() {
+            return;
+        }
+        if let Err(e) = open_socket(&inner_clone) {
+            error!(?e, "Failed to reopen WebSocket");
+            *inner_clone.state.borrow_mut() = WsState::Error(format!("{:?}", e));
+            // No socket, so no onclose will come to retry from
+            schedule_reconnect(&inner_clone);
+        }
+    });
+
+    let Some(window) = web_sys::window() else {
+        error!("No window, cannot schedule reconnect");
+        return;
+    };
+    if let Err(e) = window.set_timeout_with_callback_and_timeout_and_arguments_0(
+        retry.unchecked_ref(),
+        delay.as_millis() as i32,
+    ) {
+        error!(?e, "Failed to schedule reconnect");
     }
 }
@@ -2,6 +2,117 @@
 //!
 //! Used by both WASM and native WebSocket clients.
 
+#[cfg(any(target_arch = "wasm32", test))]
+use std::collections::VecDeque;
+use std::time::Duration;
+use crate::core::Codec;
+
+/// A message received from the socket. Text frames carry JSON; binary frames carry SCALE.
+#[derive(Clone, Debug)]
+pub enum WsMessage {
+    Text(String),
+    Binary(Vec<u8>),
+    /// Queued after each (re)subscribe, ahead of the new connection's frames; the server
+    /// may restart its event ids there. Carries no payload.
+    Connected,
+}
+
+impl WsMessage {
+    /// Encoding of the payload, given by the frame type
+    pub fn codec(&self) -> Codec {
+        match self {
+            WsMessage::Text(_) | WsMessage::Connected => Codec::Json,
+            WsMessage::Binary(_) => Codec::Scale,
+        }
+    }
+
+    /// Raw payload bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            WsMessage::Text(text) => text.as_bytes(),
+            WsMessage::Binary(bytes) => bytes,
+            WsMessage::Connected => &[],
+        }
+    }
+}
+
+/// Received messages waiting for the app, bounded: once full, the oldest are dropped
+/// and counted, so a stalled consumer (e.g. a background tab) can't grow memory without limit.
+/// Only the WASM client buffers this way; native hands messages over a channel.
+#[cfg(any(target_arch = "wasm32", test))]
+#[derive(Debug)]
+pub struct MessageQueue {
+    messages: VecDeque<WsMessage>,
+    cap: usize,
+    dropped: u64,
+}
+
+#[cfg(any(target_arch = "wasm32", test))]
+impl MessageQueue {
+    pub fn new(cap: usize) -> Self {
+        Self {
+            messages: VecDeque::new(),
+            cap: cap.max(1),
+            dropped: 0,
+        }
+    }
+
+    /// Append a message, dropping the oldest if the queue is full
+    pub fn push(&mut self, msg: WsMessage) {
+        if self.messages.len() >= self.cap {
+            self.messages.pop_front();
+            self.dropped += 1;
+        }
+        self.messages.push_back(msg);
+    }
+
+    pub fn pop_front(&mut self) -> Option<WsMessage> {
+        self.messages.pop_front()
+    }
+
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Companion to `len` (clippy's `len_without_is_empty`); the app only reads `len`
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    pub fn cap(&self) -> usize {
+        self.cap
+    }
+
+    /// Messages dropped because the queue was full, since it was created
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Discard waiting messages (not counted as dropped)
+    pub fn clear(&mut self) {
+        self.messages.clear();
+    }
+}
+
+/// Subscribe request for jamtart: every event, or only the given event type discriminants
+pub fn subscribe_message(types: Option<&[u8]>) -> String {
+    let filter = match types {
+        None => serde_json::json!({ "type": "All" }),
+        Some(types) => serde_json::json!({ "type": "Types", "types": types }),
+    };
+    serde_json::json!({ "type": "Subscribe", "filter": filter }).to_string()
+}
+
+/// Whether a server text message is an error reply (`{"type":"error",...}`),
+/// e.g. a subscribe filter the server does not support
+pub fn is_error_reply(text: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(text)
+        .ok()
+        .and_then(|json| json["type"].as_str().map(|t| t.eq_ignore_ascii_case("error")))
+        .unwrap_or(false)
+}
+
 /// WebSocket connection state
 #[derive(Clone, Debug)]
 #[allow(dead_code)]
@@ -10,6 +121,10 @@ pub enum WsState {
     Connected,
     Disconnected,
     Error(String),
+    /// Waiting to reopen the socket after it dropped (1-based attempt counter)
+    Reconnecting { attempt: u32 },
+    /// Playing back a recorded session instead of a live socket
+    Replaying,
 }
 
 impl WsState {
@@ -18,3 +133,107 @@ impl WsState {
         matches!(self, WsState::Connected)
     }
 }
+
+/// Reconnect policy: exponential backoff with jitter
+#[derive(Clone, Debug)]
+pub struct Backoff {
+    /// Delay before the first reconnect attempt; doubles on each further attempt
+    pub base_delay: Duration,
+    /// Upper bound on the delay between attempts
+    pub max_delay: Duration,
+    /// Random spread applied to each delay (0.2 = ±20%)
+    pub jitter: f64,
+    /// Give up after this many consecutive failed attempts (`None` = retry forever)
+    pub max_attempts: Option<u32>,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            base_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(30),
+            jitter: 0.2,
+            max_attempts: None,
+        }
+    }
+}
+
+impl Backoff {
+    /// Delay before reconnect `attempt` (1-based). `unit` is a random sample in [0, 1).
+    pub fn delay(&self, attempt: u32, unit: f64) -> Duration {
+        let exp = attempt.saturating_sub(1).min(16);
+        let raw = self.base_delay.saturating_mul(1 << exp).min(self.max_delay);
+        let spread = (1.0 + self.jitter * (2.0 * unit - 1.0)).max(0.0);
+        raw.mul_f64(spread).min(self.max_delay)
+    }
+
+    /// Whether `attempt` is past the configured limit
+    pub fn exhausted(&self, attempt: u32) -> bool {
+        self.max_attempts.is_some_and(|max| attempt > max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_queue_drops_oldest() {
+        let mut queue = MessageQueue::new(2);
+        for text in ["a", "b", "c"] {
+            queue.push(WsMessage::Text(text.to_string()));
+        }
+        assert_eq!((queue.len(), queue.dropped()), (2, 1));
+        assert_eq!(queue.pop_front().unwrap().as_bytes(), b"b");
+        queue.clear();
+        assert!(queue.is_empty());
+        assert_eq!(queue.dropped(), 1);
+    }
+
+    #[test]
+    fn test_backoff_doubles_and_caps() {
+        let b = Backoff::default();
+        assert_eq!(b.delay(1, 0.5), Duration::from_millis(250));
+        assert_eq!(b.delay(2, 0.5), Duration::from_millis(500));
+        assert_eq!(b.delay(3, 0.5), Duration::from_secs(1));
+        assert_eq!(b.delay(20, 0.5), Duration::from_secs(30));
+        assert_eq!(b.delay(u32::MAX, 0.99), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_backoff_jitter_bounds() {
+        let b = Backoff::default();
+        let lo = b.delay(1, 0.0);
+        let hi = b.delay(1, 0.999);
+        assert!(lo >= Duration::from_millis(199) && lo <= Duration::from_millis(201));
+        assert!(hi > Duration::from_millis(290) && hi <= Duration::from_millis(300));
+    }
+
+    #[test]
+    fn test_subscribe_message() {
+        let all: serde_json::Value = serde_json::from_str(&subscribe_message(None)).unwrap();
+        assert_eq!(all, serde_json::json!({ "type": "Subscribe", "filter": { "type": "All" } }));
+        let msg: serde_json::Value =
+            serde_json::from_str(&subscribe_message(Some(&[11, 28]))).unwrap();
+        assert_eq!(msg["type"], "Subscribe");
+        assert_eq!(msg["filter"], serde_json::json!({ "type": "Types", "types": [11, 28] }));
+    }
+
+    #[test]
+    fn test_is_error_reply() {
+        assert!(is_error_reply(r#"{"type":"error","data":{"message":"unknown filter"}}"#));
+        assert!(is_error_reply(r#"{"type":"Error"}"#));
+        assert!(!is_error_reply(r#"{"type":"subscribed"}"#));
+        assert!(!is_error_reply("not json"));
+    }
+
+    #[test]
+    fn test_backoff_max_attempts() {
+        let unlimited = Backoff::default();
+        assert!(!unlimited.exhausted(1000));
+
+        let limited = Backoff { max_attempts: Some(3), ..Backoff::default() };
+        assert!(!limited.exhausted(3));
+        assert!(limited.exhausted(4));
+    }
+}